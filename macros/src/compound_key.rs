@@ -0,0 +1,119 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{Data, DeriveInput, Fields, Generics, Ident, Index, Member, Type, WherePredicate};
+
+pub(super) struct CompoundKey {
+    ident: Ident,
+    generics: Generics,
+    named: bool,
+    fields: Vec<(Member, Type)>,
+}
+
+impl Parse for CompoundKey {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let DeriveInput {
+            ident,
+            generics,
+            data,
+            ..
+        } = input.parse()?;
+
+        let Data::Struct(data) = data else {
+            return Err(syn::Error::new_spanned(ident, "expected a struct"));
+        };
+
+        let named = matches!(data.fields, Fields::Named(_));
+        let fields = data
+            .fields
+            .into_iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                let member = match field.ident {
+                    Some(ident) => Member::Named(ident),
+                    None => Member::Unnamed(Index::from(idx)),
+                };
+                (member, field.ty)
+            })
+            .collect::<Vec<_>>();
+
+        if fields.is_empty() {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "expected at least one field",
+            ));
+        }
+        if fields.len() > 12 {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "compound keys can have at most 12 fields",
+            ));
+        }
+
+        Ok(Self {
+            ident,
+            generics,
+            named,
+            fields,
+        })
+    }
+}
+
+impl CompoundKey {
+    fn generics_with(&self, bound: &TokenStream) -> Generics {
+        let mut generics = self.generics.clone();
+        let where_clause = generics.make_where_clause();
+        for (_, ty) in &self.fields {
+            let predicate: WherePredicate = syn::parse_quote!(#ty: #bound);
+            where_clause.predicates.push(predicate);
+        }
+        generics
+    }
+}
+
+impl ToTokens for CompoundKey {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let krate = quote!(::indexed_db_futures);
+        let ident = &self.ident;
+        let members = self
+            .fields
+            .iter()
+            .map(|(member, _)| member)
+            .collect::<Vec<_>>();
+        let types = self.fields.iter().map(|(_, ty)| ty);
+        let vars = (0..members.len())
+            .map(|idx| format_ident!("__idb_{}", idx))
+            .collect::<Vec<_>>();
+
+        let construct = if self.named {
+            quote!(Self { #(#members: #vars),* })
+        } else {
+            quote!(Self(#(#vars),*))
+        };
+
+        let to_js = self.generics_with(&quote!(#krate::primitive::TryToJs));
+        let (to_impl, to_type, to_where) = to_js.split_for_impl();
+        let from_js = self.generics_with(&quote!(#krate::primitive::TryFromJs));
+        let (from_impl, from_type, from_where) = from_js.split_for_impl();
+
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #to_impl #krate::primitive::TryToJs for #ident #to_type #to_where {
+                fn try_to_js(&self) -> #krate::Result<#krate::internals::JsValue> {
+                    #krate::primitive::TryToJs::try_to_js(&(#(&self.#members,)*))
+                }
+            }
+
+            #[automatically_derived]
+            impl #from_impl #krate::primitive::TryFromJs for #ident #from_type #from_where {
+                fn from_js(
+                    js: #krate::internals::JsValue,
+                ) -> ::core::result::Result<Self, #krate::error::SimpleValueError> {
+                    let (#(#vars,)*) =
+                        <(#(#types,)*) as #krate::primitive::TryFromJs>::from_js(js)?;
+                    ::core::result::Result::Ok(#construct)
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,246 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Data, DeriveInput, Fields, Generics, Ident, LitStr, Visibility};
+
+const ATTR: &str = "idb_store";
+
+pub(super) struct IdbStore {
+    ident: Ident,
+    vis: Visibility,
+    generics: Generics,
+    name: String,
+    auto_increment: bool,
+    key_path: Vec<String>,
+    indices: Vec<IndexOpts>,
+}
+
+struct IndexOpts {
+    field: Ident,
+    name: String,
+    key_path: String,
+    unique: bool,
+    multi_entry: bool,
+}
+
+#[derive(Default)]
+struct FieldOpts {
+    key: bool,
+    rename: Option<String>,
+    index: Option<IndexFlags>,
+}
+
+#[derive(Default)]
+struct IndexFlags {
+    name: Option<String>,
+    unique: bool,
+    multi_entry: bool,
+}
+
+impl Parse for IdbStore {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let DeriveInput {
+            attrs,
+            vis,
+            ident,
+            generics,
+            data,
+        } = input.parse()?;
+
+        let fields = match data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => fields.named,
+                _ => return Err(syn::Error::new_spanned(ident, "expected named fields")),
+            },
+            _ => return Err(syn::Error::new_spanned(ident, "expected a struct")),
+        };
+
+        let mut name = None;
+        let mut auto_increment = None;
+        for_each_attr(&attrs, |meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("auto_increment") {
+                auto_increment = Some(meta.path.require_ident()?.span());
+            } else {
+                return Err(meta.error("unsupported attribute"));
+            }
+            Ok(())
+        })?;
+
+        let mut key_path = Vec::new();
+        let mut indices = Vec::new();
+        for field in fields {
+            let Some(field_ident) = field.ident else {
+                continue;
+            };
+            let opts = FieldOpts::parse(&field.attrs)?;
+            let prop = opts
+                .rename
+                .unwrap_or_else(|| field_ident.unraw().to_string());
+
+            if opts.key {
+                key_path.push(prop.clone());
+            }
+            if let Some(index) = opts.index {
+                indices.push(IndexOpts {
+                    name: index.name.unwrap_or_else(|| prop.clone()),
+                    field: field_ident,
+                    key_path: prop,
+                    unique: index.unique,
+                    multi_entry: index.multi_entry,
+                });
+            }
+        }
+
+        if let Some(span) = auto_increment {
+            if key_path.len() > 1 {
+                return Err(syn::Error::new(
+                    span,
+                    "auto-incrementing object stores cannot have compound key paths",
+                ));
+            }
+        }
+
+        Ok(Self {
+            name: name.unwrap_or_else(|| ident.unraw().to_string()),
+            ident,
+            vis,
+            generics,
+            auto_increment: auto_increment.is_some(),
+            key_path,
+            indices,
+        })
+    }
+}
+
+impl FieldOpts {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+        for_each_attr(attrs, |meta| {
+            if meta.path.is_ident("key") {
+                out.key = true;
+            } else if meta.path.is_ident("rename") {
+                out.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("index") {
+                let index = out.index.insert(IndexFlags::default());
+                if !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+                    meta.parse_nested_meta(|meta| index.parse(&meta))?;
+                }
+            } else {
+                return Err(meta.error("unsupported attribute"));
+            }
+            Ok(())
+        })?;
+
+        Ok(out)
+    }
+}
+
+impl IndexFlags {
+    fn parse(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("unique") {
+            self.unique = true;
+        } else if meta.path.is_ident("multi_entry") {
+            self.multi_entry = true;
+        } else {
+            return Err(meta.error("unsupported index attribute"));
+        }
+        Ok(())
+    }
+}
+
+fn for_each_attr<F>(attrs: &[Attribute], mut f: F) -> syn::Result<()>
+where
+    F: FnMut(ParseNestedMeta) -> syn::Result<()>,
+{
+    for attr in attrs {
+        if attr.path().is_ident(ATTR) {
+            attr.parse_nested_meta(&mut f)?;
+        }
+    }
+    Ok(())
+}
+
+impl ToTokens for IdbStore {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let krate = quote!(::indexed_db_futures);
+        let Self {
+            ident,
+            vis,
+            generics,
+            name,
+            auto_increment,
+            key_path,
+            indices,
+        } = self;
+        let (g_impl, g_type, g_where) = generics.split_for_impl();
+
+        let key_path = match key_path.as_slice() {
+            [] => None,
+            [one] => Some(quote!(.with_key_path(#krate::KeyPath::One(#one)))),
+            many => Some(quote!(.with_key_path(#krate::KeyPath::from([#(#many),*])))),
+        };
+
+        let index_schemas = indices.iter().map(|index| {
+            let IndexOpts {
+                name,
+                key_path,
+                unique,
+                multi_entry,
+                ..
+            } = index;
+
+            quote! {
+                .with_index(
+                    #krate::schema::IndexSchema::new(#name, #krate::KeyPath::One(#key_path))
+                        .with_unique(#unique)
+                        .with_multi_entry(#multi_entry)
+                )
+            }
+        });
+
+        let accessors = indices.iter().map(|index| {
+            let name = &index.name;
+            let fn_name = format_ident!("{}_index", index.field.unraw(), span = Span::call_site());
+            let doc = format!("Get the `{name}` index from the object store.");
+
+            quote! {
+                #[doc = #doc]
+                #[allow(clippy::missing_errors_doc)]
+                #vis fn #fn_name<'__idb>(
+                    store: &'__idb #krate::object_store::ObjectStore<'_>,
+                ) -> #krate::Result<#krate::index::Index<'__idb>> {
+                    store.index(#name)
+                }
+            }
+        });
+
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #g_impl #krate::schema::IdbStore for #ident #g_type #g_where {
+                const STORE_NAME: &'static str = #name;
+
+                fn store_schema() -> #krate::schema::StoreSchema {
+                    #krate::schema::StoreSchema::new(Self::STORE_NAME)
+                        #key_path
+                        .with_auto_increment(#auto_increment)
+                        #(#index_schemas)*
+                }
+            }
+        });
+
+        if !indices.is_empty() {
+            tokens.extend(quote! {
+                #[automatically_derived]
+                impl #g_impl #ident #g_type #g_where {
+                    #(#accessors)*
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Derive macros for the [indexed_db_futures](https://crates.io/crates/indexed_db_futures) crate. Use them through its
+//! `derive` feature rather than depending on this crate directly.
+
+#![deny(clippy::correctness, clippy::suspicious)]
+#![warn(
+    clippy::complexity,
+    clippy::perf,
+    clippy::style,
+    clippy::pedantic,
+    missing_docs
+)]
+
+use proc_macro::TokenStream as TokenStream1;
+
+use quote::ToTokens;
+use syn::parse_macro_input;
+
+mod compound_key;
+mod idb_store;
+
+/// Derive `indexed_db_futures::primitive::TryToJs` & `indexed_db_futures::primitive::TryFromJs` for a struct whose
+/// fields, in declaration order, make up a compound key.
+#[proc_macro_derive(CompoundKey)]
+pub fn derive_compound_key(input: TokenStream1) -> TokenStream1 {
+    parse_macro_input!(input as compound_key::CompoundKey)
+        .into_token_stream()
+        .into()
+}
+
+/// Derive `indexed_db_futures::schema::IdbStore` for a struct with named fields.
+///
+/// See the trait's documentation in the main crate for the supported attributes.
+#[proc_macro_derive(IdbStore, attributes(idb_store))]
+pub fn derive_idb_store(input: TokenStream1) -> TokenStream1 {
+    parse_macro_input!(input as idb_store::IdbStore)
+        .into_token_stream()
+        .into()
+}
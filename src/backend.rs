@@ -0,0 +1,470 @@
+//! Driver abstraction that allows the crate's API to run on top of something other than the browser's `IndexedDB`.
+//!
+//! Each role in the Indexed DB API is represented by its own trait:
+//!
+//! | Role | Trait |
+//! |------|-------|
+//! | Factory | [`FactoryDriver`] |
+//! | Connection | [`DatabaseDriver`] |
+//! | Transaction | [`TransactionDriver`] |
+//! | Object store | [`StoreDriver`] |
+//! | Index | [`IndexDriver`] |
+//! | Cursor | [`CursorDriver`] |
+//!
+//! Object stores & indices share their read operations through [`QueryDriver`].
+//!
+//! A [`DBFactory`] created through [`DBFactory::from_driver`] hands out [`Database`]s,
+//! [`Transaction`](crate::transaction::Transaction)s, [`ObjectStore`](crate::object_store::ObjectStore)s, indices &
+//! cursors whose builders ([`Get`](crate::query_source::Get), [`GetAll`](crate::query_source::GetAll),
+//! [`AddPut`](crate::object_store::AddPut), [`AnyCursorBuilder`](crate::query_source::AnyCursorBuilder),
+//! [`StoreBuilder`](crate::database::StoreBuilder) & the rest) dispatch to the driver instead of `web_sys`. Code
+//! written against the regular API therefore runs unchanged on top of any driver, be it the
+//! [in-memory one](crate::memory), a mock or an instrumented wrapper around another driver.
+//!
+//! Keys & values cross the driver boundary as [`IdbKey`]s & [`Value`]s; the crate's
+//! [primitives](crate::primitive) & [serde](crate::SerialiseToJs) conversions produce them directly, without going
+//! through JS. Driver operations complete synchronously, with the exception of opening & deleting databases, and the
+//! requests the crate returns resolve on their first poll.
+//!
+//! Browser-only features, such as [`Database::version_changes`] or
+//! [`DBFactory::databases`](crate::factory::DBFactory::databases), fail with
+//! [`NotSupportedError`](BackendError::NotSupportedError) on drivers.
+//!
+//! # Example
+//!
+//! ```
+//! use indexed_db_futures::backend::*;
+//! use indexed_db_futures::cursor::CursorDirection;
+//! use indexed_db_futures::prelude::*;
+//! use indexed_db_futures::{IdbKey, KeyPath, KeyRange};
+//! use std::rc::Rc;
+//!
+//! /// Counts the reads made through another store driver.
+//! #[derive(Debug)]
+//! struct CountingStore {
+//!     inner: Rc<dyn StoreDriver>,
+//!     reads: Rc<std::cell::Cell<usize>>,
+//! }
+//!
+//! impl QueryDriver for CountingStore {
+//!     fn name(&self) -> String { self.inner.name() }
+//!     fn set_name(&self, name: &str) -> indexed_db_futures::Result<()> { self.inner.set_name(name) }
+//!     fn key_path(&self) -> Option<KeyPath> { self.inner.key_path() }
+//!     fn count(&self, query: Option<KeyRange<IdbKey>>) -> indexed_db_futures::Result<u32> {
+//!         self.inner.count(query)
+//!     }
+//!     fn get(&self, query: KeyRange<IdbKey>) -> indexed_db_futures::Result<Option<Value>> {
+//!         self.reads.set(self.reads.get() + 1);
+//!         self.inner.get(query)
+//!     }
+//!     // ...
+//! #   fn get_key(&self, query: KeyRange<IdbKey>) -> indexed_db_futures::Result<Option<IdbKey>> {
+//! #       self.inner.get_key(query)
+//! #   }
+//! #   fn get_all(&self, query: Option<KeyRange<IdbKey>>, limit: Option<u32>) -> indexed_db_futures::Result<Vec<Value>> {
+//! #       self.inner.get_all(query, limit)
+//! #   }
+//! #   fn get_all_keys(&self, query: Option<KeyRange<IdbKey>>, limit: Option<u32>) -> indexed_db_futures::Result<Vec<IdbKey>> {
+//! #       self.inner.get_all_keys(query, limit)
+//! #   }
+//! #   fn open_cursor(&self, query: Option<KeyRange<IdbKey>>, direction: CursorDirection, key_only: bool) -> indexed_db_futures::Result<Option<Rc<dyn CursorDriver>>> {
+//! #       self.inner.open_cursor(query, direction, key_only)
+//! #   }
+//! }
+//! # impl StoreDriver for CountingStore {
+//! #   fn auto_increment(&self) -> bool { self.inner.auto_increment() }
+//! #   fn index_names(&self) -> Vec<String> { self.inner.index_names() }
+//! #   fn index(&self, name: &str) -> indexed_db_futures::Result<Rc<dyn IndexDriver>> { self.inner.index(name) }
+//! #   fn create_index(&self, name: &str, key_path: KeyPath, unique: bool, multi_entry: bool) -> indexed_db_futures::Result<Rc<dyn IndexDriver>> {
+//! #       self.inner.create_index(name, key_path, unique, multi_entry)
+//! #   }
+//! #   fn delete_index(&self, name: &str) -> indexed_db_futures::Result<()> { self.inner.delete_index(name) }
+//! #   fn add(&self, value: Value, key: Option<IdbKey>) -> indexed_db_futures::Result<IdbKey> { self.inner.add(value, key) }
+//! #   fn put(&self, value: Value, key: Option<IdbKey>) -> indexed_db_futures::Result<IdbKey> { self.inner.put(value, key) }
+//! #   fn delete(&self, query: KeyRange<IdbKey>) -> indexed_db_futures::Result<()> { self.inner.delete(query) }
+//! #   fn clear(&self) -> indexed_db_futures::Result<()> { self.inner.clear() }
+//! # }
+//! ```
+//!
+//! [`Database`]: crate::database::Database
+//! [`Database::version_changes`]: crate::database::Database::version_changes
+//! [`DBFactory`]: crate::factory::DBFactory
+//! [`DBFactory::from_driver`]: crate::factory::DBFactory::from_driver
+
+use crate::cursor::CursorDirection;
+use crate::error::BackendError;
+use crate::transaction::TransactionMode;
+use crate::{IdbKey, KeyPath, KeyRange};
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+pub use value::Value;
+
+mod value;
+
+/// A boxed future returned by asynchronous driver operations.
+pub type DriverFuture<T> = Pin<Box<dyn Future<Output = crate::Result<T>>>>;
+
+/// Callback invoked by [`FactoryDriver::open`] when a database needs to be created or upgraded.
+///
+/// Schema changes made through the [`Upgrade`]'s database & transaction must be rolled back if the returned future
+/// fails.
+pub type UpgradeHandler = Box<dyn FnOnce(Upgrade) -> DriverFuture<()>>;
+
+/// An in-progress database upgrade, passed to an [`UpgradeHandler`].
+#[derive(Debug, Clone)]
+pub struct Upgrade {
+    /// The version before the upgrade. `0` for new databases.
+    pub old_version: u32,
+
+    /// The version being upgraded to.
+    pub new_version: u32,
+
+    /// The database being upgraded. Object stores can be created & deleted through it.
+    pub database: Rc<dyn DatabaseDriver>,
+
+    /// The [`Versionchange`](TransactionMode::Versionchange) transaction the upgrade runs in.
+    pub transaction: Rc<dyn TransactionDriver>,
+}
+
+/// A factory for opening & deleting databases.
+pub trait FactoryDriver: Debug {
+    /// Open a database, optionally at a specific version. Without a version, an existing database is opened at its
+    /// current version & a new one gets created at version `1`. `on_upgrade` gets invoked if the database needs to
+    /// be created or upgraded.
+    ///
+    /// # Errors
+    ///
+    /// The future fails with a [`VersionError`](BackendError::VersionError) if `version` is lower than the
+    /// database's current version, or with whatever error `on_upgrade` fails with.
+    fn open(
+        &self,
+        name: &str,
+        version: Option<u32>,
+        on_upgrade: Option<UpgradeHandler>,
+    ) -> DriverFuture<Rc<dyn DatabaseDriver>>;
+
+    /// Delete a database. Deleting a database that doesn't exist is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Driver-specific.
+    fn delete(&self, name: &str) -> DriverFuture<()>;
+}
+
+/// A database connection.
+pub trait DatabaseDriver: Debug {
+    /// The database's name.
+    fn name(&self) -> String;
+
+    /// The database's version. During an upgrade this is the version being upgraded to.
+    fn version(&self) -> u32;
+
+    /// Names of the object stores in the database.
+    fn object_store_names(&self) -> Vec<String>;
+
+    /// Create an object store.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidStateError`](BackendError::InvalidStateError) if called outside of an upgrade.
+    /// - [`ConstraintError`](BackendError::ConstraintError) if an object store with the same name exists.
+    /// - [`InvalidAccessError`](BackendError::InvalidAccessError) if `auto_increment` is set & the key path is empty
+    ///   or a sequence.
+    fn create_object_store(
+        &self,
+        name: &str,
+        key_path: Option<KeyPath>,
+        auto_increment: bool,
+    ) -> crate::Result<Rc<dyn StoreDriver>>;
+
+    /// Delete an object store.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidStateError`](BackendError::InvalidStateError) if called outside of an upgrade.
+    /// - [`NotFoundError`](BackendError::NotFoundError) if the object store doesn't exist.
+    fn delete_object_store(&self, name: &str) -> crate::Result<()>;
+
+    /// Start a transaction over the given object stores.
+    ///
+    /// # Errors
+    ///
+    /// - [`NotFoundError`](BackendError::NotFoundError) if one of the object stores doesn't exist.
+    /// - [`InvalidAccessError`](BackendError::InvalidAccessError) if no object stores or an invalid mode were
+    ///   given.
+    /// - [`InvalidStateError`](BackendError::InvalidStateError) if the connection is closed or being upgraded.
+    fn transaction(
+        &self,
+        store_names: &[String],
+        mode: TransactionMode,
+    ) -> crate::Result<Rc<dyn TransactionDriver>>;
+
+    /// Close the connection.
+    fn close(&self);
+}
+
+/// A transaction.
+pub trait TransactionDriver: Debug {
+    /// The transaction's mode.
+    fn mode(&self) -> TransactionMode;
+
+    /// Names of the object stores in the transaction's scope.
+    fn object_store_names(&self) -> Vec<String>;
+
+    /// Get an object store in the transaction's scope.
+    ///
+    /// # Errors
+    ///
+    /// - [`NotFoundError`](BackendError::NotFoundError) if the object store isn't in the transaction's scope.
+    /// - [`TransactionInactiveError`](BackendError::TransactionInactiveError) if the transaction has finished.
+    fn object_store(&self, name: &str) -> crate::Result<Rc<dyn StoreDriver>>;
+
+    /// Commit the transaction.
+    ///
+    /// # Errors
+    ///
+    /// - [`TransactionInactiveError`](BackendError::TransactionInactiveError) if the transaction has finished.
+    /// - [`AbortError`](BackendError::AbortError) if the changes could not be applied; the transaction is rolled
+    ///   back.
+    fn commit(&self) -> crate::Result<()>;
+
+    /// Abort the transaction, rolling back its changes.
+    ///
+    /// # Errors
+    ///
+    /// [`TransactionInactiveError`](BackendError::TransactionInactiveError) if the transaction has finished.
+    fn abort(&self) -> crate::Result<()>;
+}
+
+/// Operations shared by object stores & indices.
+///
+/// Unless stated otherwise, fallible methods fail with a
+/// [`TransactionInactiveError`](BackendError::TransactionInactiveError) if the transaction has finished and with an
+/// [`InvalidStateError`](BackendError::InvalidStateError) if the object store or index has been deleted.
+pub trait QueryDriver: Debug {
+    /// The object store's or index' name.
+    fn name(&self) -> String;
+
+    /// Rename the object store or index.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidStateError`](BackendError::InvalidStateError) if called outside of an upgrade.
+    /// - [`ConstraintError`](BackendError::ConstraintError) if the name is taken.
+    fn set_name(&self, name: &str) -> crate::Result<()>;
+
+    /// The object store's or index' key path. `None` for object stores with out-of-line keys.
+    fn key_path(&self) -> Option<KeyPath>;
+
+    /// Count the records matching the query, or all records if there's no query.
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](QueryDriver).
+    fn count(&self, query: Option<KeyRange<IdbKey>>) -> crate::Result<u32>;
+
+    /// Get the first record matching the query.
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](QueryDriver).
+    fn get(&self, query: KeyRange<IdbKey>) -> crate::Result<Option<Value>>;
+
+    /// Get the primary key of the first record matching the query.
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](QueryDriver).
+    fn get_key(&self, query: KeyRange<IdbKey>) -> crate::Result<Option<IdbKey>>;
+
+    /// Get all records matching the query, or all records if there's no query, up to `limit`.
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](QueryDriver).
+    fn get_all(
+        &self,
+        query: Option<KeyRange<IdbKey>>,
+        limit: Option<u32>,
+    ) -> crate::Result<Vec<Value>>;
+
+    /// Get the primary keys of all records matching the query, or all records if there's no query, up to `limit`.
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](QueryDriver).
+    fn get_all_keys(
+        &self,
+        query: Option<KeyRange<IdbKey>>,
+        limit: Option<u32>,
+    ) -> crate::Result<Vec<IdbKey>>;
+
+    /// Open a cursor positioned on the first matching record. Returns `None` if there are no matching records.
+    /// Key-only cursors never return [values](CursorDriver::value).
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](QueryDriver).
+    fn open_cursor(
+        &self,
+        query: Option<KeyRange<IdbKey>>,
+        direction: CursorDirection,
+        key_only: bool,
+    ) -> crate::Result<Option<Rc<dyn CursorDriver>>>;
+}
+
+/// An object store.
+///
+/// Write operations fail with a [`ReadOnlyError`](BackendError::ReadOnlyError) in read-only transactions, in
+/// addition to the errors listed on [`QueryDriver`].
+pub trait StoreDriver: QueryDriver {
+    /// Whether the object store uses a key generator.
+    fn auto_increment(&self) -> bool;
+
+    /// Names of the indices on the object store.
+    fn index_names(&self) -> Vec<String>;
+
+    /// Get an index.
+    ///
+    /// # Errors
+    ///
+    /// [`NotFoundError`](BackendError::NotFoundError) if the index doesn't exist.
+    fn index(&self, name: &str) -> crate::Result<Rc<dyn IndexDriver>>;
+
+    /// Create an index, populating it from the existing records.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidStateError`](BackendError::InvalidStateError) if called outside of an upgrade.
+    /// - [`ConstraintError`](BackendError::ConstraintError) if an index with the same name exists or if `unique` is
+    ///   set & existing records violate it.
+    /// - [`InvalidAccessError`](BackendError::InvalidAccessError) if `multi_entry` is set & the key path is a
+    ///   sequence.
+    fn create_index(
+        &self,
+        name: &str,
+        key_path: KeyPath,
+        unique: bool,
+        multi_entry: bool,
+    ) -> crate::Result<Rc<dyn IndexDriver>>;
+
+    /// Delete an index.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidStateError`](BackendError::InvalidStateError) if called outside of an upgrade.
+    /// - [`NotFoundError`](BackendError::NotFoundError) if the index doesn't exist.
+    fn delete_index(&self, name: &str) -> crate::Result<()>;
+
+    /// Add a record, failing if one already exists with the same key. `key` must only be provided if the object
+    /// store uses out-of-line keys. Returns the record's key.
+    ///
+    /// # Errors
+    ///
+    /// - [`ConstraintError`](BackendError::ConstraintError) if the key or a unique index key is taken.
+    /// - [`DataError`](BackendError::DataError) if the key is missing, invalid or provided for an object store with
+    ///   in-line keys.
+    fn add(&self, value: Value, key: Option<IdbKey>) -> crate::Result<IdbKey>;
+
+    /// Add or replace a record. `key` must only be provided if the object store uses out-of-line keys. Returns the
+    /// record's key.
+    ///
+    /// # Errors
+    ///
+    /// See [`add`](StoreDriver::add).
+    fn put(&self, value: Value, key: Option<IdbKey>) -> crate::Result<IdbKey>;
+
+    /// Delete all records matching the query.
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](StoreDriver).
+    fn delete(&self, query: KeyRange<IdbKey>) -> crate::Result<()>;
+
+    /// Delete all records.
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](StoreDriver).
+    fn clear(&self) -> crate::Result<()>;
+}
+
+/// An index.
+pub trait IndexDriver: QueryDriver {
+    /// Whether the index is unique.
+    fn unique(&self) -> bool;
+
+    /// Whether the index is multi-entry.
+    fn multi_entry(&self) -> bool;
+}
+
+/// A cursor. Cursors start out positioned on their first record & are moved through `&self`, so implementations
+/// need interior mutability.
+///
+/// Fallible methods fail with an [`InvalidStateError`](BackendError::InvalidStateError) once the cursor has
+/// iterated past its end, in addition to the errors listed on [`QueryDriver`].
+pub trait CursorDriver: Debug {
+    /// The cursor's direction.
+    fn direction(&self) -> CursorDirection;
+
+    /// The key at the cursor's position: the index key for index cursors and the primary key for object store
+    /// cursors. `None` once the cursor has iterated past its end.
+    fn key(&self) -> Option<IdbKey>;
+
+    /// The primary key at the cursor's position. `None` once the cursor has iterated past its end.
+    fn primary_key(&self) -> Option<IdbKey>;
+
+    /// The value at the cursor's position. `None` once the cursor has iterated past its end, for key-only cursors &
+    /// if the record has been deleted.
+    ///
+    /// # Errors
+    ///
+    /// See the [trait docs](CursorDriver).
+    fn value(&self) -> crate::Result<Option<Value>>;
+
+    /// Move the cursor `count` records forward.
+    ///
+    /// # Errors
+    ///
+    /// [`DataError`](BackendError::DataError) if `count` is `0`.
+    fn advance(&self, count: u32) -> crate::Result<()>;
+
+    /// Move the cursor to the next record, or to the next record whose key is past or equal to `key` if one is
+    /// given.
+    ///
+    /// # Errors
+    ///
+    /// [`DataError`](BackendError::DataError) if `key` is invalid or not past the cursor's position.
+    fn continue_to_key(&self, key: Option<IdbKey>) -> crate::Result<()>;
+
+    /// Move an index cursor to the record with the given key & primary key, or the first record past it.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidAccessError`](BackendError::InvalidAccessError) for object store & unique cursors.
+    /// - [`DataError`](BackendError::DataError) if the position isn't past the cursor's current one.
+    fn continue_to_primary_key(&self, key: IdbKey, primary_key: IdbKey) -> crate::Result<()>;
+
+    /// Replace the value of the record at the cursor's position, returning its primary key.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadOnlyError`](BackendError::ReadOnlyError) in read-only transactions.
+    /// - [`DataError`](BackendError::DataError) if the value's in-line key doesn't match the primary key.
+    fn update(&self, value: Value) -> crate::Result<IdbKey>;
+
+    /// Delete the record at the cursor's position.
+    ///
+    /// # Errors
+    ///
+    /// [`ReadOnlyError`](BackendError::ReadOnlyError) in read-only transactions.
+    fn delete(&self) -> crate::Result<()>;
+}
+
+/// Error returned for browser-only features.
+pub(crate) fn not_supported(what: &str) -> crate::error::Error {
+    BackendError::NotSupportedError(format!("{what} is not supported by drivers")).into()
+}
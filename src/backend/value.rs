@@ -0,0 +1,423 @@
+use crate::error::{BackendError, SimpleValueError};
+use crate::primitive::{BinaryKey, TryFromJs, TryToJs};
+use crate::IdbKey as Key;
+use std::collections::BTreeMap;
+use wasm_bindgen::prelude::*;
+
+/// A value passed to & from a [driver](super). Roughly equivalent to what survives the
+/// [structured clone algorithm](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Structured_clone_algorithm).
+///
+/// Converts to & from JS via [`TryToJs`] & [`TryFromJs`], so it can also be stored in a browser's object stores.
+/// [Binary](Value::Binary) data converts to a `Uint8Array`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Value {
+    /// `undefined`
+    #[default]
+    Undefined,
+
+    /// `null`
+    Null,
+
+    /// A boolean.
+    Bool(bool),
+
+    /// A number.
+    Number(f64),
+
+    /// A string.
+    String(String),
+
+    /// A date, represented as milliseconds since the Unix epoch.
+    Date(f64),
+
+    /// Binary data.
+    Binary(Vec<u8>),
+
+    /// An array.
+    Array(Vec<Value>),
+
+    /// A plain object.
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Construct an [`Object`](Value::Object) from its entries.
+    pub fn object<K, I>(entries: I) -> Self
+    where
+        K: Into<String>,
+        I: IntoIterator<Item = (K, Value)>,
+    {
+        Self::Object(entries.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Get a property of an [`Object`](Value::Object). Returns `None` for non-objects.
+    #[must_use]
+    pub fn get(&self, property: &str) -> Option<&Value> {
+        match self {
+            Self::Object(map) => map.get(property),
+            _ => None,
+        }
+    }
+
+    /// Convert the value to a key. Returns `None` if the value isn't a [valid key](Key::is_valid).
+    #[must_use]
+    pub fn to_key(&self) -> Option<Key> {
+        let key = match self {
+            Self::Number(n) => Key::Number(*n),
+            Self::Date(n) => Key::Date(*n),
+            Self::String(s) => Key::String(s.clone()),
+            Self::Binary(b) => Key::Binary(b.clone()),
+            Self::Array(arr) => Key::Array(arr.iter().map(Self::to_key).collect::<Option<_>>()?),
+            Self::Undefined | Self::Null | Self::Bool(_) | Self::Object(_) => return None,
+        };
+
+        if key.is_valid() {
+            Some(key)
+        } else {
+            None
+        }
+    }
+
+    /// [`to_key`](Self::to_key), failing with a [`DataError`](BackendError::DataError) for invalid keys.
+    pub(crate) fn into_key(self) -> crate::Result<Key> {
+        match self.to_key() {
+            Some(key) => Ok(key),
+            None => Err(BackendError::DataError(format!("{self:?} is not a valid key")).into()),
+        }
+    }
+
+    /// Evaluate a single dot-separated key path string against the value.
+    #[cfg(any(feature = "memory", feature = "query"))]
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn eval_path(&self, path: &str) -> Option<Value> {
+        if path.is_empty() {
+            return Some(self.clone());
+        }
+
+        let mut current = self;
+        let mut owned;
+        for ident in path.split('.') {
+            match (current, ident) {
+                (Self::String(s), "length") => {
+                    owned = Self::Number(s.encode_utf16().count() as f64);
+                    current = &owned;
+                }
+                (Self::Array(arr), "length") => {
+                    owned = Self::Number(arr.len() as f64);
+                    current = &owned;
+                }
+                (Self::Binary(arr), "length" | "byteLength") => {
+                    owned = Self::Number(arr.len() as f64);
+                    current = &owned;
+                }
+                (Self::Object(map), ident) => current = map.get(ident)?,
+                _ => return None,
+            }
+        }
+
+        Some(current.clone())
+    }
+
+    /// Inject a key into the value at the given key path, creating intermediate objects as needed. Returns `false` if
+    /// the key could not be injected.
+    #[cfg(feature = "memory")]
+    pub(crate) fn inject_key(&mut self, path: &str, key: &Key) -> bool {
+        let mut idents = path.split('.').peekable();
+        let mut current = self;
+
+        while let Some(ident) = idents.next() {
+            let Self::Object(map) = current else {
+                return false;
+            };
+
+            if idents.peek().is_none() {
+                map.insert(ident.into(), key.clone().into());
+                return true;
+            }
+
+            current = map
+                .entry(ident.into())
+                .or_insert_with(|| Self::Object(BTreeMap::new()));
+        }
+
+        false
+    }
+}
+
+impl Value {
+    /// Convert the value to JS.
+    pub(crate) fn to_js(&self) -> JsValue {
+        match self {
+            Self::Undefined => JsValue::UNDEFINED,
+            Self::Null => JsValue::NULL,
+            Self::Bool(b) => JsValue::from_bool(*b),
+            Self::Number(n) => JsValue::from_f64(*n),
+            Self::String(s) => JsValue::from_str(s),
+            Self::Date(ms) => js_sys::Date::new(&JsValue::from_f64(*ms)).unchecked_into(),
+            Self::Binary(bytes) => js_sys::Uint8Array::from(bytes.as_slice()).unchecked_into(),
+            Self::Array(values) => values
+                .iter()
+                .map(Self::to_js)
+                .collect::<js_sys::Array>()
+                .unchecked_into(),
+            Self::Object(map) => {
+                let obj = js_sys::Object::new();
+                for (k, v) in map {
+                    // Can't fail on a plain object
+                    let _ = js_sys::Reflect::set(&obj, &JsValue::from_str(k), &v.to_js());
+                }
+                obj.unchecked_into()
+            }
+        }
+    }
+
+    /// Convert a type that can only produce JS values. Only possible on `wasm32` targets.
+    pub(crate) fn via_js<F>(to_js: F) -> crate::Result<Self>
+    where
+        F: FnOnce() -> crate::Result<JsValue>,
+    {
+        if cfg!(target_arch = "wasm32") {
+            Self::from_js(to_js()?).map_err(Into::into)
+        } else {
+            let msg = "the type can only be converted to a driver value through JS";
+            Err(BackendError::NotSupportedError(msg.into()).into())
+        }
+    }
+}
+
+impl From<Key> for Value {
+    fn from(key: Key) -> Self {
+        match key {
+            Key::Number(n) => Self::Number(n),
+            Key::Date(n) => Self::Date(n),
+            Key::String(s) => Self::String(s),
+            Key::Binary(b) => Self::Binary(b),
+            Key::Array(arr) => Self::Array(arr.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+macro_rules! from_num {
+    (lossless: $($ty: ty),+ $(,)?) => {
+        $(
+            impl From<$ty> for Value {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Self::Number(value.into())
+                }
+            }
+        )+
+    };
+    (lossy: $($ty: ty),+ $(,)?) => {
+        $(
+            impl From<$ty> for Value {
+                #[allow(clippy::cast_precision_loss)]
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Self::Number(value as f64)
+                }
+            }
+        )+
+    };
+}
+
+from_num!(lossless: f64, f32, i8, u8, i16, u16, i32, u32);
+from_num!(lossy: i64, u64, i128, u128, isize, usize);
+
+impl From<bool> for Value {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<String> for Value {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for Value {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    #[inline]
+    fn from(value: Vec<Value>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl From<BTreeMap<String, Value>> for Value {
+    #[inline]
+    fn from(value: BTreeMap<String, Value>) -> Self {
+        Self::Object(value)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(v) => v.into(),
+            None => Self::Undefined,
+        }
+    }
+}
+
+impl TryToJs for Value {
+    #[inline]
+    fn try_to_js(&self) -> crate::Result<JsValue> {
+        Ok(self.to_js())
+    }
+
+    #[inline]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        Ok(self.clone())
+    }
+}
+
+impl TryFromJs for Value {
+    fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
+        Ok(if js.is_undefined() {
+            Self::Undefined
+        } else if js.is_null() {
+            Self::Null
+        } else if let Some(b) = js.as_bool() {
+            Self::Bool(b)
+        } else if let Some(n) = js.as_f64() {
+            Self::Number(n)
+        } else if let Some(s) = js.as_string() {
+            Self::String(s)
+        } else if let Some(date) = js.dyn_ref::<js_sys::Date>() {
+            Self::Date(date.get_time())
+        } else if js.is_instance_of::<js_sys::ArrayBuffer>() || js_sys::ArrayBuffer::is_view(&js) {
+            Self::Binary(BinaryKey::from_js(js)?.into_inner())
+        } else if js_sys::Array::is_array(&js) {
+            let values = js
+                .unchecked_into::<js_sys::Array>()
+                .iter()
+                .map(Self::from_js)
+                .collect::<Result<_, _>>()?;
+            Self::Array(values)
+        } else if js.is_object() {
+            Self::Object(crate::primitive::js_to_map(js)?)
+        } else {
+            return Err(SimpleValueError::DynCast(js));
+        })
+    }
+
+    #[inline]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+const _: () = {
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use serde_value::Value as SV;
+
+    impl Value {
+        /// Serialise the given value. Structs & maps become [objects](Value::Object), sequences become
+        /// [arrays](Value::Array) and `None`/`()` become [`Undefined`](Value::Undefined), mirroring the
+        /// `serde-wasm-bindgen` config used by the rest of the crate.
+        ///
+        /// # Errors
+        ///
+        /// [`DataCloneError`](BackendError::DataCloneError) if the value fails to serialise or uses map keys other
+        /// than strings, numbers & booleans.
+        pub fn from_serde<T: Serialize + ?Sized>(value: &T) -> crate::Result<Self> {
+            match serde_value::to_value(value) {
+                Ok(v) => from_sv(v).map_err(Into::into),
+                Err(e) => Err(BackendError::DataCloneError(e.to_string()).into()),
+            }
+        }
+
+        /// Deserialise the value.
+        ///
+        /// # Errors
+        ///
+        /// [`DataCloneError`](BackendError::DataCloneError) if the value doesn't match `T`'s shape.
+        pub fn into_serde<T: DeserializeOwned>(self) -> crate::Result<T> {
+            into_sv(self)
+                .deserialize_into()
+                .map_err(|e| BackendError::DataCloneError(e.to_string()).into())
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn from_sv(value: SV) -> Result<Value, BackendError> {
+        Ok(match value {
+            SV::Bool(v) => Value::Bool(v),
+            SV::U8(v) => v.into(),
+            SV::U16(v) => v.into(),
+            SV::U32(v) => v.into(),
+            SV::U64(v) => v.into(),
+            SV::I8(v) => v.into(),
+            SV::I16(v) => v.into(),
+            SV::I32(v) => v.into(),
+            SV::I64(v) => v.into(),
+            SV::F32(v) => v.into(),
+            SV::F64(v) => v.into(),
+            SV::Char(v) => Value::String(v.into()),
+            SV::String(v) => Value::String(v),
+            SV::Unit | SV::Option(None) => Value::Undefined,
+            SV::Option(Some(v)) | SV::Newtype(v) => from_sv(*v)?,
+            SV::Seq(v) => Value::Array(v.into_iter().map(from_sv).collect::<Result<_, _>>()?),
+            SV::Bytes(v) => Value::Binary(v),
+            SV::Map(map) => {
+                let map = map
+                    .into_iter()
+                    .map(|(k, v)| Ok((map_key(k)?, from_sv(v)?)))
+                    .collect::<Result<_, BackendError>>()?;
+                Value::Object(map)
+            }
+        })
+    }
+
+    fn map_key(key: SV) -> Result<String, BackendError> {
+        match from_sv(key)? {
+            Value::String(s) => Ok(s),
+            Value::Number(n) => Ok(n.to_string()),
+            Value::Bool(b) => Ok(b.to_string()),
+            other => Err(BackendError::DataCloneError(format!(
+                "Unsupported map key: {other:?}"
+            ))),
+        }
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn into_sv(value: Value) -> SV {
+        const MAX_SAFE_INT: f64 = 9_007_199_254_740_991.0;
+
+        match value {
+            Value::Undefined | Value::Null => SV::Unit,
+            Value::Bool(v) => SV::Bool(v),
+            Value::Number(v) if v.fract() == 0.0 && v.abs() <= MAX_SAFE_INT => {
+                if v < 0.0 {
+                    SV::I64(v as i64)
+                } else {
+                    SV::U64(v as u64)
+                }
+            }
+            Value::Number(v) | Value::Date(v) => SV::F64(v),
+            Value::String(v) => SV::String(v),
+            Value::Binary(v) => SV::Seq(v.into_iter().map(SV::U8).collect()),
+            Value::Array(v) => SV::Seq(v.into_iter().map(into_sv).collect()),
+            Value::Object(v) => SV::Map(
+                v.into_iter()
+                    .map(|(k, v)| (SV::String(k), into_sv(v)))
+                    .collect(),
+            ),
+        }
+    }
+};
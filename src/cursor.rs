@@ -9,7 +9,8 @@ pub use web_sys::IdbCursorDirection as CursorDirection;
 
 use crate::future::{CursorNextRequest, VoidRequest};
 use crate::internal_utils::SystemRepr;
-use crate::primitive::TryFromJs;
+use crate::primitive::{TryFromJs, TryFromJsExt};
+use crate::repr::CursorRepr;
 use internal_macros::errdoc;
 
 mod base_cursor;
@@ -19,6 +20,9 @@ mod update;
 
 iffeat! {
     #[cfg(feature = "streams")]
+    mod merge;
+    pub use merge::{Entry, MergedStream};
+
     mod stream;
     pub use stream::*;
 }
@@ -29,7 +33,7 @@ iffeat! {
 pub struct Cursor<'a, Qs>(KeyCursor<'a, Qs>);
 
 impl<'a, Qs> Cursor<'a, Qs> {
-    pub(crate) fn new(base: CursorSys, source: &'a Qs) -> Self {
+    pub(crate) fn new(base: CursorRepr, source: &'a Qs) -> Self {
         Self(KeyCursor::new(base, source))
     }
 
@@ -45,7 +49,7 @@ impl<'a, Qs> Cursor<'a, Qs> {
     where
         Option<T>: TryFromJs,
     {
-        TryFromJs::from_js(self.as_sys().key()?).map_err(Into::into)
+        Option::<T>::from_raw(self.as_sys().key()?)
     }
 
     /// [`Self::key`] mirror for `serde`-deserialisable keys.
@@ -55,7 +59,7 @@ impl<'a, Qs> Cursor<'a, Qs> {
     where
         Option<T>: crate::serde::DeserialiseFromJs,
     {
-        crate::serde::DeserialiseFromJs::deserialise_from_js(self.as_sys().key()?)
+        crate::serde::deserialise_from_raw(self.as_sys().key()?)
     }
 
     /// Get the next record in the cursor.
@@ -64,7 +68,7 @@ impl<'a, Qs> Cursor<'a, Qs> {
     /// followed by [`value`](https://developer.mozilla.org/en-US/docs/Web/API/IDBCursorWithValue/value) in JS.
     #[errdoc(Cursor(TransactionInactiveError, InvalidStateError))]
     #[inline]
-    pub fn next_record<T>(&mut self) -> CursorNextRequest<T>
+    pub fn next_record<T>(&mut self) -> CursorNextRequest<'_, T>
     where
         T: TryFromJs,
     {
@@ -73,7 +77,7 @@ impl<'a, Qs> Cursor<'a, Qs> {
 
     /// Mirror of [`Self::next_record`] for `serde`-deserialisable values.
     #[cfg(feature = "serde")]
-    pub fn next_record_ser<T>(&mut self) -> CursorNextRequest<T>
+    pub fn next_record_ser<T>(&mut self) -> CursorNextRequest<'_, T>
     where
         T: crate::serde::DeserialiseFromJs,
     {
@@ -106,7 +110,7 @@ impl<'a, Qs> Cursor<'a, Qs> {
         DataCloneError,
     ))]
     #[inline]
-    pub fn update<V>(&self, value: V) -> Update<V> {
+    pub fn update<V>(&self, value: V) -> Update<'_, V> {
         Update::new(self, value)
     }
 
@@ -151,6 +155,30 @@ impl<'a, Qs> Cursor<'a, Qs> {
     {
         Stream::new_ser(self)
     }
+
+    /// Convert this cursor into a stream of primitive `(key, primary_key, value)` entries.
+    #[cfg(feature = "streams")]
+    #[must_use]
+    pub fn entry_stream<K, PK, V>(self) -> Stream<Self, (K, PK, V)>
+    where
+        K: TryFromJs,
+        PK: TryFromJs,
+        V: TryFromJs,
+    {
+        Stream::new_js_entry(self)
+    }
+
+    /// Convert this cursor into a stream of `serde`-deserialisable `(key, primary_key, value)` entries.
+    #[cfg(all(feature = "streams", feature = "serde"))]
+    #[must_use]
+    pub fn entry_stream_ser<K, PK, V>(self) -> Stream<Self, (K, PK, V)>
+    where
+        K: crate::serde::DeserialiseFromJs,
+        PK: crate::serde::DeserialiseFromJs,
+        V: crate::serde::DeserialiseFromJs,
+    {
+        Stream::new_ser_entry(self)
+    }
 }
 
 #[::sealed::sealed]
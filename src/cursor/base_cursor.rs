@@ -1,18 +1,18 @@
-use super::{CursorDirection, CursorSys};
+use super::CursorDirection;
 use crate::future::request::listeners::EventTargetResult;
-use crate::future::{PollUnpinned, Request};
-use crate::internal_utils::SystemRepr;
-use crate::primitive::{TryFromJs, TryToJs};
+use crate::future::{PollUnpinned, Raw, Request};
+use crate::internal_utils::{Encode, SystemRepr};
+use crate::primitive::{TryFromJs, TryFromJsExt, TryToJs};
+use crate::repr::CursorRepr;
 use fancy_constructor::new;
 use internal_macros::errdoc;
 use std::task::{Context, Poll};
-use wasm_bindgen::prelude::*;
 
 /// Base implementation for all cursors.
 #[derive(Debug, new)]
-#[new(vis(pub(super)))]
+#[new(vis(pub(crate)))]
 pub struct BaseCursor {
-    sys: CursorSys,
+    sys: CursorRepr,
 
     #[new(default)]
     state: CursorState,
@@ -44,7 +44,7 @@ impl BaseCursor {
         Option<T>: TryFromJs,
     {
         let key = self.as_sys().primary_key()?;
-        TryFromJs::from_js(key).map_err(Into::into)
+        Option::<T>::from_raw(key)
     }
 
     /// [`Self::primary_key`] mirror using `serde`.
@@ -55,7 +55,7 @@ impl BaseCursor {
         Option<T>: crate::serde::DeserialiseFromJs,
     {
         let key = self.as_sys().primary_key()?;
-        crate::serde::DeserialiseFromJs::deserialise_from_js(key)
+        crate::serde::deserialise_from_raw(key)
     }
 
     /// Advance the cursor by `step` records.
@@ -87,7 +87,6 @@ impl BaseCursor {
     where
         T: TryToJs,
     {
-        let key = key.try_to_js()?;
         self.continue_to_key_common(&key).await
     }
 
@@ -98,8 +97,8 @@ impl BaseCursor {
     where
         T: crate::serde::SerialiseToJs,
     {
-        let key = key.serialise_to_js()?;
-        self.continue_to_key_common(&key).await
+        self.continue_to_key_common(&crate::internal_utils::Ser(&key))
+            .await
     }
 
     /// Get the cursor's direction
@@ -141,15 +140,11 @@ impl BaseCursor {
     }
 
     fn has_req(&self) -> bool {
-        let req = self.as_sys().maybe_req();
-        !req.is_undefined() && !req.is_null()
+        self.as_sys().has_req()
     }
 
     fn has_key(&self) -> bool {
-        match self.as_sys().key() {
-            Ok(k) => !k.is_undefined() && !k.is_null(),
-            Err(_) => false,
-        }
+        self.as_sys().has_key()
     }
 
     fn read_current<R, F>(&mut self, callback: F) -> crate::Result<Option<R>>
@@ -175,7 +170,7 @@ impl BaseCursor {
         F: FnOnce(&mut Self) -> crate::Result<R>,
     {
         if let Err(e) = self.as_sys().continue_() {
-            return Poll::Ready(Err(e.into()));
+            return Poll::Ready(Err(e));
         }
 
         let mut req = self.req();
@@ -199,11 +194,13 @@ impl BaseCursor {
 
                 let should_continue = res.map(|event_result| match event_result {
                     EventTargetResult::Null => false,
-                    EventTargetResult::Cursor(cursor_sys) => {
-                        self.sys = cursor_sys;
+                    EventTargetResult::Cursor(cursor) => {
+                        self.sys = cursor;
                         true
                     }
                     EventTargetResult::NotNull => true,
+                    #[cfg(feature = "backend")]
+                    EventTargetResult::Value(_) => true,
                 });
 
                 Poll::Ready(match should_continue {
@@ -225,7 +222,7 @@ impl BaseCursor {
         }
     }
 
-    async fn continue_to_key_common(&mut self, key: &JsValue) -> crate::Result<()> {
+    async fn continue_to_key_common(&mut self, key: &dyn Encode) -> crate::Result<()> {
         self.as_sys().continue_with_key(key)?;
         self.req().await?;
         self.on_cursor_position_reset();
@@ -238,19 +235,25 @@ impl BaseCursor {
         }
     }
 
-    pub(crate) fn key_sys(&self) -> crate::Result<JsValue> {
-        self.as_sys().key().map_err(Into::into)
+    pub(crate) fn key_sys(&self) -> crate::Result<Raw> {
+        self.as_sys().key()
     }
 
-    pub(crate) fn value_sys(&self) -> crate::Result<JsValue> {
-        self.as_sys().value().map_err(Into::into)
+    pub(crate) fn value_sys(&self) -> crate::Result<Raw> {
+        self.as_sys().value()
+    }
+
+    /// Read the key, primary key & value at the cursor's current position.
+    #[cfg(feature = "streams")]
+    pub(crate) fn entry_sys(&self) -> crate::Result<[Raw; 3]> {
+        self.as_sys().entry()
     }
 }
 
 #[::sealed::sealed]
 #[allow(unused_qualifications)]
 impl crate::internal_utils::SystemRepr for BaseCursor {
-    type Repr = CursorSys;
+    type Repr = CursorRepr;
 
     #[inline]
     fn as_sys(&self) -> &Self::Repr {
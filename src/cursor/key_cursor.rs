@@ -1,7 +1,8 @@
-use super::{BaseCursor, CursorSys};
+use super::BaseCursor;
 use crate::future::CursorNextRequest;
 use crate::internal_utils::SystemRepr;
 use crate::primitive::TryFromJs;
+use crate::repr::CursorRepr;
 use accessory::Accessors;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
 use internal_macros::errdoc;
@@ -21,7 +22,7 @@ pub struct KeyCursor<'a, Qs> {
 }
 
 impl<'a, Qs> KeyCursor<'a, Qs> {
-    pub(crate) fn new(base: CursorSys, source: &'a Qs) -> Self {
+    pub(crate) fn new(base: CursorRepr, source: &'a Qs) -> Self {
         Self {
             base: BaseCursor::new(base),
             source,
@@ -34,7 +35,7 @@ impl<'a, Qs> KeyCursor<'a, Qs> {
     /// followed by [`key`](https://developer.mozilla.org/en-US/docs/Web/API/IDBCursor/key) in JS.
     #[inline]
     #[errdoc(Cursor(TransactionInactiveError, InvalidStateError))]
-    pub fn next_key<T>(&mut self) -> CursorNextRequest<T>
+    pub fn next_key<T>(&mut self) -> CursorNextRequest<'_, T>
     where
         T: TryFromJs,
     {
@@ -44,7 +45,7 @@ impl<'a, Qs> KeyCursor<'a, Qs> {
     /// Mirror of [`Self::next_key`] for `serde`-deserialisable keys.
     #[inline]
     #[cfg(feature = "serde")]
-    pub fn next_key_ser<T>(&mut self) -> CursorNextRequest<T>
+    pub fn next_key_ser<T>(&mut self) -> CursorNextRequest<'_, T>
     where
         T: crate::serde::DeserialiseFromJs,
     {
@@ -92,12 +93,14 @@ impl<Qs> crate::internal_utils::SystemRepr for KeyCursor<'_, Qs> {
 
 #[cfg(feature = "indices")]
 const _: () = {
+    use crate::internal_utils::Encode;
     use crate::primitive::TryToJs;
-    use wasm_bindgen::prelude::*;
 
     impl KeyCursor<'_, crate::index::Index<'_>> {
         /// Advance the cursor to the record whose key matches the `key` as well as whose primary key matches the
-        /// `primary_key`.
+        /// `primary_key`. Only applicable to cursors iterating in the [`Next`](super::CursorDirection::Next) or
+        /// [`Prev`](super::CursorDirection::Prev) direction. Also available on [`Cursor`](super::Cursor)s & cursor
+        /// streams opened on an index.
         ///
         /// A typical use case, is to resume the iteration where a previous cursor has been closed, without having to
         /// compare the keys one by one.
@@ -121,8 +124,6 @@ const _: () = {
             K: TryToJs,
             PK: TryToJs,
         {
-            let key = key.try_to_js()?;
-            let primary_key = primary_key.try_to_js()?;
             self.continue_to_primary_key_common(&key, &primary_key)
                 .await
         }
@@ -139,16 +140,15 @@ const _: () = {
             K: crate::serde::SerialiseToJs,
             PK: crate::serde::SerialiseToJs,
         {
-            let key = key.serialise_to_js()?;
-            let primary_key = primary_key.serialise_to_js()?;
-            self.continue_to_primary_key_common(&key, &primary_key)
+            use crate::internal_utils::Ser;
+            self.continue_to_primary_key_common(&Ser(&key), &Ser(&primary_key))
                 .await
         }
 
         async fn continue_to_primary_key_common(
             &mut self,
-            key: &JsValue,
-            value: &JsValue,
+            key: &dyn Encode,
+            value: &dyn Encode,
         ) -> crate::Result<()> {
             self.as_sys().continue_primary_key(key, value)?;
             self.req().await?;
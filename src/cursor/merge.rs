@@ -0,0 +1,272 @@
+use super::CursorDirection;
+use crate::IdbKey;
+use futures_core::TryStream;
+use sealed::sealed;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A k-way merge of several streams of `(key, primary_key, value)` entries, such as
+/// [entry streams](super::Cursor::entry_stream), into one stream in global key order.
+///
+/// Useful for implementing `IN (...)` and `OR` queries: open a cursor per key range, index or object store within
+/// the same transaction and merge them. Each source must already be sorted in the merge's
+/// [direction](Self::with_direction), i.e. be read by a cursor opened in that direction. Keys are compared using the
+/// [key ordering](IdbKey#impl-Ord-for-IdbKey) the browser uses & entries with equal keys are ordered by their primary
+/// keys.
+///
+/// If a source errors, the error is yielded and that source is dropped from the merge.
+#[must_use]
+pub struct MergedStream<S>
+where
+    S: TryStream,
+    S::Ok: Entry,
+{
+    sources: Vec<Source<S>>,
+    direction: CursorDirection,
+    seen: Option<BTreeSet<IdbKey>>,
+    last_key: Option<IdbKey>,
+}
+
+struct Source<S: TryStream> {
+    stream: S,
+    head: Option<Head<S::Ok>>,
+    finished: bool,
+}
+
+/// A source's next entry along with its key & primary key converted for comparison.
+struct Head<E> {
+    key: IdbKey,
+    primary_key: IdbKey,
+    entry: E,
+}
+
+impl<E: Entry> Head<E> {
+    fn new(entry: E) -> Self {
+        Self {
+            key: entry.key().clone().into(),
+            primary_key: entry.primary_key().clone().into(),
+            entry,
+        }
+    }
+}
+
+/// An entry that can be merged by a [`MergedStream`]: a `(key, primary_key, value)` tuple whose key & primary key
+/// are [`Clone`] + [`Into<IdbKey>`]. Use [`IdbKey`] itself to merge keys of arbitrary types.
+#[sealed]
+pub trait Entry {
+    /// The entry's key type.
+    #[doc(hidden)]
+    type Key: Clone + Into<IdbKey>;
+
+    /// The entry's primary key type.
+    #[doc(hidden)]
+    type PrimaryKey: Clone + Into<IdbKey>;
+
+    /// The entry's key.
+    #[doc(hidden)]
+    fn key(&self) -> &Self::Key;
+
+    /// The entry's primary key.
+    #[doc(hidden)]
+    fn primary_key(&self) -> &Self::PrimaryKey;
+}
+
+#[sealed]
+impl<K, PK, V> Entry for (K, PK, V)
+where
+    K: Clone + Into<IdbKey>,
+    PK: Clone + Into<IdbKey>,
+{
+    type Key = K;
+    type PrimaryKey = PK;
+
+    #[inline]
+    fn key(&self) -> &K {
+        &self.0
+    }
+
+    #[inline]
+    fn primary_key(&self) -> &PK {
+        &self.1
+    }
+}
+
+impl<S> MergedStream<S>
+where
+    S: TryStream,
+    S::Ok: Entry,
+{
+    /// Merge the given streams in the [`Next`](CursorDirection::Next) direction, without de-duplication.
+    pub fn new<I>(streams: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+    {
+        Self {
+            sources: streams
+                .into_iter()
+                .map(move |stream| Source {
+                    stream,
+                    head: None,
+                    finished: false,
+                })
+                .collect(),
+            direction: CursorDirection::Next,
+            seen: None,
+            last_key: None,
+        }
+    }
+
+    /// Set the direction the merged streams are sorted in. [`Prev`](CursorDirection::Prev) &
+    /// [`Prevunique`](CursorDirection::Prevunique) yield entries in descending order; the `unique` directions
+    /// additionally skip entries whose key matches that of the previously yielded one, even if they come from
+    /// different sources.
+    #[inline]
+    pub fn with_direction(mut self, direction: CursorDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Only yield the first entry for each primary key, e.g. when the same record matches several of the merged
+    /// ranges or indices. The primary keys of all yielded entries are kept in memory until the stream is dropped.
+    #[inline]
+    pub fn dedup_by_primary_key(mut self) -> Self {
+        self.seen = Some(BTreeSet::new());
+        self
+    }
+
+    /// Compare two entries in the merge's direction. Mirrors cursor behaviour: primary keys are descending in the
+    /// [`Prev`](CursorDirection::Prev) direction, but [`Prevunique`](CursorDirection::Prevunique) yields the
+    /// lowest primary key of each key.
+    fn cmp_entries(&self, a: &Head<S::Ok>, b: &Head<S::Ok>) -> Ordering {
+        let keys = a.key.cmp(&b.key);
+        let primary_keys = a.primary_key.cmp(&b.primary_key);
+
+        match self.direction {
+            CursorDirection::Prev => keys.reverse().then(primary_keys.reverse()),
+            CursorDirection::Prevunique => keys.reverse().then(primary_keys),
+            _ => keys.then(primary_keys),
+        }
+    }
+
+    fn is_unique(&self) -> bool {
+        matches!(
+            self.direction,
+            CursorDirection::Nextunique | CursorDirection::Prevunique
+        )
+    }
+
+    /// Index of the source whose head comes first in the merge's direction.
+    fn next_source(&self) -> Option<usize> {
+        let mut out: Option<(usize, &Head<S::Ok>)> = None;
+
+        for (idx, source) in self.sources.iter().enumerate() {
+            let Some(ref head) = source.head else {
+                continue;
+            };
+
+            if !matches!(out, Some((_, best)) if self.cmp_entries(head, best) != Ordering::Less) {
+                out = Some((idx, head));
+            }
+        }
+
+        out.map(move |(idx, _)| idx)
+    }
+
+    /// Returns `None` if the entry should be skipped as a duplicate. Records the entry as seen otherwise.
+    fn dedup(&mut self, head: Head<S::Ok>) -> Option<S::Ok> {
+        if self.is_unique() {
+            if self.last_key.as_ref() == Some(&head.key) {
+                return None;
+            }
+            self.last_key = Some(head.key);
+        }
+
+        match self.seen {
+            Some(ref mut seen) => seen.insert(head.primary_key).then_some(head.entry),
+            None => Some(head.entry),
+        }
+    }
+}
+
+impl<S> futures_core::Stream for MergedStream<S>
+where
+    S: TryStream + Unpin,
+    S::Ok: Entry + Unpin,
+{
+    type Item = Result<S::Ok, S::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let mut pending = false;
+            for source in &mut this.sources {
+                if source.head.is_some() || source.finished {
+                    continue;
+                }
+
+                match Pin::new(&mut source.stream).try_poll_next(cx) {
+                    Poll::Ready(Some(Ok(entry))) => {
+                        source.head = Some(Head::new(entry));
+                    }
+                    Poll::Ready(Some(Err(e))) => {
+                        source.finished = true;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    Poll::Ready(None) => {
+                        source.finished = true;
+                    }
+                    Poll::Pending => {
+                        pending = true;
+                    }
+                }
+            }
+
+            // Every unfinished source needs a head before we know which entry comes next
+            if pending {
+                return Poll::Pending;
+            }
+
+            let Some(idx) = this.next_source() else {
+                return Poll::Ready(None);
+            };
+            let Some(head) = this.sources[idx].head.take() else {
+                unreachable!("next_source() only returns sources with a head");
+            };
+
+            if let Some(entry) = this.dedup(head) {
+                return Poll::Ready(Some(Ok(entry)));
+            }
+        }
+    }
+}
+
+impl<S> futures_core::FusedStream for MergedStream<S>
+where
+    S: TryStream + Unpin,
+    S::Ok: Entry + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.sources
+            .iter()
+            .all(move |source| source.finished && source.head.is_none())
+    }
+}
+
+impl<S> Debug for MergedStream<S>
+where
+    S: TryStream,
+    S::Ok: Entry,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MergedStream")
+            .field("sources", &self.sources.len())
+            .field("direction", &self.direction)
+            .field("seen", &self.seen)
+            .field("last_key", &self.last_key)
+            .finish()
+    }
+}
@@ -5,7 +5,6 @@ use derive_more::{Deref, DerefMut};
 use internal_macros::errdoc;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use wasm_bindgen::prelude::*;
 
 /// A stream off a [`Cursor`] or [`KeyCursor`].
 #[derive(Deref, DerefMut)]
@@ -13,8 +12,7 @@ pub struct Stream<Cur, T> {
     #[deref]
     #[deref_mut]
     cursor: Cur,
-    read_sys: fn(&BaseCursor) -> crate::Result<JsValue>,
-    fmt_sys: fn(JsValue) -> crate::Result<T>,
+    read: fn(&BaseCursor) -> crate::Result<T>,
 }
 
 impl<'a, Qs, T> Stream<KeyCursor<'a, Qs>, T> {
@@ -25,8 +23,7 @@ impl<'a, Qs, T> Stream<KeyCursor<'a, Qs>, T> {
     {
         Self {
             cursor,
-            read_sys: BaseCursor::key_sys,
-            fmt_sys: T::from_js_base,
+            read: |cursor| T::from_raw(cursor.key_sys()?),
         }
     }
 
@@ -38,13 +35,21 @@ impl<'a, Qs, T> Stream<KeyCursor<'a, Qs>, T> {
     {
         Self {
             cursor,
-            read_sys: BaseCursor::key_sys,
-            fmt_sys: T::deserialise_from_js,
+            read: |cursor| crate::serde::deserialise_from_raw(cursor.key_sys()?),
         }
     }
 }
 
 impl<'a, Qs, T> Stream<Cursor<'a, Qs>, T> {
+    #[cfg(feature = "typed")]
+    #[inline]
+    pub(crate) fn new_with(
+        cursor: Cursor<'a, Qs>,
+        read: fn(&BaseCursor) -> crate::Result<T>,
+    ) -> Self {
+        Self { cursor, read }
+    }
+
     #[inline]
     pub(super) fn new_js(cursor: Cursor<'a, Qs>) -> Self
     where
@@ -52,8 +57,7 @@ impl<'a, Qs, T> Stream<Cursor<'a, Qs>, T> {
     {
         Self {
             cursor,
-            read_sys: BaseCursor::value_sys,
-            fmt_sys: T::from_js_base,
+            read: |cursor| T::from_raw(cursor.value_sys()?),
         }
     }
 
@@ -65,8 +69,7 @@ impl<'a, Qs, T> Stream<Cursor<'a, Qs>, T> {
     {
         Self {
             cursor,
-            read_sys: BaseCursor::value_sys,
-            fmt_sys: T::deserialise_from_js,
+            read: |cursor| crate::serde::deserialise_from_raw(cursor.value_sys()?),
         }
     }
 
@@ -90,7 +93,7 @@ impl<'a, Qs, T> Stream<Cursor<'a, Qs>, T> {
         DataErrorUpdate,
         DataCloneError
     ))]
-    pub fn update<V>(&self, value: V) -> super::Update<V> {
+    pub fn update<V>(&self, value: V) -> super::Update<'_, V> {
         self.cursor.update(value)
     }
 
@@ -119,6 +122,51 @@ impl<'a, Qs, T> Stream<Cursor<'a, Qs>, T> {
     }
 }
 
+impl<'a, Qs, K, PK, V> Stream<Cursor<'a, Qs>, (K, PK, V)> {
+    #[inline]
+    pub(super) fn new_js_entry(cursor: Cursor<'a, Qs>) -> Self
+    where
+        K: TryFromJs,
+        PK: TryFromJs,
+        V: TryFromJs,
+    {
+        Self {
+            cursor,
+            read: |cursor| {
+                let [key, primary_key, value] = cursor.entry_sys()?;
+                Ok((
+                    K::from_raw(key)?,
+                    PK::from_raw(primary_key)?,
+                    V::from_raw(value)?,
+                ))
+            },
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub(super) fn new_ser_entry(cursor: Cursor<'a, Qs>) -> Self
+    where
+        K: crate::serde::DeserialiseFromJs,
+        PK: crate::serde::DeserialiseFromJs,
+        V: crate::serde::DeserialiseFromJs,
+    {
+        Self {
+            cursor,
+            read: |cursor| {
+                use crate::serde::deserialise_from_raw;
+
+                let [key, primary_key, value] = cursor.entry_sys()?;
+                Ok((
+                    deserialise_from_raw(key)?,
+                    deserialise_from_raw(primary_key)?,
+                    deserialise_from_raw(value)?,
+                ))
+            },
+        }
+    }
+}
+
 impl<Cur, T> futures_core::Stream for Stream<Cur, T>
 where
     Cur: AsMut<BaseCursor> + Unpin,
@@ -129,13 +177,10 @@ where
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let Self {
             ref mut cursor,
-            read_sys,
-            fmt_sys,
+            read,
         } = *self;
 
-        let cur_poll = cursor
-            .as_mut()
-            .poll_state(cx, move |cursor| read_sys(cursor).and_then(fmt_sys));
+        let cur_poll = cursor.as_mut().poll_state(cx, move |cursor| read(cursor));
 
         match cur_poll {
             Poll::Ready(Ok(Some(v))) => Poll::Ready(Some(Ok(v))),
@@ -43,8 +43,7 @@ impl<T: TryToJs> crate::BuildPrimitive for Update<'_, T> {
             key_type: _,
         } = self;
 
-        let js = value.try_to_js()?;
-        let req = cur.as_sys().update(&js)?;
+        let req = cur.as_sys().update(&value)?;
 
         Ok(VoidRequest::new(req))
     }
@@ -61,8 +60,7 @@ impl<T: TryToJs, KT: TryFromJs + Unpin> crate::BuildPrimitive for Update<'_, T,
             key_type: _,
         } = self;
 
-        let js = value.try_to_js()?;
-        let req = cur.as_sys().update(&js)?;
+        let req = cur.as_sys().update(&value)?;
 
         Ok(BasicRequest::new_primitive(req))
     }
@@ -70,6 +68,7 @@ impl<T: TryToJs, KT: TryFromJs + Unpin> crate::BuildPrimitive for Update<'_, T,
 
 #[cfg(feature = "serde")]
 const _: () = {
+    use crate::internal_utils::Ser;
     use serde::{de::DeserializeOwned, Serialize};
 
     #[sealed]
@@ -83,8 +82,7 @@ const _: () = {
                 key_type: _,
             } = self;
 
-            let js = serde_wasm_bindgen::to_value(&value)?;
-            let req = cur.as_sys().update(&js)?;
+            let req = cur.as_sys().update(&Ser(&value))?;
 
             Ok(VoidRequest::new(req))
         }
@@ -101,8 +99,7 @@ const _: () = {
                 key_type: _,
             } = self;
 
-            let js = serde_wasm_bindgen::to_value(&value)?;
-            let req = cur.as_sys().update(&js)?;
+            let req = cur.as_sys().update(&Ser(&value))?;
 
             Ok(BasicRequest::new_ser(req))
         }
@@ -12,6 +12,7 @@ pub use version_change_event::VersionChangeEvent;
 use crate::factory::{DBFactory, OpenDbRequestBuilder};
 use crate::internal_utils::SystemRepr;
 use crate::iter::DomStringIter;
+use crate::repr::DatabaseRepr;
 use crate::transaction::TransactionOptionsSys;
 
 pub(crate) mod db_sys;
@@ -32,7 +33,7 @@ iffeat! {
 
 /// An [`IDBDatabase`](https://developer.mozilla.org/en-US/docs/Web/API/IDBDatabase) implementation.
 #[derive(DelegateDebug, Clone)]
-pub struct Database(DbSys);
+pub struct Database(DatabaseRepr);
 
 impl Database {
     /// Open a database with the given name. Convenience method for [`OpenDbRequestBuilder::new`] - use it when opening
@@ -52,7 +53,7 @@ impl Database {
     /// Create an object store with the given name.
     #[generic_bounds(store_name(N))]
     #[inline]
-    pub fn create_object_store<N>(&self, name: N) -> StoreBuilder<N> {
+    pub fn create_object_store<N>(&self, name: N) -> StoreBuilder<'_, N> {
         StoreBuilder::new(self, name)
     }
 
@@ -70,9 +71,16 @@ impl Database {
     /// of this version.
     pub fn delete(self) -> crate::OpenDbResult<VoidRequest> {
         let name = self.name();
-        self.close();
-
-        Self::delete_by_name(&name)
+        let repr = self.into_sys();
+        repr.close();
+
+        match repr {
+            DatabaseRepr::Web(_) => Self::delete_by_name(&name),
+            #[cfg(feature = "backend")]
+            DatabaseRepr::Driver(db) => DBFactory::from_driver_rc(db.factory)
+                .delete_db(&name)
+                .map_err(Into::into),
+        }
     }
 
     /// Delete the object store with the given name.
@@ -83,11 +91,7 @@ impl Database {
     ))]
     #[allow(clippy::missing_errors_doc)]
     pub fn delete_object_store(&self, name: &str) -> crate::Result<()> {
-        if let Err(e) = self.as_sys().delete_object_store(name) {
-            Err(e.into())
-        } else {
-            Ok(())
-        }
+        self.as_sys().delete_object_store(name)
     }
 
     /// Get the database name.
@@ -99,15 +103,15 @@ impl Database {
 
     /// List the names of the object stores within this database.
     #[inline]
-    pub fn object_store_names(&self) -> DomStringIter {
-        DomStringIter::new(self.as_sys().object_store_names())
+    pub fn object_store_names(&self) -> DomStringIter<'_> {
+        self.as_sys().object_store_names()
     }
 
     /// Start a transaction on the given store name(s). Finish the builder with a call to
     /// [`Build::build`](crate::Build::build).
     #[errdoc(Database(NotFoundErrorTx, InvalidAccessErrorTx))]
     #[inline]
-    pub fn transaction<S: ObjectStoreName>(&self, store_names: S) -> TransactionBuilder<S> {
+    pub fn transaction<S: ObjectStoreName>(&self, store_names: S) -> TransactionBuilder<'_, S> {
         TransactionBuilder::new(self, store_names)
     }
 
@@ -131,7 +135,7 @@ impl Database {
 
     /// Create a new
     /// [`versionchange`](https://developer.mozilla.org/en-US/docs/Web/API/IDBDatabase/versionchange_event)
-    /// listener.
+    /// listener. Not supported by [drivers](crate::backend).
     #[cfg(feature = "version-change")]
     #[allow(clippy::missing_errors_doc)]
     pub fn version_changes(&self) -> crate::Result<VersionChangeListener> {
@@ -154,9 +158,24 @@ impl Database {
         }
     }
 
+    /// Wrap a [driver](crate::backend) connection. `upgrade` is the `versionchange` transaction while the
+    /// connection is being upgraded.
+    #[cfg(feature = "backend")]
+    pub(crate) fn from_driver(
+        database: std::rc::Rc<dyn crate::backend::DatabaseDriver>,
+        factory: std::rc::Rc<dyn crate::backend::FactoryDriver>,
+        upgrade: Option<std::rc::Rc<dyn crate::backend::TransactionDriver>>,
+    ) -> Self {
+        Self(DatabaseRepr::Driver(crate::repr::DriverDatabase {
+            database,
+            factory,
+            upgrade,
+        }))
+    }
+
     fn from_js(js: JsValue) -> crate::Result<Self> {
         match js.dyn_into::<web_sys::IdbDatabase>() {
-            Ok(base_db) => Ok(Self(base_db.unchecked_into())),
+            Ok(base_db) => Ok(Self(DatabaseRepr::Web(base_db.unchecked_into()))),
             Err(e) => Err(SimpleValueError::DynCast(e).into()),
         }
     }
@@ -165,7 +184,7 @@ impl Database {
 #[::sealed::sealed]
 #[allow(unused_qualifications)]
 impl crate::internal_utils::SystemRepr for Database {
-    type Repr = DbSys;
+    type Repr = DatabaseRepr;
 
     #[inline]
     #[doc(hidden)]
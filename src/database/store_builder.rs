@@ -62,19 +62,19 @@ impl<'a, N, AI, KP> StoreBuilder<'a, N, AI, KP> {
             key_path,
         }
     }
+}
 
-    #[generic_bounds(store_name(N))]
-    fn build_with_params(
-        &self,
-        p: &web_sys::IdbObjectStoreParameters,
-    ) -> crate::Result<ObjectStore<'a>> {
-        let sys = self
-            .db
-            .as_sys()
-            .create_object_store_with_optional_parameters(self.store_name.as_ref(), p)?;
-
-        Ok(ObjectStore::from_version_change(sys, self.db))
-    }
+fn build_with<'a>(
+    db: &'a Database,
+    name: &str,
+    key_path: Option<KeyPath>,
+    auto_increment: Option<bool>,
+) -> crate::Result<ObjectStore<'a>> {
+    let repr = db
+        .as_sys()
+        .create_object_store(name, key_path, auto_increment)?;
+
+    ObjectStore::from_version_change(repr, db)
 }
 
 #[generic_bounds(store_name(N))]
@@ -84,11 +84,7 @@ impl<'a, N> crate::Build for StoreBuilder<'a, N> {
     type Err = Error;
 
     fn build(self) -> Result<Self::Ok, Self::Err> {
-        let sys = self
-            .db
-            .as_sys()
-            .create_object_store(self.store_name.as_ref())?;
-        Ok(ObjectStore::from_version_change(sys, self.db))
+        build_with(self.db, self.store_name.as_ref(), None, None)
     }
 }
 
@@ -99,10 +95,12 @@ impl<'a, N> crate::Build for StoreBuilder<'a, N, bool> {
     type Err = Error;
 
     fn build(self) -> Result<Self::Ok, Self::Err> {
-        let p = web_sys::IdbObjectStoreParameters::new();
-        p.set_auto_increment(self.auto_increment);
-
-        self.build_with_params(&p)
+        build_with(
+            self.db,
+            self.store_name.as_ref(),
+            None,
+            Some(self.auto_increment),
+        )
     }
 }
 
@@ -113,10 +111,12 @@ impl<'a, N, KP> crate::Build for StoreBuilder<'a, N, (), KeyPath<KP>> {
     type Err = Error;
 
     fn build(self) -> Result<Self::Ok, Self::Err> {
-        let p = web_sys::IdbObjectStoreParameters::new();
-        p.set_key_path(&self.key_path.to_js());
-
-        self.build_with_params(&p)
+        build_with(
+            self.db,
+            self.store_name.as_ref(),
+            Some(self.key_path.into_owned()),
+            None,
+        )
     }
 }
 
@@ -127,10 +127,11 @@ impl<'a, N, KP> crate::Build for StoreBuilder<'a, N, bool, KeyPath<KP>> {
     type Err = Error;
 
     fn build(self) -> Result<Self::Ok, Self::Err> {
-        let p = web_sys::IdbObjectStoreParameters::new();
-        p.set_auto_increment(self.auto_increment);
-        p.set_key_path(&self.key_path.to_js());
-
-        self.build_with_params(&p)
+        build_with(
+            self.db,
+            self.store_name.as_ref(),
+            Some(self.key_path.into_owned()),
+            Some(self.auto_increment),
+        )
     }
 }
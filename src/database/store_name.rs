@@ -1,9 +1,8 @@
 use sealed::sealed;
 use wasm_bindgen::prelude::*;
 
-use super::{Database, TransactionOptionsSys};
+use super::{DbSys, TransactionOptionsSys};
 use crate::internal_utils::slice_to_arr;
-use crate::internal_utils::SystemRepr;
 use crate::transaction::TransactionMode;
 
 type TxResult = Result<web_sys::IdbTransaction, JsValue>;
@@ -14,161 +13,192 @@ type TxResult = Result<web_sys::IdbTransaction, JsValue>;
 pub trait ObjectStoreName {
     /// Open a transaction on the given database.
     #[doc(hidden)]
-    fn transaction(self, db: &Database) -> TxResult;
+    fn transaction(self, db: &DbSys) -> TxResult;
 
     /// Open a transaction on the given database with the given mode.
     #[doc(hidden)]
-    fn transaction_with_mode(self, db: &Database, mode: TransactionMode) -> TxResult;
+    fn transaction_with_mode(self, db: &DbSys, mode: TransactionMode) -> TxResult;
 
     /// Open a transaction on the given database with the given mode and options.
     #[doc(hidden)]
     fn transaction_with_mode_and_options(
         self,
-        db: &Database,
+        db: &DbSys,
         mode: TransactionMode,
         opts: TransactionOptionsSys,
     ) -> TxResult;
+
+    /// Collect the names for a [driver](crate::backend) transaction.
+    #[cfg(feature = "backend")]
+    #[doc(hidden)]
+    fn store_names(self) -> Vec<String>;
 }
 
 #[sealed]
 impl ObjectStoreName for &str {
     #[inline]
-    fn transaction(self, db: &Database) -> TxResult {
-        db.as_sys().transaction_with_str(self.as_ref())
+    fn transaction(self, db: &DbSys) -> TxResult {
+        db.transaction_with_str(self.as_ref())
     }
 
     #[inline]
-    fn transaction_with_mode(self, db: &Database, mode: web_sys::IdbTransactionMode) -> TxResult {
-        db.as_sys()
-            .transaction_with_str_and_mode(self.as_ref(), mode)
+    fn transaction_with_mode(self, db: &DbSys, mode: web_sys::IdbTransactionMode) -> TxResult {
+        db.transaction_with_str_and_mode(self.as_ref(), mode)
     }
 
     #[inline]
     fn transaction_with_mode_and_options(
         self,
-        db: &Database,
+        db: &DbSys,
         mode: web_sys::IdbTransactionMode,
         opts: TransactionOptionsSys,
     ) -> TxResult {
-        db.as_sys()
-            .transaction_with_str_and_mode_and_opts(self.as_ref(), mode, &opts)
+        db.transaction_with_str_and_mode_and_opts(self.as_ref(), mode, &opts)
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn store_names(self) -> Vec<String> {
+        vec![self.into()]
     }
 }
 
 #[sealed]
 impl ObjectStoreName for &String {
     #[inline]
-    fn transaction(self, db: &Database) -> TxResult {
+    fn transaction(self, db: &DbSys) -> TxResult {
         self.as_str().transaction(db)
     }
 
     #[inline]
-    fn transaction_with_mode(self, db: &Database, mode: web_sys::IdbTransactionMode) -> TxResult {
+    fn transaction_with_mode(self, db: &DbSys, mode: web_sys::IdbTransactionMode) -> TxResult {
         self.as_str().transaction_with_mode(db, mode)
     }
 
     #[inline]
     fn transaction_with_mode_and_options(
         self,
-        db: &Database,
+        db: &DbSys,
         mode: web_sys::IdbTransactionMode,
         opts: TransactionOptionsSys,
     ) -> TxResult {
         self.as_str()
             .transaction_with_mode_and_options(db, mode, opts)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn store_names(self) -> Vec<String> {
+        vec![self.clone()]
+    }
 }
 
 #[sealed]
 impl ObjectStoreName for String {
     #[inline]
-    fn transaction(self, db: &Database) -> TxResult {
+    fn transaction(self, db: &DbSys) -> TxResult {
         <&Self>::transaction(&self, db)
     }
 
     #[inline]
-    fn transaction_with_mode(self, db: &Database, mode: web_sys::IdbTransactionMode) -> TxResult {
+    fn transaction_with_mode(self, db: &DbSys, mode: web_sys::IdbTransactionMode) -> TxResult {
         <&Self>::transaction_with_mode(&self, db, mode)
     }
 
     #[inline]
     fn transaction_with_mode_and_options(
         self,
-        db: &Database,
+        db: &DbSys,
         mode: web_sys::IdbTransactionMode,
         opts: TransactionOptionsSys,
     ) -> TxResult {
         <&Self>::transaction_with_mode_and_options(&self, db, mode, opts)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn store_names(self) -> Vec<String> {
+        vec![self]
+    }
 }
 
 #[sealed]
 impl<T: AsRef<str>> ObjectStoreName for &[T] {
-    fn transaction(self, db: &Database) -> TxResult {
-        db.as_sys()
-            .transaction_with_str_sequence(&slice_to_arr(self))
+    fn transaction(self, db: &DbSys) -> TxResult {
+        db.transaction_with_str_sequence(&slice_to_arr(self))
     }
 
-    fn transaction_with_mode(self, db: &Database, mode: web_sys::IdbTransactionMode) -> TxResult {
-        db.as_sys()
-            .transaction_with_str_sequence_and_mode(&slice_to_arr(self), mode)
+    fn transaction_with_mode(self, db: &DbSys, mode: web_sys::IdbTransactionMode) -> TxResult {
+        db.transaction_with_str_sequence_and_mode(&slice_to_arr(self), mode)
     }
 
     fn transaction_with_mode_and_options(
         self,
-        db: &Database,
+        db: &DbSys,
         mode: web_sys::IdbTransactionMode,
         opts: TransactionOptionsSys,
     ) -> TxResult {
-        db.as_sys().transaction_with_str_sequence_and_mode_and_opts(
-            &slice_to_arr(self),
-            mode,
-            &opts,
-        )
+        db.transaction_with_str_sequence_and_mode_and_opts(&slice_to_arr(self), mode, &opts)
+    }
+
+    #[cfg(feature = "backend")]
+    fn store_names(self) -> Vec<String> {
+        self.iter().map(|name| name.as_ref().into()).collect()
     }
 }
 
 #[sealed]
 impl<T: AsRef<str>, const N: usize> ObjectStoreName for [T; N] {
     #[inline]
-    fn transaction(self, db: &Database) -> TxResult {
+    fn transaction(self, db: &DbSys) -> TxResult {
         self.as_slice().transaction(db)
     }
 
     #[inline]
-    fn transaction_with_mode(self, db: &Database, mode: TransactionMode) -> TxResult {
+    fn transaction_with_mode(self, db: &DbSys, mode: TransactionMode) -> TxResult {
         self.as_slice().transaction_with_mode(db, mode)
     }
 
     #[inline]
     fn transaction_with_mode_and_options(
         self,
-        db: &Database,
+        db: &DbSys,
         mode: TransactionMode,
         opts: TransactionOptionsSys,
     ) -> TxResult {
         self.as_slice()
             .transaction_with_mode_and_options(db, mode, opts)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn store_names(self) -> Vec<String> {
+        self.as_slice().store_names()
+    }
 }
 
 #[sealed]
 impl<T: AsRef<str>> ObjectStoreName for Vec<T> {
-    fn transaction(self, db: &Database) -> TxResult {
+    fn transaction(self, db: &DbSys) -> TxResult {
         self.as_slice().transaction(db)
     }
 
-    fn transaction_with_mode(self, db: &Database, mode: TransactionMode) -> TxResult {
+    fn transaction_with_mode(self, db: &DbSys, mode: TransactionMode) -> TxResult {
         self.as_slice().transaction_with_mode(db, mode)
     }
 
     fn transaction_with_mode_and_options(
         self,
-        db: &Database,
+        db: &DbSys,
         mode: TransactionMode,
         opts: TransactionOptionsSys,
     ) -> TxResult {
         self.as_slice()
             .transaction_with_mode_and_options(db, mode, opts)
     }
+
+    #[cfg(feature = "backend")]
+    fn store_names(self) -> Vec<String> {
+        self.as_slice().store_names()
+    }
 }
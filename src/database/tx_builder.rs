@@ -1,5 +1,7 @@
-use super::{Database, ObjectStoreName};
+use super::{Database, DbSys, ObjectStoreName};
 use crate::error::Error;
+use crate::internal_utils::SystemRepr;
+use crate::repr::DatabaseRepr;
 use crate::transaction::{Transaction, TransactionMode, TransactionOptions};
 use sealed::sealed;
 
@@ -51,7 +53,7 @@ impl<'a, S, M, O> TransactionBuilder<'a, S, M, O> {
         }
     }
 
-    /// Set the options for this transaction.
+    /// Set the options for this transaction. Ignored by [drivers](crate::backend).
     #[inline]
     pub fn with_options(
         self,
@@ -66,13 +68,27 @@ impl<'a, S, M, O> TransactionBuilder<'a, S, M, O> {
     }
 }
 
-macro_rules! try_match {
-    ($db: expr, $expr: expr) => {
-        match $expr {
-            Ok(tx) => Ok($crate::transaction::Transaction::new($db, tx)),
-            Err(e) => Err(e.into()),
+/// Start a transaction through `web_sys` or, if the database is backed by a driver, through the driver.
+#[cfg_attr(not(feature = "backend"), allow(unused_variables))]
+fn build_tx<S: ObjectStoreName>(
+    db: &Database,
+    store_names: S,
+    mode: TransactionMode,
+    web: impl FnOnce(S, &DbSys) -> crate::Result<web_sys::IdbTransaction>,
+) -> crate::Result<Transaction<'_>> {
+    match db.as_sys() {
+        DatabaseRepr::Web(sys) => Ok(Transaction::new(db, web(store_names, sys)?)),
+        #[cfg(feature = "backend")]
+        DatabaseRepr::Driver(driver) => {
+            let tx = driver
+                .database
+                .transaction(&store_names.store_names(), mode)?;
+            Ok(Transaction::new(
+                db,
+                crate::repr::TransactionRepr::Driver(tx),
+            ))
         }
-    };
+    }
 }
 
 #[sealed]
@@ -84,7 +100,12 @@ where
     type Err = Error;
 
     fn build(self) -> crate::Result<Transaction<'a>> {
-        try_match!(self.db, self.store_names.transaction(self.db))
+        build_tx(
+            self.db,
+            self.store_names,
+            TransactionMode::Readonly,
+            |names, sys| Ok(names.transaction(sys)?),
+        )
     }
 }
 
@@ -97,8 +118,10 @@ where
     type Err = Error;
 
     fn build(self) -> crate::Result<Transaction<'a>> {
-        let req = self.store_names.transaction_with_mode(self.db, self.mode);
-        try_match!(self.db, req)
+        let mode = self.mode;
+        build_tx(self.db, self.store_names, mode, |names, sys| {
+            Ok(names.transaction_with_mode(sys, mode)?)
+        })
     }
 }
 
@@ -111,11 +134,17 @@ where
     type Err = Error;
 
     fn build(self) -> crate::Result<Transaction<'a>> {
-        let opts = self.opts.try_into()?;
-        let req = self
-            .store_names
-            .transaction_with_mode_and_options(self.db, self.mode, opts);
-        try_match!(self.db, req)
+        let Self {
+            db,
+            store_names,
+            mode,
+            opts,
+        } = self;
+
+        build_tx(db, store_names, mode, move |names, sys| {
+            let opts = opts.try_into()?;
+            Ok(names.transaction_with_mode_and_options(sys, mode, opts)?)
+        })
     }
 }
 
@@ -6,25 +6,79 @@ use std::fmt::{Debug, Formatter};
 /// & [`versionchange`](https://developer.mozilla.org/en-US/docs/Web/API/IDBDatabase/versionchange_event) events.
 #[allow(clippy::too_long_first_doc_paragraph)]
 #[derive(StructName, Clone)]
-pub struct VersionChangeEvent(web_sys::IdbVersionChangeEvent);
+pub struct VersionChangeEvent(Inner);
+
+#[derive(Clone)]
+enum Inner {
+    Web(web_sys::IdbVersionChangeEvent),
+    #[cfg(feature = "backend")]
+    Driver(crate::backend::Upgrade),
+}
 
 impl VersionChangeEvent {
     pub(crate) fn new(event: web_sys::IdbVersionChangeEvent) -> Self {
-        Self(event)
+        Self(Inner::Web(event))
+    }
+
+    /// Create an `upgradeneeded` event for a [driver](crate::backend) upgrade.
+    #[cfg(feature = "backend")]
+    pub(crate) fn driver(upgrade: crate::backend::Upgrade) -> Self {
+        Self(Inner::Driver(upgrade))
     }
 
     /// The old version number of the database.
     #[inline]
     #[must_use]
     pub fn old_version(&self) -> f64 {
-        self.0.old_version()
+        match self.0 {
+            Inner::Web(ref evt) => evt.old_version(),
+            #[cfg(feature = "backend")]
+            Inner::Driver(ref upgrade) => upgrade.old_version.into(),
+        }
     }
 
     /// The new version number of the database. Will be `None` if the database is being deleted.
     #[inline]
     #[must_use]
     pub fn new_version(&self) -> Option<f64> {
-        self.0.new_version()
+        match self.0 {
+            Inner::Web(ref evt) => evt.new_version(),
+            #[cfg(feature = "backend")]
+            Inner::Driver(ref upgrade) => Some(upgrade.new_version.into()),
+        }
+    }
+
+    /// The `versionchange` transaction of the open request that fired an `upgradeneeded` event.
+    #[cfg(any(feature = "schema", feature = "migrations"))]
+    #[cfg_attr(
+        not(feature = "backend"),
+        allow(clippy::infallible_destructuring_match)
+    )]
+    pub(crate) fn upgrade_transaction(&self) -> crate::Result<crate::repr::TransactionRepr> {
+        use crate::error::{SimpleValueError, UnexpectedDataError};
+        use wasm_bindgen::JsCast;
+
+        let evt = match self.0 {
+            Inner::Web(ref evt) => evt,
+            #[cfg(feature = "backend")]
+            Inner::Driver(ref upgrade) => {
+                return Ok(crate::repr::TransactionRepr::Driver(
+                    upgrade.transaction.clone(),
+                ));
+            }
+        };
+
+        let Some(target) = evt.target() else {
+            return Err(UnexpectedDataError::NoEventTarget.into());
+        };
+
+        match target.dyn_into::<web_sys::IdbRequest>() {
+            Ok(req) => match req.transaction() {
+                Some(tx) => Ok(tx.into()),
+                None => Err(UnexpectedDataError::NoUpgradeTransaction.into()),
+            },
+            Err(target) => Err(SimpleValueError::DynCast(target.unchecked_into()).into()),
+        }
     }
 }
 
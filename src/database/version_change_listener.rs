@@ -1,5 +1,6 @@
 use super::{Database, VersionChangeEvent};
 use crate::internal_utils::SystemRepr;
+use crate::repr::DatabaseRepr;
 use accessory::Accessors;
 use std::task::{Context, Poll};
 use wasm_evt_listener::Listener as EvtListener;
@@ -21,12 +22,24 @@ pub struct VersionChangeListener {
 }
 
 impl VersionChangeListener {
+    #[cfg_attr(
+        not(feature = "backend"),
+        allow(clippy::infallible_destructuring_match)
+    )]
     pub(super) fn new(db: Database) -> crate::Result<Self> {
+        let sys = match db.as_sys() {
+            DatabaseRepr::Web(sys) => sys,
+            #[cfg(feature = "backend")]
+            DatabaseRepr::Driver(_) => {
+                return Err(crate::backend::not_supported("Database::version_changes"))
+            }
+        };
+
         let on_close = EvtListener::builder().build()?;
         let on_change = EvtListener::builder().build()?;
 
-        on_close.add_to(EVT_CLOSE, db.as_sys())?;
-        on_change.add_to(EVT_CHANGE, db.as_sys())?;
+        on_close.add_to(EVT_CLOSE, sys)?;
+        on_change.add_to(EVT_CHANGE, sys)?;
 
         Ok(Self {
             db,
@@ -77,8 +90,15 @@ impl VersionChangeListener {
 
 impl Drop for VersionChangeListener {
     fn drop(&mut self) {
-        let _ = self.on_close.rm_from(EVT_CLOSE, self.db.as_sys());
-        let _ = self.on_change.rm_from(EVT_CHANGE, self.db.as_sys());
+        match self.db.as_sys() {
+            DatabaseRepr::Web(sys) => {
+                let _ = self.on_close.rm_from(EVT_CLOSE, sys);
+                let _ = self.on_change.rm_from(EVT_CHANGE, sys);
+            }
+            // Only ever constructed for browser databases
+            #[cfg(feature = "backend")]
+            DatabaseRepr::Driver(_) => {}
+        }
     }
 }
 
@@ -51,6 +51,17 @@ impl TryFromJs for SystemTime {
             },
         }
     }
+
+    #[cfg(feature = "backend")]
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    fn from_value(value: crate::backend::Value) -> Result<Self, SimpleValueError> {
+        match value {
+            crate::backend::Value::Date(ms) if ms >= 0.0 => {
+                Ok(SystemTime::UNIX_EPOCH + Duration::from_millis(ms as u64))
+            }
+            value => Err(SimpleValueError::UnexpectedValue(value)),
+        }
+    }
 }
 
 impl TryToJs for SystemTime {
@@ -64,4 +75,13 @@ impl TryToJs for SystemTime {
             Err(e) => Err(SimpleValueError::Other(Box::new(e)).into()),
         }
     }
+
+    #[cfg(feature = "backend")]
+    #[allow(clippy::cast_precision_loss)]
+    fn try_to_value(&self) -> crate::Result<crate::backend::Value> {
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(duration) => Ok(crate::backend::Value::Date(duration.as_millis() as f64)),
+            Err(e) => Err(SimpleValueError::Other(Box::new(e)).into()),
+        }
+    }
 }
@@ -3,9 +3,16 @@
 use std::sync::PoisonError;
 use wasm_bindgen::prelude::*;
 
+pub use backend::BackendError;
+#[cfg(feature = "typed")]
+pub use codec::CodecError;
 pub use dom_exception::DomException;
 pub use js_error::JSError;
+pub use migration::MigrationError;
 pub use open_db::OpenDbError;
+#[cfg(feature = "cursors")]
+pub use page_token::PageTokenError;
+pub use schema::SchemaMismatchError;
 pub use serde::SerdeError;
 pub use serialisation::SerialisationError;
 pub use simple_value::SimpleValueError;
@@ -20,9 +27,16 @@ macro_rules! fwd_from {
     };
 }
 
+mod backend;
+#[cfg(feature = "typed")]
+mod codec;
 mod dom_exception;
 mod js_error;
+mod migration;
 mod open_db;
+#[cfg(feature = "cursors")]
+mod page_token;
+mod schema;
 mod serde;
 mod serialisation;
 mod simple_value;
@@ -48,6 +62,14 @@ pub enum Error {
     /// Generic JS error.
     #[error("Generic JS error: {0}")]
     Unknown(#[from] JSError),
+
+    /// A [migration](crate::migration) step failed.
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+
+    /// A [driver](crate::backend) error.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
 }
 
 fwd_from!(SimpleValueError, SerialisationError > Error);
@@ -0,0 +1,60 @@
+/// An error raised by a driver.
+///
+/// Is an empty struct if the `backend` feature is not enabled.
+#[cfg(not(feature = "backend"))]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("BackendError")]
+pub struct BackendError {}
+
+/// An error raised by a [driver](crate::backend). Variants mirror the [`DomException`]s a browser would throw in the
+/// same situation.
+///
+/// [`DomException`]: crate::error::DomException
+#[cfg(feature = "backend")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BackendError {
+    /// The object is in an invalid state.
+    #[error("InvalidStateError: {0}")]
+    InvalidStateError(String),
+
+    /// A request was placed against a transaction that is not active.
+    #[error("TransactionInactiveError: {0}")]
+    TransactionInactiveError(String),
+
+    /// The object cannot be found here.
+    #[error("NotFoundError: {0}")]
+    NotFoundError(String),
+
+    /// A mutation operation in a transaction failed because a constraint was not satisfied.
+    #[error("ConstraintError: {0}")]
+    ConstraintError(String),
+
+    /// The object does not support the operation or argument.
+    #[error("InvalidAccessError: {0}")]
+    InvalidAccessError(String),
+
+    /// Provided data is inadequate.
+    #[error("DataError: {0}")]
+    DataError(String),
+
+    /// The operation was aborted.
+    #[error("AbortError: {0}")]
+    AbortError(String),
+
+    /// A mutating operation was attempted in a [`Readonly`](crate::transaction::TransactionMode::Readonly)
+    /// transaction.
+    #[error("ReadOnlyError: {0}")]
+    ReadOnlyError(String),
+
+    /// The value could not be (de)serialised.
+    #[error("DataCloneError: {0}")]
+    DataCloneError(String),
+
+    /// An attempt was made to open a database using a lower version than the existing version.
+    #[error("VersionError: {0}")]
+    VersionError(String),
+
+    /// The operation isn't supported by the driver, e.g. it relies on browser-only APIs.
+    #[error("NotSupportedError: {0}")]
+    NotSupportedError(String),
+}
@@ -0,0 +1,55 @@
+use super::{Error, SerialisationError};
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+
+/// Error encoding or decoding a value with a [`Codec`](crate::typed::Codec).
+#[derive(Debug)]
+pub struct CodecError {
+    codec: &'static str,
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+impl CodecError {
+    /// Wrap an error raised by the named codec.
+    pub fn new<E>(codec: &'static str, source: E) -> Self
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        Self {
+            codec,
+            source: source.into(),
+        }
+    }
+
+    /// Name of the codec that raised the error.
+    #[inline]
+    #[must_use]
+    pub fn codec(&self) -> &'static str {
+        self.codec
+    }
+}
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} codec error: {}", self.codec, self.source)
+    }
+}
+
+impl StdError for CodecError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+impl PartialEq for CodecError {
+    fn eq(&self, other: &Self) -> bool {
+        self.codec == other.codec && self.source.to_string() == other.source.to_string()
+    }
+}
+
+impl From<CodecError> for Error {
+    fn from(value: CodecError) -> Self {
+        SerialisationError::from(value).into()
+    }
+}
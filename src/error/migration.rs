@@ -0,0 +1,42 @@
+#[cfg(feature = "migrations")]
+use super::Error;
+
+/// A [migration](crate::migration) step failed.
+///
+/// Is an empty struct if the `migrations` feature is not enabled.
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[cfg_attr(
+    feature = "migrations",
+    error("Migration to version {version} failed: {source}")
+)]
+#[cfg_attr(not(feature = "migrations"), error("MigrationError"))]
+pub struct MigrationError {
+    #[cfg(feature = "migrations")]
+    version: u32,
+    #[cfg(feature = "migrations")]
+    source: Box<Error>,
+}
+
+#[cfg(feature = "migrations")]
+impl MigrationError {
+    pub(crate) fn new(version: u32, source: Error) -> Self {
+        Self {
+            version,
+            source: Box::new(source),
+        }
+    }
+
+    /// The version of the migration that failed.
+    #[inline]
+    #[must_use]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The error the migration failed with.
+    #[inline]
+    #[must_use]
+    pub fn error(&self) -> &Error {
+        &self.source
+    }
+}
@@ -17,6 +17,11 @@ pub enum OpenDbError {
     #[error("The `indexedDB` getter returned `null` or `undefined`")]
     NullFactory,
 
+    /// The opened database didn't match the
+    /// [expected schema](crate::factory::OpenDbRequestBuilder::with_expected_schema).
+    #[error(transparent)]
+    SchemaMismatch(#[from] super::SchemaMismatchError),
+
     /// A forwarded base error.
     #[error(transparent)]
     Base(#[from] Error),
@@ -27,3 +32,4 @@ fwd_from!(web_sys::DomException, Error > OpenDbError);
 fwd_from!(js_sys::Error, Error > OpenDbError);
 fwd_from!(JsValue, Error > OpenDbError);
 fwd_from!(UnexpectedDataError, Error > OpenDbError);
+fwd_from!(super::BackendError, Error > OpenDbError);
@@ -0,0 +1,4 @@
+/// A [`PageToken`](crate::query_source::PageToken) couldn't be parsed.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, thiserror::Error)]
+#[error("Malformed page token.")]
+pub struct PageTokenError;
@@ -0,0 +1,58 @@
+use cfg_if::cfg_if;
+use std::fmt::{Display, Formatter};
+
+cfg_if! {
+    if #[cfg(feature = "schema")] {
+        use crate::schema::SchemaMismatch;
+    } else {
+        use crate::internal_utils::StructName;
+    }
+}
+
+/// The opened database didn't match the
+/// [expected schema](crate::factory::OpenDbRequestBuilder::with_expected_schema).
+///
+/// Is an empty struct if the `schema` feature is not enabled.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(not(feature = "schema"), derive(StructName))]
+pub struct SchemaMismatchError {
+    #[cfg(feature = "schema")]
+    mismatches: Vec<SchemaMismatch>,
+}
+
+#[cfg(feature = "schema")]
+impl SchemaMismatchError {
+    pub(crate) fn new(mismatches: Vec<SchemaMismatch>) -> Self {
+        Self { mismatches }
+    }
+
+    /// Every way the database differed from the expected schema.
+    #[inline]
+    #[must_use]
+    pub fn mismatches(&self) -> &[SchemaMismatch] {
+        &self.mismatches
+    }
+}
+
+impl Display for SchemaMismatchError {
+    #[cfg(feature = "schema")]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Database schema mismatch")?;
+
+        let mut sep = ": ";
+        for mismatch in &self.mismatches {
+            f.write_str(sep)?;
+            Display::fmt(mismatch, f)?;
+            sep = "; ";
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "schema"))]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(<Self as StructName>::TYPE_NAME)
+    }
+}
+
+impl std::error::Error for SchemaMismatchError {}
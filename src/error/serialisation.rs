@@ -10,4 +10,9 @@ pub enum SerialisationError {
     /// Error (de)serialising a value. Holds an empty struct if the `serde` feature is not enabled.
     #[error(transparent)]
     Serde(#[from] SerdeError),
+
+    /// Error encoding or decoding a value with a [`Codec`](crate::typed::Codec).
+    #[cfg(feature = "typed")]
+    #[error(transparent)]
+    Codec(#[from] super::CodecError),
 }
@@ -33,11 +33,26 @@ pub enum SimpleValueError {
     #[error("The value is signed: {0}")]
     Signed(f64),
 
+    /// Expected an array of a fixed length, e.g. a compound key being converted into a tuple.
+    #[error("Expected an array of length {expected}, got {actual}")]
+    ArrayLength {
+        /// The expected length.
+        expected: u32,
+
+        /// The array's actual length.
+        actual: u32,
+    },
+
     /// Error performing [`TryFromJs`](crate::primitive::TryFromJs) on a [`Switch2`](crate::primitive::Switch2) or its
     /// derivatives.
     #[error("Errors performing `TryFromJs` on `Switch`: {}", &FmtSimpleValueErrorArray(_0))]
     Switch(Vec<SimpleValueError>),
 
+    /// A [driver](crate::backend) returned a value of a different type than expected.
+    #[cfg(feature = "backend")]
+    #[error("Unexpected driver value: {0:?}")]
+    UnexpectedValue(crate::backend::Value),
+
     /// Any other catch-all error.
     #[error(transparent)]
     Other(Box<dyn StdError>),
@@ -57,9 +72,23 @@ impl PartialEq for SimpleValueError {
             | (Self::TooSmall(a), Self::TooSmall(b))
             | (Self::Signed(a), Self::Signed(b)) => a.eq(b),
 
+            (
+                Self::ArrayLength {
+                    expected: a_exp,
+                    actual: a_act,
+                },
+                Self::ArrayLength {
+                    expected: b_exp,
+                    actual: b_act,
+                },
+            ) => a_exp == b_exp && a_act == b_act,
+
             // Switch
             (Self::Switch(ref a), Self::Switch(ref b)) => a.eq(b),
 
+            #[cfg(feature = "backend")]
+            (Self::UnexpectedValue(a), Self::UnexpectedValue(b)) => a.eq(b),
+
             // Other
             (Self::Other(ref a), Self::Other(ref b)) => a.to_string() == b.to_string(),
 
@@ -30,6 +30,10 @@ pub enum UnexpectedDataError {
     #[error("Expected the Transaction to be committed, but it was aborted.")]
     TransactionAborted,
 
+    /// An `upgradeneeded` event's request had no version change transaction.
+    #[error("No version change transaction.")]
+    NoUpgradeTransaction,
+
     /// A mutex was poisoned.
     #[error("Mutex poisoned.")]
     PoisonedLock,
@@ -5,13 +5,16 @@ use wasm_bindgen::prelude::*;
 use web_sys::{Window, WorkerGlobalScope};
 
 pub use db_version::DBVersion;
-use internal_macros::generic_bounds;
+use internal_macros::{errdoc, generic_bounds};
 pub use req_builder::OpenDbRequestBuilder;
 
 use crate::error::OpenDbError;
-use crate::future::{Request, VoidRequest};
+use crate::future::{OpenDbListener, OpenDbRequest, Request, VoidRequest};
 use crate::internal_utils::SystemRepr;
+use crate::primitive::TryToJs;
+use crate::repr::FactoryRepr;
 use crate::OpenDbResult;
+use std::cmp::Ordering;
 
 mod db_version;
 
@@ -27,13 +30,54 @@ mod req_builder;
 ///
 /// Use this instead of [`Database::open`](crate::database::Database::open) when you want to open multiple databases.
 #[derive(Clone, Eq, PartialEq, DelegateDebug)]
-pub struct DBFactory(FactorySys);
+pub struct DBFactory(FactoryRepr);
 
 impl DBFactory {
     /// Create a new instance of the factory.
     #[allow(clippy::missing_errors_doc)]
     pub fn new() -> OpenDbResult<Self> {
-        raw_factory().map(Self)
+        raw_factory().map(|sys| Self(FactoryRepr::Web(sys)))
+    }
+
+    /// Create a factory backed by a [driver](crate::backend) instead of the browser's `IndexedDB`.
+    #[cfg(feature = "backend")]
+    pub fn from_driver<D: crate::backend::FactoryDriver + 'static>(driver: D) -> Self {
+        Self::from_driver_rc(std::rc::Rc::new(driver))
+    }
+
+    /// Create a factory backed by a new, empty [in-memory driver](crate::memory).
+    #[cfg(feature = "memory")]
+    #[must_use]
+    pub fn memory() -> Self {
+        Self::from_driver(crate::memory::MemoryFactory::new())
+    }
+
+    #[cfg(feature = "backend")]
+    pub(crate) fn from_driver_rc(driver: std::rc::Rc<dyn crate::backend::FactoryDriver>) -> Self {
+        Self(FactoryRepr::Driver(driver))
+    }
+
+    /// Compare two keys using the browser's
+    /// [key ordering](https://developer.mozilla.org/en-US/docs/Web/API/IDBFactory/cmp). [`IdbKey`](crate::IdbKey)'s
+    /// [`Ord`] implementation follows the same rules without a round trip to JS.
+    #[errdoc(QuerySource(DataError))]
+    #[allow(clippy::missing_errors_doc)]
+    pub fn cmp<A, B>(&self, first: &A, second: &B) -> crate::Result<Ordering>
+    where
+        A: TryToJs + ?Sized,
+        B: TryToJs + ?Sized,
+    {
+        match self.as_sys() {
+            FactoryRepr::Web(sys) => {
+                let res = sys.cmp(&first.try_to_js()?, &second.try_to_js()?)?;
+                Ok(res.cmp(&0))
+            }
+            #[cfg(feature = "backend")]
+            FactoryRepr::Driver(_) => {
+                use crate::internal_utils::Encode;
+                Ok(first.to_key()?.cmp(&second.to_key()?))
+            }
+        }
     }
 
     /// Delete the database with the given name
@@ -43,9 +87,16 @@ impl DBFactory {
     /// [Undocumented](https://developer.mozilla.org/en-US/docs/Web/API/IDBFactory/deleteDatabase) as of the release
     /// of this version.
     pub fn delete_db(&self, name: &str) -> crate::Result<VoidRequest> {
-        let req = self.as_sys().delete_database(name)?;
-
-        Ok(Request::new(req.unchecked_into()))
+        match self.as_sys() {
+            FactoryRepr::Web(sys) => {
+                let req = sys.delete_database(name)?;
+                Ok(Request::new(req.unchecked_into::<web_sys::IdbRequest>()))
+            }
+            #[cfg(feature = "backend")]
+            FactoryRepr::Driver(driver) => Ok(Request::new(
+                crate::future::UntypedRequest::driver_future(driver.delete(name)),
+            )),
+        }
     }
 
     /// Open a database with the given name. Convenience method for [`OpenDbRequestBuilder::new`] followed by
@@ -55,25 +106,52 @@ impl DBFactory {
         OpenDbRequestBuilder::new(name).with_factory(self.clone())
     }
 
+    /// Start opening a database. `blocked` listeners are ignored by [drivers](crate::backend) as their connections
+    /// never block each other.
     #[generic_bounds(db_version(V))]
-    pub(crate) fn open_versioned_request<V>(
+    pub(crate) fn open_with<V>(
         &self,
         name: &str,
-        version: V,
-    ) -> OpenDbResult<VoidRequest> {
-        let res = version.into_idb_open_request(self, name);
-        fmt_open_raw(res)
-    }
-
-    pub(crate) fn open_request(&self, name: &str) -> OpenDbResult<VoidRequest> {
-        fmt_open_raw(self.as_sys().open(name))
+        version: Option<V>,
+        on_blocked: Option<OpenDbListener>,
+        on_upgrade_needed: Option<OpenDbListener>,
+    ) -> OpenDbResult<OpenDbRequest> {
+        match self.as_sys() {
+            FactoryRepr::Web(sys) => {
+                let res = match version {
+                    Some(version) => version.into_idb_open_request(sys, name),
+                    None => sys.open(name),
+                };
+                let req = fmt_open_raw(res)?;
+
+                Ok(OpenDbRequest::web(req, on_blocked, on_upgrade_needed))
+            }
+            #[cfg(feature = "backend")]
+            FactoryRepr::Driver(driver) => {
+                let version = match version {
+                    Some(version) => version.to_driver_version()?,
+                    None => None,
+                };
+                let on_upgrade_needed = on_upgrade_needed
+                    .and_then(|listener| listener.into_driver_upgrade(driver.clone()));
+                let fut = driver.open(name, version, on_upgrade_needed);
+                let factory = driver.clone();
+
+                Ok(OpenDbRequest::driver(Box::pin(async move {
+                    let database = fut.await?;
+                    Ok(crate::database::Database::from_driver(
+                        database, factory, None,
+                    ))
+                })))
+            }
+        }
     }
 }
 
 #[::sealed::sealed]
 #[allow(unused_qualifications)]
 impl crate::internal_utils::SystemRepr for DBFactory {
-    type Repr = FactorySys;
+    type Repr = FactoryRepr;
 
     #[inline]
     fn as_sys(&self) -> &Self::Repr {
@@ -86,12 +164,10 @@ impl crate::internal_utils::SystemRepr for DBFactory {
     }
 }
 
-fn fmt_open_raw(res: Result<web_sys::IdbOpenDbRequest, JsValue>) -> OpenDbResult<VoidRequest> {
-    if let Ok(v) = res {
-        Ok(Request::new(v.unchecked_into()))
-    } else {
-        Err(OpenDbError::VersionZero)
-    }
+fn fmt_open_raw(
+    res: Result<web_sys::IdbOpenDbRequest, JsValue>,
+) -> OpenDbResult<web_sys::IdbOpenDbRequest> {
+    res.map_err(|_| OpenDbError::VersionZero)
 }
 
 /// Access to the low-level `wasm-bindgen` factory
@@ -1,9 +1,7 @@
 use sealed::sealed;
 use wasm_bindgen::prelude::*;
 
-use crate::internal_utils::SystemRepr;
-
-use super::DBFactory;
+use super::FactorySys;
 
 /// A low-level trait marking the type as usable in
 /// [`OpenDbRequestBuilder::with_version`](crate::factory::OpenDbRequestBuilder::with_version).
@@ -14,9 +12,20 @@ pub trait DBVersion: Copy + Clone {
     #[doc(hidden)]
     fn into_idb_open_request(
         self,
-        factory: &DBFactory,
+        factory: &FactorySys,
         name: &str,
     ) -> Result<web_sys::IdbOpenDbRequest, JsValue>;
+
+    /// Convert into the version passed to [`FactoryDriver::open`](crate::backend::FactoryDriver::open).
+    ///
+    /// # Errors
+    ///
+    /// - [`VersionZero`](crate::error::OpenDbError::VersionZero) for `0`.
+    /// - [`NotSupportedError`](crate::error::BackendError::NotSupportedError) for versions that aren't whole
+    ///   numbers within `u32`'s range.
+    #[cfg(feature = "backend")]
+    #[doc(hidden)]
+    fn to_driver_version(self) -> crate::OpenDbResult<Option<u32>>;
 }
 
 #[sealed]
@@ -24,10 +33,19 @@ impl DBVersion for u32 {
     #[inline]
     fn into_idb_open_request(
         self,
-        factory: &DBFactory,
+        factory: &FactorySys,
         name: &str,
     ) -> Result<web_sys::IdbOpenDbRequest, JsValue> {
-        factory.as_sys().open_with_u32(name, self)
+        factory.open_with_u32(name, self)
+    }
+
+    #[cfg(feature = "backend")]
+    fn to_driver_version(self) -> crate::OpenDbResult<Option<u32>> {
+        if self == 0 {
+            Err(crate::error::OpenDbError::VersionZero)
+        } else {
+            Ok(Some(self))
+        }
     }
 }
 
@@ -36,10 +54,43 @@ impl DBVersion for f64 {
     #[inline]
     fn into_idb_open_request(
         self,
-        factory: &DBFactory,
+        factory: &FactorySys,
         name: &str,
     ) -> Result<web_sys::IdbOpenDbRequest, JsValue> {
-        factory.as_sys().open_with_f64(name, self)
+        factory.open_with_f64(name, self)
+    }
+
+    #[cfg(feature = "backend")]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn to_driver_version(self) -> crate::OpenDbResult<Option<u32>> {
+        if self.fract() == 0.0 && (0.0..=f64::from(u32::MAX)).contains(&self) {
+            (self as u32).to_driver_version()
+        } else {
+            Err(crate::backend::not_supported("A non-u32 database version").into())
+        }
+    }
+}
+
+/// `None` opens the database at its current version, or at version `1` if it doesn't exist yet.
+#[sealed]
+impl DBVersion for Option<u32> {
+    fn into_idb_open_request(
+        self,
+        factory: &FactorySys,
+        name: &str,
+    ) -> Result<web_sys::IdbOpenDbRequest, JsValue> {
+        match self {
+            Some(version) => version.into_idb_open_request(factory, name),
+            None => factory.open(name),
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    fn to_driver_version(self) -> crate::OpenDbResult<Option<u32>> {
+        match self {
+            Some(version) => version.to_driver_version(),
+            None => Ok(None),
+        }
     }
 }
 
@@ -48,9 +99,15 @@ macro_rules! db_version_alias {
         $(
             #[::sealed::sealed]
             impl DBVersion for $src {
-                fn into_idb_open_request(self, factory: &super::DBFactory, name: &str) -> Result<::web_sys::IdbOpenDbRequest, ::wasm_bindgen::JsValue> {
+                fn into_idb_open_request(self, factory: &super::FactorySys, name: &str) -> Result<::web_sys::IdbOpenDbRequest, ::wasm_bindgen::JsValue> {
                     <$as as DBVersion>::into_idb_open_request(self.into(), factory, name)
                 }
+
+                #[cfg(feature = "backend")]
+                #[inline]
+                fn to_driver_version(self) -> crate::OpenDbResult<Option<u32>> {
+                    <$as as DBVersion>::to_driver_version(self.into())
+                }
             }
         )+
     };
@@ -59,9 +116,15 @@ macro_rules! db_version_alias {
             #[::sealed::sealed]
             impl DBVersion for ::std::num::$ty {
                 #[inline]
-                fn into_idb_open_request(self, factory: &super::DBFactory, name: &str) -> Result<::web_sys::IdbOpenDbRequest, ::wasm_bindgen::JsValue> {
+                fn into_idb_open_request(self, factory: &super::FactorySys, name: &str) -> Result<::web_sys::IdbOpenDbRequest, ::wasm_bindgen::JsValue> {
                     self.get().into_idb_open_request(factory, name)
                 }
+
+                #[cfg(feature = "backend")]
+                #[inline]
+                fn to_driver_version(self) -> crate::OpenDbResult<Option<u32>> {
+                    Ok(Some(self.get().into()))
+                }
             }
         )+
     };
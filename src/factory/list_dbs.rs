@@ -10,6 +10,7 @@ use wasm_bindgen::prelude::*;
 use crate::error::SimpleValueError;
 use crate::future::ListDatabasesFuture;
 use crate::internal_utils::SystemRepr;
+use crate::repr::FactoryRepr;
 
 use super::DBFactory;
 
@@ -28,10 +29,22 @@ pub struct DatabaseDetails {
 }
 
 impl DBFactory {
-    /// List the names and versions of all databases.
+    /// List the names and versions of all databases. Not supported by [drivers](crate::backend).
     #[allow(clippy::missing_errors_doc)]
+    #[cfg_attr(
+        not(feature = "backend"),
+        allow(clippy::infallible_destructuring_match)
+    )]
     pub fn databases(&self) -> crate::Result<ListDatabasesFuture> {
-        match self.as_sys().databases()?.dyn_into::<js_sys::Promise>() {
+        let sys = match self.as_sys() {
+            FactoryRepr::Web(sys) => sys,
+            #[cfg(feature = "backend")]
+            FactoryRepr::Driver(_) => {
+                return Err(crate::backend::not_supported("DBFactory::databases"))
+            }
+        };
+
+        match sys.databases()?.dyn_into::<js_sys::Promise>() {
             Ok(promise) => Ok(ListDatabasesFuture::list_databases(promise)),
             Err(jsval) => Err(SimpleValueError::DynCast(jsval).into()),
         }
@@ -1,6 +1,6 @@
 use super::DBFactory;
 use crate::error::OpenDbError;
-use crate::future::{OpenDbListener, OpenDbRequest};
+use crate::future::{ExpectedSchema, OpenDbListener, OpenDbRequest};
 use internal_macros::{generic_bounds, BuildIntoFut, StructName};
 use sealed::sealed;
 
@@ -15,6 +15,7 @@ pub struct OpenDbRequestBuilder<N, V = (), B = (), U = (), Fa = ()> {
     on_blocked: B,
     on_upgrade_needed: U,
     factory: Fa,
+    expected_schema: ExpectedSchema,
 }
 
 impl<N> OpenDbRequestBuilder<N> {
@@ -28,6 +29,7 @@ impl<N> OpenDbRequestBuilder<N> {
             on_blocked: (),
             on_upgrade_needed: (),
             factory: (),
+            expected_schema: ExpectedSchema::default(),
         }
     }
 }
@@ -42,6 +44,7 @@ impl<N, V, B, U, Fa> OpenDbRequestBuilder<N, V, B, U, Fa> {
             on_blocked: self.on_blocked,
             on_upgrade_needed: self.on_upgrade_needed,
             factory: self.factory,
+            expected_schema: self.expected_schema,
         }
     }
 
@@ -54,6 +57,7 @@ impl<N, V, B, U, Fa> OpenDbRequestBuilder<N, V, B, U, Fa> {
             on_blocked: self.on_blocked,
             on_upgrade_needed: self.on_upgrade_needed,
             factory: self.factory,
+            expected_schema: self.expected_schema,
         }
     }
 
@@ -65,6 +69,7 @@ impl<N, V, B, U, Fa> OpenDbRequestBuilder<N, V, B, U, Fa> {
             on_blocked: self.on_blocked,
             on_upgrade_needed: self.on_upgrade_needed,
             factory,
+            expected_schema: self.expected_schema,
         }
     }
 
@@ -81,6 +86,7 @@ impl<N, V, B, U, Fa> OpenDbRequestBuilder<N, V, B, U, Fa> {
             on_blocked: OpenDbListener::new_blocked(on_blocked),
             on_upgrade_needed: self.on_upgrade_needed,
             factory: self.factory,
+            expected_schema: self.expected_schema,
         }
     }
 
@@ -97,6 +103,7 @@ impl<N, V, B, U, Fa> OpenDbRequestBuilder<N, V, B, U, Fa> {
             on_blocked: self.on_blocked,
             on_upgrade_needed: OpenDbListener::new_upgrade(on_upgrade_needed),
             factory: self.factory,
+            expected_schema: self.expected_schema,
         }
     }
 
@@ -114,6 +121,62 @@ impl<N, V, B, U, Fa> OpenDbRequestBuilder<N, V, B, U, Fa> {
             on_blocked: self.on_blocked,
             on_upgrade_needed: OpenDbListener::new_upgrade_fut(on_upgrade_needed),
             factory: self.factory,
+            expected_schema: self.expected_schema,
+        }
+    }
+
+    /// Bring the database in line with the given schema when an upgrade is needed. See the
+    /// [module docs](crate::schema) for how existing object stores & indices are altered.
+    ///
+    /// Replaces any previously set [`upgradeneeded`](Self::with_on_upgrade_needed) event handler.
+    #[cfg(feature = "schema")]
+    pub fn with_schema(
+        self,
+        schema: crate::schema::Schema,
+    ) -> OpenDbRequestBuilder<N, V, B, OpenDbListener, Fa> {
+        OpenDbRequestBuilder {
+            name: self.name,
+            version: self.version,
+            on_blocked: self.on_blocked,
+            on_upgrade_needed: OpenDbListener::new_upgrade(move |evt, db| {
+                schema.apply_to(&crate::schema::UpgradeTarget::new(&evt, &db)?)
+            }),
+            factory: self.factory,
+            expected_schema: self.expected_schema,
+        }
+    }
+
+    /// Verify the opened database against the given schema, failing with an
+    /// [`OpenDbError::SchemaMismatch`] listing every difference if it doesn't match. Runs after any
+    /// [`upgradeneeded`](Self::with_on_upgrade_needed) event handler, so it can be combined with
+    /// [`with_schema`](Self::with_schema) or [migrations](crate::migration) to catch upgrades that didn't produce the
+    /// expected layout.
+    ///
+    /// The database gets closed if it doesn't match.
+    #[cfg(feature = "schema")]
+    pub fn with_expected_schema(mut self, schema: crate::schema::Schema) -> Self {
+        self.expected_schema = schema.into();
+        self
+    }
+
+    /// Open the database at the [migrations'](crate::migration) highest version & run the steps needed to get there
+    /// when an upgrade is needed. Empty migrations open the database at its current version.
+    ///
+    /// Replaces any previously set version & [`upgradeneeded`](Self::with_on_upgrade_needed) event handler.
+    #[cfg(feature = "migrations")]
+    pub fn with_migrations(
+        self,
+        migrations: crate::migration::Migrations,
+    ) -> OpenDbRequestBuilder<N, Option<u32>, B, OpenDbListener, Fa> {
+        OpenDbRequestBuilder {
+            name: self.name,
+            version: migrations.version(),
+            on_blocked: self.on_blocked,
+            on_upgrade_needed: OpenDbListener::new_upgrade_fut(move |evt, db| {
+                migrations.run(evt, db)
+            }),
+            factory: self.factory,
+            expected_schema: self.expected_schema,
         }
     }
 
@@ -141,8 +204,10 @@ impl<N> crate::Build for WithFactory<N> {
     type Err = OpenDbError;
 
     fn build(self) -> crate::OpenDbResult<Self::Ok> {
-        let req = self.factory.open_request(self.name())?;
-        Ok(OpenDbRequest::bare(req))
+        let req = self
+            .factory
+            .open_with(self.name.as_ref(), None::<u32>, None, None)?;
+        Ok(req.with_expected_schema(self.expected_schema))
     }
 }
 
@@ -153,14 +218,13 @@ impl<N, V> crate::Build for WithFactory<N, V, OpenDbListener, OpenDbListener> {
     type Err = OpenDbError;
 
     fn build(self) -> crate::OpenDbResult<Self::Ok> {
-        let req = self
-            .factory
-            .open_versioned_request(self.name(), self.version())?;
-        Ok(OpenDbRequest::with_both(
-            req,
-            self.on_blocked,
-            self.on_upgrade_needed,
-        ))
+        let req = self.factory.open_with(
+            self.name.as_ref(),
+            Some(self.version),
+            Some(self.on_blocked),
+            Some(self.on_upgrade_needed),
+        )?;
+        Ok(req.with_expected_schema(self.expected_schema))
     }
 }
 
@@ -171,12 +235,13 @@ impl<N> crate::Build for WithFactory<N, (), OpenDbListener, OpenDbListener> {
     type Err = OpenDbError;
 
     fn build(self) -> crate::OpenDbResult<Self::Ok> {
-        let req = self.factory.open_request(self.name())?;
-        Ok(OpenDbRequest::with_both(
-            req,
-            self.on_blocked,
-            self.on_upgrade_needed,
-        ))
+        let req = self.factory.open_with(
+            self.name.as_ref(),
+            None::<u32>,
+            Some(self.on_blocked),
+            Some(self.on_upgrade_needed),
+        )?;
+        Ok(req.with_expected_schema(self.expected_schema))
     }
 }
 
@@ -187,10 +252,13 @@ impl<N, V> crate::Build for WithFactory<N, V, (), OpenDbListener> {
     type Err = OpenDbError;
 
     fn build(self) -> crate::OpenDbResult<Self::Ok> {
-        let req = self
-            .factory
-            .open_versioned_request(self.name(), self.version())?;
-        Ok(OpenDbRequest::with_upgrade(req, self.on_upgrade_needed))
+        let req = self.factory.open_with(
+            self.name.as_ref(),
+            Some(self.version),
+            None,
+            Some(self.on_upgrade_needed),
+        )?;
+        Ok(req.with_expected_schema(self.expected_schema))
     }
 }
 
@@ -201,8 +269,13 @@ impl<N> crate::Build for WithFactory<N, (), (), OpenDbListener> {
     type Err = OpenDbError;
 
     fn build(self) -> crate::OpenDbResult<Self::Ok> {
-        let req = self.factory.open_request(self.name())?;
-        Ok(OpenDbRequest::with_upgrade(req, self.on_upgrade_needed))
+        let req = self.factory.open_with(
+            self.name.as_ref(),
+            None::<u32>,
+            None,
+            Some(self.on_upgrade_needed),
+        )?;
+        Ok(req.with_expected_schema(self.expected_schema))
     }
 }
 
@@ -213,10 +286,13 @@ impl<N, V> crate::Build for WithFactory<N, V, OpenDbListener> {
     type Err = OpenDbError;
 
     fn build(self) -> crate::OpenDbResult<Self::Ok> {
-        let req = self
-            .factory
-            .open_versioned_request(self.name(), self.version())?;
-        Ok(OpenDbRequest::with_block(req, self.on_blocked))
+        let req = self.factory.open_with(
+            self.name.as_ref(),
+            Some(self.version),
+            Some(self.on_blocked),
+            None,
+        )?;
+        Ok(req.with_expected_schema(self.expected_schema))
     }
 }
 
@@ -227,8 +303,10 @@ impl<N> crate::Build for WithFactory<N, (), OpenDbListener> {
     type Err = OpenDbError;
 
     fn build(self) -> crate::OpenDbResult<Self::Ok> {
-        let req = self.factory.open_request(self.name())?;
-        Ok(OpenDbRequest::with_block(req, self.on_blocked))
+        let req =
+            self.factory
+                .open_with(self.name.as_ref(), None::<u32>, Some(self.on_blocked), None)?;
+        Ok(req.with_expected_schema(self.expected_schema))
     }
 }
 
@@ -241,8 +319,8 @@ impl<N, V> crate::Build for WithFactory<N, V> {
     fn build(self) -> crate::OpenDbResult<Self::Ok> {
         let req = self
             .factory
-            .open_versioned_request(self.name(), self.version())?;
-        Ok(OpenDbRequest::bare(req))
+            .open_with(self.name.as_ref(), Some(self.version), None, None)?;
+        Ok(req.with_expected_schema(self.expected_schema))
     }
 }
 
@@ -1,31 +1,28 @@
-use crate::error::{Error, SimpleValueError};
+use crate::error::Error;
+use crate::future::Raw;
 use crate::iter::ArrayMapIter;
 use fancy_constructor::new;
 use sealed::sealed;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use wasm_bindgen::prelude::*;
 
 /// A future returning an [`ArrayMapIter`].
 #[derive(new)]
 #[new(vis(pub(crate)))]
 pub struct ArrayMapFuture<T, F> {
     src: F,
-    constructor: fn(js_sys::Array) -> ArrayMapIter<T>,
+    constructor: fn(Raw) -> crate::Result<ArrayMapIter<T>>,
 }
 
 impl<T, F> ArrayMapFuture<T, F> {
     fn on_polled<V, E>(&self, poll: Poll<Result<V, E>>) -> Poll<crate::Result<ArrayMapIter<T>>>
     where
-        V: JsCast,
+        V: Into<Raw>,
         E: Into<Error>,
     {
         match poll {
-            Poll::Ready(Ok(js)) => Poll::Ready(match js.dyn_into::<js_sys::Array>() {
-                Ok(arr) => Ok((self.constructor)(arr)),
-                Err(js) => Err(SimpleValueError::DynCast(js.unchecked_into()).into()),
-            }),
+            Poll::Ready(Ok(raw)) => Poll::Ready((self.constructor)(raw.into())),
             Poll::Pending => Poll::Pending,
             Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
         }
@@ -36,7 +33,7 @@ impl<T, F> ArrayMapFuture<T, F> {
 impl<T, F, V, E> super::PollUnpinned for ArrayMapFuture<T, F>
 where
     F: super::PollUnpinned<Output = Result<V, E>>,
-    V: JsCast,
+    V: Into<Raw>,
     E: Into<Error>,
 {
     type Output = crate::Result<ArrayMapIter<T>>;
@@ -50,7 +47,7 @@ where
 impl<T, F, V, E> Future for ArrayMapFuture<T, F>
 where
     F: Future<Output = Result<V, E>> + Unpin,
-    V: JsCast,
+    V: Into<Raw>,
     E: Into<Error>,
 {
     type Output = crate::Result<ArrayMapIter<T>>;
@@ -1,38 +1,41 @@
+use super::request::{Raw, UntypedRequest};
 use super::Request;
-use crate::internal_utils::SystemRepr;
 use crate::primitive::{TryFromJs, TryFromJsExt};
-use fancy_constructor::new;
 use internal_macros::{FutureFromPollUnpinned, StructNameDebug};
 use sealed::sealed;
 use std::task::{Context, Poll};
-use wasm_bindgen::prelude::*;
 
-/// A basic [`Request`] that only performs basic [`JsValue`] conversion.
-#[derive(StructNameDebug, FutureFromPollUnpinned, new)]
-#[new(vis(), args(req: web_sys::IdbRequest))]
+/// A basic [`Request`] that only performs basic [`JsValue`](wasm_bindgen::JsValue) conversion.
+#[derive(StructNameDebug, FutureFromPollUnpinned)]
 pub struct BasicRequest<T> {
-    #[new(val(Request::new(req)))]
     #[debug]
     base: Request,
-    map_fn: fn(JsValue) -> crate::Result<T>,
+    map_fn: fn(Raw) -> crate::Result<T>,
 }
 
 impl<T> BasicRequest<T> {
+    pub(crate) fn new(req: impl Into<UntypedRequest>, map_fn: fn(Raw) -> crate::Result<T>) -> Self {
+        Self {
+            base: Request::new(req),
+            map_fn,
+        }
+    }
+
     #[inline]
-    pub(crate) fn new_primitive(req: web_sys::IdbRequest) -> Self
+    pub(crate) fn new_primitive(req: impl Into<UntypedRequest>) -> Self
     where
         T: TryFromJs,
     {
-        Self::new(req, T::from_js_base)
+        Self::new(req, T::from_raw)
     }
 
     #[cfg(feature = "serde")]
     #[inline]
-    pub(crate) fn new_ser(req: web_sys::IdbRequest) -> Self
+    pub(crate) fn new_ser(req: impl Into<UntypedRequest>) -> Self
     where
         T: crate::serde::DeserialiseFromJs,
     {
-        Self::new(req, T::deserialise_from_js)
+        Self::new(req, crate::serde::deserialise_from_raw)
     }
 }
 
@@ -42,25 +45,9 @@ impl<T> super::PollUnpinned for BasicRequest<T> {
 
     fn poll_unpinned(&mut self, cx: &mut Context) -> Poll<Self::Output> {
         match self.base.poll_unpinned(cx) {
-            Poll::Ready(Ok(js)) => Poll::Ready((self.map_fn)(js)),
+            Poll::Ready(Ok(raw)) => Poll::Ready((self.map_fn)(raw)),
             Poll::Pending => Poll::Pending,
             Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
         }
     }
 }
-
-#[::sealed::sealed]
-#[allow(unused_qualifications)]
-impl<T> crate::internal_utils::SystemRepr for BasicRequest<T> {
-    type Repr = <Request as SystemRepr>::Repr;
-
-    #[inline]
-    fn as_sys(&self) -> &Self::Repr {
-        self.base.as_sys()
-    }
-
-    #[inline]
-    fn into_sys(self) -> Self::Repr {
-        self.base.into_sys()
-    }
-}
@@ -1,5 +1,7 @@
-use super::Request;
-use crate::cursor::{Cursor, CursorSys, KeyCursor};
+use super::request::listeners::EventTargetResult;
+use super::{Request, UntypedRequest};
+use crate::cursor::{Cursor, KeyCursor};
+use crate::repr::CursorRepr;
 use internal_macros::FutureFromPollUnpinned;
 use sealed::sealed;
 use std::marker::PhantomData;
@@ -10,12 +12,12 @@ use wasm_bindgen::JsCast;
 #[derive(FutureFromPollUnpinned)]
 pub struct CursorRequest<'a, T, Qs> {
     source: &'a Qs,
-    req: Request,
+    req: Request<EventTargetResult>,
     cursor_ty: PhantomData<T>,
 }
 
 impl<'a, T, Qs> CursorRequest<'a, T, Qs> {
-    pub(crate) fn new(req: web_sys::IdbRequest, source: &'a Qs) -> Self {
+    pub(crate) fn new(req: UntypedRequest, source: &'a Qs) -> Self {
         Self {
             source,
             req: Request::new(req),
@@ -24,6 +26,26 @@ impl<'a, T, Qs> CursorRequest<'a, T, Qs> {
     }
 }
 
+/// Resolve the result of a request that opens or moves a cursor to the cursor, if it's positioned on a record.
+pub(crate) fn read_cursor(
+    req: &Request<EventTargetResult>,
+    res: EventTargetResult,
+) -> crate::Result<Option<CursorRepr>> {
+    match res {
+        EventTargetResult::Cursor(repr) => Ok(Some(repr)),
+        EventTargetResult::NotNull => match req.web_sys() {
+            Some(req) => Ok(match req.result()?.dyn_into::<web_sys::IdbCursor>() {
+                Ok(sys) => Some(CursorRepr::Web(sys.unchecked_into())),
+                Err(_) => None,
+            }),
+            None => Ok(None),
+        },
+        EventTargetResult::Null => Ok(None),
+        #[cfg(feature = "backend")]
+        EventTargetResult::Value(_) => Ok(None),
+    }
+}
+
 #[sealed]
 impl<'a, T, Qs> super::PollUnpinned for CursorRequest<'a, T, Qs>
 where
@@ -34,10 +56,8 @@ where
     fn poll_unpinned(&mut self, cx: &mut Context) -> Poll<Self::Output> {
         match self.req.poll_unpinned(cx) {
             Poll::Ready(res) => Poll::Ready(match res {
-                Ok(js) => Ok(match js.dyn_into::<web_sys::IdbCursor>() {
-                    Ok(sys) => Some(T::construct(sys.unchecked_into(), self.source)),
-                    Err(_) => None,
-                }),
+                Ok(res) => read_cursor(&self.req, res)
+                    .map(|repr| repr.map(|repr| T::construct(repr, self.source))),
                 Err(e) => Err(e),
             }),
             Poll::Pending => Poll::Pending,
@@ -45,25 +65,25 @@ where
     }
 }
 
-#[sealed]
+#[sealed(pub(crate))]
 #[allow(missing_docs)]
 pub trait CursorKind<'a, Qs> {
     #[doc(hidden)]
-    fn construct(sys: CursorSys, query_source: &'a Qs) -> Self;
+    fn construct(repr: CursorRepr, query_source: &'a Qs) -> Self;
 }
 
 #[sealed]
 impl<'a, Qs> CursorKind<'a, Qs> for Cursor<'a, Qs> {
     #[inline]
-    fn construct(sys: CursorSys, query_source: &'a Qs) -> Self {
-        Cursor::new(sys, query_source)
+    fn construct(repr: CursorRepr, query_source: &'a Qs) -> Self {
+        Cursor::new(repr, query_source)
     }
 }
 
 #[sealed]
 impl<'a, Qs> CursorKind<'a, Qs> for KeyCursor<'a, Qs> {
     #[inline]
-    fn construct(sys: CursorSys, query_source: &'a Qs) -> Self {
-        KeyCursor::new(sys, query_source)
+    fn construct(repr: CursorRepr, query_source: &'a Qs) -> Self {
+        KeyCursor::new(repr, query_source)
     }
 }
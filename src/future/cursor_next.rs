@@ -1,18 +1,18 @@
+use super::Raw;
 use crate::cursor::BaseCursor;
 use crate::primitive::{TryFromJs, TryFromJsExt};
 use fancy_constructor::new;
 use internal_macros::FutureFromPollUnpinned;
 use sealed::sealed;
 use std::task::{Context, Poll};
-use wasm_bindgen::prelude::*;
 
 /// Future for resolving cursors' `next` & `next_key` method calls.
 #[derive(FutureFromPollUnpinned, new)]
-#[new(vis())]
+#[new(vis(pub(crate)))]
 pub struct CursorNextRequest<'a, T> {
     cursor: &'a mut BaseCursor,
-    read_sys: fn(&BaseCursor) -> crate::Result<JsValue>,
-    fmt_sys: fn(JsValue) -> crate::Result<T>,
+    read_sys: fn(&BaseCursor) -> crate::Result<Raw>,
+    fmt_sys: fn(Raw) -> crate::Result<T>,
 }
 
 impl<'a, T> CursorNextRequest<'a, T>
@@ -21,12 +21,12 @@ where
 {
     #[inline]
     pub(crate) fn key_js(cursor: &'a mut BaseCursor) -> Self {
-        Self::new(cursor, BaseCursor::key_sys, T::from_js_base)
+        Self::new(cursor, BaseCursor::key_sys, T::from_raw)
     }
 
     #[inline]
     pub(crate) fn value_js(cursor: &'a mut BaseCursor) -> Self {
-        Self::new(cursor, BaseCursor::value_sys, T::from_js_base)
+        Self::new(cursor, BaseCursor::value_sys, T::from_raw)
     }
 }
 
@@ -40,7 +40,7 @@ where
         Self::new(
             cursor,
             BaseCursor::key_sys,
-            crate::serde::DeserialiseFromJs::deserialise_from_js,
+            crate::serde::deserialise_from_raw,
         )
     }
 
@@ -49,7 +49,7 @@ where
         Self::new(
             cursor,
             BaseCursor::value_sys,
-            crate::serde::DeserialiseFromJs::deserialise_from_js,
+            crate::serde::deserialise_from_raw,
         )
     }
 }
@@ -0,0 +1,83 @@
+use super::cursor::read_cursor;
+use super::request::listeners::EventTargetResult;
+use super::{Request, UntypedRequest};
+use crate::error::UnexpectedDataError;
+use crate::primitive::TryFromJsExt;
+use crate::repr::{CursorRepr, StoreRepr};
+use crate::IdbKey;
+use internal_macros::FutureFromPollUnpinned;
+use sealed::sealed;
+use std::task::{ready, Context, Poll};
+
+/// Future for [`Index::delete_range`](crate::index::Index::delete_range) &
+/// [`Index::delete_range_count`](crate::index::Index::delete_range_count).
+#[derive(FutureFromPollUnpinned)]
+pub struct DeleteRangeRequest<T> {
+    /// `None` once the future's resolved.
+    req: Option<Request<EventTargetResult>>,
+    store: StoreRepr,
+
+    /// Set while waiting on the delete request for the cursor's current record.
+    cursor: Option<CursorRepr>,
+    count: u32,
+    finish: fn(u32) -> T,
+}
+
+impl<T> DeleteRangeRequest<T> {
+    pub(crate) fn new(req: UntypedRequest, store: StoreRepr, finish: fn(u32) -> T) -> Self {
+        Self {
+            req: Some(Request::new(req)),
+            store,
+            cursor: None,
+            count: 0,
+            finish,
+        }
+    }
+
+    /// Handle the request's result, returning `false` once the cursor's iterated past its end.
+    fn on_result(&mut self, cursor: Option<CursorRepr>) -> crate::Result<bool> {
+        // The previous record's been deleted - move on to the next one
+        if let Some(cursor) = self.cursor.take() {
+            cursor.continue_()?;
+            self.req = Some(Request::new(cursor.req()));
+            return Ok(true);
+        }
+
+        let Some(cursor) = cursor else {
+            return Ok(false);
+        };
+
+        let primary_key = IdbKey::from_raw(cursor.primary_key()?)?;
+        let req = self.store.delete(&primary_key)?;
+        self.count += 1;
+        self.cursor = Some(cursor);
+        self.req = Some(Request::new(req));
+
+        Ok(true)
+    }
+}
+
+#[sealed]
+impl<T> super::PollUnpinned for DeleteRangeRequest<T> {
+    type Output = crate::Result<T>;
+
+    fn poll_unpinned(&mut self, cx: &mut Context) -> Poll<Self::Output> {
+        loop {
+            let Some(ref mut req) = self.req else {
+                return Poll::Ready(Err(UnexpectedDataError::PollState.into()));
+            };
+
+            let res = ready!(req.poll_unpinned(cx)).and_then(|res| read_cursor(req, res));
+            self.req = None;
+
+            match res.and_then(|cursor| self.on_result(cursor)) {
+                Ok(true) => {}
+                Ok(false) => return Poll::Ready(Ok((self.finish)(self.count))),
+                Err(e) => {
+                    self.cursor = None;
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use super::cursor::read_cursor;
+use super::request::listeners::EventTargetResult;
+use super::{Raw, Request, UntypedRequest};
+use internal_macros::FutureFromPollUnpinned;
+use sealed::sealed;
+use std::task::{ready, Context, Poll};
+
+/// Future for reading the first record or key of a single-step cursor.
+#[derive(FutureFromPollUnpinned)]
+pub struct EdgeRequest<T> {
+    req: Request<EventTargetResult>,
+    key_only: bool,
+    map_fn: fn(Raw) -> crate::Result<T>,
+}
+
+impl<T> EdgeRequest<T> {
+    pub(crate) fn new(
+        req: UntypedRequest,
+        key_only: bool,
+        map_fn: fn(Raw) -> crate::Result<T>,
+    ) -> Self {
+        Self {
+            req: Request::new(req),
+            key_only,
+            map_fn,
+        }
+    }
+
+    fn read(&self, res: EventTargetResult) -> crate::Result<Option<T>> {
+        let Some(cursor) = read_cursor(&self.req, res)? else {
+            return Ok(None);
+        };
+
+        let raw = if self.key_only {
+            cursor.key()?
+        } else {
+            cursor.value()?
+        };
+
+        (self.map_fn)(raw).map(Some)
+    }
+}
+
+#[sealed]
+impl<T> super::PollUnpinned for EdgeRequest<T> {
+    type Output = crate::Result<Option<T>>;
+
+    fn poll_unpinned(&mut self, cx: &mut Context) -> Poll<Self::Output> {
+        let res = ready!(self.req.poll_unpinned(cx));
+        Poll::Ready(res.and_then(|res| self.read(res)))
+    }
+}
@@ -4,8 +4,10 @@ pub use array_map::ArrayMapFuture;
 pub use basic::BasicRequest;
 pub use get_all::*;
 pub use maybe_errored::MaybeErrored;
+pub(crate) use open_db::ExpectedSchema;
 pub use open_db::{OpenDbListener, OpenDbRequest};
-pub use request::{Request, VoidRequest};
+pub(crate) use request::UntypedRequest;
+pub use request::{Raw, Request, VoidRequest};
 pub use traits::*;
 
 mod array_map;
@@ -26,6 +28,17 @@ iffeat! {
     #[cfg(feature = "cursors")]
     mod cursor_next;
     pub(crate) mod cursor;
+    mod edge;
+    mod page;
+    mod update_where;
     pub use cursor_next::CursorNextRequest;
     pub use cursor::CursorRequest;
+    pub use edge::EdgeRequest;
+    pub use page::PageRequest;
+    pub use update_where::UpdateWhereRequest;
 }
+
+#[cfg(all(feature = "cursors", feature = "indices"))]
+mod delete_range;
+#[cfg(all(feature = "cursors", feature = "indices"))]
+pub use delete_range::DeleteRangeRequest;
@@ -14,63 +14,139 @@ use super::traits::*;
 use super::VoidRequest;
 use crate::database::Database;
 use crate::error::{Error, UnexpectedDataError};
-use crate::internal_utils::SystemRepr;
+
+/// The schema an opened database gets verified against, if any.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExpectedSchema(#[cfg(feature = "schema")] Option<crate::schema::Schema>);
+
+#[cfg(feature = "schema")]
+impl From<crate::schema::Schema> for ExpectedSchema {
+    #[inline]
+    fn from(schema: crate::schema::Schema) -> Self {
+        Self(Some(schema))
+    }
+}
 
 /// Future for opening a database.
 #[derive(Debug, new, FutureFromPollUnpinned)]
 #[new(vis())]
 pub struct OpenDbRequest {
-    req: VoidRequest,
-    listeners: Listeners,
+    inner: Inner,
+
+    #[new(val(ExpectedSchema::default()))]
+    expected_schema: ExpectedSchema,
 
     #[new(val(Self::phase_poll_req))]
     polling_fn: fn(&mut Self, &mut Context) -> Poll<crate::OpenDbResult<Database>>,
 }
 
-impl OpenDbRequest {
-    pub(crate) fn with_upgrade(req: VoidRequest, on_upgrade_needed: OpenDbListener) -> Self {
-        let idb_req = req.as_sys().unchecked_ref();
-        let listeners = Listeners::with_on_upgrade_needed(idb_req, on_upgrade_needed);
+#[derive(derive_more::Debug)]
+enum Inner {
+    Web {
+        sys: web_sys::IdbOpenDbRequest,
+        req: VoidRequest,
+        listeners: Listeners,
+    },
+    #[cfg(feature = "backend")]
+    Driver(#[debug(skip)] crate::backend::DriverFuture<Database>),
+}
 
-        Self::new(req, listeners)
+impl OpenDbRequest {
+    pub(crate) fn web(
+        sys: web_sys::IdbOpenDbRequest,
+        on_blocked: Option<OpenDbListener>,
+        on_upgrade_needed: Option<OpenDbListener>,
+    ) -> Self {
+        let listeners = match (on_blocked, on_upgrade_needed) {
+            (Some(on_blocked), Some(on_upgrade_needed)) => {
+                Listeners::with_both(&sys, on_blocked, on_upgrade_needed)
+            }
+            (Some(on_blocked), None) => Listeners::with_block(&sys, on_blocked),
+            (None, Some(on_upgrade_needed)) => {
+                Listeners::with_on_upgrade_needed(&sys, on_upgrade_needed)
+            }
+            (None, None) => Listeners::with_neither(),
+        };
+        let req = VoidRequest::new(sys.clone().unchecked_into::<web_sys::IdbRequest>());
+
+        Self::new(Inner::Web {
+            sys,
+            req,
+            listeners,
+        })
     }
 
-    pub(crate) fn with_block(req: VoidRequest, on_blocked: OpenDbListener) -> Self {
-        let idb_req = req.as_sys().unchecked_ref();
-        let listeners = Listeners::with_block(idb_req, on_blocked);
-
-        Self::new(req, listeners)
+    /// Create a request that resolves once a [driver](crate::backend) has opened the database.
+    #[cfg(feature = "backend")]
+    pub(crate) fn driver(fut: crate::backend::DriverFuture<Database>) -> Self {
+        Self::new(Inner::Driver(fut))
     }
 
-    pub(crate) fn with_both(
-        req: VoidRequest,
-        on_blocked: OpenDbListener,
-        on_upgrade_needed: OpenDbListener,
-    ) -> Self {
-        let idb_req = req.as_sys().unchecked_ref();
-        let listeners = Listeners::with_both(idb_req, on_blocked, on_upgrade_needed);
-
-        Self::new(req, listeners)
+    #[inline]
+    pub(crate) fn with_expected_schema(mut self, expected_schema: ExpectedSchema) -> Self {
+        self.expected_schema = expected_schema;
+        self
     }
 
-    pub(crate) fn bare(req: VoidRequest) -> Self {
-        Self::new(req, Listeners::with_neither())
+    fn take_listener_error(&self) -> crate::Result<()> {
+        match self.inner {
+            Inner::Web { ref listeners, .. } => listeners.take_error(),
+            #[cfg(feature = "backend")]
+            Inner::Driver(_) => Ok(()),
+        }
     }
 
     fn take_ok(&self) -> crate::OpenDbResult<Database> {
-        self.listeners.take_error()?;
-        Database::from_req(self.as_sys()).map_err(Into::into)
+        self.take_listener_error()?;
+        let db = match self.inner {
+            Inner::Web { ref sys, .. } => Database::from_req(sys)?,
+            #[cfg(feature = "backend")]
+            Inner::Driver(_) => return Err(UnexpectedDataError::PollState.into()),
+        };
+
+        self.verify(db)
+    }
+
+    #[cfg_attr(
+        not(feature = "schema"),
+        allow(clippy::unused_self, clippy::unnecessary_wraps)
+    )]
+    fn verify(&self, db: Database) -> crate::OpenDbResult<Database> {
+        #[cfg(feature = "schema")]
+        if let Some(ref expected) = self.expected_schema.0 {
+            let mismatches = expected.mismatches(db.schema_snapshot()?.schema());
+            if !mismatches.is_empty() {
+                db.close();
+                return Err(crate::error::SchemaMismatchError::new(mismatches).into());
+            }
+        }
+
+        Ok(db)
     }
 
     fn take_err<V>(&self, err_thrown: Error) -> crate::OpenDbResult<V> {
-        self.listeners.take_error()?;
+        self.take_listener_error()?;
         Err(err_thrown.into())
     }
 }
 
 impl OpenDbRequest {
     fn phase_poll_req(&mut self, cx: &mut Context) -> Poll<crate::OpenDbResult<Database>> {
-        match self.req.poll_unpinned(cx) {
+        let req = match self.inner {
+            Inner::Web { ref mut req, .. } => req,
+            #[cfg(feature = "backend")]
+            Inner::Driver(ref mut fut) => {
+                return match std::future::Future::poll(fut.as_mut(), cx) {
+                    Poll::Ready(res) => {
+                        self.polling_fn = Self::phase_done;
+                        Poll::Ready(res.map_err(Into::into).and_then(|db| self.verify(db)))
+                    }
+                    Poll::Pending => Poll::Pending,
+                };
+            }
+        };
+
+        match req.poll_unpinned(cx) {
             Poll::Ready(Ok(())) => {
                 cfg_if! {
                     if #[cfg(feature = "async-upgrade")] {
@@ -92,7 +168,15 @@ impl OpenDbRequest {
 
     #[cfg(feature = "async-upgrade")]
     fn phase_poll_listeners(&mut self, cx: &mut Context) -> Poll<crate::OpenDbResult<Database>> {
-        match self.listeners.poll_unpinned(cx) {
+        let listeners = match self.inner {
+            Inner::Web {
+                ref mut listeners, ..
+            } => listeners,
+            #[cfg(feature = "backend")]
+            Inner::Driver(_) => return Poll::Ready(Err(UnexpectedDataError::PollState.into())),
+        };
+
+        match listeners.poll_unpinned(cx) {
             Poll::Ready(Ok(())) => {
                 self.polling_fn = Self::phase_done;
                 Poll::Ready(self.take_ok())
@@ -124,20 +208,14 @@ impl PollUnpinned for OpenDbRequest {
 
 impl Drop for OpenDbRequest {
     fn drop(&mut self) {
-        self.listeners.drop_listeners(self.as_sys());
-    }
-}
-
-#[::sealed::sealed]
-#[allow(unused_qualifications)]
-impl crate::internal_utils::SystemRepr for OpenDbRequest {
-    type Repr = web_sys::IdbOpenDbRequest;
-
-    fn as_sys(&self) -> &Self::Repr {
-        self.req.as_sys().unchecked_ref()
-    }
-
-    fn into_sys(self) -> Self::Repr {
-        self.as_sys().clone()
+        match self.inner {
+            Inner::Web {
+                ref sys,
+                ref listeners,
+                ..
+            } => listeners.drop_listeners(sys),
+            #[cfg(feature = "backend")]
+            Inner::Driver(_) => {}
+        }
     }
 }
@@ -7,6 +7,15 @@ use std::sync::{Arc, Mutex};
 use wasm_bindgen::prelude::*;
 
 type TClosure = Closure<dyn FnMut(web_sys::IdbVersionChangeEvent) -> Result<(), JsValue> + 'static>;
+type BlockedFn = Box<dyn FnOnce(VersionChangeEvent) -> crate::Result<()>>;
+type UpgradeFn = Box<dyn FnOnce(VersionChangeEvent, Database) -> crate::Result<()>>;
+#[cfg(feature = "async-upgrade")]
+type UpgradeFutFn = Box<
+    dyn FnOnce(
+        VersionChangeEvent,
+        Database,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = crate::Result<()>>>>,
+>;
 
 const LBL_UPGRADE: &str = "onupgradeneeded";
 const LBL_BLOCKED: &str = "onblocked";
@@ -26,7 +35,18 @@ pub struct OpenDbListener {
     status: Arc<Mutex<Status>>,
     #[cfg(feature = "async-upgrade")]
     async_notify: tokio::sync::mpsc::UnboundedReceiver<()>,
-    listener: TClosure,
+    handler: Handler,
+}
+
+/// An event handler. JS closures only get created once the handler's attached to a browser request, so that
+/// [drivers](crate::backend) can run them outside of a JS environment.
+enum Handler {
+    Blocked(BlockedFn),
+    Upgrade(UpgradeFn),
+    #[cfg(feature = "async-upgrade")]
+    UpgradeFut(UpgradeFutFn, tokio::sync::mpsc::UnboundedSender<()>),
+    Attached(TClosure),
+    Taken,
 }
 
 impl Status {
@@ -50,31 +70,78 @@ impl From<Status> for crate::Result<()> {
 impl OpenDbListener {
     #[generic_bounds(upgrade_cb(F))]
     pub(crate) fn new_upgrade<F>(callback: F) -> Self {
-        let status = Status::new();
         Self {
-            status: status.clone(),
+            status: Status::new(),
             #[cfg(feature = "async-upgrade")]
             async_notify: Self::fake_rx(),
-            listener: Closure::once(move |evt: web_sys::IdbVersionChangeEvent| {
-                let res = Database::from_event(&evt)
-                    .and_then(move |db| callback(VersionChangeEvent::new(evt), db));
-
-                Self::handle_result(LBL_UPGRADE, &status, res)
-            }),
+            handler: Handler::Upgrade(Box::new(callback)),
         }
     }
 
     #[generic_bounds(blocked_cb(F))]
     pub(crate) fn new_blocked<F>(callback: F) -> Self {
-        let status = Status::new();
         Self {
-            status: status.clone(),
+            status: Status::new(),
             #[cfg(feature = "async-upgrade")]
             async_notify: Self::fake_rx(),
-            listener: Closure::once(move |evt: web_sys::IdbVersionChangeEvent| {
-                let res = callback(VersionChangeEvent::new(evt));
-                Self::handle_result(LBL_BLOCKED, &status, res)
-            }),
+            handler: Handler::Blocked(Box::new(callback)),
+        }
+    }
+
+    /// Create the JS closure for the handler.
+    fn create_closure(&self, handler: Handler) -> TClosure {
+        let status = self.status.clone();
+        match handler {
+            Handler::Blocked(callback) => {
+                Closure::once(move |evt: web_sys::IdbVersionChangeEvent| {
+                    let res = callback(VersionChangeEvent::new(evt));
+                    Self::handle_result(LBL_BLOCKED, &status, res)
+                })
+            }
+            Handler::Upgrade(callback) => {
+                Closure::once(move |evt: web_sys::IdbVersionChangeEvent| {
+                    let res = Database::from_event(&evt)
+                        .and_then(move |db| callback(VersionChangeEvent::new(evt), db));
+
+                    Self::handle_result(LBL_UPGRADE, &status, res)
+                })
+            }
+            #[cfg(feature = "async-upgrade")]
+            Handler::UpgradeFut(callback, tx) => Self::create_fut_closure(callback, tx, status),
+            Handler::Attached(closure) => closure,
+            Handler::Taken => Closure::once(|_: web_sys::IdbVersionChangeEvent| Ok(())),
+        }
+    }
+
+    /// Convert an `upgradeneeded` handler into a [driver](crate::backend) one. `None` for `blocked` handlers, as
+    /// drivers don't block.
+    #[cfg(feature = "backend")]
+    pub(crate) fn into_driver_upgrade(
+        self,
+        factory: std::rc::Rc<dyn crate::backend::FactoryDriver>,
+    ) -> Option<crate::backend::UpgradeHandler> {
+        use crate::backend::Upgrade;
+
+        let connect = move |upgrade: &Upgrade| {
+            Database::from_driver(
+                upgrade.database.clone(),
+                factory,
+                Some(upgrade.transaction.clone()),
+            )
+        };
+
+        match self.handler {
+            Handler::Upgrade(callback) => Some(Box::new(move |upgrade: Upgrade| {
+                let db = connect(&upgrade);
+                let res = callback(VersionChangeEvent::driver(upgrade), db);
+                Box::pin(std::future::ready(res))
+            })),
+            #[cfg(feature = "async-upgrade")]
+            Handler::UpgradeFut(callback, _) => Some(Box::new(move |upgrade: Upgrade| {
+                let db = connect(&upgrade);
+                callback(VersionChangeEvent::driver(upgrade), db)
+            })),
+            Handler::Blocked(_) | Handler::Attached(_) | Handler::Taken => None,
         }
     }
 
@@ -130,8 +197,17 @@ impl OpenDbListener {
         Ok(mem::replace(&mut *self.status.lock()?, Status::Taken))
     }
 
-    pub(super) fn as_fn(&self) -> &js_sys::Function {
-        self.listener.as_ref().unchecked_ref()
+    /// Get the JS function to attach to a browser request, creating it if needed.
+    pub(super) fn as_fn(&mut self) -> &js_sys::Function {
+        if !matches!(self.handler, Handler::Attached(_)) {
+            let handler = mem::replace(&mut self.handler, Handler::Taken);
+            self.handler = Handler::Attached(self.create_closure(handler));
+        }
+
+        match self.handler {
+            Handler::Attached(ref closure) => closure.as_ref().unchecked_ref(),
+            _ => unreachable!(),
+        }
     }
 }
 
@@ -156,32 +232,42 @@ const _: () = {
 
         #[generic_bounds(upgrade_async_cb(fun(Fn), fut(Fut)))]
         pub(crate) fn new_upgrade_fut<Fn, Fut>(callback: Fn) -> Self {
-            let status = Status::new();
             let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
             Self {
-                status: status.clone(),
+                status: Status::new(),
                 async_notify: rx,
-                listener: Closure::once(move |evt: web_sys::IdbVersionChangeEvent| {
-                    let db = match Database::from_event(&evt) {
-                        Ok(db) => db,
-                        Err(e) => return Self::handle_error_result(LBL_UPGRADE, &status, e),
-                    };
+                handler: Handler::UpgradeFut(
+                    Box::new(move |evt, db| Box::pin(callback(evt, db))),
+                    tx,
+                ),
+            }
+        }
 
-                    Self::set_status(&status, Status::Pending, LBL_UPGRADE)?;
-                    let fut = callback(VersionChangeEvent::new(evt), db);
+        fn create_fut_closure(
+            callback: UpgradeFutFn,
+            tx: tokio::sync::mpsc::UnboundedSender<()>,
+            status: Arc<Mutex<Status>>,
+        ) -> TClosure {
+            Closure::once(move |evt: web_sys::IdbVersionChangeEvent| {
+                let db = match Database::from_event(&evt) {
+                    Ok(db) => db,
+                    Err(e) => return Self::handle_error_result(LBL_UPGRADE, &status, e),
+                };
 
-                    wasm_bindgen_futures::spawn_local(async move {
-                        let result = match fut.await {
-                            Ok(()) => Status::Ok,
-                            Err(e) => Status::Err(e),
-                        };
-                        let _ = Self::set_status(&status, result, LBL_UPGRADE);
-                        let _ = tx.send(());
-                    });
+                Self::set_status(&status, Status::Pending, LBL_UPGRADE)?;
+                let fut = callback(VersionChangeEvent::new(evt), db);
 
-                    Ok(())
-                }),
-            }
+                wasm_bindgen_futures::spawn_local(async move {
+                    let result = match fut.await {
+                        Ok(()) => Status::Ok,
+                        Err(e) => Status::Err(e),
+                    };
+                    let _ = Self::set_status(&status, result, LBL_UPGRADE);
+                    let _ = tx.send(());
+                });
+
+                Ok(())
+            })
         }
 
         fn poll_rx(&mut self, cx: &mut Context<'_>) -> Poll<crate::Result<()>> {
@@ -32,7 +32,7 @@ impl Listeners {
 }
 
 impl Listeners {
-    pub fn with_block(req: &Req, listener: OpenDbListener) -> Self {
+    pub fn with_block(req: &Req, mut listener: OpenDbListener) -> Self {
         #[inline]
         fn cleanup_fn(req: &Req) {
             req.set_onblocked(None);
@@ -43,7 +43,7 @@ impl Listeners {
         Self::with_one(listener, cleanup_fn)
     }
 
-    pub fn with_on_upgrade_needed(req: &Req, listener: OpenDbListener) -> Self {
+    pub fn with_on_upgrade_needed(req: &Req, mut listener: OpenDbListener) -> Self {
         #[inline]
         fn cleanup_fn(req: &Req) {
             req.set_onupgradeneeded(None);
@@ -54,7 +54,7 @@ impl Listeners {
         Self::with_one(listener, cleanup_fn)
     }
 
-    pub fn with_both(req: &Req, blocked: OpenDbListener, upgrade: OpenDbListener) -> Self {
+    pub fn with_both(req: &Req, mut blocked: OpenDbListener, mut upgrade: OpenDbListener) -> Self {
         fn cleanup_fn(req: &Req) {
             req.set_onblocked(None);
             req.set_onupgradeneeded(None);
@@ -0,0 +1,194 @@
+use super::cursor::read_cursor;
+use super::request::listeners::EventTargetResult;
+use super::{Raw, Request, UntypedRequest};
+use crate::cursor::CursorDirection;
+use crate::error::UnexpectedDataError;
+use crate::primitive::TryFromJsExt;
+use crate::query_source::{Page, PageToken};
+use crate::repr::CursorRepr;
+use crate::IdbKey;
+use internal_macros::FutureFromPollUnpinned;
+use sealed::sealed;
+use std::cmp::Ordering;
+use std::task::{ready, Context, Poll};
+
+/// Future for reading a [`Page`] of records.
+#[derive(FutureFromPollUnpinned)]
+pub struct PageRequest<V> {
+    /// `None` once the future's resolved.
+    req: Option<Request<EventTargetResult>>,
+
+    /// Whether the cursor iterates over an index, in which case positions include primary keys.
+    index: bool,
+
+    /// Position to skip past before collecting records.
+    seek: Option<PageToken>,
+    backward: bool,
+    resumed: bool,
+    page_size: u32,
+    records: Vec<V>,
+    first: Option<PageToken>,
+    last: Option<PageToken>,
+    map_fn: fn(Raw) -> crate::Result<V>,
+}
+
+impl<V> PageRequest<V> {
+    pub(crate) fn new(
+        req: UntypedRequest,
+        index: bool,
+        page_size: u32,
+        seek: Option<PageToken>,
+        backward: bool,
+        map_fn: fn(Raw) -> crate::Result<V>,
+    ) -> Self {
+        Self {
+            req: Some(Request::new(req)),
+            index,
+            resumed: seek.is_some(),
+            seek,
+            backward,
+            page_size,
+            records: Vec::new(),
+            first: None,
+            last: None,
+            map_fn,
+        }
+    }
+
+    /// Handle the cursor's current position, resolving to `Some(has_more)` once the page is full or `None` if the
+    /// cursor's been moved.
+    fn on_cursor(&mut self, cursor: &CursorRepr) -> crate::Result<Option<bool>> {
+        let position = PageToken::new(
+            IdbKey::from_raw(cursor.key()?)?,
+            if self.index {
+                Some(IdbKey::from_raw(cursor.primary_key()?)?)
+            } else {
+                None
+            },
+        );
+
+        if let Some(ref seek) = self.seek {
+            let direction = cursor.direction();
+            let unique = matches!(
+                direction,
+                CursorDirection::Nextunique | CursorDirection::Prevunique
+            );
+
+            let mut ord = position.key().cmp(seek.key());
+            if let (Ordering::Equal, false, Some(a), Some(b)) =
+                (ord, unique, position.primary_key(), seek.primary_key())
+            {
+                ord = a.cmp(b);
+            }
+            if matches!(
+                direction,
+                CursorDirection::Prev | CursorDirection::Prevunique
+            ) {
+                ord = ord.reverse();
+            }
+
+            match ord {
+                Ordering::Less => {
+                    match seek.primary_key() {
+                        Some(primary_key) if !unique && position.primary_key().is_some() => {
+                            continue_primary_key(cursor, seek.key(), primary_key)?;
+                        }
+                        _ => cursor.continue_with_key(seek.key())?,
+                    }
+                    return Ok(None);
+                }
+                Ordering::Equal => {
+                    self.seek = None;
+                    cursor.continue_()?;
+                    return Ok(None);
+                }
+                Ordering::Greater => {
+                    self.seek = None;
+                }
+            }
+        }
+
+        if self.records.len() >= usize::try_from(self.page_size).unwrap_or(usize::MAX) {
+            return Ok(Some(true));
+        }
+
+        self.records.push((self.map_fn)(cursor.value()?)?);
+        if self.first.is_none() {
+            self.first = Some(position.clone());
+        }
+        self.last = Some(position);
+
+        cursor.continue_()?;
+        Ok(None)
+    }
+
+    fn finish(&mut self, has_more: bool) -> Page<V> {
+        self.req = None;
+
+        let mut records = std::mem::take(&mut self.records);
+        let first = self.first.take();
+        let last = self.last.take();
+
+        if self.backward {
+            // Records were read in reverse, so the last one read comes first.
+            records.reverse();
+            Page::new(records, first, last.filter(|_| has_more))
+        } else {
+            Page::new(
+                records,
+                last.filter(|_| has_more),
+                first.filter(|_| self.resumed),
+            )
+        }
+    }
+}
+
+#[sealed]
+impl<V> super::PollUnpinned for PageRequest<V> {
+    type Output = crate::Result<Page<V>>;
+
+    fn poll_unpinned(&mut self, cx: &mut Context) -> Poll<Self::Output> {
+        loop {
+            let Some(ref mut req) = self.req else {
+                return Poll::Ready(Err(UnexpectedDataError::PollState.into()));
+            };
+
+            let cursor = ready!(req.poll_unpinned(cx)).and_then(|res| read_cursor(req, res));
+            let res = cursor.and_then(|cursor| {
+                let Some(cursor) = cursor else {
+                    return Ok(Some(false));
+                };
+
+                let out = self.on_cursor(&cursor)?;
+                if out.is_none() {
+                    self.req = Some(Request::new(cursor.req()));
+                }
+                Ok(out)
+            });
+
+            match res {
+                Ok(Some(has_more)) => return Poll::Ready(Ok(self.finish(has_more))),
+                Ok(None) => {}
+                Err(e) => {
+                    self.req = None;
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "indices")]
+fn continue_primary_key(
+    cursor: &CursorRepr,
+    key: &IdbKey,
+    primary_key: &IdbKey,
+) -> crate::Result<()> {
+    cursor.continue_primary_key(key, primary_key)
+}
+
+/// Only index cursors have primary keys that differ from their keys.
+#[cfg(not(feature = "indices"))]
+fn continue_primary_key(cursor: &CursorRepr, key: &IdbKey, _: &IdbKey) -> crate::Result<()> {
+    cursor.continue_with_key(key)
+}
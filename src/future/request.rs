@@ -1,60 +1,85 @@
 pub(crate) mod listeners;
 mod untyped;
 
-use crate::internal_utils::SystemRepr;
 use internal_macros::{FutureFromPollUnpinned, StructNameDebug};
-use listeners::Listeners;
 use sealed::sealed;
 use std::marker::PhantomData;
 use std::task::{Context, Poll};
 use wasm_bindgen::prelude::*;
 
 use super::traits::*;
-use untyped::UntypedRequest;
+pub use untyped::UntypedRequest;
 
 /// Alias for [`Request<()>`](Request).
 pub type VoidRequest = Request<()>;
 
-/// Future for a [`web-sys` request](web_sys::IdbRequest).
+/// Future for a [`web-sys` request](web_sys::IdbRequest) or a [driver](crate::backend) operation.
 #[derive(StructNameDebug, FutureFromPollUnpinned)]
-#[debug(expr(self.as_sys()))]
-pub struct Request<T = JsValue> {
+#[debug(expr(&self.inner))]
+pub struct Request<T = Raw> {
     inner: UntypedRequest,
     _marker: PhantomData<T>,
 }
 
-impl<T> Request<T> {
-    pub(crate) fn new(req: web_sys::IdbRequest) -> Self {
-        Self {
-            inner: UntypedRequest::Bare(req),
-            _marker: PhantomData,
+/// The raw result of a [`Request`].
+#[derive(Debug, Clone)]
+pub enum Raw {
+    /// A browser's result.
+    Js(JsValue),
+
+    /// A [driver's](crate::backend) result.
+    #[cfg(feature = "backend")]
+    Value(crate::backend::Value),
+}
+
+impl Raw {
+    /// Check if the result is `undefined`.
+    pub(crate) fn is_undefined(&self) -> bool {
+        match self {
+            Self::Js(js) => js.is_undefined(),
+            #[cfg(feature = "backend")]
+            Self::Value(value) => matches!(value, crate::backend::Value::Undefined),
         }
     }
 }
 
-#[::sealed::sealed]
-#[allow(unused_qualifications)]
-impl<T> crate::internal_utils::SystemRepr for Request<T> {
-    type Repr = web_sys::IdbRequest;
-
+impl From<JsValue> for Raw {
     #[inline]
-    fn as_sys(&self) -> &Self::Repr {
-        self.inner.as_sys()
+    fn from(js: JsValue) -> Self {
+        Self::Js(js)
     }
+}
 
+#[cfg(feature = "backend")]
+impl From<crate::backend::Value> for Raw {
     #[inline]
-    fn into_sys(self) -> Self::Repr {
-        self.inner.into_sys()
+    fn from(value: crate::backend::Value) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl<T> Request<T> {
+    pub(crate) fn new(req: impl Into<UntypedRequest>) -> Self {
+        Self {
+            inner: req.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The underlying [`web_sys::IdbRequest`]; `None` for [driver](crate::backend) requests.
+    #[cfg(feature = "cursors")]
+    pub(crate) fn web_sys(&self) -> Option<&web_sys::IdbRequest> {
+        self.inner.web_sys()
     }
 }
 
 #[sealed]
 impl PollUnpinned for Request {
-    type Output = crate::Result<JsValue>;
+    type Output = crate::Result<Raw>;
 
     fn poll_unpinned(&mut self, cx: &mut Context) -> Poll<Self::Output> {
         match self.inner.poll_unpinned(cx) {
-            Poll::Ready(Ok(_)) => Poll::Ready(self.as_sys().result().map_err(Into::into)),
+            Poll::Ready(Ok(res)) => Poll::Ready(self.inner.result(res)),
             Poll::Pending => Poll::Pending,
             Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
         }
@@ -8,17 +8,21 @@ use wasm_bindgen::prelude::*;
 type Callback = Closure<dyn FnMut(web_sys::Event) + 'static>;
 
 /// represents the value on an event.target.result
-pub(crate) enum EventTargetResult {
+pub enum EventTargetResult {
     /// the event.target.result was null
     Null,
-    /// the event.target.result was a [`web_sys::IdbCursor`] instance
+    /// the event.target.result was a [`web_sys::IdbCursor`] instance or a driver opened a cursor
     #[cfg(feature = "cursors")]
-    Cursor(crate::cursor::CursorSys),
+    Cursor(crate::repr::CursorRepr),
     /// the event.target.result was not null
     NotNull,
+    /// a driver produced a value
+    #[cfg(feature = "backend")]
+    Value(crate::backend::Value),
 }
 
-pub(super) struct Listeners {
+/// Success & error listeners attached to a [`web_sys::IdbRequest`].
+pub struct Listeners {
     rx: mpsc::Receiver<EventTargetResult>,
     req: web_sys::IdbRequest,
     _callback: Callback,
@@ -44,7 +48,7 @@ impl Listeners {
                     cfg_if! {
                         if #[cfg(feature = "cursors")] {
                              match val.dyn_into::<crate::cursor::CursorSys>() {
-                                Ok(cursor) => EventTargetResult::Cursor(cursor),
+                                Ok(cursor) => EventTargetResult::Cursor(cursor.into()),
                                 Err(_) => EventTargetResult::NotNull,
                             }
                         } else {
@@ -65,6 +69,11 @@ impl Listeners {
             _callback: callback,
         }
     }
+
+    #[inline]
+    pub(super) fn req(&self) -> &web_sys::IdbRequest {
+        &self.req
+    }
 }
 
 impl Drop for Listeners {
@@ -89,19 +98,3 @@ impl PollUnpinned for Listeners {
         }
     }
 }
-
-#[::sealed::sealed]
-#[allow(unused_qualifications)]
-impl crate::internal_utils::SystemRepr for Listeners {
-    type Repr = web_sys::IdbRequest;
-
-    #[inline]
-    fn as_sys(&self) -> &Self::Repr {
-        &self.req
-    }
-
-    #[inline]
-    fn into_sys(self) -> Self::Repr {
-        self.req.clone()
-    }
-}
@@ -1,36 +1,73 @@
-use super::Listeners;
-use super::{super::traits::*, listeners::EventTargetResult};
+use super::listeners::Listeners;
+use super::{super::traits::*, listeners::EventTargetResult, Raw};
 use sealed::sealed;
+use std::fmt::{self, Debug, Formatter};
 use std::task::{Context, Poll};
 use wasm_bindgen::prelude::*;
 use web_sys::IdbRequestReadyState;
 
-pub(super) enum UntypedRequest {
+/// A request that hasn't had its result type applied yet.
+pub enum UntypedRequest {
+    /// A browser request that hasn't had listeners attached yet.
     Bare(web_sys::IdbRequest),
+
+    /// A browser request with listeners attached.
     WithListeners(Listeners),
+
+    /// A [driver](crate::backend) operation's result, taken on the first poll.
+    #[cfg(feature = "backend")]
+    Driver(Option<crate::Result<EventTargetResult>>),
+
+    /// An asynchronous [driver](crate::backend) operation.
+    #[cfg(feature = "backend")]
+    DriverFuture(crate::backend::DriverFuture<EventTargetResult>),
 }
 
-#[::sealed::sealed]
-#[allow(unused_qualifications)]
-impl crate::internal_utils::SystemRepr for UntypedRequest {
-    type Repr = web_sys::IdbRequest;
+impl UntypedRequest {
+    /// Create an already-completed [driver](crate::backend) request.
+    #[cfg(feature = "backend")]
+    pub(crate) fn driver(result: crate::Result<EventTargetResult>) -> Self {
+        Self::Driver(Some(result))
+    }
+
+    /// Create an already-completed [driver](crate::backend) request that resolves to a value.
+    #[cfg(feature = "backend")]
+    pub(crate) fn driver_value<V>(result: crate::Result<V>) -> Self
+    where
+        V: Into<crate::backend::Value>,
+    {
+        Self::driver(result.map(|v| EventTargetResult::Value(v.into())))
+    }
 
-    fn as_sys(&self) -> &Self::Repr {
+    /// Create a request from an asynchronous [driver](crate::backend) operation.
+    #[cfg(feature = "backend")]
+    pub(crate) fn driver_future(fut: crate::backend::DriverFuture<()>) -> Self {
+        Self::DriverFuture(Box::pin(async move {
+            fut.await?;
+            Ok(EventTargetResult::Null)
+        }))
+    }
+
+    #[cfg_attr(not(feature = "backend"), allow(clippy::unnecessary_wraps))]
+    pub(super) fn web_sys(&self) -> Option<&web_sys::IdbRequest> {
         match self {
-            Self::Bare(req) => req,
-            Self::WithListeners(listeners) => listeners.as_sys(),
+            Self::Bare(req) => Some(req),
+            Self::WithListeners(listeners) => Some(listeners.req()),
+            #[cfg(feature = "backend")]
+            Self::Driver(_) | Self::DriverFuture(_) => None,
         }
     }
 
-    fn into_sys(self) -> Self::Repr {
-        match self {
-            Self::Bare(req) => req,
-            Self::WithListeners(listeners) => listeners.into_sys(),
+    /// Read the result of a completed request.
+    pub(super) fn result(&self, res: EventTargetResult) -> crate::Result<Raw> {
+        match (self.web_sys(), res) {
+            #[cfg(feature = "backend")]
+            (_, EventTargetResult::Value(value)) => Ok(Raw::Value(value)),
+            (Some(req), _) => req.result().map(Raw::Js).map_err(Into::into),
+            (None, _) => Ok(Raw::Js(JsValue::UNDEFINED)),
         }
     }
-}
 
-impl UntypedRequest {
     pub(super) fn req_to_result<T>(req: &web_sys::IdbRequest, v: T) -> crate::Result<T> {
         match req.error() {
             Ok(None) => Ok(v),
@@ -54,6 +91,22 @@ impl UntypedRequest {
     }
 }
 
+impl From<web_sys::IdbRequest> for UntypedRequest {
+    #[inline]
+    fn from(req: web_sys::IdbRequest) -> Self {
+        Self::Bare(req)
+    }
+}
+
+impl Debug for UntypedRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.web_sys() {
+            Some(req) => Debug::fmt(req, f),
+            None => f.write_str("Driver"),
+        }
+    }
+}
+
 #[sealed]
 impl PollUnpinned for UntypedRequest {
     type Output = crate::Result<EventTargetResult>;
@@ -73,6 +126,13 @@ impl PollUnpinned for UntypedRequest {
                     out
                 }
             }
+            #[cfg(feature = "backend")]
+            Self::Driver(result) => match result.take() {
+                Some(result) => Poll::Ready(result),
+                None => Poll::Ready(Err(crate::error::UnexpectedDataError::PollState.into())),
+            },
+            #[cfg(feature = "backend")]
+            Self::DriverFuture(fut) => std::future::Future::poll(fut.as_mut(), cx),
         }
     }
 }
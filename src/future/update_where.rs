@@ -0,0 +1,110 @@
+use super::cursor::read_cursor;
+use super::request::listeners::EventTargetResult;
+use super::{Raw, Request, UntypedRequest};
+use crate::error::UnexpectedDataError;
+use crate::internal_utils::EncodeFn;
+use crate::object_store::{UpdateAction, UpdateCounts};
+use crate::repr::CursorRepr;
+use internal_macros::FutureFromPollUnpinned;
+use sealed::sealed;
+use std::task::{ready, Context, Poll};
+
+/// Future for [`ObjectStore::update_where`](crate::object_store::ObjectStore::update_where).
+#[derive(FutureFromPollUnpinned)]
+pub struct UpdateWhereRequest<V, F> {
+    /// `None` once the future's resolved.
+    req: Option<Request<EventTargetResult>>,
+
+    /// Set while waiting on an update or delete request for the cursor's current record.
+    cursor: Option<CursorRepr>,
+    callback: F,
+    counts: UpdateCounts,
+    decode: fn(Raw) -> crate::Result<V>,
+    encode: EncodeFn<V>,
+}
+
+impl<V, F> UpdateWhereRequest<V, F>
+where
+    F: FnMut(V) -> UpdateAction<V>,
+{
+    pub(crate) fn new(
+        req: UntypedRequest,
+        callback: F,
+        decode: fn(Raw) -> crate::Result<V>,
+        encode: EncodeFn<V>,
+    ) -> Self {
+        Self {
+            req: Some(Request::new(req)),
+            cursor: None,
+            callback,
+            counts: UpdateCounts::default(),
+            decode,
+            encode,
+        }
+    }
+
+    /// Handle the request's result, returning `false` once the cursor's iterated past its end.
+    fn on_result(&mut self, cursor: Option<CursorRepr>) -> crate::Result<bool> {
+        // An update or delete finished - move on to the next record
+        if let Some(cursor) = self.cursor.take() {
+            cursor.continue_()?;
+            self.req = Some(Request::new(cursor.req()));
+            return Ok(true);
+        }
+
+        let Some(cursor) = cursor else {
+            return Ok(false);
+        };
+
+        let value = (self.decode)(cursor.value()?)?;
+        let req = match (self.callback)(value) {
+            UpdateAction::Keep => {
+                cursor.continue_()?;
+                cursor.req()
+            }
+            UpdateAction::Replace(value) => {
+                let req = cursor.update(&*(self.encode)(&value))?;
+                self.counts.add_updated();
+                self.cursor = Some(cursor);
+                req
+            }
+            UpdateAction::Delete => {
+                let req = cursor.delete()?;
+                self.counts.add_deleted();
+                self.cursor = Some(cursor);
+                req
+            }
+        };
+
+        self.req = Some(Request::new(req));
+        Ok(true)
+    }
+}
+
+#[sealed]
+impl<V, F> super::PollUnpinned for UpdateWhereRequest<V, F>
+where
+    F: FnMut(V) -> UpdateAction<V>,
+{
+    type Output = crate::Result<UpdateCounts>;
+
+    fn poll_unpinned(&mut self, cx: &mut Context) -> Poll<Self::Output> {
+        loop {
+            let Some(ref mut req) = self.req else {
+                return Poll::Ready(Err(UnexpectedDataError::PollState.into()));
+            };
+
+            let res = ready!(req.poll_unpinned(cx)).and_then(|res| read_cursor(req, res));
+            self.req = None;
+
+            match res.and_then(|cursor| self.on_result(cursor)) {
+                Ok(true) => {}
+                Ok(false) => return Poll::Ready(Ok(self.counts)),
+                Err(e) => {
+                    self.cursor = None;
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+    }
+}
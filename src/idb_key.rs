@@ -0,0 +1,298 @@
+use crate::error::SimpleValueError;
+use crate::primitive::{BinaryKey, TryFromJs, TryToJs};
+use std::cmp::Ordering;
+use wasm_bindgen::prelude::*;
+
+/// The largest time value a JS `Date` can hold, in milliseconds either side of the Unix epoch.
+const MAX_DATE: f64 = 8.64e15;
+
+/// A valid [`IndexedDB` key](https://w3c.github.io/IndexedDB/#key-construct).
+///
+/// Keys are ordered as per the [spec](https://w3c.github.io/IndexedDB/#compare-two-keys): all numbers sort before
+/// all dates, which sort before all strings, which sort before all binary keys, which sort before all arrays. Strings
+/// are compared by their UTF-16 code units. This matches what [`DBFactory::cmp`](crate::factory::DBFactory::cmp)
+/// returns, letting keys be sorted, deduplicated & merged in Rust the same way the browser would.
+///
+/// Converts to & from JS via [`TryToJs`] & [`TryFromJs`]; binary keys are converted like a
+/// [`BinaryKey`]. Values that aren't valid keys fail to convert.
+///
+/// ```
+/// # use indexed_db_futures::IdbKey;
+/// let mut keys = vec![IdbKey::from("a"), IdbKey::from([IdbKey::from(1)]), IdbKey::from(10)];
+/// keys.sort();
+/// assert_eq!(keys, [IdbKey::from(10), IdbKey::from("a"), IdbKey::from([IdbKey::from(1)])]);
+/// ```
+#[derive(Clone, Debug)]
+pub enum IdbKey {
+    /// A number key. `NaN` is not a valid key.
+    Number(f64),
+
+    /// A date key, represented as milliseconds since the Unix epoch. `NaN` is not a valid key.
+    Date(f64),
+
+    /// A string key.
+    String(String),
+
+    /// A binary key.
+    Binary(Vec<u8>),
+
+    /// An array key.
+    Array(Vec<IdbKey>),
+}
+
+impl IdbKey {
+    /// Check if the key is valid, i.e. it doesn't contain any `NaN`s.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Number(n) | Self::Date(n) => !n.is_nan(),
+            Self::String(_) | Self::Binary(_) => true,
+            Self::Array(arr) => arr.iter().all(Self::is_valid),
+        }
+    }
+
+    /// The smallest key that sorts after this one.
+    pub(crate) fn successor(&self) -> Self {
+        match self {
+            Self::Number(n) if *n < f64::INFINITY => Self::Number(next_up(*n)),
+            Self::Number(_) => Self::Date(-MAX_DATE),
+            Self::Date(ms) if *ms < MAX_DATE => Self::Date(ms.floor() + 1.0),
+            Self::Date(_) => Self::String(String::new()),
+            Self::String(s) => Self::String(format!("{s}\0")),
+            Self::Binary(bytes) => Self::Binary([bytes.as_slice(), &[0]].concat()),
+            Self::Array(keys) => {
+                let mut keys = keys.clone();
+                keys.push(Self::Number(f64::NEG_INFINITY));
+                Self::Array(keys)
+            }
+        }
+    }
+
+    /// The smallest key that sorts after every string starting with `prefix`.
+    pub(crate) fn string_prefix_end(prefix: &str) -> Self {
+        let mut units = prefix.encode_utf16().collect::<Vec<_>>();
+        while let Some(last) = units.pop() {
+            match last {
+                0xFFFF => continue,
+                // Incrementing would produce a lone high surrogate; the next valid string starts with U+10000
+                0xD7FF => units.extend([0xD800, 0xDC00]),
+                // Incrementing the low half of a surrogate pair past its range moves on to the next high surrogate
+                0xDFFF if units.last().is_some_and(|u| (0xD800..=0xDBFF).contains(u)) => {
+                    match units.pop() {
+                        Some(0xDBFF) => units.push(0xE000),
+                        Some(high) => units.extend([high + 1, 0xDC00]),
+                        None => unreachable!(),
+                    }
+                }
+                unit => units.push(unit + 1),
+            }
+
+            return Self::String(String::from_utf16_lossy(&units));
+        }
+
+        Self::Binary(Vec::new())
+    }
+
+    /// The smallest key that sorts after every binary key starting with `prefix`.
+    pub(crate) fn binary_prefix_end(prefix: &[u8]) -> Self {
+        let mut bytes = prefix.to_vec();
+        while let Some(last) = bytes.pop() {
+            if last != u8::MAX {
+                bytes.push(last + 1);
+                return Self::Binary(bytes);
+            }
+        }
+
+        Self::Array(Vec::new())
+    }
+
+    fn type_rank(&self) -> u8 {
+        match self {
+            Self::Number(_) => 0,
+            Self::Date(_) => 1,
+            Self::String(_) => 2,
+            Self::Binary(_) => 3,
+            Self::Array(_) => 4,
+        }
+    }
+}
+
+impl Ord for IdbKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) | (Self::Date(a), Self::Date(b)) => {
+                a.partial_cmp(b).unwrap_or_else(|| a.total_cmp(b))
+            }
+            (Self::String(a), Self::String(b)) => a.encode_utf16().cmp(b.encode_utf16()),
+            (Self::Binary(a), Self::Binary(b)) => a.cmp(b),
+            (Self::Array(a), Self::Array(b)) => a.cmp(b),
+            (a, b) => a.type_rank().cmp(&b.type_rank()),
+        }
+    }
+}
+
+impl PartialOrd for IdbKey {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for IdbKey {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for IdbKey {}
+
+macro_rules! from_num {
+    (lossless: $($ty: ty),+ $(,)?) => {
+        $(
+            impl From<$ty> for IdbKey {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Self::Number(value.into())
+                }
+            }
+        )+
+    };
+    (lossy: $($ty: ty),+ $(,)?) => {
+        $(
+            impl From<$ty> for IdbKey {
+                #[allow(clippy::cast_precision_loss)]
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Self::Number(value as f64)
+                }
+            }
+        )+
+    };
+}
+
+from_num!(lossless: f64, f32, i8, u8, i16, u16, i32, u32);
+from_num!(lossy: i64, u64, i128, u128, isize, usize);
+
+impl From<String> for IdbKey {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for IdbKey {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<&String> for IdbKey {
+    #[inline]
+    fn from(value: &String) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl From<Vec<u8>> for IdbKey {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        Self::Binary(value)
+    }
+}
+
+impl From<&[u8]> for IdbKey {
+    #[inline]
+    fn from(value: &[u8]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+impl From<Vec<IdbKey>> for IdbKey {
+    #[inline]
+    fn from(value: Vec<IdbKey>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl<const N: usize> From<[IdbKey; N]> for IdbKey {
+    #[inline]
+    fn from(value: [IdbKey; N]) -> Self {
+        Self::Array(value.into())
+    }
+}
+
+impl TryToJs for IdbKey {
+    fn try_to_js(&self) -> crate::Result<JsValue> {
+        Ok(match self {
+            Self::Number(n) => JsValue::from_f64(*n),
+            Self::Date(ms) => js_sys::Date::new(&JsValue::from_f64(*ms)).unchecked_into(),
+            Self::String(s) => JsValue::from_str(s),
+            Self::Binary(bytes) => js_sys::Uint8Array::from(bytes.as_slice())
+                .buffer()
+                .unchecked_into(),
+            Self::Array(keys) => {
+                let arr = js_sys::Array::new();
+                for key in keys {
+                    arr.push(&key.try_to_js()?);
+                }
+                arr.unchecked_into()
+            }
+        })
+    }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<crate::backend::Value> {
+        Ok(self.clone().into())
+    }
+}
+
+impl TryFromJs for IdbKey {
+    fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
+        let key = if let Some(n) = js.as_f64() {
+            Self::Number(n)
+        } else if let Some(s) = js.as_string() {
+            Self::String(s)
+        } else if let Some(date) = js.dyn_ref::<js_sys::Date>() {
+            Self::Date(date.get_time())
+        } else if js.is_instance_of::<js_sys::ArrayBuffer>() || js_sys::ArrayBuffer::is_view(&js) {
+            return BinaryKey::from_js(js).map(Into::into);
+        } else if js_sys::Array::is_array(&js) {
+            let keys = js
+                .unchecked_into::<js_sys::Array>()
+                .iter()
+                .map(Self::from_js)
+                .collect::<Result<_, _>>()?;
+            return Ok(Self::Array(keys));
+        } else {
+            return Err(SimpleValueError::DynCast(js));
+        };
+
+        if key.is_valid() {
+            Ok(key)
+        } else {
+            Err(SimpleValueError::DynCast(js))
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    fn from_value(value: crate::backend::Value) -> Result<Self, SimpleValueError> {
+        match value.to_key() {
+            Some(key) => Ok(key),
+            None => Err(SimpleValueError::UnexpectedValue(value)),
+        }
+    }
+}
+
+/// The smallest `f64` greater than `n`; `n` must be finite or negative infinity.
+fn next_up(n: f64) -> f64 {
+    let bits = n.to_bits();
+    if n == 0.0 {
+        f64::from_bits(1)
+    } else if n > 0.0 {
+        f64::from_bits(bits + 1)
+    } else {
+        f64::from_bits(bits - 1)
+    }
+}
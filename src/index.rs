@@ -8,7 +8,14 @@ mod object_store_ext;
 
 mod index_builder;
 
+iffeat! {
+    #[cfg(feature = "cursors")]
+    mod delete_range;
+    pub use delete_range::DeleteRange;
+}
+
 use crate::object_store::ObjectStore;
+use crate::repr::IndexRepr;
 
 /// An [`IDBIndex`](https://developer.mozilla.org/en-US/docs/Web/API/IDBIndex) implementation.
 #[derive(Debug, Clone, Accessors, new)]
@@ -17,13 +24,13 @@ pub struct Index<'a> {
     /// The object store this index is for.
     #[access(get(cp))]
     object_store: &'a ObjectStore<'a>,
-    base: web_sys::IdbIndex,
+    base: IndexRepr,
 }
 
 #[::sealed::sealed]
 #[allow(unused_qualifications)]
 impl crate::internal_utils::SystemRepr for Index<'_> {
-    type Repr = web_sys::IdbIndex;
+    type Repr = IndexRepr;
 
     #[inline]
     fn as_sys(&self) -> &Self::Repr {
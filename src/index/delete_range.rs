@@ -0,0 +1,89 @@
+use super::Index;
+use crate::future::DeleteRangeRequest;
+use crate::internal_utils::{Encode, SystemRepr};
+use crate::primitive::TryToJs;
+use crate::query_source::internal::QuerySourceInternal;
+use crate::KeyRange;
+use fancy_constructor::new;
+use internal_macros::{errdoc, BuildIntoFut};
+use sealed::sealed;
+
+/// Builder for [`Index::delete_range`] & [`Index::delete_range_count`].
+#[derive(BuildIntoFut, new)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct DeleteRange<'a, K, T> {
+    index: &'a Index<'a>,
+    key: KeyRange<K>,
+    finish: fn(u32) -> T,
+}
+
+impl Index<'_> {
+    /// Delete every record whose index key matches the given key or key range. Primary keys are resolved via an
+    /// index [key cursor](crate::cursor::KeyCursor) and deleted from the object store in the same transaction.
+    ///
+    /// The range should implement either [`TryToJs`] or, if the `serde` feature is enabled,
+    /// [`Serialize`](serde::Serialize).
+    ///
+    /// Use [`delete_range_count`](Self::delete_range_count) if you need to know how many records got deleted.
+    #[errdoc(ObjectStore(
+        ReadOnlyError,
+        TransactionInactiveError,
+        InvalidStateError,
+        DataErrorDelete,
+    ))]
+    pub fn delete_range<K, I>(&self, key_range: I) -> DeleteRange<'_, K, ()>
+    where
+        I: Into<KeyRange<K>>,
+    {
+        DeleteRange::new(self, key_range.into(), drop)
+    }
+
+    /// [`delete_range`](Self::delete_range) variant that resolves to the number of records deleted.
+    #[errdoc(ObjectStore(
+        ReadOnlyError,
+        TransactionInactiveError,
+        InvalidStateError,
+        DataErrorDelete,
+    ))]
+    pub fn delete_range_count<K, I>(&self, key_range: I) -> DeleteRange<'_, K, u32>
+    where
+        I: Into<KeyRange<K>>,
+    {
+        DeleteRange::new(self, key_range.into(), std::convert::identity)
+    }
+}
+
+impl<K, T> DeleteRange<'_, K, T> {
+    fn to_req(&self, key: &dyn Encode) -> crate::Result<DeleteRangeRequest<T>> {
+        let req = self.index.as_sys().open_cursor(Some(key), None, true)?;
+        let store = self.index.object_store().as_sys().clone();
+
+        Ok(DeleteRangeRequest::new(req, store, self.finish))
+    }
+}
+
+#[sealed]
+impl<K, T> crate::BuildPrimitive for DeleteRange<'_, K, T>
+where
+    KeyRange<K>: TryToJs,
+{
+    type Fut = DeleteRangeRequest<T>;
+
+    fn primitive(self) -> crate::Result<Self::Fut> {
+        self.to_req(&self.key)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[sealed]
+impl<K, T> crate::BuildSerde for DeleteRange<'_, K, T>
+where
+    KeyRange<K>: crate::serde::SerialiseToJs,
+{
+    type Fut = DeleteRangeRequest<T>;
+
+    fn serde(self) -> crate::Result<Self::Fut> {
+        self.to_req(&crate::internal_utils::Ser(&self.key))
+    }
+}
@@ -63,18 +63,19 @@ impl<'a, N, KP, U, ME> IndexBuilder<'a, N, KP, U, ME> {
     }
 
     #[generic_bounds(index_name(N), key_path(KP))]
-    fn build_with_params(&self, params: &web_sys::IdbIndexParameters) -> crate::Result<Index<'a>> {
-        let kp = self.key_path.to_js();
-        let sys = self
-            .store
-            .as_sys()
-            .create_index_with_str_sequence_and_optional_parameters(
-                self.name.as_ref(),
-                &kp,
-                params,
-            )?;
+    fn build_with(
+        self,
+        unique: Option<bool>,
+        multi_entry: Option<bool>,
+    ) -> crate::Result<Index<'a>> {
+        let repr = self.store.as_sys().create_index(
+            self.name.as_ref(),
+            self.key_path.into_owned(),
+            unique,
+            multi_entry,
+        )?;
 
-        Ok(Index::new(self.store, sys))
+        Ok(Index::new(self.store, repr))
     }
 }
 
@@ -85,12 +86,7 @@ impl<'a, N, KP> crate::Build for IndexBuilder<'a, N, KP> {
     type Err = Error;
 
     fn build(self) -> Result<Self::Ok, Self::Err> {
-        let kp = self.key_path.to_js();
-        let sys = self
-            .store
-            .as_sys()
-            .create_index_with_str_sequence(self.name.as_ref(), &kp)?;
-        Ok(Index::new(self.store, sys))
+        self.build_with(None, None)
     }
 }
 
@@ -101,10 +97,8 @@ impl<'a, N, KP> crate::Build for IndexBuilder<'a, N, KP, bool> {
     type Err = Error;
 
     fn build(self) -> Result<Self::Ok, Self::Err> {
-        let p = web_sys::IdbIndexParameters::new();
-        p.set_unique(self.unique);
-
-        self.build_with_params(&p)
+        let unique = self.unique;
+        self.build_with(Some(unique), None)
     }
 }
 
@@ -115,10 +109,8 @@ impl<'a, N, KP> crate::Build for IndexBuilder<'a, N, KP, (), bool> {
     type Err = Error;
 
     fn build(self) -> Result<Self::Ok, Self::Err> {
-        let p = web_sys::IdbIndexParameters::new();
-        p.set_multi_entry(self.multi_entry);
-
-        self.build_with_params(&p)
+        let multi_entry = self.multi_entry;
+        self.build_with(None, Some(multi_entry))
     }
 }
 
@@ -129,10 +121,7 @@ impl<'a, N, KP> crate::Build for IndexBuilder<'a, N, KP, bool, bool> {
     type Err = Error;
 
     fn build(self) -> Result<Self::Ok, Self::Err> {
-        let p = web_sys::IdbIndexParameters::new();
-        p.set_unique(self.unique);
-        p.set_multi_entry(self.multi_entry);
-
-        self.build_with_params(&p)
+        let (unique, multi_entry) = (self.unique, self.multi_entry);
+        self.build_with(Some(unique), Some(multi_entry))
     }
 }
@@ -19,7 +19,7 @@ impl ObjectStore<'_> {
     ))]
     #[generic_bounds(index_name(N), key_path(KP))]
     #[inline]
-    pub fn create_index<N, KP>(&self, name: N, key_path: KeyPath<KP>) -> IndexBuilder<N, KP> {
+    pub fn create_index<N, KP>(&self, name: N, key_path: KeyPath<KP>) -> IndexBuilder<'_, N, KP> {
         IndexBuilder::new(self, name, key_path)
     }
 
@@ -28,25 +28,19 @@ impl ObjectStore<'_> {
     #[errdoc(Index(InvalidStateError, TransactionInactiveError, NotFoundError))]
     #[allow(clippy::missing_errors_doc)]
     pub fn delete_index(&self, name: &str) -> crate::Result<()> {
-        if let Err(e) = self.as_sys().delete_index(name) {
-            Err(e.into())
-        } else {
-            Ok(())
-        }
+        self.as_sys().delete_index(name)
     }
 
     /// Open an index with the given name
     #[errdoc(Index(InvalidStateErrorIndex, NotFoundError))]
     #[allow(clippy::missing_errors_doc)]
-    pub fn index(&self, name: &str) -> crate::Result<Index> {
-        match self.as_sys().index(name) {
-            Ok(sys) => Ok(Index::new(self, sys)),
-            Err(e) => Err(e.into()),
-        }
+    pub fn index(&self, name: &str) -> crate::Result<Index<'_>> {
+        let repr = self.as_sys().index(name)?;
+        Ok(Index::new(self, repr))
     }
 
     /// Return the names of the indices on this object store.
-    pub fn index_names(&self) -> DomStringIter {
-        DomStringIter::new(self.as_sys().index_names())
+    pub fn index_names(&self) -> DomStringIter<'_> {
+        self.as_sys().index_names()
     }
 }
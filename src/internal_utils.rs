@@ -16,11 +16,11 @@ pub(crate) trait StructName {
     const TYPE_NAME: &'static str;
 }
 
-/// Internal [`web_sys`]-based representation.
+/// Internal representation: a [`web_sys`] object or, with the `backend` feature, a [driver](crate::backend).
 #[sealed(pub(crate))]
 pub trait SystemRepr {
-    /// Internal [`web_sys`]-based representation type.
-    type Repr: JsCast;
+    /// Internal representation type.
+    type Repr;
 
     /// Get the base representation. Using this fn to bypass the library can lead to unaccounted for errors.
     #[must_use]
@@ -33,6 +33,86 @@ pub trait SystemRepr {
     fn into_sys(self) -> Self::Repr;
 }
 
+/// A key, key range or value that gets converted to JS or to a [driver](crate::backend) value depending on where
+/// it's sent.
+pub trait Encode {
+    /// Convert to JS.
+    fn to_js(&self) -> crate::Result<JsValue>;
+
+    /// Convert to a driver value.
+    #[cfg(feature = "backend")]
+    fn to_value(&self) -> crate::Result<crate::backend::Value>;
+
+    /// Convert to a driver key range.
+    #[cfg(feature = "backend")]
+    fn to_key_range(&self) -> crate::Result<crate::KeyRange<crate::IdbKey>>;
+
+    /// Convert to a driver key.
+    #[cfg(feature = "backend")]
+    fn to_key(&self) -> crate::Result<crate::IdbKey> {
+        self.to_value()?.into_key()
+    }
+}
+
+impl<T: crate::primitive::TryToJs + ?Sized> Encode for T {
+    #[inline]
+    fn to_js(&self) -> crate::Result<JsValue> {
+        self.try_to_js()
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn to_value(&self) -> crate::Result<crate::backend::Value> {
+        self.try_to_value()
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn to_key_range(&self) -> crate::Result<crate::KeyRange<crate::IdbKey>> {
+        self.try_to_key_range()
+    }
+}
+
+/// [`Encode`]s the wrapped value via [`SerialiseToJs`](crate::SerialiseToJs).
+#[cfg(feature = "serde")]
+pub struct Ser<'a, T: ?Sized>(pub &'a T);
+
+#[cfg(feature = "serde")]
+impl<T: crate::SerialiseToJs + ?Sized> Encode for Ser<'_, T> {
+    #[inline]
+    fn to_js(&self) -> crate::Result<JsValue> {
+        self.0.serialise_to_js()
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn to_value(&self) -> crate::Result<crate::backend::Value> {
+        self.0.serialise_to_value()
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn to_key_range(&self) -> crate::Result<crate::KeyRange<crate::IdbKey>> {
+        self.0.serialise_to_key_range()
+    }
+}
+
+/// Erased [`Encode`] constructor for values that are converted after the builder's been built.
+#[cfg(feature = "cursors")]
+pub(crate) type EncodeFn<V> = for<'a> fn(&'a V) -> Box<dyn Encode + 'a>;
+
+/// [`EncodeFn`] for [`TryToJs`](crate::primitive::TryToJs) values.
+#[cfg(feature = "cursors")]
+pub(crate) fn encode_primitive<V: crate::primitive::TryToJs>(value: &V) -> Box<dyn Encode + '_> {
+    Box::new(value)
+}
+
+/// [`EncodeFn`] for [`SerialiseToJs`](crate::SerialiseToJs) values.
+#[cfg(all(feature = "cursors", feature = "serde"))]
+pub(crate) fn encode_serde<V: crate::SerialiseToJs>(value: &V) -> Box<dyn Encode + '_> {
+    Box::new(Ser(value))
+}
+
 pub(crate) fn slice_to_arr<I, T>(slice: I) -> js_sys::Array
 where
     I: IntoIterator<Item = T>,
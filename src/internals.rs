@@ -5,6 +5,7 @@ pub use crate::internal_utils::{SystemRepr, Void};
 pub use crate::object_store::add_put::kind::InsertKind;
 pub use crate::query_source::get_all::kind::GetAllKind;
 pub use crate::query_source::internal::QuerySourceInternal;
+pub use wasm_bindgen::JsValue;
 
 #[cfg(feature = "cursors")]
 pub use crate::{
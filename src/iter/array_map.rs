@@ -1,46 +1,86 @@
-use crate::error::Error;
-use fancy_constructor::new;
+use crate::error::{Error, SimpleValueError};
+use crate::future::Raw;
 use std::iter::FusedIterator;
 use wasm_bindgen::prelude::*;
 
-/// An iterator that maps [js array](js_sys::Array) values.
-#[derive(new)]
-#[new(vis(pub(crate)))]
+/// An iterator that maps [js array](js_sys::Array) values or the values a [driver](crate::backend) returned.
 #[must_use]
 pub struct ArrayMapIter<T, E = Error> {
-    src: js_sys::ArrayIntoIter,
-    mapper: fn(JsValue) -> Result<T, E>,
+    src: Source,
+    mapper: fn(Raw) -> Result<T, E>,
+}
+
+enum Source {
+    Js(js_sys::ArrayIntoIter),
+    #[cfg(feature = "backend")]
+    Value(std::vec::IntoIter<crate::backend::Value>),
+}
+
+/// Forward an iterator method call to whichever source is in use, wrapping items in [`Raw`].
+macro_rules! fwd {
+    (plain $src: expr, |$iter: ident| $call: expr) => {
+        match $src {
+            Source::Js($iter) => $call,
+            #[cfg(feature = "backend")]
+            Source::Value($iter) => $call,
+        }
+    };
+    ($src: expr, |$iter: ident| $call: expr) => {
+        match $src {
+            Source::Js($iter) => $call.map(Raw::Js),
+            #[cfg(feature = "backend")]
+            Source::Value($iter) => $call.map(Raw::Value),
+        }
+    };
+}
+
+impl<T, E> ArrayMapIter<T, E> {
+    /// Construct from a request's result, which must be an array.
+    pub(crate) fn from_raw(src: Raw, mapper: fn(Raw) -> Result<T, E>) -> crate::Result<Self> {
+        let src = match src {
+            Raw::Js(js) => match js.dyn_into::<js_sys::Array>() {
+                Ok(arr) => Source::Js(arr.into_iter()),
+                Err(js) => return Err(SimpleValueError::DynCast(js).into()),
+            },
+            #[cfg(feature = "backend")]
+            Raw::Value(crate::backend::Value::Array(values)) => Source::Value(values.into_iter()),
+            #[cfg(feature = "backend")]
+            Raw::Value(value) => return Err(SimpleValueError::UnexpectedValue(value).into()),
+        };
+
+        Ok(Self { src, mapper })
+    }
 }
 
 impl<T, E> Iterator for ArrayMapIter<T, E> {
     type Item = Result<T, E>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.src.next().map(self.mapper)
+        fwd!(&mut self.src, |src| src.next()).map(self.mapper)
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.src.size_hint()
+        fwd!(plain & self.src, |src| src.size_hint())
     }
 
     #[inline]
     fn count(self) -> usize {
-        self.src.count()
+        fwd!(plain self.src, |src| src.count())
     }
 
     fn last(self) -> Option<Self::Item> {
-        self.src.last().map(self.mapper)
+        fwd!(self.src, |src| src.last()).map(self.mapper)
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        self.src.nth(n).map(self.mapper)
+        fwd!(&mut self.src, |src| src.nth(n)).map(self.mapper)
     }
 }
 
 impl<T, E> DoubleEndedIterator for ArrayMapIter<T, E> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.src.next_back().map(self.mapper)
+        fwd!(&mut self.src, |src| src.next_back()).map(self.mapper)
     }
 }
 
@@ -49,6 +89,6 @@ impl<T, E> FusedIterator for ArrayMapIter<T, E> {}
 impl<T, E> ExactSizeIterator for ArrayMapIter<T, E> {
     #[inline]
     fn len(&self) -> usize {
-        self.src.len()
+        fwd!(plain & self.src, |src| src.len())
     }
 }
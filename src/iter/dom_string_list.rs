@@ -1,31 +1,56 @@
 use std::marker::PhantomData;
 
-use fancy_constructor::new;
 use web_sys::DomStringList;
 
-/// An iterator over a [`DOMStringList`](https://developer.mozilla.org/en-US/docs/Web/API/DOMStringList).
-#[derive(new)]
-#[new(vis(pub(crate)))]
+/// An iterator over a [`DOMStringList`](https://developer.mozilla.org/en-US/docs/Web/API/DOMStringList) or the
+/// names a [driver](crate::backend) returned.
 #[must_use]
 pub struct DomStringIter<'a> {
-    inner: DomStringList,
+    inner: Inner,
+    _marker: PhantomData<&'a ()>,
+}
 
-    #[new(val(0))]
-    idx: u32,
+enum Inner {
+    Web {
+        list: DomStringList,
+        idx: u32,
+    },
+    #[cfg(feature = "backend")]
+    Driver(std::vec::IntoIter<String>),
+}
 
-    #[new(val(PhantomData))]
-    _marker: PhantomData<&'a ()>,
+impl DomStringIter<'_> {
+    pub(crate) fn new(list: DomStringList) -> Self {
+        Self {
+            inner: Inner::Web { list, idx: 0 },
+            _marker: PhantomData,
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    pub(crate) fn from_vec(names: Vec<String>) -> Self {
+        Self {
+            inner: Inner::Driver(names.into_iter()),
+            _marker: PhantomData,
+        }
+    }
 }
 
 impl Iterator for DomStringIter<'_> {
     type Item = String;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(v) = self.inner.item(self.idx) {
-            self.idx += 1;
-            Some(v)
-        } else {
-            None
+        match self.inner {
+            Inner::Web {
+                ref list,
+                ref mut idx,
+            } => {
+                let v = list.item(*idx)?;
+                *idx += 1;
+                Some(v)
+            }
+            #[cfg(feature = "backend")]
+            Inner::Driver(ref mut names) => names.next(),
         }
     }
 
@@ -38,6 +63,10 @@ impl Iterator for DomStringIter<'_> {
 impl ExactSizeIterator for DomStringIter<'_> {
     #[inline]
     fn len(&self) -> usize {
-        (self.inner.length() - self.idx) as usize
+        match self.inner {
+            Inner::Web { ref list, idx } => (list.length() - idx) as usize,
+            #[cfg(feature = "backend")]
+            Inner::Driver(ref names) => names.len(),
+        }
     }
 }
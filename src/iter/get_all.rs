@@ -1,6 +1,6 @@
 use super::ArrayMapIter;
-use crate::primitive::TryFromJs;
-use wasm_bindgen::prelude::*;
+use crate::future::Raw;
+use crate::primitive::{TryFromJs, TryFromJsExt};
 
 iffeat! {
     #[cfg(feature = "serde")]
@@ -21,23 +21,19 @@ iffeat! {
 pub type GetAllPrimitiveIter<V> = ArrayMapIter<V>;
 
 impl<V> GetAllPrimitiveIter<V> {
-    /// [`new`](ArrayMapIter::new) alias for [`GetAllPrimitiveIter`].
-    pub(crate) fn get_all_primitive(array: js_sys::Array) -> Self
+    /// [`from_raw`](ArrayMapIter::from_raw) alias for [`GetAllPrimitiveIter`].
+    pub(crate) fn get_all_primitive(array: Raw) -> crate::Result<Self>
     where
         V: TryFromJs,
     {
-        fn map_primitive<V: TryFromJs>(value: JsValue) -> crate::Result<V> {
-            V::from_js(value).map_err(Into::into)
-        }
-
-        Self::new(array.into_iter(), map_primitive::<V>)
+        Self::from_raw(array, V::from_raw)
     }
 
     #[cfg(feature = "serde")]
-    pub(crate) fn get_all_serde(array: js_sys::Array) -> Self
+    pub(crate) fn get_all_serde(array: Raw) -> crate::Result<Self>
     where
         V: DeserialiseFromJs,
     {
-        Self::new(array.into_iter(), V::deserialise_from_js)
+        Self::from_raw(array, crate::serde::deserialise_from_raw)
     }
 }
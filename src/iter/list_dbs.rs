@@ -1,4 +1,5 @@
 use crate::factory::DatabaseDetails;
+use crate::future::Raw;
 
 use super::ArrayMapIter;
 
@@ -6,8 +7,18 @@ use super::ArrayMapIter;
 pub type ListDatabasesIter = ArrayMapIter<DatabaseDetails>;
 
 impl ListDatabasesIter {
-    /// [`new`](ArrayMapIter::new) alias for [`ListDatabasesIter`].
-    pub(crate) fn list_databases(array: js_sys::Array) -> Self {
-        Self::new(array.into_iter(), TryInto::try_into)
+    /// [`from_raw`](ArrayMapIter::from_raw) alias for [`ListDatabasesIter`].
+    pub(crate) fn list_databases(array: Raw) -> crate::Result<Self> {
+        fn map(raw: Raw) -> crate::Result<DatabaseDetails> {
+            match raw {
+                Raw::Js(js) => js.try_into(),
+                #[cfg(feature = "backend")]
+                Raw::Value(value) => {
+                    Err(crate::error::SimpleValueError::UnexpectedValue(value).into())
+                }
+            }
+        }
+
+        Self::from_raw(array, map)
     }
 }
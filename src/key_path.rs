@@ -22,7 +22,7 @@ pub enum KeyPath<T = String> {
 
 #[generic_bounds(key_path(T))]
 impl<T> KeyPath<T> {
-    /// Convert the key path to a JsValue.
+    /// Convert the key path to a `JsValue`.
     pub fn to_js(&self) -> JsValue {
         match self {
             Self::One(v) => JsValue::from_str(v.as_ref()),
@@ -30,6 +30,16 @@ impl<T> KeyPath<T> {
             Self::JsValue(v) => v.clone(),
         }
     }
+
+    /// Convert the key path into one that owns its strings.
+    #[must_use]
+    pub fn into_owned(self) -> KeyPath {
+        match self {
+            Self::One(v) => KeyPath::One(v.as_ref().into()),
+            Self::Sequence(seq) => seq.iter().map(|v| v.as_ref().to_owned()).collect(),
+            Self::JsValue(v) => KeyPath::JsValue(v),
+        }
+    }
 }
 
 #[generic_bounds(key_path(T))]
@@ -1,7 +1,11 @@
 use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
 use wasm_bindgen::prelude::*;
 
-use crate::primitive::TryToJs;
+use crate::error::SimpleValueError;
+use crate::primitive::{TryFromJs, TryToJs};
+use crate::IdbKey;
+
+mod algebra;
 
 /// An [`IDBKeyRange`](https://developer.mozilla.org/en-US/docs/Web/API/IDBKeyRange) implementation.
 ///
@@ -40,7 +44,116 @@ impl KeyRange<JsValue> {
     }
 }
 
+/// Prefix ranges. Their exclusive upper bound is the smallest key that sorts after every key starting with the prefix,
+/// computed using the same [ordering](IdbKey#impl-Ord-for-IdbKey) as the browser, so they can be passed anywhere a
+/// [`KeyRange`] is accepted.
+///
+/// ```
+/// # use indexed_db_futures::object_store::ObjectStore;
+/// # use indexed_db_futures::prelude::*;
+/// # use indexed_db_futures::{IdbKey, KeyRange};
+/// # async fn example(store: ObjectStore<'_>) -> indexed_db_futures::Result<()> {
+/// let user_keys: Vec<IdbKey> = store
+///     .get_all_keys()
+///     .with_query::<IdbKey, _>(KeyRange::string_prefix("user:42:"))
+///     .await?
+///     .collect::<indexed_db_futures::Result<_>>()?;
+///
+/// // [42, ...] on a compound index or key path
+/// let count = store
+///     .count()
+///     .with_query::<IdbKey, _>(KeyRange::array_prefix([42]))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+impl KeyRange<IdbKey> {
+    /// All string keys starting with `prefix`.
+    #[must_use]
+    pub fn string_prefix(prefix: &str) -> Self {
+        Self::Bound(
+            IdbKey::from(prefix),
+            false,
+            IdbKey::string_prefix_end(prefix),
+            true,
+        )
+    }
+
+    /// All binary keys starting with `prefix`.
+    #[must_use]
+    pub fn binary_prefix(prefix: &[u8]) -> Self {
+        Self::Bound(
+            IdbKey::from(prefix),
+            false,
+            IdbKey::binary_prefix_end(prefix),
+            true,
+        )
+    }
+
+    /// All array keys whose leading elements equal `prefix`.
+    pub fn array_prefix<I>(prefix: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<IdbKey>,
+    {
+        let start = prefix.into_iter().map(Into::into).collect::<Vec<_>>();
+
+        let mut end = start.clone();
+        let Some(last) = end.pop() else {
+            return Self::LowerBound(IdbKey::Array(start), false);
+        };
+        end.push(last.successor());
+
+        Self::Bound(IdbKey::Array(start), false, IdbKey::Array(end), true)
+    }
+}
+
+impl<T: TryToJs> KeyRange<T> {
+    /// Convert the range into an [`IDBKeyRange`](web_sys::IdbKeyRange). Unlike the [`TryToJs`] implementation,
+    /// [`Only`](KeyRange::Only) ranges get wrapped in an
+    /// [`IDBKeyRange.only`](https://developer.mozilla.org/en-US/docs/Web/API/IDBKeyRange/only) call.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn to_idb_key_range(&self) -> crate::Result<web_sys::IdbKeyRange> {
+        if let Self::Only(key) = self {
+            Ok(web_sys::IdbKeyRange::only(&key.try_to_js()?)?)
+        } else {
+            self.try_to_js().map(JsCast::unchecked_into)
+        }
+    }
+}
+
 impl<T> KeyRange<T> {
+    /// Convert the range's keys.
+    #[cfg(any(feature = "cursors", feature = "streams"))]
+    pub(crate) fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> KeyRange<U> {
+        match self {
+            Self::LowerBound(key, open) => KeyRange::LowerBound(f(key), open),
+            Self::UpperBound(key, open) => KeyRange::UpperBound(f(key), open),
+            Self::Bound(lower, lower_open, upper, upper_open) => {
+                KeyRange::Bound(f(lower), lower_open, f(upper), upper_open)
+            }
+            Self::Only(key) => KeyRange::Only(f(key)),
+        }
+    }
+
+    /// Convert the range's keys for a [driver](crate::backend).
+    #[cfg(feature = "backend")]
+    fn try_to_driver<F>(&self, key_fn: F) -> crate::Result<KeyRange<IdbKey>>
+    where
+        F: Fn(&T) -> crate::Result<crate::backend::Value>,
+    {
+        let key = |key| key_fn(key)?.into_key();
+
+        Ok(match self {
+            Self::LowerBound(lower, open) => KeyRange::LowerBound(key(lower)?, *open),
+            Self::UpperBound(upper, open) => KeyRange::UpperBound(key(upper)?, *open),
+            Self::Bound(lower, lower_open, upper, upper_open) => {
+                KeyRange::Bound(key(lower)?, *lower_open, key(upper)?, *upper_open)
+            }
+            Self::Only(only) => KeyRange::Only(key(only)?),
+        })
+    }
+
     fn try_to_js_any<F>(&self, key_fn: F) -> crate::Result<JsValue>
     where
         F: Fn(&T) -> crate::Result<JsValue>,
@@ -95,14 +208,14 @@ impl<T> From<RangeInclusive<T>> for KeyRange<T> {
 impl<T> From<RangeTo<T>> for KeyRange<T> {
     #[inline]
     fn from(value: RangeTo<T>) -> Self {
-        Self::UpperBound(value.end, false)
+        Self::UpperBound(value.end, true)
     }
 }
 
 impl<T> From<RangeToInclusive<T>> for KeyRange<T> {
     #[inline]
     fn from(value: RangeToInclusive<T>) -> Self {
-        Self::UpperBound(value.end, true)
+        Self::UpperBound(value.end, false)
     }
 }
 
@@ -113,11 +226,42 @@ impl<T: TryToJs> From<T> for KeyRange<T> {
     }
 }
 
+/// Reads an [`IDBKeyRange`](web_sys::IdbKeyRange) or, like [`KeyRange::Only`], a plain key.
+impl<T: TryFromJs> TryFromJs for KeyRange<T> {
+    fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
+        let Some(range) = js.dyn_ref::<web_sys::IdbKeyRange>() else {
+            return T::from_js(js).map(Self::Only);
+        };
+
+        let lower = range.lower().map_err(SimpleValueError::DynCast)?;
+        let upper = range.upper().map_err(SimpleValueError::DynCast)?;
+        let (lower_open, upper_open) = (range.lower_open(), range.upper_open());
+
+        Ok(match (lower.is_undefined(), upper.is_undefined()) {
+            (false, false) => Self::Bound(
+                T::from_js(lower)?,
+                lower_open,
+                T::from_js(upper)?,
+                upper_open,
+            ),
+            (false, true) => Self::LowerBound(T::from_js(lower)?, lower_open),
+            (true, false) => Self::UpperBound(T::from_js(upper)?, upper_open),
+            (true, true) => return Err(SimpleValueError::DynCast(js)),
+        })
+    }
+}
+
 impl<T: TryToJs> TryToJs for KeyRange<T> {
     #[inline]
     fn try_to_js(&self) -> crate::Result<JsValue> {
         self.try_to_js_any(TryToJs::try_to_js)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn try_to_key_range(&self) -> crate::Result<KeyRange<IdbKey>> {
+        self.try_to_driver(TryToJs::try_to_value)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -130,6 +274,12 @@ const _: () = {
         fn serialise_to_js(&self) -> crate::Result<JsValue> {
             self.try_to_js_any(SerialiseToJs::serialise_to_js)
         }
+
+        #[cfg(feature = "backend")]
+        #[inline]
+        fn serialise_to_key_range(&self) -> crate::Result<KeyRange<IdbKey>> {
+            self.try_to_driver(SerialiseToJs::serialise_to_value)
+        }
     }
 
     impl<T: Serialize> SerialiseToJs for &KeyRange<T> {
@@ -137,5 +287,11 @@ const _: () = {
         fn serialise_to_js(&self) -> crate::Result<JsValue> {
             <KeyRange<T>>::serialise_to_js(self)
         }
+
+        #[cfg(feature = "backend")]
+        #[inline]
+        fn serialise_to_key_range(&self) -> crate::Result<KeyRange<IdbKey>> {
+            <KeyRange<T>>::serialise_to_key_range(self)
+        }
     }
 };
@@ -0,0 +1,195 @@
+use super::KeyRange;
+use crate::IdbKey;
+use std::cmp::Ordering;
+
+/// A key & whether it is excluded from the range.
+type Endpoint = (IdbKey, bool);
+
+/// A [`KeyRange`] with both ends spelled out; `None` means unbounded.
+#[derive(Clone)]
+struct Span {
+    lower: Option<Endpoint>,
+    upper: Option<Endpoint>,
+}
+
+/// Range operations evaluated in Rust using the [key ordering](IdbKey#impl-Ord-for-IdbKey) the browser uses.
+///
+/// ```
+/// # use indexed_db_futures::{IdbKey, KeyRange};
+/// let a = KeyRange::<IdbKey>::from(IdbKey::from(1)..IdbKey::from(5));
+/// let b = KeyRange::<IdbKey>::from(IdbKey::from(3)..);
+///
+/// assert_eq!(
+///     a.intersect(&b),
+///     Some(KeyRange::Bound(IdbKey::from(3), false, IdbKey::from(5), true)),
+/// );
+/// assert_eq!(a.union(&b), vec![KeyRange::LowerBound(IdbKey::from(1), false)]);
+/// assert!(a.contains(&IdbKey::from(4.5)));
+/// ```
+impl KeyRange<IdbKey> {
+    /// Check if the key falls within the range.
+    #[must_use]
+    pub fn contains(&self, key: &IdbKey) -> bool {
+        let span = Span::from(self);
+
+        let above_lower = span
+            .lower
+            .as_ref()
+            .map_or(true, |(lower, open)| match key.cmp(lower) {
+                Ordering::Greater => true,
+                Ordering::Equal => !open,
+                Ordering::Less => false,
+            });
+        let below_upper = span
+            .upper
+            .as_ref()
+            .map_or(true, |(upper, open)| match key.cmp(upper) {
+                Ordering::Less => true,
+                Ordering::Equal => !open,
+                Ordering::Greater => false,
+            });
+
+        above_lower && below_upper
+    }
+
+    /// Check if no key can fall within the range, e.g. because its lower bound is greater than its upper bound. The
+    /// browser rejects such ranges.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        Span::from(self).is_empty()
+    }
+
+    /// The range of keys contained in both `self` & `other`, or `None` if there are no such keys.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let (a, b) = (Span::from(self), Span::from(other));
+        let span = Span {
+            lower: match (a.lower, b.lower) {
+                (Some(a), Some(b)) => Some(if cmp_lower(&a, &b).is_ge() { a } else { b }),
+                (a, b) => a.or(b),
+            },
+            upper: match (a.upper, b.upper) {
+                (Some(a), Some(b)) => Some(if cmp_upper(&a, &b).is_le() { a } else { b }),
+                (a, b) => a.or(b),
+            },
+        };
+
+        if span.is_empty() {
+            None
+        } else {
+            Some(span.into())
+        }
+    }
+
+    /// The keys contained in either `self` or `other` as a sorted list of disjoint ranges. See
+    /// [`union_all`](Self::union_all).
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Vec<Self> {
+        Self::union_all([self.clone(), other.clone()])
+    }
+
+    /// The keys contained in any of the given ranges as a sorted list of disjoint ranges. Overlapping & adjacent
+    /// ranges get merged; empty ranges get dropped.
+    pub fn union_all<I>(ranges: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut spans = ranges
+            .into_iter()
+            .map(|range| Span::from(&range))
+            .filter(|span| !span.is_empty())
+            .collect::<Vec<_>>();
+        spans.sort_by(|a, b| match (&a.lower, &b.lower) {
+            (Some(a), Some(b)) => cmp_lower(a, b),
+            (a, b) => b.is_none().cmp(&a.is_none()),
+        });
+
+        let mut out = Vec::<Span>::with_capacity(spans.len());
+        for span in spans {
+            match out.last_mut() {
+                Some(last) if last.touches(&span) => {
+                    if let (Some(a), Some(b)) = (&last.upper, &span.upper) {
+                        if cmp_upper(a, b).is_lt() {
+                            last.upper = span.upper;
+                        }
+                    } else {
+                        last.upper = None;
+                    }
+                }
+                _ => out.push(span),
+            }
+        }
+
+        out.into_iter().map(Into::into).collect()
+    }
+}
+
+impl Span {
+    fn is_empty(&self) -> bool {
+        let (Some((lower, lower_open)), Some((upper, upper_open))) = (&self.lower, &self.upper)
+        else {
+            return false;
+        };
+
+        match lower.cmp(upper) {
+            Ordering::Greater => true,
+            Ordering::Equal => *lower_open || *upper_open,
+            Ordering::Less => false,
+        }
+    }
+
+    /// Check if `next`, which starts no earlier than `self`, overlaps with or directly follows `self`.
+    fn touches(&self, next: &Self) -> bool {
+        let (Some((upper, upper_open)), Some((lower, lower_open))) = (&self.upper, &next.lower)
+        else {
+            return true;
+        };
+
+        match lower.cmp(upper) {
+            Ordering::Less => true,
+            Ordering::Equal => !(*upper_open && *lower_open),
+            Ordering::Greater => false,
+        }
+    }
+}
+
+/// Compare lower bounds by where they start; an open bound starts just after its key.
+fn cmp_lower((a, a_open): &Endpoint, (b, b_open): &Endpoint) -> Ordering {
+    a.cmp(b).then_with(|| a_open.cmp(b_open))
+}
+
+/// Compare upper bounds by where they end; an open bound ends just before its key.
+fn cmp_upper((a, a_open): &Endpoint, (b, b_open): &Endpoint) -> Ordering {
+    a.cmp(b).then_with(|| b_open.cmp(a_open))
+}
+
+impl From<&KeyRange<IdbKey>> for Span {
+    fn from(range: &KeyRange<IdbKey>) -> Self {
+        let (lower, upper) = match range {
+            KeyRange::Only(key) => (Some((key.clone(), false)), Some((key.clone(), false))),
+            KeyRange::LowerBound(key, open) => (Some((key.clone(), *open)), None),
+            KeyRange::UpperBound(key, open) => (None, Some((key.clone(), *open))),
+            KeyRange::Bound(lower, lower_open, upper, upper_open) => (
+                Some((lower.clone(), *lower_open)),
+                Some((upper.clone(), *upper_open)),
+            ),
+        };
+
+        Self { lower, upper }
+    }
+}
+
+impl From<Span> for KeyRange<IdbKey> {
+    fn from(span: Span) -> Self {
+        match (span.lower, span.upper) {
+            (Some((lower, false)), Some((upper, false))) if lower == upper => Self::Only(lower),
+            (Some((lower, lower_open)), Some((upper, upper_open))) => {
+                Self::Bound(lower, lower_open, upper, upper_open)
+            }
+            (Some((lower, open)), None) => Self::LowerBound(lower, open),
+            (None, Some((upper, open))) => Self::UpperBound(upper, open),
+            // Negative infinity is the smallest possible key
+            (None, None) => Self::LowerBound(IdbKey::Number(f64::NEG_INFINITY), false),
+        }
+    }
+}
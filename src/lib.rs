@@ -225,14 +225,25 @@
 //! | Feature | Description |
 //! |---------|-------------|
 //! | `async-upgrade` | Enable async closures in [`upgradeneeded`](https://developer.mozilla.org/en-US/docs/Web/API/IDBOpenDBRequest/upgradeneeded_event) event listeners. |
+//! | `backend` | Enable [driver](backend) support, allowing the API to run on top of something other than the browser's `IndexedDB`. Implies `cursors` & `indices`. |
+//! | `compression` | Enable the [compressing](typed::Compressed) value codec. Implies `typed` & `typed-arrays`. |
 //! | `cursors` | Enable opening IndexedDB [cursors](https://developer.mozilla.org/en-US/docs/Web/API/IDBCursor). |
 //! | `dates` | Enable [`SystemTime`](std::time::SystemTime) & [`Date`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date) handling. |
+//! | `derive` | Enable [`#[derive(IdbStore)]`](macro@schema::IdbStore) & [`#[derive(CompoundKey)]`](macro@primitive::CompoundKey). Implies `schema`. |
+//! | `encryption` | Enable the [encrypting](typed::Encrypted) value codec. Implies `typed` & `typed-arrays`. |
 //! | `indices` | Enable IndexedDB [indices](https://developer.mozilla.org/en-US/docs/Web/API/IDBIndex). |
+//! | `json` | Enable the [JSON](typed::Json) value codec. Implies `serde` & `typed`. |
 //! | `list-databases` | Enable getting a list of defined databases. |
+//! | `memory` | Enable the pure-Rust [in-memory driver](memory) for running storage code outside of a browser. Implies `backend`. |
+//! | `migrations` | Enable ordered, version-keyed [migrations](migration). Implies `async-upgrade`. |
+//! | `postcard` | Enable the [postcard](typed::Postcard) binary value codec. Implies `serde` & `typed`. |
+//! | `query` | Enable the [query builder](query). Implies `cursors`, `indices`, `streams` & `typed`. |
+//! | `schema` | Enable [declarative schemas](schema) applied during upgrades. Implies `indices`. |
 //! | `serde` | Enable [`serde`](::serde) integration. |
 //! | `streams` | Implement [`Stream`](::futures_core::Stream) where applicable. |
 //! | `switch` | Enable [switches](primitive::Switch2). |
 //! | `tx-done` | Enable waiting for transactions to complete without consuming them. |
+//! | `typed` | Enable [typed](typed) object store & index handles. |
 //! | `typed-arrays` | Enable [typed array](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/TypedArray) handling. |
 //! | `version-change` | Enable listening for [`versionchange`](https://developer.mozilla.org/en-US/docs/Web/API/IDBDatabase/versionchange_event) events. |
 
@@ -248,6 +259,7 @@
 #![cfg_attr(doc_cfg, feature(doc_auto_cfg))]
 
 pub use build::*;
+pub use idb_key::IdbKey;
 pub use key_path::{KeyPath, KeyPathSeq};
 pub use key_range::KeyRange;
 
@@ -308,12 +320,14 @@ pub type Result<T> = std::result::Result<T, error::Error>;
 /// A [`Result`](std::result::Result) with an [`OpenDbError`](error::OpenDbError) as the error type.
 pub type OpenDbResult<T> = std::result::Result<T, error::OpenDbError>;
 
+mod idb_key;
 mod internal_utils;
 mod key_path;
 mod key_range;
 pub mod object_store;
 pub mod prelude;
 pub mod primitive;
+mod repr;
 pub mod transaction;
 
 #[cfg(feature = "dates")]
@@ -321,13 +335,25 @@ pub mod date;
 pub mod factory;
 
 mod build;
+#[cfg(feature = "typed")]
+pub mod typed;
 #[cfg(feature = "typed-arrays")]
 pub mod typed_array;
 
+#[cfg(feature = "backend")]
+pub mod backend;
 #[cfg(feature = "cursors")]
 pub mod cursor;
 #[cfg(feature = "indices")]
 pub mod index;
+#[cfg(feature = "memory")]
+pub mod memory;
+#[cfg(feature = "migrations")]
+pub mod migration;
+#[cfg(feature = "query")]
+pub mod query;
+#[cfg(feature = "schema")]
+pub mod schema;
 
 pub mod internals;
 
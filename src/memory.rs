@@ -0,0 +1,66 @@
+//! A pure-Rust, in-memory [driver](crate::backend) for the crate's regular API.
+//!
+//! [`DBFactory::memory`](crate::factory::DBFactory::memory) creates a factory whose [`Database`]s,
+//! [`Transaction`](crate::transaction::Transaction)s, [`ObjectStore`](crate::object_store::ObjectStore)s, indices &
+//! cursors don't touch `JavaScript` at all, so storage code written against them can be exercised from plain
+//! `cargo test` runs on any target. Requests resolve on their first poll as there is no event loop to wait on.
+//!
+//! The following `IndexedDB` semantics are honoured:
+//!
+//! - Keys are ordered per the [spec](https://w3c.github.io/IndexedDB/#compare-two-keys): numbers < dates < strings <
+//!   binary < arrays.
+//! - In-line & out-of-line keys, key paths, key generators (auto-increment).
+//! - Unique & multi-entry indices.
+//! - Transaction scoping & modes. Like the rest of the crate, transactions roll back unless explicitly
+//!   [committed](crate::transaction::Transaction::commit).
+//! - Schema changes being restricted to upgrades.
+//!
+//! Each transaction works on a snapshot of the object stores in its scope; the snapshot gets written back when the
+//! transaction is committed. Read/write transactions with overlapping scopes are not queued like they would be in a
+//! browser. Instead, committing one fails with an [`AbortError`](crate::error::BackendError::AbortError) & rolls it
+//! back if an object store in its scope has been written to since it started, so updates never get lost.
+//!
+//! # Example
+//!
+//! ```
+//! use indexed_db_futures::factory::DBFactory;
+//! use indexed_db_futures::prelude::*;
+//! use indexed_db_futures::transaction::TransactionMode;
+//!
+//! # fn main() -> indexed_db_futures::OpenDbResult<()> {
+//! # futures::executor::block_on(async {
+//! let db = DBFactory::memory()
+//!     .open_db("my_db")
+//!     .with_version(1u32)
+//!     .with_on_upgrade_needed(|_, db| {
+//!         db.create_object_store("users").build()?;
+//!         Ok(())
+//!     })
+//!     .await?;
+//!
+//! let tx = db.transaction("users").with_mode(TransactionMode::Readwrite).build()?;
+//! tx.object_store("users")?.put("Bobby").with_key(1u32).await?;
+//! tx.commit().await?;
+//!
+//! let tx = db.transaction("users").build()?;
+//! let name: Option<String> = tx.object_store("users")?.get(1u32).await?;
+//! assert_eq!(name.as_deref(), Some("Bobby"));
+//! # Ok(())
+//! # })
+//! # }
+//! ```
+//!
+//! [`Database`]: crate::database::Database
+
+pub use factory::MemoryFactory;
+
+use crate::IdbKey as Key;
+
+mod cursor;
+mod database;
+mod factory;
+mod index;
+mod key_range;
+mod object_store;
+mod state;
+mod transaction;
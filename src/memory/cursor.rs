@@ -0,0 +1,309 @@
+use super::index::find_index;
+use super::key_range::{bounds, Bounds};
+use super::object_store::{is_reverse, MemoryObjectStore};
+use super::state::{KeyPathRepr, Position};
+use super::Key;
+use crate::backend::{CursorDriver, QueryDriver, StoreDriver, Value};
+use crate::cursor::CursorDirection;
+use crate::error::BackendError;
+use crate::KeyRange;
+use std::cell::RefCell;
+use std::ops::Bound;
+
+/// An in-memory cursor over an object store or an index.
+#[derive(Debug)]
+pub(super) struct MemoryCursor {
+    store: MemoryObjectStore,
+    index: Option<String>,
+    range: Option<KeyRange<Key>>,
+    direction: CursorDirection,
+    key_only: bool,
+
+    /// `None` once the cursor has iterated past its end.
+    position: RefCell<Option<Position>>,
+}
+
+impl MemoryCursor {
+    pub(super) fn new(
+        store: MemoryObjectStore,
+        index: Option<String>,
+        range: Option<KeyRange<Key>>,
+        direction: CursorDirection,
+        key_only: bool,
+        position: Position,
+    ) -> Self {
+        Self {
+            store,
+            index,
+            range,
+            direction,
+            key_only,
+            position: RefCell::new(Some(position)),
+        }
+    }
+
+    fn current(&self) -> crate::Result<Position> {
+        match *self.position.borrow() {
+            Some(ref position) => Ok(position.clone()),
+            None => Err(BackendError::InvalidStateError("The cursor has finished".into()).into()),
+        }
+    }
+
+    /// The current position, failing if the cursor can't be written through.
+    fn writable(&self) -> crate::Result<Position> {
+        if self.key_only {
+            return Err(BackendError::InvalidStateError(
+                "Key-only cursors can't modify records".into(),
+            )
+            .into());
+        }
+
+        self.current()
+    }
+
+    fn bounds(&self) -> Bounds<'_> {
+        bounds(self.range.as_ref())
+    }
+
+    fn is_unique(&self) -> bool {
+        matches!(
+            self.direction,
+            CursorDirection::Nextunique | CursorDirection::Prevunique
+        )
+    }
+
+    /// Move to the next record.
+    fn step(&self) -> crate::Result<()> {
+        let after = self.current()?;
+        let next = self.find(self.bounds(), Some(&after))?;
+        *self.position.borrow_mut() = next;
+
+        Ok(())
+    }
+
+    fn check_target(&self, target: &Key, current: &Key, strict: bool) -> crate::Result<()> {
+        if !target.is_valid() {
+            return Err(BackendError::DataError("Not a valid key".into()).into());
+        }
+
+        let ok = match (is_reverse(self.direction), strict) {
+            (false, true) => target > current,
+            (false, false) => target >= current,
+            (true, true) => target < current,
+            (true, false) => target <= current,
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(BackendError::DataError("The key is not past the cursor's position".into()).into())
+        }
+    }
+
+    /// Find the next position within the given bounds, strictly after `after` if provided.
+    fn find(&self, bounds: Bounds, after: Option<&Position>) -> crate::Result<Option<Position>> {
+        let reverse = is_reverse(self.direction);
+        let unique = self.is_unique();
+        self.store.with_state(|store| {
+            if let Some(name) = &self.index {
+                return Ok(find_index(&store.indices, name)?.seek(bounds, after, reverse, unique));
+            }
+
+            let bounds = match after {
+                None => bounds,
+                Some((key, _)) if reverse => (bounds.0, Bound::Excluded(key)),
+                Some((key, _)) => (Bound::Excluded(key), bounds.1),
+            };
+            Ok(store.seek(bounds, reverse))
+        })
+    }
+}
+
+impl CursorDriver for MemoryCursor {
+    #[inline]
+    fn direction(&self) -> CursorDirection {
+        self.direction
+    }
+
+    fn key(&self) -> Option<Key> {
+        self.position.borrow().as_ref().map(|(key, _)| key.clone())
+    }
+
+    fn primary_key(&self) -> Option<Key> {
+        self.position.borrow().as_ref().map(|(_, pk)| pk.clone())
+    }
+
+    fn value(&self) -> crate::Result<Option<Value>> {
+        if self.key_only {
+            return Ok(None);
+        }
+        let Some((_, pk)) = self.position.borrow().clone() else {
+            return Ok(None);
+        };
+
+        self.store
+            .with_state(|store| Ok(store.records.get(&pk).cloned()))
+    }
+
+    fn advance(&self, count: u32) -> crate::Result<()> {
+        if count == 0 {
+            return Err(BackendError::DataError("Count cannot be 0".into()).into());
+        }
+        for _ in 0..count {
+            self.step()?;
+            if self.position.borrow().is_none() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn continue_to_key(&self, key: Option<Key>) -> crate::Result<()> {
+        let Some(key) = key else {
+            return self.step();
+        };
+        let (current, _) = self.current()?;
+        self.check_target(&key, &current, true)?;
+
+        let (lower, upper) = self.bounds();
+        let bounds = if is_reverse(self.direction) {
+            (lower, Bound::Included(&key).min_upper(upper))
+        } else {
+            (Bound::Included(&key).max_lower(lower), upper)
+        };
+        let next = self.find(bounds, None)?;
+        *self.position.borrow_mut() = next;
+
+        Ok(())
+    }
+
+    fn continue_to_primary_key(&self, key: Key, primary_key: Key) -> crate::Result<()> {
+        let Some(ref index_name) = self.index else {
+            return Err(BackendError::InvalidAccessError(
+                "continue_to_primary_key requires an index cursor".into(),
+            )
+            .into());
+        };
+        if self.is_unique() {
+            return Err(BackendError::InvalidAccessError(
+                "continue_to_primary_key requires a non-unique cursor".into(),
+            )
+            .into());
+        }
+
+        let target = (key, primary_key);
+        let current = self.current()?;
+        self.check_target(&target.0, &current.0, false)?;
+
+        let reverse = is_reverse(self.direction);
+        let past_current = if reverse {
+            target < current
+        } else {
+            target > current
+        };
+        if !past_current {
+            return Err(BackendError::DataError(
+                "The key is not past the cursor's position".into(),
+            )
+            .into());
+        }
+
+        let found = self.store.with_state(|store| {
+            let index = find_index(&store.indices, index_name)?;
+            let mut found = index.iter_range(self.range.as_ref()).filter(|&(k, pk)| {
+                let ord = (k, pk).cmp(&(&target.0, &target.1));
+                if reverse {
+                    ord.is_le()
+                } else {
+                    ord.is_ge()
+                }
+            });
+
+            let pos = if reverse {
+                found.next_back()
+            } else {
+                found.next()
+            };
+            Ok(pos.map(|(k, pk)| (k.clone(), pk.clone())))
+        })?;
+        *self.position.borrow_mut() = found;
+
+        Ok(())
+    }
+
+    fn update(&self, value: Value) -> crate::Result<Key> {
+        let (_, pk) = self.writable()?;
+
+        match self.store.key_path() {
+            Some(key_path) => {
+                if KeyPathRepr::from_public(&key_path)?.eval(&value).as_ref() != Some(&pk) {
+                    return Err(BackendError::DataError(
+                        "The value's key does not match the cursor's primary key".into(),
+                    )
+                    .into());
+                }
+                self.store.store(value, None, true)
+            }
+            None => self.store.store(value, Some(pk), true),
+        }
+    }
+
+    fn delete(&self) -> crate::Result<()> {
+        let (_, pk) = self.writable()?;
+        StoreDriver::delete(&self.store, KeyRange::Only(pk))
+    }
+}
+
+/// Helpers for tightening bounds.
+trait BoundExt<'a> {
+    fn max_lower(self, other: Bound<&'a Key>) -> Bound<&'a Key>;
+    fn min_upper(self, other: Bound<&'a Key>) -> Bound<&'a Key>;
+}
+
+impl<'a> BoundExt<'a> for Bound<&'a Key> {
+    fn max_lower(self, other: Bound<&'a Key>) -> Bound<&'a Key> {
+        match (self, other) {
+            (Bound::Unbounded, b) | (b, Bound::Unbounded) => b,
+            (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b))
+                if a != b =>
+            {
+                if a > b {
+                    self
+                } else {
+                    other
+                }
+            }
+            (Bound::Excluded(_), _) | (_, Bound::Excluded(_)) => {
+                if matches!(self, Bound::Excluded(_)) {
+                    self
+                } else {
+                    other
+                }
+            }
+            _ => self,
+        }
+    }
+
+    fn min_upper(self, other: Bound<&'a Key>) -> Bound<&'a Key> {
+        match (self, other) {
+            (Bound::Unbounded, b) | (b, Bound::Unbounded) => b,
+            (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b))
+                if a != b =>
+            {
+                if a < b {
+                    self
+                } else {
+                    other
+                }
+            }
+            (Bound::Excluded(_), _) | (_, Bound::Excluded(_)) => {
+                if matches!(self, Bound::Excluded(_)) {
+                    self
+                } else {
+                    other
+                }
+            }
+            _ => self,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use super::object_store::MemoryObjectStore;
+use super::state::{DbState, KeyPathRepr, StoreState};
+use super::transaction::MemoryTransaction;
+use crate::backend::{DatabaseDriver, StoreDriver, TransactionDriver};
+use crate::error::BackendError;
+use crate::transaction::TransactionMode;
+use crate::KeyPath;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// An in-memory database connection.
+pub(super) struct MemoryDatabase {
+    name: String,
+    state: Rc<RefCell<DbState>>,
+    closed: Cell<bool>,
+
+    /// The upgrade transaction for connections handed to upgrade handlers.
+    upgrade: Option<MemoryTransaction>,
+}
+
+impl MemoryDatabase {
+    pub(super) fn new(name: String, state: Rc<RefCell<DbState>>) -> Self {
+        Self {
+            name,
+            state,
+            closed: Cell::new(false),
+            upgrade: None,
+        }
+    }
+
+    pub(super) fn new_upgrade(
+        name: String,
+        state: Rc<RefCell<DbState>>,
+        tx: MemoryTransaction,
+    ) -> Self {
+        Self {
+            upgrade: Some(tx),
+            ..Self::new(name, state)
+        }
+    }
+
+    /// The upgrade transaction, if it's still running.
+    fn active_upgrade(&self) -> Option<&MemoryTransaction> {
+        self.upgrade.as_ref().filter(|tx| tx.check_active().is_ok())
+    }
+
+    fn upgrade_tx(&self) -> crate::Result<&MemoryTransaction> {
+        match self.upgrade {
+            Some(ref tx) => {
+                tx.check_upgrade()?;
+                Ok(tx)
+            }
+            None => Err(BackendError::InvalidStateError(
+                "Schema changes can only be made during an upgrade".into(),
+            )
+            .into()),
+        }
+    }
+}
+
+impl DatabaseDriver for MemoryDatabase {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn version(&self) -> u32 {
+        match self
+            .active_upgrade()
+            .and_then(MemoryTransaction::upgrade_version)
+        {
+            Some(version) => version,
+            None => self.state.borrow().version,
+        }
+    }
+
+    fn object_store_names(&self) -> Vec<String> {
+        match self.active_upgrade() {
+            Some(tx) => tx.object_store_names(),
+            None => self.state.borrow().stores.keys().cloned().collect(),
+        }
+    }
+
+    fn create_object_store(
+        &self,
+        name: &str,
+        key_path: Option<KeyPath>,
+        auto_increment: bool,
+    ) -> crate::Result<Rc<dyn StoreDriver>> {
+        let tx = self.upgrade_tx()?;
+        let key_path = match key_path {
+            Some(ref kp) => Some(KeyPathRepr::from_public(kp)?),
+            None => None,
+        };
+
+        if auto_increment {
+            match key_path {
+                Some(KeyPathRepr::Sequence(_)) => {
+                    return Err(BackendError::InvalidAccessError(
+                        "Auto-incrementing object stores cannot have sequence key paths".into(),
+                    )
+                    .into());
+                }
+                Some(KeyPathRepr::One(ref path)) if path.is_empty() => {
+                    return Err(BackendError::InvalidAccessError(
+                        "Auto-incrementing object stores cannot have empty key paths".into(),
+                    )
+                    .into());
+                }
+                _ => {}
+            }
+        }
+
+        let mut stores = tx.stores_mut();
+        if stores.contains_key(name) {
+            return Err(BackendError::ConstraintError(format!(
+                "An object store named {name:?} already exists"
+            ))
+            .into());
+        }
+        stores.insert(name.into(), StoreState::new(key_path, auto_increment));
+
+        Ok(Rc::new(MemoryObjectStore::new(tx.clone(), name.into())))
+    }
+
+    fn delete_object_store(&self, name: &str) -> crate::Result<()> {
+        let tx = self.upgrade_tx()?;
+        let removed = tx.stores_mut().remove(name);
+        match removed {
+            Some(_) => Ok(()),
+            None => {
+                Err(BackendError::NotFoundError(format!("Object store {name:?} not found")).into())
+            }
+        }
+    }
+
+    fn transaction(
+        &self,
+        store_names: &[String],
+        mode: TransactionMode,
+    ) -> crate::Result<Rc<dyn TransactionDriver>> {
+        if self.closed.get() {
+            return Err(BackendError::InvalidStateError("The connection is closed".into()).into());
+        }
+        if self.active_upgrade().is_some() {
+            return Err(BackendError::InvalidStateError(
+                "Transactions cannot be started during an upgrade".into(),
+            )
+            .into());
+        }
+        if store_names.is_empty() {
+            return Err(BackendError::InvalidAccessError(
+                "At least one object store name must be provided".into(),
+            )
+            .into());
+        }
+        if !matches!(mode, TransactionMode::Readonly | TransactionMode::Readwrite) {
+            return Err(BackendError::InvalidAccessError(format!(
+                "Invalid transaction mode: {mode:?}"
+            ))
+            .into());
+        }
+
+        let tx = MemoryTransaction::new(Rc::clone(&self.state), store_names, mode)?;
+        Ok(Rc::new(tx))
+    }
+
+    fn close(&self) {
+        self.closed.set(true);
+    }
+}
+
+impl Debug for MemoryDatabase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryDatabase")
+            .field("name", &self.name)
+            .field("version", &self.version())
+            .field("object_store_names", &self.object_store_names())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,113 @@
+use super::database::MemoryDatabase;
+use super::state::DbState;
+use super::transaction::MemoryTransaction;
+use crate::backend::{
+    DatabaseDriver, DriverFuture, FactoryDriver, TransactionDriver, Upgrade, UpgradeHandler,
+};
+use crate::error::BackendError;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// An in-memory [factory driver](FactoryDriver). Usually created through
+/// [`DBFactory::memory`](crate::factory::DBFactory::memory); construct it directly to wrap it in another driver.
+///
+/// Clones share the same set of databases; separate [`MemoryFactory::new`] calls produce isolated ones.
+#[derive(Clone, Default)]
+pub struct MemoryFactory {
+    databases: Rc<RefCell<BTreeMap<String, Rc<RefCell<DbState>>>>>,
+}
+
+impl MemoryFactory {
+    /// Create a new factory with no databases.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl FactoryDriver for MemoryFactory {
+    fn open(
+        &self,
+        name: &str,
+        version: Option<u32>,
+        on_upgrade: Option<UpgradeHandler>,
+    ) -> DriverFuture<Rc<dyn DatabaseDriver>> {
+        let databases = Rc::clone(&self.databases);
+        let name = name.to_owned();
+
+        Box::pin(async move {
+            let existing = databases.borrow().get(&name).cloned();
+            let old_version = existing.as_ref().map_or(0, |db| db.borrow().version);
+
+            let version = match version {
+                Some(0) => {
+                    return Err(BackendError::DataError("Version cannot be zero".into()).into());
+                }
+                Some(v) if v < old_version => {
+                    return Err(BackendError::VersionError(format!(
+                        "The requested version ({v}) is less than the existing version ({old_version})"
+                    ))
+                    .into());
+                }
+                Some(v) => v,
+                None => old_version.max(1),
+            };
+
+            let state = existing.unwrap_or_default();
+            if version != old_version {
+                let tx = MemoryTransaction::new_upgrade(Rc::clone(&state), version);
+                let database =
+                    MemoryDatabase::new_upgrade(name.clone(), Rc::clone(&state), tx.clone());
+
+                if let Some(on_upgrade) = on_upgrade {
+                    let upgrade = Upgrade {
+                        old_version,
+                        new_version: version,
+                        database: Rc::new(database),
+                        transaction: Rc::new(tx.clone()),
+                    };
+                    if let Err(e) = on_upgrade(upgrade).await {
+                        let _ = tx.abort();
+                        return Err(e);
+                    }
+                }
+
+                if tx.is_aborted() {
+                    return Err(BackendError::AbortError(
+                        "The upgrade transaction was aborted".into(),
+                    )
+                    .into());
+                }
+                tx.commit()?;
+
+                databases
+                    .borrow_mut()
+                    .insert(name.clone(), Rc::clone(&state));
+            }
+
+            Ok(Rc::new(MemoryDatabase::new(name, state)) as Rc<dyn DatabaseDriver>)
+        })
+    }
+
+    fn delete(&self, name: &str) -> DriverFuture<()> {
+        let databases = Rc::clone(&self.databases);
+        let name = name.to_owned();
+
+        Box::pin(async move {
+            databases.borrow_mut().remove(&name);
+            Ok(())
+        })
+    }
+}
+
+impl Debug for MemoryFactory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let databases = self.databases.borrow();
+        f.debug_struct("MemoryFactory")
+            .field("databases", &databases.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,179 @@
+use super::cursor::MemoryCursor;
+use super::key_range::{bounds, validate};
+use super::object_store::{is_reverse, saturate, MemoryObjectStore};
+use super::state::{IndexState, StoreState};
+use super::Key;
+use crate::backend::{CursorDriver, IndexDriver, QueryDriver, Value};
+use crate::cursor::CursorDirection;
+use crate::error::BackendError;
+use crate::{KeyPath, KeyRange};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// An in-memory index.
+#[derive(Debug)]
+pub(super) struct MemoryIndex {
+    store: MemoryObjectStore,
+    name: RefCell<String>,
+}
+
+impl MemoryIndex {
+    #[inline]
+    pub(super) fn new(store: MemoryObjectStore, name: String) -> Self {
+        Self {
+            store,
+            name: RefCell::new(name),
+        }
+    }
+
+    fn with_index<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&StoreState, &IndexState) -> crate::Result<R>,
+    {
+        self.store
+            .with_state(|store| f(store, find_index(&store.indices, &self.name.borrow())?))
+    }
+}
+
+impl QueryDriver for MemoryIndex {
+    fn name(&self) -> String {
+        self.name.borrow().clone()
+    }
+
+    fn set_name(&self, name: &str) -> crate::Result<()> {
+        self.store.transaction().check_upgrade()?;
+        self.store.with_state(|store| {
+            if *self.name.borrow() == name {
+                return Ok(());
+            }
+            if store.indices.contains_key(name) {
+                return Err(BackendError::ConstraintError(format!(
+                    "An index named {name:?} already exists"
+                ))
+                .into());
+            }
+
+            let Some(index) = store.indices.remove(&*self.name.borrow()) else {
+                return Err(BackendError::InvalidStateError(format!(
+                    "Index {:?} has been deleted",
+                    self.name.borrow()
+                ))
+                .into());
+            };
+            store.indices.insert(name.into(), index);
+            *self.name.borrow_mut() = name.into();
+
+            Ok(())
+        })
+    }
+
+    fn key_path(&self) -> Option<KeyPath> {
+        self.with_index(|_, index| Ok(index.key_path.to_public()))
+            .ok()
+    }
+
+    fn count(&self, query: Option<KeyRange<Key>>) -> crate::Result<u32> {
+        validate(query.as_ref())?;
+        self.with_index(|_, index| Ok(saturate(index.iter_range(query.as_ref()).count())))
+    }
+
+    fn get(&self, query: KeyRange<Key>) -> crate::Result<Option<Value>> {
+        validate(Some(&query))?;
+        self.with_index(|store, index| {
+            let pk = index.iter_range(Some(&query)).next().map(|(_, pk)| pk);
+            Ok(pk.and_then(|pk| store.records.get(pk).cloned()))
+        })
+    }
+
+    fn get_key(&self, query: KeyRange<Key>) -> crate::Result<Option<Key>> {
+        validate(Some(&query))?;
+        self.with_index(|_, index| {
+            Ok(index
+                .iter_range(Some(&query))
+                .next()
+                .map(|(_, pk)| pk.clone()))
+        })
+    }
+
+    fn get_all(
+        &self,
+        query: Option<KeyRange<Key>>,
+        limit: Option<u32>,
+    ) -> crate::Result<Vec<Value>> {
+        validate(query.as_ref())?;
+        self.with_index(|store, index| {
+            Ok(index
+                .iter_range(query.as_ref())
+                .take(limit.map_or(usize::MAX, |l| l as usize))
+                .filter_map(|(_, pk)| store.records.get(pk).cloned())
+                .collect())
+        })
+    }
+
+    fn get_all_keys(
+        &self,
+        query: Option<KeyRange<Key>>,
+        limit: Option<u32>,
+    ) -> crate::Result<Vec<Key>> {
+        validate(query.as_ref())?;
+        self.with_index(|_, index| {
+            Ok(index
+                .iter_range(query.as_ref())
+                .take(limit.map_or(usize::MAX, |l| l as usize))
+                .map(|(_, pk)| pk.clone())
+                .collect())
+        })
+    }
+
+    fn open_cursor(
+        &self,
+        query: Option<KeyRange<Key>>,
+        direction: CursorDirection,
+        key_only: bool,
+    ) -> crate::Result<Option<Rc<dyn CursorDriver>>> {
+        validate(query.as_ref())?;
+        let start = self.with_index(|_, index| {
+            let unique = matches!(
+                direction,
+                CursorDirection::Nextunique | CursorDirection::Prevunique
+            );
+            Ok(index.seek(bounds(query.as_ref()), None, is_reverse(direction), unique))
+        })?;
+
+        Ok(start.map(|pos| {
+            Rc::new(MemoryCursor::new(
+                self.store.clone(),
+                Some(self.name()),
+                query,
+                direction,
+                key_only,
+                pos,
+            )) as Rc<dyn CursorDriver>
+        }))
+    }
+}
+
+impl IndexDriver for MemoryIndex {
+    fn unique(&self) -> bool {
+        self.with_index(|_, index| Ok(index.unique))
+            .unwrap_or_default()
+    }
+
+    fn multi_entry(&self) -> bool {
+        self.with_index(|_, index| Ok(index.multi_entry))
+            .unwrap_or_default()
+    }
+}
+
+pub(super) fn find_index<'a>(
+    indices: &'a BTreeMap<String, IndexState>,
+    name: &str,
+) -> crate::Result<&'a IndexState> {
+    match indices.get(name) {
+        Some(index) => Ok(index),
+        None => {
+            Err(BackendError::InvalidStateError(format!("Index {name:?} has been deleted")).into())
+        }
+    }
+}
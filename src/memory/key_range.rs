@@ -0,0 +1,66 @@
+use super::Key;
+use crate::error::BackendError;
+use crate::KeyRange;
+use std::ops::Bound;
+
+/// Lower & upper bounds of an optional key range.
+pub(super) type Bounds<'a> = (Bound<&'a Key>, Bound<&'a Key>);
+
+pub(super) fn bounds(range: Option<&KeyRange<Key>>) -> Bounds<'_> {
+    fn bound(key: &Key, open: bool) -> Bound<&Key> {
+        if open {
+            Bound::Excluded(key)
+        } else {
+            Bound::Included(key)
+        }
+    }
+
+    match range {
+        None => (Bound::Unbounded, Bound::Unbounded),
+        Some(KeyRange::Only(key)) => (Bound::Included(key), Bound::Included(key)),
+        Some(KeyRange::LowerBound(key, open)) => (bound(key, *open), Bound::Unbounded),
+        Some(KeyRange::UpperBound(key, open)) => (Bound::Unbounded, bound(key, *open)),
+        Some(KeyRange::Bound(lower, lower_open, upper, upper_open)) => {
+            (bound(lower, *lower_open), bound(upper, *upper_open))
+        }
+    }
+}
+
+/// Check that the bounds can be passed to [`BTreeMap::range`](std::collections::BTreeMap::range) without panicking
+/// and that they can match at least one key.
+pub(super) fn is_satisfiable((lower, upper): Bounds) -> bool {
+    match (lower, upper) {
+        (Bound::Included(l), Bound::Included(u)) => l <= u,
+        (Bound::Included(l) | Bound::Excluded(l), Bound::Excluded(u))
+        | (Bound::Excluded(l), Bound::Included(u)) => l < u,
+        _ => true,
+    }
+}
+
+/// Validate a key range the same way `IDBKeyRange`'s constructors would.
+pub(super) fn validate(range: Option<&KeyRange<Key>>) -> crate::Result<()> {
+    let Some(range) = range else {
+        return Ok(());
+    };
+
+    let keys_valid = match range {
+        KeyRange::Only(key) | KeyRange::LowerBound(key, _) | KeyRange::UpperBound(key, _) => {
+            key.is_valid()
+        }
+        KeyRange::Bound(lower, _, upper, _) => lower.is_valid() && upper.is_valid(),
+    };
+    if !keys_valid {
+        return Err(BackendError::DataError("Not a valid key".into()).into());
+    }
+
+    if let KeyRange::Bound(lower, lower_open, upper, upper_open) = range {
+        if lower > upper || (lower == upper && (*lower_open || *upper_open)) {
+            return Err(BackendError::DataError(
+                "The lower key is greater than the upper key or the range is empty".into(),
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
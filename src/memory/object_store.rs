@@ -0,0 +1,252 @@
+use super::cursor::MemoryCursor;
+use super::index::MemoryIndex;
+use super::key_range::{bounds, validate};
+use super::state::{IndexState, KeyPathRepr};
+use super::transaction::MemoryTransaction;
+use super::Key;
+use crate::backend::{CursorDriver, IndexDriver, QueryDriver, StoreDriver, Value};
+use crate::cursor::CursorDirection;
+use crate::error::BackendError;
+use crate::{KeyPath, KeyRange};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// An in-memory object store. Clones share the same name, so they follow renames.
+#[derive(Debug, Clone)]
+pub(super) struct MemoryObjectStore {
+    tx: MemoryTransaction,
+    name: Rc<RefCell<String>>,
+}
+
+impl MemoryObjectStore {
+    pub(super) fn new(tx: MemoryTransaction, name: String) -> Self {
+        Self {
+            tx,
+            name: Rc::new(RefCell::new(name)),
+        }
+    }
+
+    /// The transaction this object store belongs to.
+    #[inline]
+    pub(super) fn transaction(&self) -> &MemoryTransaction {
+        &self.tx
+    }
+
+    /// Run a closure against the object store's working copy.
+    pub(super) fn with_state<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&mut super::state::StoreState) -> crate::Result<R>,
+    {
+        self.tx.with_store(&self.name.borrow(), f)
+    }
+
+    /// Run a closure that writes to the object store's working copy.
+    pub(super) fn write_state<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&mut super::state::StoreState) -> crate::Result<R>,
+    {
+        self.tx.write_store(&self.name.borrow(), f)
+    }
+
+    pub(super) fn store(
+        &self,
+        value: Value,
+        key: Option<Key>,
+        overwrite: bool,
+    ) -> crate::Result<Key> {
+        self.write_state(|store| store.store(value, key, overwrite))
+    }
+}
+
+impl QueryDriver for MemoryObjectStore {
+    fn name(&self) -> String {
+        self.name.borrow().clone()
+    }
+
+    fn set_name(&self, name: &str) -> crate::Result<()> {
+        self.tx.check_upgrade()?;
+
+        let mut stores = self.tx.stores_mut();
+        if *self.name.borrow() == name {
+            return Ok(());
+        }
+        if stores.contains_key(name) {
+            return Err(BackendError::ConstraintError(format!(
+                "An object store named {name:?} already exists"
+            ))
+            .into());
+        }
+
+        let Some(store) = stores.remove(&*self.name.borrow()) else {
+            return Err(BackendError::InvalidStateError(format!(
+                "Object store {:?} has been deleted",
+                self.name.borrow()
+            ))
+            .into());
+        };
+        stores.insert(name.into(), store);
+        *self.name.borrow_mut() = name.into();
+
+        Ok(())
+    }
+
+    fn key_path(&self) -> Option<KeyPath> {
+        self.with_state(|store| Ok(store.key_path.as_ref().map(KeyPathRepr::to_public)))
+            .ok()
+            .flatten()
+    }
+
+    fn count(&self, query: Option<KeyRange<Key>>) -> crate::Result<u32> {
+        validate(query.as_ref())?;
+        self.with_state(|store| Ok(saturate(store.count(query.as_ref()))))
+    }
+
+    fn get(&self, query: KeyRange<Key>) -> crate::Result<Option<Value>> {
+        validate(Some(&query))?;
+        self.with_state(|store| {
+            let key = store.keys_in(Some(&query), Some(1)).into_iter().next();
+            Ok(key.and_then(|k| store.records.get(&k).cloned()))
+        })
+    }
+
+    fn get_key(&self, query: KeyRange<Key>) -> crate::Result<Option<Key>> {
+        validate(Some(&query))?;
+        self.with_state(|store| Ok(store.keys_in(Some(&query), Some(1)).into_iter().next()))
+    }
+
+    fn get_all(
+        &self,
+        query: Option<KeyRange<Key>>,
+        limit: Option<u32>,
+    ) -> crate::Result<Vec<Value>> {
+        validate(query.as_ref())?;
+        self.with_state(|store| {
+            let keys = store.keys_in(query.as_ref(), limit.map(|l| l as usize));
+            Ok(keys
+                .iter()
+                .filter_map(|k| store.records.get(k).cloned())
+                .collect())
+        })
+    }
+
+    fn get_all_keys(
+        &self,
+        query: Option<KeyRange<Key>>,
+        limit: Option<u32>,
+    ) -> crate::Result<Vec<Key>> {
+        validate(query.as_ref())?;
+        self.with_state(|store| Ok(store.keys_in(query.as_ref(), limit.map(|l| l as usize))))
+    }
+
+    fn open_cursor(
+        &self,
+        query: Option<KeyRange<Key>>,
+        direction: CursorDirection,
+        key_only: bool,
+    ) -> crate::Result<Option<Rc<dyn CursorDriver>>> {
+        validate(query.as_ref())?;
+        let start =
+            self.with_state(|store| Ok(store.seek(bounds(query.as_ref()), is_reverse(direction))))?;
+
+        Ok(start.map(|pos| {
+            Rc::new(MemoryCursor::new(
+                self.clone(),
+                None,
+                query,
+                direction,
+                key_only,
+                pos,
+            )) as Rc<dyn CursorDriver>
+        }))
+    }
+}
+
+impl StoreDriver for MemoryObjectStore {
+    fn auto_increment(&self) -> bool {
+        self.with_state(|store| Ok(store.auto_increment))
+            .unwrap_or_default()
+    }
+
+    fn index_names(&self) -> Vec<String> {
+        self.with_state(|store| Ok(store.indices.keys().cloned().collect()))
+            .unwrap_or_default()
+    }
+
+    fn index(&self, name: &str) -> crate::Result<Rc<dyn IndexDriver>> {
+        self.with_state(|store| {
+            if store.indices.contains_key(name) {
+                Ok(Rc::new(MemoryIndex::new(self.clone(), name.into())) as Rc<dyn IndexDriver>)
+            } else {
+                Err(BackendError::NotFoundError(format!("Index {name:?} not found")).into())
+            }
+        })
+    }
+
+    fn create_index(
+        &self,
+        name: &str,
+        key_path: KeyPath,
+        unique: bool,
+        multi_entry: bool,
+    ) -> crate::Result<Rc<dyn IndexDriver>> {
+        self.tx.check_upgrade()?;
+        let key_path = KeyPathRepr::from_public(&key_path)?;
+        if multi_entry && matches!(key_path, KeyPathRepr::Sequence(_)) {
+            return Err(BackendError::InvalidAccessError(
+                "Multi-entry indices cannot have sequence key paths".into(),
+            )
+            .into());
+        }
+
+        self.with_state(|store| {
+            store.create_index(name, IndexState::new(key_path, unique, multi_entry))
+        })?;
+
+        Ok(Rc::new(MemoryIndex::new(self.clone(), name.into())))
+    }
+
+    fn delete_index(&self, name: &str) -> crate::Result<()> {
+        self.tx.check_upgrade()?;
+        self.with_state(|store| match store.indices.remove(name) {
+            Some(_) => Ok(()),
+            None => Err(BackendError::NotFoundError(format!("Index {name:?} not found")).into()),
+        })
+    }
+
+    #[inline]
+    fn add(&self, value: Value, key: Option<Key>) -> crate::Result<Key> {
+        self.store(value, key, false)
+    }
+
+    #[inline]
+    fn put(&self, value: Value, key: Option<Key>) -> crate::Result<Key> {
+        self.store(value, key, true)
+    }
+
+    fn delete(&self, query: KeyRange<Key>) -> crate::Result<()> {
+        validate(Some(&query))?;
+        self.write_state(|store| {
+            store.delete(Some(&query));
+            Ok(())
+        })
+    }
+
+    fn clear(&self) -> crate::Result<()> {
+        self.write_state(|store| {
+            store.clear();
+            Ok(())
+        })
+    }
+}
+
+pub(super) fn is_reverse(direction: CursorDirection) -> bool {
+    matches!(
+        direction,
+        CursorDirection::Prev | CursorDirection::Prevunique
+    )
+}
+
+/// Convert a count for the driver API, saturating at [`u32::MAX`].
+pub(super) fn saturate(count: usize) -> u32 {
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
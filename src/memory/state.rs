@@ -0,0 +1,450 @@
+//! Internal storage structures.
+
+use super::key_range::{bounds, is_satisfiable, Bounds};
+use super::Key;
+use crate::backend::Value;
+use crate::error::BackendError;
+use crate::KeyRange;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+
+/// The highest number a key generator can produce.
+const MAX_GENERATED_KEY: f64 = 9_007_199_254_740_992.0;
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct DbState {
+    pub version: u32,
+    pub stores: BTreeMap<String, StoreState>,
+
+    /// The last [revision](StoreState::revision) handed out.
+    revision: u64,
+}
+
+impl DbState {
+    /// Write an object store back, giving it a new revision.
+    pub fn write_store(&mut self, name: String, mut store: StoreState) {
+        self.revision += 1;
+        store.revision = self.revision;
+        self.stores.insert(name, store);
+    }
+}
+
+/// A key path in its internal representation.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum KeyPathRepr {
+    One(String),
+    Sequence(Vec<String>),
+}
+
+impl KeyPathRepr {
+    pub fn from_public<T: AsRef<str>>(key_path: &crate::KeyPath<T>) -> crate::Result<Self> {
+        match key_path {
+            crate::KeyPath::One(path) => Ok(Self::One(path.as_ref().into())),
+            crate::KeyPath::Sequence(seq) => Ok(Self::Sequence(
+                seq.iter().map(|path| path.as_ref().into()).collect(),
+            )),
+            crate::KeyPath::JsValue(_) => Err(BackendError::InvalidAccessError(
+                "Raw JsValue key paths are not supported by the in-memory driver".into(),
+            )
+            .into()),
+        }
+    }
+
+    pub fn to_public(&self) -> crate::KeyPath {
+        match self {
+            Self::One(path) => crate::KeyPath::One(path.clone()),
+            Self::Sequence(seq) => seq.iter().cloned().collect(),
+        }
+    }
+
+    /// Evaluate the key path against the given value, returning `None` if it doesn't resolve to a valid key.
+    pub fn eval(&self, value: &Value) -> Option<Key> {
+        match self {
+            Self::One(path) => value.eval_path(path)?.to_key(),
+            Self::Sequence(seq) => seq
+                .iter()
+                .map(|path| value.eval_path(path)?.to_key())
+                .collect::<Option<Vec<_>>>()
+                .map(Key::Array),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct StoreState {
+    pub key_path: Option<KeyPathRepr>,
+    pub auto_increment: bool,
+    pub key_generator: f64,
+    pub records: BTreeMap<Key, Value>,
+    pub indices: BTreeMap<String, IndexState>,
+
+    /// Changes whenever a transaction writes the object store back, letting transactions detect that it changed
+    /// after they took their snapshot.
+    pub revision: u64,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct IndexState {
+    pub key_path: KeyPathRepr,
+    pub unique: bool,
+    pub multi_entry: bool,
+
+    /// Index key -> primary keys
+    pub entries: BTreeMap<Key, BTreeSet<Key>>,
+}
+
+/// A position in a store or index: `(key, primary_key)`.
+pub(super) type Position = (Key, Key);
+
+impl StoreState {
+    pub fn new(key_path: Option<KeyPathRepr>, auto_increment: bool) -> Self {
+        Self {
+            key_path,
+            auto_increment,
+            key_generator: 1.0,
+            records: BTreeMap::new(),
+            indices: BTreeMap::new(),
+            revision: 0,
+        }
+    }
+
+    fn generate_key(&mut self) -> crate::Result<Key> {
+        if self.key_generator > MAX_GENERATED_KEY {
+            return Err(BackendError::ConstraintError(
+                "The key generator has been exhausted".into(),
+            )
+            .into());
+        }
+
+        let key = self.key_generator;
+        self.key_generator += 1.0;
+
+        Ok(Key::Number(key))
+    }
+
+    fn bump_generator(&mut self, key: &Key) {
+        if let Key::Number(n) = *key {
+            if n >= self.key_generator {
+                self.key_generator = n.floor().min(MAX_GENERATED_KEY) + 1.0;
+            }
+        }
+    }
+
+    /// Store a record, returning its primary key.
+    pub fn store(
+        &mut self,
+        mut value: Value,
+        key: Option<Key>,
+        overwrite: bool,
+    ) -> crate::Result<Key> {
+        let key = match (&self.key_path, key) {
+            (Some(_), Some(_)) => {
+                return Err(BackendError::DataError(
+                    "The object store uses in-line keys and the key parameter was provided".into(),
+                )
+                .into());
+            }
+            (None, Some(key)) => {
+                if !key.is_valid() {
+                    return Err(BackendError::DataError("Not a valid key".into()).into());
+                }
+                if self.auto_increment {
+                    self.bump_generator(&key);
+                }
+                key
+            }
+            (None, None) if self.auto_increment => self.generate_key()?,
+            (None, None) => {
+                return Err(BackendError::DataError(
+                    "The object store uses out-of-line keys, has no key generator and the key parameter was not provided".into(),
+                ).into());
+            }
+            (Some(key_path), None) => {
+                if let Some(key) = key_path.eval(&value) {
+                    if self.auto_increment {
+                        self.bump_generator(&key);
+                    }
+                    key
+                } else {
+                    let KeyPathRepr::One(ref path) = key_path else {
+                        return Err(BackendError::DataError(
+                            "Evaluating the object store's key path did not yield a value".into(),
+                        )
+                        .into());
+                    };
+                    let path_yields_value =
+                        value.eval_path(path).is_some_and(|v| v != Value::Undefined);
+                    if !self.auto_increment || path_yields_value {
+                        return Err(BackendError::DataError(
+                            "Evaluating the object store's key path did not yield a valid key"
+                                .into(),
+                        )
+                        .into());
+                    }
+
+                    let path = path.clone();
+                    let key = self.generate_key()?;
+                    if !value.inject_key(&path, &key) {
+                        return Err(BackendError::DataError(
+                            "Could not inject the generated key into the value".into(),
+                        )
+                        .into());
+                    }
+                    key
+                }
+            }
+        };
+
+        if !overwrite && self.records.contains_key(&key) {
+            return Err(BackendError::ConstraintError(
+                "A record with the given key already exists".into(),
+            )
+            .into());
+        }
+
+        let index_keys = self
+            .indices
+            .iter()
+            .map(|(name, index)| {
+                let keys = index.keys_for(&value);
+                index.check_unique(name, &keys, &key)?;
+                Ok((name.clone(), keys))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        self.remove_index_entries(&key);
+        for (name, keys) in index_keys {
+            if let Some(index) = self.indices.get_mut(&name) {
+                index.insert(keys, &key);
+            }
+        }
+        self.records.insert(key.clone(), value);
+
+        Ok(key)
+    }
+
+    fn remove_index_entries(&mut self, primary_key: &Key) {
+        let Some(old_value) = self.records.get(primary_key) else {
+            return;
+        };
+
+        for index in self.indices.values_mut() {
+            for index_key in index.keys_for(old_value) {
+                if let Some(pks) = index.entries.get_mut(&index_key) {
+                    pks.remove(primary_key);
+                    if pks.is_empty() {
+                        index.entries.remove(&index_key);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Delete all records in the range, returning the number of deleted records.
+    pub fn delete(&mut self, range: Option<&KeyRange<Key>>) -> usize {
+        let keys = self.keys_in(range, None);
+        for key in &keys {
+            self.remove_index_entries(key);
+            self.records.remove(key);
+        }
+
+        keys.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+        for index in self.indices.values_mut() {
+            index.entries.clear();
+        }
+    }
+
+    /// Primary keys in the given range, in ascending order.
+    pub fn keys_in(&self, range: Option<&KeyRange<Key>>, limit: Option<usize>) -> Vec<Key> {
+        let bounds = bounds(range);
+        if !is_satisfiable(bounds) {
+            return Vec::new();
+        }
+
+        self.records
+            .range::<Key, _>(bounds)
+            .map(|(k, _)| k.clone())
+            .take(limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    pub fn count(&self, range: Option<&KeyRange<Key>>) -> usize {
+        let bounds = bounds(range);
+        if is_satisfiable(bounds) {
+            self.records.range::<Key, _>(bounds).count()
+        } else {
+            0
+        }
+    }
+
+    /// Create an index, populating it from existing records.
+    pub fn create_index(&mut self, name: &str, index: IndexState) -> crate::Result<()> {
+        if self.indices.contains_key(name) {
+            return Err(BackendError::ConstraintError(format!(
+                "An index named {name:?} already exists"
+            ))
+            .into());
+        }
+
+        let mut index = index;
+        for (pk, value) in &self.records {
+            let keys = index.keys_for(value);
+            index.check_unique(name, &keys, pk)?;
+            index.insert(keys, pk);
+        }
+
+        self.indices.insert(name.into(), index);
+        Ok(())
+    }
+
+    /// The first position at or after (or before, if `reverse`) the given bound.
+    pub fn seek(&self, bounds: Bounds, reverse: bool) -> Option<Position> {
+        if !is_satisfiable(bounds) {
+            return None;
+        }
+
+        let mut iter = self.records.range::<Key, _>(bounds);
+        let key = if reverse {
+            iter.next_back()
+        } else {
+            iter.next()
+        }?
+        .0;
+
+        Some((key.clone(), key.clone()))
+    }
+}
+
+impl IndexState {
+    pub fn new(key_path: KeyPathRepr, unique: bool, multi_entry: bool) -> Self {
+        Self {
+            key_path,
+            unique,
+            multi_entry,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Index keys for the given value.
+    fn keys_for(&self, value: &Value) -> Vec<Key> {
+        match (self.multi_entry, &self.key_path) {
+            (true, KeyPathRepr::One(path)) => match value.eval_path(path) {
+                Some(Value::Array(arr)) => {
+                    let set = arr
+                        .iter()
+                        .filter_map(Value::to_key)
+                        .collect::<BTreeSet<_>>();
+                    set.into_iter().collect()
+                }
+                Some(v) => v.to_key().into_iter().collect(),
+                None => Vec::new(),
+            },
+            _ => self.key_path.eval(value).into_iter().collect(),
+        }
+    }
+
+    fn check_unique(&self, name: &str, keys: &[Key], primary_key: &Key) -> crate::Result<()> {
+        if !self.unique {
+            return Ok(());
+        }
+
+        let violated = keys.iter().any(|key| {
+            self.entries
+                .get(key)
+                .is_some_and(|pks| pks.iter().any(|pk| pk != primary_key))
+        });
+
+        if violated {
+            Err(BackendError::ConstraintError(format!(
+                "Unique index {name:?} already contains the given key"
+            ))
+            .into())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn insert(&mut self, keys: Vec<Key>, primary_key: &Key) {
+        for key in keys {
+            self.entries
+                .entry(key)
+                .or_default()
+                .insert(primary_key.clone());
+        }
+    }
+
+    /// Iterate over `(key, primary_key)` entries in the given range, in ascending order.
+    pub fn iter_range<'a>(
+        &'a self,
+        range: Option<&'a KeyRange<Key>>,
+    ) -> impl DoubleEndedIterator<Item = (&'a Key, &'a Key)> + 'a {
+        let bounds = bounds(range);
+        let entries = if is_satisfiable(bounds) {
+            Some(self.entries.range::<Key, _>(bounds))
+        } else {
+            None
+        };
+
+        entries
+            .into_iter()
+            .flatten()
+            .flat_map(|(key, pks)| pks.iter().map(move |pk| (key, pk)))
+    }
+
+    /// The first position within `bounds`, strictly after/before `after` if given, when moving forwards (or backwards
+    /// if `reverse`). If `unique`, only the first primary key of each index key is considered.
+    pub fn seek(
+        &self,
+        bounds: Bounds,
+        after: Option<&Position>,
+        reverse: bool,
+        unique: bool,
+    ) -> Option<Position> {
+        if !is_satisfiable(bounds) {
+            return None;
+        }
+
+        // Try to continue within the current key first
+        if let (Some((key, pk)), false) = (after, unique) {
+            if let Some(pks) = self.entries.get(key) {
+                let next = if reverse {
+                    pks.range::<Key, _>((Bound::Unbounded, Bound::Excluded(pk)))
+                        .next_back()
+                } else {
+                    pks.range::<Key, _>((Bound::Excluded(pk), Bound::Unbounded))
+                        .next()
+                };
+                if let Some(next) = next {
+                    return Some((key.clone(), next.clone()));
+                }
+            }
+        }
+
+        let bounds = match after {
+            None => bounds,
+            Some((key, _)) if reverse => (bounds.0, Bound::Excluded(key)),
+            Some((key, _)) => (Bound::Excluded(key), bounds.1),
+        };
+        if !is_satisfiable(bounds) {
+            return None;
+        }
+
+        let mut iter = self.entries.range::<Key, _>(bounds);
+        let (key, pks) = if reverse {
+            iter.next_back()
+        } else {
+            iter.next()
+        }?;
+        let pk = if reverse && !unique {
+            pks.last()
+        } else {
+            pks.first()
+        }?;
+
+        Some((key.clone(), pk.clone()))
+    }
+}
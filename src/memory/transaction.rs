@@ -0,0 +1,245 @@
+use super::object_store::MemoryObjectStore;
+use super::state::{DbState, StoreState};
+use crate::backend::{StoreDriver, TransactionDriver};
+use crate::error::BackendError;
+use crate::transaction::TransactionMode;
+use std::cell::{Cell, RefCell, RefMut};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Active,
+    Committed,
+    Aborted,
+}
+
+/// An in-memory transaction. Clones share the same state.
+///
+/// The transaction operates on a snapshot of the object stores in its scope.
+#[derive(Clone)]
+pub(super) struct MemoryTransaction(Rc<Inner>);
+
+struct Inner {
+    db: Rc<RefCell<DbState>>,
+    mode: TransactionMode,
+    status: Cell<Status>,
+
+    /// Working copies of the object stores in scope
+    stores: RefCell<BTreeMap<String, StoreState>>,
+
+    /// Object stores written to
+    dirty: RefCell<BTreeSet<String>>,
+
+    /// The version being upgraded to in [`Versionchange`](TransactionMode::Versionchange) transactions.
+    upgrade_version: Option<u32>,
+}
+
+impl MemoryTransaction {
+    pub(super) fn new(
+        db: Rc<RefCell<DbState>>,
+        scope: &[String],
+        mode: TransactionMode,
+    ) -> crate::Result<Self> {
+        let stores = {
+            let state = db.borrow();
+            scope
+                .iter()
+                .map(|name| match state.stores.get(name) {
+                    Some(store) => Ok((name.clone(), store.clone())),
+                    None => Err(BackendError::NotFoundError(format!(
+                        "Object store {name:?} not found"
+                    ))
+                    .into()),
+                })
+                .collect::<crate::Result<_>>()?
+        };
+
+        Ok(Self::from_parts(db, mode, stores, None))
+    }
+
+    pub(super) fn new_upgrade(db: Rc<RefCell<DbState>>, version: u32) -> Self {
+        let stores = db.borrow().stores.clone();
+        Self::from_parts(db, TransactionMode::Versionchange, stores, Some(version))
+    }
+
+    fn from_parts(
+        db: Rc<RefCell<DbState>>,
+        mode: TransactionMode,
+        stores: BTreeMap<String, StoreState>,
+        upgrade_version: Option<u32>,
+    ) -> Self {
+        Self(Rc::new(Inner {
+            db,
+            mode,
+            status: Cell::new(Status::Active),
+            stores: RefCell::new(stores),
+            dirty: RefCell::default(),
+            upgrade_version,
+        }))
+    }
+
+    /// Write the working copies back to the database.
+    fn apply(&self) -> crate::Result<()> {
+        let stores = self.0.stores.take();
+        let mut db = self.0.db.borrow_mut();
+
+        if let Some(version) = self.0.upgrade_version {
+            db.version = version;
+            db.stores.clear();
+            for (name, store) in stores {
+                db.write_store(name, store);
+            }
+            return Ok(());
+        }
+
+        let dirty = self.0.dirty.take();
+        if dirty.is_empty() {
+            return Ok(());
+        }
+
+        // Overwriting the snapshot would lose whatever got committed since it was taken
+        for (name, store) in &stores {
+            if db.stores.get(name).map(|s| s.revision) != Some(store.revision) {
+                return Err(BackendError::AbortError(format!(
+                    "Object store {name:?} was modified by another transaction after this one started"
+                ))
+                .into());
+            }
+        }
+
+        for (name, store) in stores {
+            if dirty.contains(&name) {
+                db.write_store(name, store);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn check_active(&self) -> crate::Result<()> {
+        match self.0.status.get() {
+            Status::Active => Ok(()),
+            Status::Committed => Err(BackendError::TransactionInactiveError(
+                "The transaction has been committed".into(),
+            )
+            .into()),
+            Status::Aborted => Err(BackendError::TransactionInactiveError(
+                "The transaction has been aborted".into(),
+            )
+            .into()),
+        }
+    }
+
+    pub(super) fn is_aborted(&self) -> bool {
+        self.0.status.get() == Status::Aborted
+    }
+
+    pub(super) fn check_upgrade(&self) -> crate::Result<()> {
+        self.check_active()?;
+        if self.0.upgrade_version.is_some() {
+            Ok(())
+        } else {
+            Err(BackendError::InvalidStateError(
+                "Schema changes can only be made in upgrade transactions".into(),
+            )
+            .into())
+        }
+    }
+
+    #[inline]
+    pub(super) fn upgrade_version(&self) -> Option<u32> {
+        self.0.upgrade_version
+    }
+
+    pub(super) fn stores_mut(&self) -> RefMut<'_, BTreeMap<String, StoreState>> {
+        self.0.stores.borrow_mut()
+    }
+
+    /// Run a closure against an object store in the working copy.
+    pub(super) fn with_store<F, R>(&self, name: &str, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&mut StoreState) -> crate::Result<R>,
+    {
+        self.check_active()?;
+        match self.0.stores.borrow_mut().get_mut(name) {
+            Some(store) => f(store),
+            None => Err(BackendError::InvalidStateError(format!(
+                "Object store {name:?} has been deleted"
+            ))
+            .into()),
+        }
+    }
+
+    /// [`with_store`](Self::with_store) for writes.
+    pub(super) fn write_store<F, R>(&self, name: &str, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&mut StoreState) -> crate::Result<R>,
+    {
+        self.check_active()?;
+        if self.0.mode == TransactionMode::Readonly {
+            return Err(BackendError::ReadOnlyError("The transaction is read-only".into()).into());
+        }
+
+        let out = self.with_store(name, f)?;
+        self.0.dirty.borrow_mut().insert(name.into());
+
+        Ok(out)
+    }
+}
+
+impl TransactionDriver for MemoryTransaction {
+    #[inline]
+    fn mode(&self) -> TransactionMode {
+        self.0.mode
+    }
+
+    fn object_store_names(&self) -> Vec<String> {
+        self.0.stores.borrow().keys().cloned().collect()
+    }
+
+    fn object_store(&self, name: &str) -> crate::Result<Rc<dyn StoreDriver>> {
+        self.check_active()?;
+        if self.0.stores.borrow().contains_key(name) {
+            Ok(Rc::new(MemoryObjectStore::new(self.clone(), name.into())))
+        } else {
+            Err(BackendError::NotFoundError(format!(
+                "Object store {name:?} is not in the transaction's scope"
+            ))
+            .into())
+        }
+    }
+
+    fn commit(&self) -> crate::Result<()> {
+        self.check_active()?;
+        match self.apply() {
+            Ok(()) => {
+                self.0.status.set(Status::Committed);
+                Ok(())
+            }
+            Err(e) => {
+                self.0.status.set(Status::Aborted);
+                Err(e)
+            }
+        }
+    }
+
+    fn abort(&self) -> crate::Result<()> {
+        self.check_active()?;
+        self.0.status.set(Status::Aborted);
+        self.0.stores.take();
+
+        Ok(())
+    }
+}
+
+impl Debug for MemoryTransaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryTransaction")
+            .field("mode", &self.0.mode)
+            .field("status", &self.0.status.get())
+            .field("object_store_names", &self.object_store_names())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,121 @@
+//! Ordered, version-keyed database migrations.
+//!
+//! [`Migrations`] is a list of steps keyed by the database version they migrate to. Passing it to
+//! [`OpenDbRequestBuilder::with_migrations`](crate::factory::OpenDbRequestBuilder::with_migrations) opens the database
+//! at the highest registered version and, when an upgrade is needed, runs every step whose version is greater than
+//! [`VersionChangeEvent::old_version`](crate::database::VersionChangeEvent::old_version), in ascending order, inside
+//! the upgrade's `versionchange` transaction. Empty migrations open the database at its current version.
+//!
+//! If a step fails, the upgrade is aborted and the database stays at its old version. The error is reported as an
+//! [`Error::Migration`](crate::error::Error::Migration) identifying the failed step's version.
+//!
+//! Like any other [async upgrade](crate::factory::OpenDbRequestBuilder::with_on_upgrade_needed_fut) handler, steps
+//! must keep the transaction busy: awaiting anything that isn't an Indexed DB request lets it auto-commit.
+//!
+//! # Example
+//!
+//! ```
+//! use indexed_db_futures::database::Database;
+//! use indexed_db_futures::migration::Migrations;
+//! use indexed_db_futures::prelude::*;
+//!
+//! # async fn example() -> indexed_db_futures::OpenDbResult<()> {
+//! let migrations = Migrations::new()
+//!     .with_migration(1, |db, _| {
+//!         Box::pin(async move {
+//!             db.create_object_store("users").with_key_path("id".into()).build()?;
+//!             Ok(())
+//!         })
+//!     })
+//!     .with_migration(2, |_, tx| {
+//!         Box::pin(async move {
+//!             tx.object_store("users")?.clear()?.await
+//!         })
+//!     });
+//!
+//! let db = Database::open("my_db").with_migrations(migrations).await?;
+//! assert_eq!(db.version(), 2.0);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::database::{Database, VersionChangeEvent};
+use crate::error::MigrationError;
+use crate::transaction::Transaction;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::ops::Bound;
+use std::pin::Pin;
+
+/// The future returned by a migration step.
+pub type MigrationFuture<'a> = Pin<Box<dyn Future<Output = crate::Result<()>> + 'a>>;
+
+type MigrationFn =
+    dyn for<'a> FnOnce(&'a Database, &'a Transaction<'a>) -> MigrationFuture<'a> + 'static;
+
+/// An ordered list of migration steps keyed by the database version they migrate to.
+#[derive(Default)]
+#[must_use]
+pub struct Migrations {
+    steps: BTreeMap<u32, Box<MigrationFn>>,
+}
+
+impl Migrations {
+    /// Create an empty list of migrations.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the step that migrates the database to `version`, replacing any step previously registered for the
+    /// same version. Steps for version `0` never run.
+    pub fn with_migration<F>(mut self, version: u32, migration: F) -> Self
+    where
+        F: for<'a> FnOnce(&'a Database, &'a Transaction<'a>) -> MigrationFuture<'a> + 'static,
+    {
+        self.steps.insert(version, Box::new(migration));
+        self
+    }
+
+    /// The version the database gets opened at: the highest registered version or `None` if there are no steps, in
+    /// which case the database gets opened at its current version.
+    #[must_use]
+    pub fn version(&self) -> Option<u32> {
+        self.steps.keys().next_back().copied()
+    }
+
+    /// The versions of the steps that would run when upgrading from `old_version`, in the order they'd run.
+    pub fn pending(&self, old_version: u32) -> impl Iterator<Item = u32> + '_ {
+        self.steps
+            .range((Bound::Excluded(old_version), Bound::Unbounded))
+            .map(|(version, _)| *version)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) async fn run(self, event: VersionChangeEvent, db: Database) -> crate::Result<()> {
+        let tx = Transaction::new(&db, event.upgrade_transaction()?);
+        let old_version = event.old_version() as u32;
+        let steps = self
+            .steps
+            .into_iter()
+            .filter(move |(v, _)| *v > old_version);
+
+        for (version, step) in steps {
+            if let Err(e) = step(&db, &tx).await {
+                return Err(MigrationError::new(version, e).into());
+            }
+        }
+
+        tx.release();
+        Ok(())
+    }
+}
+
+impl Debug for Migrations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Migrations")
+            .field("versions", &self.steps.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -4,6 +4,7 @@ use crate::database::Database;
 use crate::future::{Request, VoidRequest};
 use crate::internal_utils::SystemRepr;
 use crate::query_source::QuerySource;
+use crate::repr::StoreRepr;
 use crate::transaction::TransactionRef as BaseTransactionRef;
 use crate::KeyRange;
 pub use add_put::{Add, AddPut, Put};
@@ -17,6 +18,18 @@ pub(crate) mod add_put;
 
 mod delete;
 
+iffeat! {
+    #[cfg(feature = "cursors")]
+    mod update_where;
+    pub use update_where::{UpdateAction, UpdateCounts, UpdateWhere};
+}
+
+iffeat! {
+    #[cfg(feature = "streams")]
+    mod chunked;
+    pub use chunked::{ChunkedStream, StreamChunked};
+}
+
 /// [`std::borrow::Cow`] without the [`Clone`] requirement.
 #[derive(DelegateDebug, From)]
 #[ddebug(delegate_to(BaseTransactionRef<'a>))]
@@ -29,7 +42,7 @@ enum TransactionRef<'a> {
 #[derive(new, Debug)]
 #[new(vis(pub(crate)))]
 pub struct ObjectStore<'a> {
-    inner: web_sys::IdbObjectStore,
+    inner: StoreRepr,
 
     #[new(into)]
     transaction: TransactionRef<'a>,
@@ -56,7 +69,7 @@ impl<'a> ObjectStore<'a> {
         ConstraintError,
     ))]
     #[inline]
-    pub fn add<V>(&self, value: V) -> Add<V> {
+    pub fn add<V>(&self, value: V) -> Add<'_, V> {
         Add::new(self, value)
     }
 
@@ -80,7 +93,7 @@ impl<'a> ObjectStore<'a> {
         ConstraintError,
     ))]
     #[inline]
-    pub fn put<V>(&self, value: V) -> Put<V> {
+    pub fn put<V>(&self, value: V) -> Put<'_, V> {
         Put::new(self, value)
     }
 
@@ -117,13 +130,39 @@ impl<'a> ObjectStore<'a> {
         InvalidStateError,
         DataErrorDelete,
     ))]
-    pub fn delete<K, I>(&self, key_range: I) -> Delete<K>
+    pub fn delete<K, I>(&self, key_range: I) -> Delete<'_, K>
     where
         I: Into<KeyRange<K>>,
     {
         Delete::new(self, key_range.into())
     }
 
+    /// Read, and optionally replace or delete, every record matching the given key or key range in a single
+    /// cursor pass. The callback receives each record's value and returns what should be done with it.
+    ///
+    /// The range should implement either [`TryToJs`](crate::primitive::TryToJs) or, if the `serde` feature is enabled,
+    /// [`Serialize`](serde::Serialize). Values are converted via [`TryFromJs`](crate::primitive::TryFromJs) &
+    /// [`TryToJs`](crate::primitive::TryToJs) or their `serde` counterparts depending on how the builder is built.
+    ///
+    /// # Returns
+    ///
+    /// A builder that, when built and `await`ed, resolves to the number of records updated & deleted.
+    #[cfg(feature = "cursors")]
+    #[errdoc(Cursor(
+        TransactionInactiveError,
+        ReadOnlyError,
+        InvalidStateError,
+        DataErrorUpdate,
+        DataCloneError
+    ))]
+    pub fn update_where<K, I, V, F>(&self, key_range: I, callback: F) -> UpdateWhere<'_, K, V, F>
+    where
+        I: Into<KeyRange<K>>,
+        F: FnMut(V) -> UpdateAction<V>,
+    {
+        UpdateWhere::new(self, key_range.into(), callback)
+    }
+
     /// Delete this object store.
     #[errdoc(Database(
         InvalidStateErrorObjectStore,
@@ -151,16 +190,24 @@ impl<'a> ObjectStore<'a> {
         }
     }
 
-    pub(crate) fn from_version_change(inner: web_sys::IdbObjectStore, db: &'a Database) -> Self {
-        let tx = BaseTransactionRef::new(db, inner.transaction());
-        Self::new(inner, tx)
+    #[cfg_attr(not(feature = "backend"), allow(clippy::unnecessary_wraps))]
+    pub(crate) fn from_version_change(inner: StoreRepr, db: &'a Database) -> crate::Result<Self> {
+        let tx: crate::repr::TransactionRepr = match inner {
+            StoreRepr::Web(ref store) => store.transaction().into(),
+            #[cfg(feature = "backend")]
+            StoreRepr::Driver(_) => {
+                crate::repr::TransactionRepr::Driver(db.as_sys().upgrade_transaction()?)
+            }
+        };
+
+        Ok(Self::new(inner, BaseTransactionRef::new(db, tx)))
     }
 }
 
 #[::sealed::sealed]
 #[allow(unused_qualifications)]
 impl crate::internal_utils::SystemRepr for ObjectStore<'_> {
-    type Repr = web_sys::IdbObjectStore;
+    type Repr = StoreRepr;
 
     #[inline]
     fn as_sys(&self) -> &Self::Repr {
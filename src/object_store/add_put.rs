@@ -73,16 +73,6 @@ impl<'a, AP, T, K, KT> AddPut<'a, AP, T, K, KT> {
             marker: PhantomData,
         }
     }
-
-    fn jsify_key_value(&self) -> crate::Result<[::wasm_bindgen::JsValue; 2]>
-    where
-        T: TryToJs,
-        K: TryToJs,
-    {
-        let key = self.key.try_to_js()?;
-        let value = self.value.try_to_js()?;
-        Ok([key, value])
-    }
 }
 
 #[sealed]
@@ -101,8 +91,7 @@ where
             marker: _,
         } = self;
 
-        let value = value.try_to_js()?;
-        let req = AP::add(object_store, value)?;
+        let req = AP::add(object_store, &value, None)?;
 
         Ok(VoidRequest::new(req))
     }
@@ -125,8 +114,7 @@ where
             marker: _,
         } = self;
 
-        let value = value.try_to_js()?;
-        let req = AP::add(object_store, value)?;
+        let req = AP::add(object_store, &value, None)?;
 
         Ok(BasicRequest::new_primitive(req))
     }
@@ -142,8 +130,7 @@ where
     type Fut = VoidRequest;
 
     fn primitive(self) -> crate::Result<Self::Fut> {
-        let [key, value] = self.jsify_key_value()?;
-        let req = AP::add_with_key(self.object_store, key, value)?;
+        let req = AP::add(self.object_store, &self.value, Some(&self.key))?;
 
         Ok(VoidRequest::new(req))
     }
@@ -160,8 +147,7 @@ where
     type Fut = BasicRequest<KT>;
 
     fn primitive(self) -> crate::Result<Self::Fut> {
-        let [key, value] = self.jsify_key_value()?;
-        let req = AP::add_with_key(self.object_store, key, value)?;
+        let req = AP::add(self.object_store, &self.value, Some(&self.key))?;
 
         Ok(BasicRequest::new_primitive(req))
     }
@@ -183,18 +169,9 @@ where
 
 #[cfg(feature = "serde")]
 const _: () = {
+    use crate::internal_utils::Ser;
     use crate::serde::{DeserialiseFromJs, SerialiseToJs};
 
-    impl<AP, T, K, KT> AddPut<'_, AP, T, K, KT>
-    where
-        T: SerialiseToJs,
-        K: SerialiseToJs,
-    {
-        fn serialise_key_value(&self) -> crate::Result<[::wasm_bindgen::JsValue; 2]> {
-            Ok([self.key.serialise_to_js()?, self.value.serialise_to_js()?])
-        }
-    }
-
     #[sealed]
     impl<AP, T> crate::BuildSerde for AddPut<'_, AP, T>
     where
@@ -211,8 +188,7 @@ const _: () = {
                 marker: _,
             } = self;
 
-            let value = value.serialise_to_js()?;
-            let req = AP::add(object_store, value)?;
+            let req = AP::add(object_store, &Ser(&value), None)?;
 
             Ok(VoidRequest::new(req))
         }
@@ -235,8 +211,7 @@ const _: () = {
                 marker: _,
             } = self;
 
-            let value = value.serialise_to_js()?;
-            let req = AP::add(object_store, value)?;
+            let req = AP::add(object_store, &Ser(&value), None)?;
 
             Ok(BasicRequest::new_ser(req))
         }
@@ -252,8 +227,7 @@ const _: () = {
         type Fut = VoidRequest;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let [key, value] = self.serialise_key_value()?;
-            let req = AP::add_with_key(self.object_store, key, value)?;
+            let req = AP::add(self.object_store, &Ser(&self.value), Some(&Ser(&self.key)))?;
 
             Ok(VoidRequest::new(req))
         }
@@ -270,8 +244,7 @@ const _: () = {
         type Fut = BasicRequest<KT>;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let [key, value] = self.serialise_key_value()?;
-            let req = AP::add_with_key(self.object_store, key, value)?;
+            let req = AP::add(self.object_store, &Ser(&self.value), Some(&Ser(&self.key)))?;
 
             Ok(BasicRequest::new_ser(req))
         }
@@ -280,9 +253,9 @@ const _: () = {
 
 pub(crate) mod kind {
     use super::super::ObjectStore;
-    use crate::internal_utils::SystemRepr;
+    use crate::future::UntypedRequest;
+    use crate::internal_utils::{Encode, SystemRepr};
     use sealed::sealed;
-    use wasm_bindgen::prelude::*;
 
     #[allow(missing_docs)]
     pub struct Add();
@@ -294,47 +267,34 @@ pub(crate) mod kind {
     #[sealed]
     pub trait InsertKind {
         #[doc(hidden)]
-        fn add(store: &ObjectStore<'_>, value: JsValue) -> Result<web_sys::IdbRequest, JsValue>;
-
-        #[doc(hidden)]
-        fn add_with_key(
+        fn add(
             store: &ObjectStore<'_>,
-            key: JsValue,
-            value: JsValue,
-        ) -> Result<web_sys::IdbRequest, JsValue>;
+            value: &dyn Encode,
+            key: Option<&dyn Encode>,
+        ) -> crate::Result<UntypedRequest>;
     }
 
     #[sealed]
     impl InsertKind for Add {
         #[inline]
-        fn add(store: &ObjectStore<'_>, value: JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-            store.as_sys().add(&value)
-        }
-
-        #[inline]
-        fn add_with_key(
+        fn add(
             store: &ObjectStore<'_>,
-            key: JsValue,
-            value: JsValue,
-        ) -> Result<web_sys::IdbRequest, JsValue> {
-            store.as_sys().add_with_key(&value, &key)
+            value: &dyn Encode,
+            key: Option<&dyn Encode>,
+        ) -> crate::Result<UntypedRequest> {
+            store.as_sys().add(value, key)
         }
     }
 
     #[sealed]
     impl InsertKind for Put {
         #[inline]
-        fn add(store: &ObjectStore<'_>, value: JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-            store.as_sys().put(&value)
-        }
-
-        #[inline]
-        fn add_with_key(
+        fn add(
             store: &ObjectStore<'_>,
-            key: JsValue,
-            value: JsValue,
-        ) -> Result<web_sys::IdbRequest, JsValue> {
-            store.as_sys().put_with_key(&value, &key)
+            value: &dyn Encode,
+            key: Option<&dyn Encode>,
+        ) -> crate::Result<UntypedRequest> {
+            store.as_sys().put(value, key)
         }
     }
 }
@@ -1,12 +1,11 @@
 use super::ObjectStore;
 use crate::future::VoidRequest;
-use crate::internal_utils::SystemRepr;
+use crate::internal_utils::{Encode, SystemRepr};
 use crate::primitive::TryToJs;
 use crate::KeyRange;
 use fancy_constructor::new;
 use internal_macros::BuildIntoFut;
 use sealed::sealed;
-use wasm_bindgen::prelude::*;
 
 /// Builder for [`ObjectStore::delete`].
 #[derive(BuildIntoFut, new)]
@@ -18,7 +17,7 @@ pub struct Delete<'a, K> {
 }
 
 impl<K> Delete<'_, K> {
-    fn into_req(self, key: &JsValue) -> crate::Result<VoidRequest> {
+    fn to_req(&self, key: &dyn Encode) -> crate::Result<VoidRequest> {
         let req = self.store.as_sys().delete(key)?;
         Ok(VoidRequest::new(req))
     }
@@ -32,8 +31,7 @@ where
     type Fut = VoidRequest;
 
     fn primitive(self) -> crate::Result<Self::Fut> {
-        let key = self.key.try_to_js()?;
-        self.into_req(&key)
+        self.to_req(&self.key)
     }
 }
 
@@ -46,7 +44,6 @@ where
     type Fut = VoidRequest;
 
     fn serde(self) -> crate::Result<Self::Fut> {
-        let key = crate::serde::SerialiseToJs::serialise_to_js(&self.key)?;
-        self.into_req(&key)
+        self.to_req(&crate::internal_utils::Ser(&self.key))
     }
 }
@@ -0,0 +1,122 @@
+use super::ObjectStore;
+use crate::future::{Raw, UpdateWhereRequest};
+use crate::internal_utils::{EncodeFn, SystemRepr};
+use crate::primitive::{TryFromJs, TryFromJsExt, TryToJs};
+use crate::query_source::internal::QuerySourceInternal;
+use crate::KeyRange;
+use fancy_constructor::new;
+use internal_macros::BuildIntoFut;
+use sealed::sealed;
+use std::marker::PhantomData;
+
+/// Builder for [`ObjectStore::update_where`].
+#[derive(BuildIntoFut, new)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct UpdateWhere<'a, K, V, F> {
+    store: &'a ObjectStore<'a>,
+    key: KeyRange<K>,
+    callback: F,
+
+    #[new(val(PhantomData))]
+    marker: PhantomData<fn(V) -> V>,
+}
+
+/// What [`ObjectStore::update_where`] should do with a record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateAction<V> {
+    /// Leave the record as it is.
+    Keep,
+
+    /// Overwrite the record with the given value.
+    Replace(V),
+
+    /// Delete the record.
+    Delete,
+}
+
+/// The number of records changed by [`ObjectStore::update_where`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpdateCounts {
+    updated: u32,
+    deleted: u32,
+}
+
+impl UpdateCounts {
+    /// The number of records [replaced](UpdateAction::Replace).
+    #[inline]
+    #[must_use]
+    pub fn updated(&self) -> u32 {
+        self.updated
+    }
+
+    /// The number of records [deleted](UpdateAction::Delete).
+    #[inline]
+    #[must_use]
+    pub fn deleted(&self) -> u32 {
+        self.deleted
+    }
+
+    #[inline]
+    pub(crate) fn add_updated(&mut self) {
+        self.updated += 1;
+    }
+
+    #[inline]
+    pub(crate) fn add_deleted(&mut self) {
+        self.deleted += 1;
+    }
+}
+
+impl<K, V, F> UpdateWhere<'_, K, V, F>
+where
+    F: FnMut(V) -> UpdateAction<V>,
+{
+    fn into_req(
+        self,
+        encode_key: EncodeFn<KeyRange<K>>,
+        decode: fn(Raw) -> crate::Result<V>,
+        encode: EncodeFn<V>,
+    ) -> crate::Result<UpdateWhereRequest<V, F>> {
+        let req = self
+            .store
+            .as_sys()
+            .open_cursor(Some(&*encode_key(&self.key)), None, false)?;
+        Ok(UpdateWhereRequest::new(req, self.callback, decode, encode))
+    }
+}
+
+#[sealed]
+impl<K, V, F> crate::BuildPrimitive for UpdateWhere<'_, K, V, F>
+where
+    KeyRange<K>: TryToJs,
+    V: TryFromJs + TryToJs,
+    F: FnMut(V) -> UpdateAction<V> + Unpin,
+{
+    type Fut = UpdateWhereRequest<V, F>;
+
+    fn primitive(self) -> crate::Result<Self::Fut> {
+        use crate::internal_utils::encode_primitive;
+        self.into_req(encode_primitive, V::from_raw, encode_primitive)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[sealed]
+impl<K, V, F> crate::BuildSerde for UpdateWhere<'_, K, V, F>
+where
+    KeyRange<K>: crate::serde::SerialiseToJs,
+    V: crate::serde::DeserialiseFromJs + crate::serde::SerialiseToJs,
+    F: FnMut(V) -> UpdateAction<V> + Unpin,
+{
+    type Fut = UpdateWhereRequest<V, F>;
+
+    fn serde(self) -> crate::Result<Self::Fut> {
+        use crate::internal_utils::encode_serde;
+        self.into_req(
+            encode_serde,
+            crate::serde::deserialise_from_raw,
+            encode_serde,
+        )
+    }
+}
@@ -1,11 +1,37 @@
 //! Types for working with `wasm-bindgen` and `js-sys` primitive that
 //! don't require [`serde`] for converting between Rust & JS.
 
+mod binary_key;
 mod from_js;
 
 mod try_to_js;
 
-pub use {from_js::*, try_to_js::*};
+pub use {binary_key::BinaryKey, from_js::*, try_to_js::*};
+
+/// Converts the struct to & from the array `IndexedDB` uses for
+/// [compound keys](crate::KeyPath::Sequence), the same way a tuple of its field types would be.
+///
+/// ```
+/// use indexed_db_futures::object_store::ObjectStore;
+/// use indexed_db_futures::prelude::*;
+/// use indexed_db_futures::primitive::CompoundKey;
+///
+/// #[derive(CompoundKey)]
+/// struct EventKey {
+///     user_id: u32,
+///     timestamp: f64,
+/// }
+///
+/// # async fn example(store: ObjectStore<'_>) -> indexed_db_futures::Result<()> {
+/// let event: Option<String> = store
+///     .get(EventKey { user_id: 1, timestamp: 1_700_000_000.0 })
+///     .await?;
+/// let key: Option<(u32, f64)> = store.get_key((1u32, 0.0)..(2u32, 0.0)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "derive")]
+pub use indexed_db_futures_macros::CompoundKey;
 
 iffeat! {
     #[cfg(feature = "switch")]
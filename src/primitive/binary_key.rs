@@ -0,0 +1,160 @@
+use super::{TryFromJs, TryToJs};
+use crate::error::SimpleValueError;
+use crate::IdbKey;
+use wasm_bindgen::prelude::*;
+
+/// A [binary key](https://w3c.github.io/IndexedDB/#key-construct).
+///
+/// Converts to an `ArrayBuffer` & can be read back from an `ArrayBuffer` or any view of one; binary keys are always
+/// returned as `ArrayBuffer`s, e.g. by [`get_key`](crate::query_source::QuerySource::get_key) or a cursor's
+/// `primary_key`. Binary keys sort bytewise, matching this type's [`Ord`] implementation, after all number, date &
+/// string keys.
+///
+/// ```
+/// # use indexed_db_futures::object_store::ObjectStore;
+/// # use indexed_db_futures::prelude::*;
+/// # use indexed_db_futures::primitive::BinaryKey;
+/// # async fn example(store: ObjectStore<'_>) -> indexed_db_futures::Result<()> {
+/// let hash = BinaryKey::from([0xde, 0xad, 0xbe, 0xef]);
+/// store.put("blob").with_key(hash.clone()).await?;
+///
+/// let blob: Option<String> = store.get(hash).await?;
+/// let first: Option<BinaryKey> = store.get_key(BinaryKey::from([0x00])..).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    derive_more::Deref,
+    derive_more::DerefMut,
+    derive_more::Into,
+)]
+pub struct BinaryKey(Vec<u8>);
+
+impl BinaryKey {
+    /// Create a new binary key.
+    #[inline]
+    #[must_use]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Unwrap the key's bytes.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for BinaryKey {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for BinaryKey {
+    #[inline]
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for BinaryKey {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for BinaryKey {
+    #[inline]
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes.into())
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for BinaryKey {
+    #[inline]
+    fn from(bytes: &[u8; N]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<BinaryKey> for IdbKey {
+    #[inline]
+    fn from(key: BinaryKey) -> Self {
+        Self::Binary(key.0)
+    }
+}
+
+impl TryToJs for BinaryKey {
+    fn try_to_js(&self) -> crate::Result<JsValue> {
+        Ok(js_sys::Uint8Array::from(self.0.as_slice())
+            .buffer()
+            .unchecked_into())
+    }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<crate::backend::Value> {
+        Ok(crate::backend::Value::Binary(self.0.clone()))
+    }
+}
+
+impl TryFromJs for BinaryKey {
+    fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
+        if let Some(buf) = js.dyn_ref::<js_sys::ArrayBuffer>() {
+            Ok(Self(js_sys::Uint8Array::new(buf).to_vec()))
+        } else if js_sys::ArrayBuffer::is_view(&js) {
+            view_bytes(&js).map(Self)
+        } else {
+            Err(SimpleValueError::DynCast(js))
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    fn from_value(value: crate::backend::Value) -> Result<Self, SimpleValueError> {
+        match value {
+            crate::backend::Value::Binary(bytes) => Ok(Self(bytes)),
+            value => Err(SimpleValueError::UnexpectedValue(value)),
+        }
+    }
+}
+
+/// Copy the bytes an `ArrayBuffer` view covers, regardless of the view's element type.
+fn view_bytes(view: &JsValue) -> Result<Vec<u8>, SimpleValueError> {
+    let get = |prop: &str| {
+        js_sys::Reflect::get(view, &JsValue::from_str(prop)).map_err(SimpleValueError::DynCast)
+    };
+    let offset = u32::from_js(get("byteOffset")?)?;
+    let len = u32::from_js(get("byteLength")?)?;
+    let bytes = js_sys::Uint8Array::new_with_byte_offset_and_length(&get("buffer")?, offset, len);
+
+    Ok(bytes.to_vec())
+}
+
+#[cfg(feature = "serde")]
+const _: () = {
+    use crate::serde::{DeserialiseFromJs, SerialiseToJs};
+
+    impl SerialiseToJs for BinaryKey {
+        #[inline]
+        fn serialise_to_js(&self) -> crate::Result<JsValue> {
+            self.try_to_js()
+        }
+    }
+
+    impl DeserialiseFromJs for BinaryKey {
+        fn deserialise_from_js(js: JsValue) -> crate::Result<Self> {
+            Self::from_js(js).map_err(Into::into)
+        }
+    }
+};
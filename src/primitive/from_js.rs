@@ -1,5 +1,7 @@
+#[cfg(feature = "backend")]
+use crate::backend::Value;
 use crate::error::SimpleValueError;
-use crate::iter::ArrayMapIter;
+use crate::future::Raw;
 use cfg_if::cfg_if;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
 use std::hash::{BuildHasher, Hash};
@@ -12,18 +14,50 @@ pub trait TryFromJs {
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError>
     where
         Self: Sized;
+
+    /// Convert from a [`Value`](crate::backend::Value) returned by a [driver](crate::backend). Implemented for all of
+    /// the crate's primitives; the default implementation converts the value to JS & passes it to
+    /// [`from_js`](TryFromJs::from_js) on `wasm32` targets.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the value doesn't match the type. The default implementation fails with
+    /// [`UnexpectedValue`](SimpleValueError::UnexpectedValue) outside `wasm32` targets.
+    #[cfg(feature = "backend")]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError>
+    where
+        Self: Sized,
+    {
+        if cfg!(target_arch = "wasm32") {
+            Self::from_js(value.to_js())
+        } else {
+            Err(SimpleValueError::UnexpectedValue(value))
+        }
+    }
 }
 
 pub(crate) trait TryFromJsExt: TryFromJs {
     fn from_js_base(js: JsValue) -> crate::Result<Self>
     where
         Self: Sized;
+
+    fn from_raw(raw: Raw) -> crate::Result<Self>
+    where
+        Self: Sized;
 }
 
 impl<T: TryFromJs> TryFromJsExt for T {
     fn from_js_base(js: JsValue) -> crate::Result<Self> {
         Self::from_js(js).map_err(Into::into)
     }
+
+    fn from_raw(raw: Raw) -> crate::Result<Self> {
+        match raw {
+            Raw::Js(js) => Self::from_js_base(js),
+            #[cfg(feature = "backend")]
+            Raw::Value(value) => Self::from_value(value).map_err(Into::into),
+        }
+    }
 }
 
 impl<T: TryFromJs> TryFromJs for Option<T> {
@@ -34,19 +68,35 @@ impl<T: TryFromJs> TryFromJs for Option<T> {
             T::from_js(js).map(Some)
         }
     }
+
+    #[cfg(feature = "backend")]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        match value {
+            Value::Undefined | Value::Null => Ok(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
 }
 
 impl TryFromJs for f32 {
-    #[allow(clippy::cast_possible_truncation)]
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
-        const MAX: f64 = f32::MAX as f64;
-        let big = <f64 as TryFromJs>::from_js(js)?;
+        f32_from_f64(<f64 as TryFromJs>::from_js(js)?)
+    }
 
-        if big > MAX {
-            Err(SimpleValueError::TooLarge(big))
-        } else {
-            Ok(big as f32)
-        }
+    #[cfg(feature = "backend")]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        f32_from_f64(<f64 as TryFromJs>::from_value(value)?)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn f32_from_f64(big: f64) -> Result<f32, SimpleValueError> {
+    const MAX: f64 = f32::MAX as f64;
+
+    if big > MAX {
+        Err(SimpleValueError::TooLarge(big))
+    } else {
+        Ok(big as f32)
     }
 }
 
@@ -57,23 +107,39 @@ impl TryFromJs for String {
             None => Err(SimpleValueError::NotAString(js)),
         }
     }
+
+    #[cfg(feature = "backend")]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        match value {
+            Value::String(v) => Ok(v),
+            value => Err(SimpleValueError::UnexpectedValue(value)),
+        }
+    }
 }
 
 impl TryFromJs for char {
-    #[allow(clippy::cast_precision_loss)]
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
-        let string = String::from_js(js)?;
-        let mut chars = string.chars();
+        char_from_string(&String::from_js(js)?)
+    }
 
-        if let Some(ch) = chars.next() {
-            if chars.next().is_some() {
-                Err(SimpleValueError::TooLarge(string.len() as f64))
-            } else {
-                Ok(ch)
-            }
+    #[cfg(feature = "backend")]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        char_from_string(&String::from_value(value)?)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn char_from_string(string: &str) -> Result<char, SimpleValueError> {
+    let mut chars = string.chars();
+
+    if let Some(ch) = chars.next() {
+        if chars.next().is_some() {
+            Err(SimpleValueError::TooLarge(string.len() as f64))
         } else {
-            Err(SimpleValueError::TooSmall(0.0))
+            Ok(ch)
         }
+    } else {
+        Err(SimpleValueError::TooSmall(0.0))
     }
 }
 
@@ -94,7 +160,7 @@ where
     }
 
     match js.dyn_into::<js_sys::Array>() {
-        Ok(arr) => ArrayMapIter::new(arr.into_iter(), T::from_js).collect(),
+        Ok(arr) => arr.into_iter().map(T::from_js).collect(),
         Err(js) => Err(SimpleValueError::DynCast(js)),
     }
 }
@@ -132,11 +198,46 @@ where
     }
 }
 
+#[cfg(feature = "backend")]
+fn value_to_list<L, T>(value: Value) -> Result<L, SimpleValueError>
+where
+    T: TryFromJs,
+    L: FromIterator<T>,
+{
+    match value {
+        Value::Undefined | Value::Null => Ok(None::<T>.into_iter().collect()),
+        Value::Array(values) => values.into_iter().map(T::from_value).collect(),
+        value => Err(SimpleValueError::UnexpectedValue(value)),
+    }
+}
+
+#[cfg(feature = "backend")]
+fn value_to_map<M, T>(value: Value) -> Result<M, SimpleValueError>
+where
+    M: FromIterator<(String, T)>,
+    T: TryFromJs,
+{
+    match value {
+        Value::Undefined | Value::Null => Ok(None::<(String, T)>.into_iter().collect()),
+        Value::Object(entries) => entries
+            .into_iter()
+            .map(|(k, v)| Ok((k, T::from_value(v)?)))
+            .collect(),
+        value => Err(SimpleValueError::UnexpectedValue(value)),
+    }
+}
+
 impl<T: TryFromJs> TryFromJs for BTreeMap<String, T> {
     #[inline]
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
         js_to_map(js)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        value_to_map(value)
+    }
 }
 
 impl<T: TryFromJs + Hash, H: BuildHasher + Default> TryFromJs for HashMap<String, T, H> {
@@ -144,6 +245,12 @@ impl<T: TryFromJs + Hash, H: BuildHasher + Default> TryFromJs for HashMap<String
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
         js_to_map(js)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        value_to_map(value)
+    }
 }
 
 impl<T: TryFromJs> TryFromJs for Vec<T> {
@@ -151,6 +258,12 @@ impl<T: TryFromJs> TryFromJs for Vec<T> {
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
         js_to_list(js)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        value_to_list(value)
+    }
 }
 
 impl<T: TryFromJs> TryFromJs for VecDeque<T> {
@@ -158,6 +271,12 @@ impl<T: TryFromJs> TryFromJs for VecDeque<T> {
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
         js_to_list(js)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        value_to_list(value)
+    }
 }
 
 impl<T: TryFromJs + Hash + Eq, H: BuildHasher + Default> TryFromJs for HashSet<T, H> {
@@ -165,6 +284,12 @@ impl<T: TryFromJs + Hash + Eq, H: BuildHasher + Default> TryFromJs for HashSet<T
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
         js_to_list(js)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        value_to_list(value)
+    }
 }
 
 impl<T: TryFromJs> TryFromJs for BTreeSet<T>
@@ -175,6 +300,12 @@ where
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
         js_to_list(js)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        value_to_list(value)
+    }
 }
 
 impl<T: TryFromJs> TryFromJs for LinkedList<T> {
@@ -182,6 +313,26 @@ impl<T: TryFromJs> TryFromJs for LinkedList<T> {
     fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
         js_to_list(js)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        value_to_list(value)
+    }
+}
+
+macro_rules! minmax {
+    ($min: expr, $max: expr, $conv: ident, $js: ident, $as: ty, $err_ident: ident) => {{
+        let float = <f64 as TryFromJs>::$conv($js)?;
+
+        if float > $max {
+            Err($crate::error::SimpleValueError::TooLarge(float))
+        } else if float < $min {
+            Err($crate::error::SimpleValueError::$err_ident(float))
+        } else {
+            Ok(float as $as)
+        }
+    }};
 }
 
 impl TryFromJs for usize {
@@ -204,6 +355,17 @@ impl TryFromJs for usize {
             }
         }
     }
+
+    #[cfg(feature = "backend")]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        const MAX: f64 = usize::MAX as f64;
+        minmax!(0.0, MAX, from_value, value, usize, Signed)
+    }
 }
 
 impl TryFromJs for isize {
@@ -228,24 +390,18 @@ impl TryFromJs for isize {
             }
         }
     }
-}
-
-macro_rules! minmax {
-    ($min: expr, $max: expr, $js: ident, $as: ty, $err_ident: ident) => {{
-        let float = <f64 as TryFromJs>::from_js($js)?;
 
-        if float > $max {
-            Err($crate::error::SimpleValueError::TooLarge(float))
-        } else if float < $min {
-            Err($crate::error::SimpleValueError::$err_ident(float))
-        } else {
-            Ok(float as $as)
-        }
-    }};
+    #[cfg(feature = "backend")]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+        const MAX: f64 = isize::MAX as f64;
+        const MIN: f64 = isize::MIN as f64;
+        minmax!(MIN, MAX, from_value, value, isize, TooSmall)
+    }
 }
 
 macro_rules! impl_from_js {
-    (direct > $for: ty, $method: ident, $err: ident) => {
+    (direct > $for: ty, $method: ident, $err: ident, $variant: ident) => {
         impl TryFromJs for $for {
             fn from_js(js: wasm_bindgen::JsValue) -> Result<Self, $crate::error::SimpleValueError> {
                 match js.$method() {
@@ -253,6 +409,14 @@ macro_rules! impl_from_js {
                     None => Err($crate::error::SimpleValueError::$err(js)),
                 }
             }
+
+            #[cfg(feature = "backend")]
+            fn from_value(value: Value) -> Result<Self, $crate::error::SimpleValueError> {
+                match value {
+                    Value::$variant(v) => Ok(v),
+                    value => Err($crate::error::SimpleValueError::UnexpectedValue(value)),
+                }
+            }
         }
     };
     (signed, $err_variant: ident > $($ty: ty),+) => {
@@ -262,7 +426,15 @@ macro_rules! impl_from_js {
                 fn from_js(js: wasm_bindgen::JsValue) -> Result<Self, $crate::error::SimpleValueError> {
                     const MAX: f64 = <$ty>::MAX as f64;
                     const MIN: f64 = <$ty>::MIN as f64;
-                    minmax!(MIN, MAX, js, $ty, $err_variant)
+                    minmax!(MIN, MAX, from_js, js, $ty, $err_variant)
+                }
+
+                #[cfg(feature = "backend")]
+                #[allow(clippy::cast_possible_truncation)]
+                fn from_value(value: Value) -> Result<Self, $crate::error::SimpleValueError> {
+                    const MAX: f64 = <$ty>::MAX as f64;
+                    const MIN: f64 = <$ty>::MIN as f64;
+                    minmax!(MIN, MAX, from_value, value, $ty, $err_variant)
                 }
             }
         )+
@@ -273,12 +445,19 @@ macro_rules! impl_from_js {
                 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
                 fn from_js(js: wasm_bindgen::JsValue) -> Result<Self, $crate::error::SimpleValueError> {
                     const MAX: f64 = <$ty>::MAX as f64;
-                    minmax!(0.0, MAX, js, $ty, $err_variant)
+                    minmax!(0.0, MAX, from_js, js, $ty, $err_variant)
+                }
+
+                #[cfg(feature = "backend")]
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                fn from_value(value: Value) -> Result<Self, $crate::error::SimpleValueError> {
+                    const MAX: f64 = <$ty>::MAX as f64;
+                    minmax!(0.0, MAX, from_value, value, $ty, $err_variant)
                 }
             }
         )+
     };
-    (big > $($ty: ty),+ $(,)?) => {
+    (big, $err_variant: ident > $($ty: ty),+ $(,)?) => {
         $(impl TryFromJs for $ty {
             fn from_js(js: wasm_bindgen::JsValue) -> Result<Self, $crate::error::SimpleValueError> {
                 match js.try_into() {
@@ -286,13 +465,80 @@ macro_rules! impl_from_js {
                     Err(js) => Err($crate::error::SimpleValueError::DynCast(js)),
                 }
             }
+
+            // Drivers pass all numbers as `f64`s, so values beyond 2^53 lose precision
+            #[cfg(feature = "backend")]
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_precision_loss,
+                clippy::cast_sign_loss
+            )]
+            fn from_value(value: Value) -> Result<Self, $crate::error::SimpleValueError> {
+                const MAX: f64 = <$ty>::MAX as f64;
+                const MIN: f64 = <$ty>::MIN as f64;
+                minmax!(MIN, MAX, from_value, value, $ty, $err_variant)
+            }
         })+
     };
 }
 
-impl_from_js!(direct > f64, as_f64, NotANumber);
-impl_from_js!(direct > bool, as_bool, NotABoolean);
+impl_from_js!(direct > f64, as_f64, NotANumber, Number);
+impl_from_js!(direct > bool, as_bool, NotABoolean, Bool);
 
 impl_from_js!(unsigned, Signed > u8, u16, u32);
 impl_from_js!(signed, TooSmall > i8, i16, i32);
-impl_from_js!(big > i64, u64, i128, u128);
+impl_from_js!(big, Signed > u64, u128);
+impl_from_js!(big, TooSmall > i64, i128);
+
+macro_rules! impl_from_js_tuple {
+    ($($len: literal => ($($idx: tt $ty: ident),+)),+ $(,)?) => {
+        $(
+            impl<$($ty: TryFromJs),+> TryFromJs for ($($ty,)+) {
+                fn from_js(js: JsValue) -> Result<Self, SimpleValueError> {
+                    let arr = match js.dyn_into::<js_sys::Array>() {
+                        Ok(arr) => arr,
+                        Err(js) => return Err(SimpleValueError::DynCast(js)),
+                    };
+
+                    let actual = arr.length();
+                    if actual != $len {
+                        return Err(SimpleValueError::ArrayLength { expected: $len, actual });
+                    }
+
+                    Ok(($($ty::from_js(arr.get($idx))?,)+))
+                }
+
+                #[cfg(feature = "backend")]
+                #[allow(clippy::cast_possible_truncation)]
+                fn from_value(value: Value) -> Result<Self, SimpleValueError> {
+                    let Value::Array(arr) = value else {
+                        return Err(SimpleValueError::UnexpectedValue(value));
+                    };
+
+                    let actual = arr.len() as u32;
+                    if actual != $len {
+                        return Err(SimpleValueError::ArrayLength { expected: $len, actual });
+                    }
+
+                    let mut values = arr.into_iter();
+                    Ok(($($ty::from_value(values.next().unwrap_or_default())?,)+))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_js_tuple!(
+    1 => (0 A),
+    2 => (0 A, 1 B),
+    3 => (0 A, 1 B, 2 C),
+    4 => (0 A, 1 B, 2 C, 3 D),
+    5 => (0 A, 1 B, 2 C, 3 D, 4 E),
+    6 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F),
+    7 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G),
+    8 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H),
+    9 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I),
+    10 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J),
+    11 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K),
+    12 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K, 11 L),
+);
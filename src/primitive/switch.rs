@@ -58,6 +58,18 @@ macro_rules! from {
             $($generic: super::TryFromJs,)+
         {
             fn from_js($js: ::wasm_bindgen::JsValue) -> Result<Self, $crate::error::SimpleValueError> $impl
+
+            #[cfg(feature = "backend")]
+            fn from_value(value: $crate::backend::Value) -> Result<Self, $crate::error::SimpleValueError> {
+                let mut errors = Vec::new();
+                $(
+                    match <$generic as super::TryFromJs>::from_value(value.clone()) {
+                        Ok(v) => return Ok(Self::$generic(v)),
+                        Err(e) => errors.push(e),
+                    }
+                )+
+                Err(SimpleValueError::Switch(errors))
+            }
         }
     };
     ($js: expr, $variant: ident, |$e: ident| $else: expr) => {
@@ -80,6 +92,13 @@ macro_rules! to {
                     $(Self::$generic(v) => super::TryToJs::try_to_js(v),)+
                 }
             }
+
+            #[cfg(feature = "backend")]
+            fn try_to_value(&self) -> crate::Result<$crate::backend::Value> {
+                match self {
+                    $(Self::$generic(v) => super::TryToJs::try_to_value(v),)+
+                }
+            }
         }
     };
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "backend")]
+use crate::backend::Value;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
 use wasm_bindgen::prelude::*;
 
@@ -16,6 +18,27 @@ pub trait TryToJs {
     {
         self.try_to_js()
     }
+
+    /// Convert to a [`Value`](crate::backend::Value) for a [driver](crate::backend). Implemented for all of the
+    /// crate's primitives; the default implementation converts the output of [`try_to_js`](TryToJs::try_to_js) on
+    /// `wasm32` targets.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the value can't be converted. The default implementation fails with
+    /// [`NotSupportedError`](crate::error::BackendError::NotSupportedError) outside `wasm32` targets.
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        Value::via_js(|| self.try_to_js())
+    }
+
+    /// Convert to a key range for a [driver](crate::backend). Only [`KeyRange`](crate::KeyRange)s produce anything
+    /// other than [`Only`](crate::KeyRange::Only).
+    #[cfg(feature = "backend")]
+    #[doc(hidden)]
+    fn try_to_key_range(&self) -> crate::Result<crate::KeyRange<crate::IdbKey>> {
+        self.try_to_value()?.into_key().map(crate::KeyRange::Only)
+    }
 }
 
 impl<T: TryToJs> TryToJs for &T {
@@ -23,6 +46,18 @@ impl<T: TryToJs> TryToJs for &T {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         TryToJs::try_to_js(*self)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        TryToJs::try_to_value(*self)
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn try_to_key_range(&self) -> crate::Result<crate::KeyRange<crate::IdbKey>> {
+        TryToJs::try_to_key_range(*self)
+    }
 }
 
 impl<T: TryToJs> TryToJs for Option<T> {
@@ -32,24 +67,47 @@ impl<T: TryToJs> TryToJs for Option<T> {
             None => Ok(JsValue::UNDEFINED),
         }
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        match *self {
+            Some(ref v) => v.try_to_value(),
+            None => Ok(Value::Undefined),
+        }
+    }
 }
 
 impl TryToJs for char {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         String::from(*self).try_to_js()
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        Ok(Value::String(String::from(*self)))
+    }
 }
 
 impl TryToJs for &str {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         Ok(JsValue::from_str(self))
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        Ok(Value::String((*self).into()))
+    }
 }
 
 impl TryToJs for String {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         self.as_str().try_to_js()
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        Ok(Value::String(self.clone()))
+    }
 }
 
 impl TryToJs for JsValue {
@@ -62,6 +120,11 @@ impl TryToJs for bool {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         Ok(if *self { JsValue::TRUE } else { JsValue::FALSE })
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        Ok(Value::Bool(*self))
+    }
 }
 
 fn list_to_js<E, It>(iter: It) -> crate::Result<JsValue>
@@ -93,16 +156,55 @@ where
     Ok(obj.unchecked_into())
 }
 
+#[cfg(feature = "backend")]
+fn list_to_value<E, It>(iter: It) -> crate::Result<Value>
+where
+    It: IntoIterator<Item = E>,
+    E: TryToJs,
+{
+    let values = iter
+        .into_iter()
+        .map(|v| v.try_to_value())
+        .collect::<crate::Result<_>>()?;
+
+    Ok(Value::Array(values))
+}
+
+#[cfg(feature = "backend")]
+fn map_to_value<K, V, It>(iter: It) -> crate::Result<Value>
+where
+    It: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: TryToJs,
+{
+    let entries = iter
+        .into_iter()
+        .map(|(k, v)| Ok((k.as_ref().into(), v.try_to_value()?)))
+        .collect::<crate::Result<_>>()?;
+
+    Ok(Value::Object(entries))
+}
+
 impl<K: AsRef<str>, V: TryToJs> TryToJs for BTreeMap<K, V> {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         map_to_js(self.iter())
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        map_to_value(self.iter())
+    }
 }
 
 impl<K: AsRef<str>, V: TryToJs, H> TryToJs for HashMap<K, V, H> {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         map_to_js(self.iter())
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        map_to_value(self.iter())
+    }
 }
 
 impl<T: TryToJs> TryToJs for &[T] {
@@ -110,12 +212,23 @@ impl<T: TryToJs> TryToJs for &[T] {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         list_to_js(*self)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        list_to_value(*self)
+    }
 }
 
 impl<T: TryToJs, const N: usize> TryToJs for [T; N] {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         list_to_js(self.iter())
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        list_to_value(self.iter())
+    }
 }
 
 impl<T: TryToJs> TryToJs for Vec<T> {
@@ -123,30 +236,56 @@ impl<T: TryToJs> TryToJs for Vec<T> {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         self.as_slice().try_to_js()
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        self.as_slice().try_to_value()
+    }
 }
 
 impl<T: TryToJs> TryToJs for VecDeque<T> {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         list_to_js(self.iter())
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        list_to_value(self.iter())
+    }
 }
 
 impl<T: TryToJs, H> TryToJs for HashSet<T, H> {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         list_to_js(self.iter())
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        list_to_value(self.iter())
+    }
 }
 
 impl<T: TryToJs> TryToJs for BTreeSet<T> {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         list_to_js(self.iter())
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        list_to_value(self.iter())
+    }
 }
 
 impl<T: TryToJs> TryToJs for LinkedList<T> {
     fn try_to_js(&self) -> crate::Result<JsValue> {
         list_to_js(self.iter())
     }
+
+    #[cfg(feature = "backend")]
+    fn try_to_value(&self) -> crate::Result<Value> {
+        list_to_value(self.iter())
+    }
 }
 
 macro_rules! impl_for_num {
@@ -157,9 +296,51 @@ macro_rules! impl_for_num {
                 fn try_to_js(&self) -> crate::Result<JsValue> {
                     Ok(JsValue::from(*self))
                 }
+
+                #[cfg(feature = "backend")]
+                #[allow(clippy::cast_lossless, clippy::cast_precision_loss)]
+                #[inline]
+                fn try_to_value(&self) -> crate::Result<Value> {
+                    Ok(Value::Number(*self as f64))
+                }
             }
         )+
     };
 }
 
 impl_for_num!(f32, f64, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128, u128);
+
+macro_rules! impl_for_tuple {
+    ($($len: literal => ($($idx: tt $ty: ident),+)),+ $(,)?) => {
+        $(
+            impl<$($ty: TryToJs),+> TryToJs for ($($ty,)+) {
+                fn try_to_js(&self) -> crate::Result<JsValue> {
+                    let arr = js_sys::Array::new_with_length($len);
+                    $(arr.set($idx, self.$idx.try_to_js()?);)+
+
+                    Ok(arr.unchecked_into())
+                }
+
+                #[cfg(feature = "backend")]
+                fn try_to_value(&self) -> crate::Result<Value> {
+                    Ok(Value::Array(vec![$(self.$idx.try_to_value()?),+]))
+                }
+            }
+        )+
+    };
+}
+
+impl_for_tuple!(
+    1 => (0 A),
+    2 => (0 A, 1 B),
+    3 => (0 A, 1 B, 2 C),
+    4 => (0 A, 1 B, 2 C, 3 D),
+    5 => (0 A, 1 B, 2 C, 3 D, 4 E),
+    6 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F),
+    7 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G),
+    8 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H),
+    9 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I),
+    10 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J),
+    11 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K),
+    12 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K, 11 L),
+);
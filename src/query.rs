@@ -0,0 +1,239 @@
+//! Declarative queries over an object store's records.
+//!
+//! A [`Query`] is built from filters on the values at key paths, an optional order & an offset/limit. When executed it
+//! picks the object store itself or one of its indices based on their key paths, pushes as much of the filters as
+//! possible into the cursor's key range & checks the rest in Rust. If no index iterates in the requested order, the
+//! matching records are sorted in Rust before the offset & limit get applied. As records without a valid key at each of
+//! an index's key path fields are missing from the index, indices only get considered if every one of those fields is
+//! filtered or ordered on.
+//!
+//! Filters & ordering follow the same rules as indices: a record only matches if the value at the key path is a
+//! valid key, which gets compared using the [key ordering](crate::IdbKey#impl-Ord-for-IdbKey) the browser uses. As
+//! filters are evaluated on the stored JS values, records stored using a binary or string [`Codec`] such as
+//! [`Json`](crate::typed::Json) can't be filtered.
+//!
+//! ```
+//! # use indexed_db_futures::object_store::ObjectStore;
+//! # use indexed_db_futures::cursor::CursorDirection;
+//! # use indexed_db_futures::typed::Serde;
+//! # use serde::{Deserialize, Serialize};
+//! # use futures::TryStreamExt;
+//! #
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!   id: u32,
+//!   name: String,
+//!   age: u8,
+//! }
+//!
+//! # #[allow(dead_code)]
+//! # async fn example(users: ObjectStore<'_>) -> indexed_db_futures::Result<()> {
+//! let adults: Vec<User> = users
+//!     .query()
+//!     .with_codec::<Serde>()
+//!     .with_filter("age", 18..)
+//!     .with_filter("name", "Bobby")
+//!     .with_order("age", CursorDirection::Prev)
+//!     .with_limit(10)
+//!     .await?;
+//!
+//! let mut stream = users
+//!     .query::<User>()
+//!     .with_codec::<Serde>()
+//!     .with_filter("age", ..18)
+//!     .stream()?;
+//! while let Some(user) = stream.try_next().await? {
+//!     // ...
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cursor::CursorDirection;
+use crate::internal_utils::{Encode, SystemRepr};
+use crate::object_store::ObjectStore;
+use crate::query_source::internal::QuerySourceInternal;
+use crate::repr::StoreRepr;
+use crate::typed::{Codec, Primitive};
+use crate::{IdbKey, KeyRange};
+use internal_macros::BuildIntoFut;
+use plan::{Candidate, Filter};
+use sealed::sealed;
+use std::marker::PhantomData;
+
+pub use plan::QueryPlan;
+pub use stream::{QueryCollect, QueryStream};
+
+mod plan;
+mod stream;
+
+/// Builder for [`ObjectStore::query`]. See the [module docs](self) for more information.
+#[derive(BuildIntoFut)]
+#[must_use]
+pub struct Query<'a, V, C = Primitive> {
+    store: &'a StoreRepr,
+
+    /// `None` if the filters contradict each other.
+    filters: Option<Vec<Filter>>,
+
+    order: Option<(String, CursorDirection)>,
+    offset: u32,
+    limit: Option<u32>,
+    marker: PhantomData<fn() -> (V, C)>,
+}
+
+impl ObjectStore<'_> {
+    /// Query the records in this object store. See the [`query`](crate::query) module for more information.
+    pub fn query<V>(&self) -> Query<'_, V> {
+        Query {
+            store: self.as_sys(),
+            filters: Some(Vec::new()),
+            order: None,
+            offset: 0,
+            limit: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, V, C> Query<'a, V, C> {
+    /// Only match records whose value at `key_path` falls within the given key or key range. Filters on the same key
+    /// path get intersected.
+    pub fn with_filter<K, I>(mut self, key_path: &str, range: I) -> Self
+    where
+        I: Into<KeyRange<K>>,
+        K: Into<IdbKey>,
+    {
+        let range = range.into().map(Into::into);
+        let Some(ref mut filters) = self.filters else {
+            return self;
+        };
+
+        if let Some(filter) = filters.iter_mut().find(|f| f.key_path == key_path) {
+            match filter.range.intersect(&range) {
+                Some(range) => filter.range = range,
+                None => self.filters = None,
+            }
+        } else if range.is_empty() {
+            self.filters = None;
+        } else {
+            filters.push(Filter {
+                key_path: key_path.into(),
+                range,
+            });
+        }
+
+        self
+    }
+
+    /// Order the records by their value at `key_path`. Records whose value at the key path isn't a valid key are
+    /// skipped, as they would be when iterating over an index.
+    pub fn with_order(mut self, key_path: &str, direction: CursorDirection) -> Self {
+        self.order = Some((key_path.into(), direction));
+        self
+    }
+
+    /// Skip the first `offset` matching records.
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Return at most `limit` records.
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Decode records using the given [`Codec`].
+    pub fn with_codec<C2>(self) -> Query<'a, V, C2> {
+        Query {
+            store: self.store,
+            filters: self.filters,
+            order: self.order,
+            offset: self.offset,
+            limit: self.limit,
+            marker: PhantomData,
+        }
+    }
+
+    /// Work out how the query would be executed.
+    ///
+    /// # Errors
+    ///
+    /// Fails if one of the object store's indices can't be opened, e.g. because the transaction has finished.
+    pub fn explain(&self) -> crate::Result<QueryPlan> {
+        let mut candidates = vec![Candidate {
+            index: None,
+            key_path: self.store.key_path(),
+        }];
+
+        for name in self.store.index_names() {
+            let index = self.store.index(&name)?;
+            if !index.multi_entry() {
+                candidates.push(Candidate {
+                    index: Some(name),
+                    key_path: index.key_path(),
+                });
+            }
+        }
+
+        Ok(QueryPlan::new(
+            candidates,
+            self.filters.as_deref(),
+            self.order.as_ref(),
+        ))
+    }
+
+    /// Execute the query, streaming the matching records.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query can't be [planned](Self::explain) or if opening the cursor fails, e.g. because the
+    /// transaction has finished or the object store has been deleted.
+    pub fn stream(self) -> crate::Result<QueryStream<'a, V, C>> {
+        let plan = self.explain()?;
+
+        let req = if plan.is_empty() {
+            None
+        } else {
+            let range = plan.range().map(|range| range as &dyn Encode);
+            let direction = Some(plan.direction());
+
+            Some(match plan.index() {
+                Some(index) => self
+                    .store
+                    .index(index)?
+                    .open_cursor(range, direction, false)?,
+                None => self.store.open_cursor(range, direction, false)?,
+            })
+        };
+
+        let filters = self
+            .filters
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|f| plan.residual_filters().contains(&f.key_path))
+            .collect();
+        let sort = self.order.filter(|_| plan.sorts_in_rust());
+
+        Ok(QueryStream::new(
+            req,
+            filters,
+            sort,
+            self.offset,
+            self.limit,
+        ))
+    }
+}
+
+#[sealed]
+impl<'a, V, C: Codec<V>> crate::Build for Query<'a, V, C> {
+    type Ok = QueryCollect<'a, V, C>;
+    type Err = crate::error::Error;
+
+    #[inline]
+    fn build(self) -> crate::Result<Self::Ok> {
+        self.stream().map(QueryStream::collect)
+    }
+}
@@ -0,0 +1,270 @@
+use crate::cursor::CursorDirection;
+use crate::future::Raw;
+use crate::primitive::TryFromJs;
+use crate::{IdbKey, KeyPath, KeyRange};
+use wasm_bindgen::prelude::*;
+
+/// A filter on the value at a key path.
+#[derive(Clone, Debug)]
+pub(super) struct Filter {
+    pub key_path: String,
+    pub range: KeyRange<IdbKey>,
+}
+
+/// A key path an [`Index`](crate::index::Index) or the object store itself is keyed by.
+pub(super) struct Candidate {
+    /// `None` for the object store.
+    pub index: Option<String>,
+
+    /// `None` for object stores with out-of-line keys.
+    pub key_path: Option<KeyPath>,
+}
+
+/// How a [`Query`](super::Query) gets executed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryPlan {
+    index: Option<String>,
+    range: Option<KeyRange<IdbKey>>,
+    direction: CursorDirection,
+    residual_filters: Vec<String>,
+    sorts_in_rust: bool,
+    is_empty: bool,
+}
+
+/// A [`Candidate`] evaluated against the query.
+struct Scored {
+    index: Option<String>,
+    range: Option<KeyRange<IdbKey>>,
+    covered: Vec<usize>,
+    rank: (u8, usize, bool),
+}
+
+impl QueryPlan {
+    /// The name of the index the cursor gets opened on or `None` if it gets opened on the object store.
+    #[must_use]
+    pub fn index(&self) -> Option<&str> {
+        self.index.as_deref()
+    }
+
+    /// The key range pushed into the cursor.
+    #[must_use]
+    pub fn range(&self) -> Option<&KeyRange<IdbKey>> {
+        self.range.as_ref()
+    }
+
+    /// The direction the cursor iterates in.
+    #[must_use]
+    pub fn direction(&self) -> CursorDirection {
+        self.direction
+    }
+
+    /// Key paths of the filters that couldn't be fully pushed into the cursor & get checked in Rust.
+    #[must_use]
+    pub fn residual_filters(&self) -> &[String] {
+        &self.residual_filters
+    }
+
+    /// Whether the records need to be sorted in Rust because the cursor doesn't iterate in the requested order.
+    #[must_use]
+    pub fn sorts_in_rust(&self) -> bool {
+        self.sorts_in_rust
+    }
+
+    /// Whether the filters contradict each other, in which case no cursor gets opened.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.is_empty
+    }
+
+    pub(super) fn new(
+        candidates: Vec<Candidate>,
+        filters: Option<&[Filter]>,
+        order: Option<&(String, CursorDirection)>,
+    ) -> Self {
+        let Some(filters) = filters else {
+            return Self {
+                index: None,
+                range: None,
+                direction: CursorDirection::Next,
+                residual_filters: Vec::new(),
+                sorts_in_rust: false,
+                is_empty: true,
+            };
+        };
+
+        // The object store comes first & wins ties as it's the only candidate guaranteed to hold every record
+        let best = candidates
+            .into_iter()
+            .filter_map(|candidate| score(candidate, filters, order))
+            .fold(None::<Scored>, |best, next| match best {
+                Some(best) if best.rank >= next.rank => Some(best),
+                _ => Some(next),
+            });
+
+        let (index, range, covered, ordered) = match best {
+            Some(s) => (s.index, s.range, s.covered, s.rank.2),
+            None => (None, None, Vec::new(), order.is_none()),
+        };
+
+        let residual_filters = filters
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !covered.contains(idx))
+            .map(|(_, filter)| filter.key_path.clone())
+            .collect();
+
+        Self {
+            index,
+            range,
+            direction: match order {
+                Some((_, direction)) if ordered => *direction,
+                _ => CursorDirection::Next,
+            },
+            residual_filters,
+            sorts_in_rust: !ordered,
+            is_empty: false,
+        }
+    }
+}
+
+/// Work out the range to push into the candidate's cursor, which filters it fully covers & whether it iterates in the
+/// requested order. Returns `None` if the candidate can't be used.
+fn score(
+    candidate: Candidate,
+    filters: &[Filter],
+    order: Option<&(String, CursorDirection)>,
+) -> Option<Scored> {
+    let (fields, single) = match candidate.key_path {
+        Some(KeyPath::One(field)) => (vec![field], true),
+        Some(KeyPath::Sequence(fields)) => (fields.into_vec(), false),
+        // Out-of-line keys: the object store can only be scanned in full
+        None | Some(KeyPath::JsValue(_)) if candidate.index.is_none() => (Vec::new(), false),
+        None | Some(KeyPath::JsValue(_)) => return None,
+    };
+    let filter_for = |field: &str| filters.iter().position(|f| f.key_path == field);
+
+    // Records without a valid key at each of the index's fields are missing from the index, so it can only be used
+    // if every field is filtered or ordered on, which skips such records anyway.
+    if candidate.index.is_some() {
+        let is_order = |field: &String| order.is_some_and(|(order, _)| order == field);
+        if !fields
+            .iter()
+            .all(|field| filter_for(field).is_some() || is_order(field))
+        {
+            return None;
+        }
+    }
+
+    let mut covered = Vec::new();
+    let mut eq = Vec::new();
+    for field in &fields {
+        match filter_for(field).map(|idx| (idx, &filters[idx].range)) {
+            Some((idx, KeyRange::Only(key))) => {
+                covered.push(idx);
+                eq.push(key.clone());
+            }
+            _ => break,
+        }
+    }
+
+    let next = fields.get(eq.len());
+    let ordered = match order {
+        None => true,
+        Some((field, _)) => next == Some(field) || fields[..eq.len()].contains(field),
+    };
+
+    let range_filter = next.and_then(|field| filter_for(field));
+    let (level, range) = if let Some(idx) = range_filter {
+        covered.push(idx);
+        let range = &filters[idx].range;
+        let range = if single {
+            range.clone()
+        } else {
+            compound_range(&eq, range)
+        };
+        (2, Some(range))
+    } else if eq.is_empty() {
+        (0, None)
+    } else if eq.len() == fields.len() {
+        let key = if single { eq.pop()? } else { IdbKey::Array(eq) };
+        (3, Some(KeyRange::Only(key)))
+    } else {
+        (1, Some(KeyRange::array_prefix(eq)))
+    };
+
+    Some(Scored {
+        index: candidate.index,
+        range,
+        rank: (level, covered.len(), ordered),
+        covered,
+    })
+}
+
+/// Narrow a compound key to the records whose leading fields equal `eq` & whose next field falls within `range`.
+fn compound_range(eq: &[IdbKey], range: &KeyRange<IdbKey>) -> KeyRange<IdbKey> {
+    let with = |key: IdbKey| {
+        let mut keys = eq.to_vec();
+        keys.push(key);
+        IdbKey::Array(keys)
+    };
+
+    let (lower, upper) = match range {
+        KeyRange::Only(key) => (Some((key, false)), Some((key, false))),
+        KeyRange::LowerBound(key, open) => (Some((key, *open)), None),
+        KeyRange::UpperBound(key, open) => (None, Some((key, *open))),
+        KeyRange::Bound(lower, lower_open, upper, upper_open) => {
+            (Some((lower, *lower_open)), Some((upper, *upper_open)))
+        }
+    };
+
+    // Keys are arrays of the same length, so "greater than x" is "at least x's successor" & "at most x" is "less than
+    // x's successor"; this stays exact when more fields follow.
+    let lower = match lower {
+        Some((key, open)) => Some(with(if open { key.successor() } else { key.clone() })),
+        None if eq.is_empty() => None,
+        None => Some(IdbKey::Array(eq.to_vec())),
+    };
+    let upper = match upper {
+        Some((key, true)) => Some(with(key.clone())),
+        Some((key, false)) => Some(with(key.successor())),
+        None => match eq.split_last() {
+            Some((last, head)) => {
+                let mut keys = head.to_vec();
+                keys.push(last.successor());
+                Some(IdbKey::Array(keys))
+            }
+            None => None,
+        },
+    };
+
+    match (lower, upper) {
+        (Some(lower), Some(upper)) => KeyRange::Bound(lower, false, upper, true),
+        (Some(lower), None) => KeyRange::LowerBound(lower, false),
+        (None, Some(upper)) => KeyRange::UpperBound(upper, true),
+        (None, None) => unreachable!("key ranges always have at least one bound"),
+    }
+}
+
+/// Evaluate a [key path](https://w3c.github.io/IndexedDB/#evaluate-a-key-path-on-a-value) on a value, returning `None`
+/// if it doesn't resolve to a valid key.
+pub(super) fn evaluate(value: &Raw, key_path: &str) -> Option<IdbKey> {
+    match value {
+        Raw::Js(js) => evaluate_js(js, key_path),
+        #[cfg(feature = "backend")]
+        Raw::Value(value) => value.eval_path(key_path)?.to_key(),
+    }
+}
+
+fn evaluate_js(value: &JsValue, key_path: &str) -> Option<IdbKey> {
+    let mut value = value.clone();
+    if !key_path.is_empty() {
+        for identifier in key_path.split('.') {
+            if !value.is_object() && !value.is_string() {
+                return None;
+            }
+            value = js_sys::Reflect::get(&value, &JsValue::from_str(identifier)).ok()?;
+        }
+    }
+
+    IdbKey::from_js(value).ok()
+}
@@ -0,0 +1,241 @@
+use super::plan::{evaluate, Filter};
+use crate::cursor::{BaseCursor, CursorDirection};
+use crate::future::cursor::read_cursor;
+use crate::future::request::listeners::EventTargetResult;
+use crate::future::{PollUnpinned as _, Raw, Request, UntypedRequest};
+use crate::internal_utils::SystemRepr;
+use crate::typed::{decode, Codec};
+use crate::IdbKey;
+use internal_macros::FutureFromPollUnpinned;
+use sealed::sealed;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+/// A stream of the records matching a [`Query`](super::Query).
+pub struct QueryStream<'a, V, C> {
+    state: State,
+    filters: Vec<Filter>,
+    sort: Option<(String, CursorDirection)>,
+    offset: u32,
+    limit: Option<u32>,
+    _lifetime: PhantomData<&'a ()>,
+    marker: PhantomData<fn() -> (V, C)>,
+}
+
+/// Future for collecting a [`QueryStream`] into a [`Vec`].
+#[derive(FutureFromPollUnpinned)]
+pub struct QueryCollect<'a, V, C> {
+    stream: QueryStream<'a, V, C>,
+    out: Vec<V>,
+}
+
+/// The value at the sort key path & the record's primary key.
+type SortKey = (IdbKey, Option<IdbKey>);
+
+enum State {
+    Opening(Request<EventTargetResult>),
+    Scanning {
+        cursor: BaseCursor,
+        sorted: Option<Vec<(SortKey, Raw)>>,
+    },
+    Draining(std::vec::IntoIter<Raw>),
+    Done,
+}
+
+impl<'a, V, C> QueryStream<'a, V, C> {
+    pub(super) fn new(
+        req: Option<UntypedRequest>,
+        filters: Vec<Filter>,
+        sort: Option<(String, CursorDirection)>,
+        offset: u32,
+        limit: Option<u32>,
+    ) -> Self {
+        Self {
+            state: match req {
+                Some(req) if limit != Some(0) => State::Opening(Request::new(req)),
+                _ => State::Done,
+            },
+            filters,
+            sort,
+            offset,
+            limit,
+            _lifetime: PhantomData,
+            marker: PhantomData,
+        }
+    }
+
+    /// Collect the remaining records into a [`Vec`].
+    #[must_use]
+    #[inline]
+    pub fn collect(self) -> QueryCollect<'a, V, C> {
+        QueryCollect {
+            stream: self,
+            out: Vec::new(),
+        }
+    }
+
+    fn matches(&self, value: &Raw) -> bool {
+        self.filters.iter().all(move |filter| {
+            evaluate(value, &filter.key_path).is_some_and(|key| filter.range.contains(&key))
+        })
+    }
+
+    /// Apply the offset & limit to a matching record.
+    fn take(&mut self, value: Raw) -> Option<Raw> {
+        if self.offset != 0 {
+            self.offset -= 1;
+            return None;
+        }
+
+        if let Some(ref mut limit) = self.limit {
+            *limit -= 1;
+            if *limit == 0 {
+                self.state = State::Done;
+            }
+        }
+
+        Some(value)
+    }
+
+    /// Sort the buffered records & start emitting them.
+    fn drain(&mut self, mut sorted: Vec<(SortKey, Raw)>) {
+        // Ties are broken by primary key, the order an index cursor visits them in.
+        sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        // Like an index cursor, unique directions keep the record with the lowest primary key for each key. Reversing
+        // afterwards mirrors a `prev` cursor, which visits ties in descending primary key order.
+        let direction = self.sort.as_ref().map(|(_, direction)| *direction);
+        if matches!(
+            direction,
+            Some(CursorDirection::Nextunique | CursorDirection::Prevunique)
+        ) {
+            sorted.dedup_by(|((a, _), _), ((b, _), _)| a == b);
+        }
+        if matches!(
+            direction,
+            Some(CursorDirection::Prev | CursorDirection::Prevunique)
+        ) {
+            sorted.reverse();
+        }
+
+        let offset = usize::try_from(self.offset).unwrap_or(usize::MAX);
+        let limit = self
+            .limit
+            .map_or(usize::MAX, |l| usize::try_from(l).unwrap_or(usize::MAX));
+        let values = sorted
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+
+        self.offset = 0;
+        self.limit = None;
+        self.state = State::Draining(values.into_iter());
+    }
+
+    fn poll_raw(&mut self, cx: &mut Context<'_>) -> Poll<Option<crate::Result<Raw>>> {
+        loop {
+            match self.state {
+                State::Opening(ref mut req) => {
+                    let res = ready!(req.poll_unpinned(cx)).and_then(|res| read_cursor(req, res));
+                    self.state = match res {
+                        Ok(Some(repr)) => State::Scanning {
+                            cursor: BaseCursor::new(repr),
+                            sorted: self.sort.as_ref().map(|_| Vec::new()),
+                        },
+                        Ok(None) => State::Done,
+                        Err(e) => {
+                            self.state = State::Done;
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                    };
+                }
+                State::Scanning {
+                    ref mut cursor,
+                    ref mut sorted,
+                } => match ready!(cursor.poll_state(cx, read_record(sorted.is_some()))) {
+                    Ok(Some((value, primary_key))) => {
+                        if !self.matches(&value) {
+                            continue;
+                        }
+
+                        if let State::Scanning {
+                            sorted: Some(ref mut sorted),
+                            ..
+                        } = self.state
+                        {
+                            let key_path = self.sort.as_ref().map_or("", |(path, _)| path);
+                            if let Some(key) = evaluate(&value, key_path) {
+                                let primary_key = primary_key.and_then(|pk| evaluate(&pk, ""));
+                                sorted.push(((key, primary_key), value));
+                            }
+                        } else if let Some(value) = self.take(value) {
+                            return Poll::Ready(Some(Ok(value)));
+                        }
+                    }
+                    Ok(None) => match sorted.take() {
+                        Some(sorted) => self.drain(sorted),
+                        None => self.state = State::Done,
+                    },
+                    Err(e) => {
+                        self.state = State::Done;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                },
+                State::Draining(ref mut values) => return Poll::Ready(values.next().map(Ok)),
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Read the value at the cursor's position, plus its primary key if the records get sorted in Rust.
+fn read_record(sorting: bool) -> impl FnOnce(&mut BaseCursor) -> crate::Result<(Raw, Option<Raw>)> {
+    move |cursor| {
+        let primary_key = if sorting {
+            Some(cursor.as_sys().primary_key()?)
+        } else {
+            None
+        };
+        Ok((cursor.value_sys()?, primary_key))
+    }
+}
+
+impl<V, C: Codec<V>> futures_core::Stream for QueryStream<'_, V, C> {
+    type Item = crate::Result<V>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let value = ready!(self.get_mut().poll_raw(cx));
+        Poll::Ready(value.map(|res| res.and_then(decode::<C, V>)))
+    }
+}
+
+impl<V, C> futures_core::FusedStream for QueryStream<'_, V, C>
+where
+    C: Codec<V>,
+{
+    #[inline]
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+}
+
+#[sealed]
+impl<V, C: Codec<V>> crate::future::PollUnpinned for QueryCollect<'_, V, C> {
+    type Output = crate::Result<Vec<V>>;
+
+    fn poll_unpinned(&mut self, cx: &mut Context) -> Poll<Self::Output> {
+        loop {
+            match ready!(self.stream.poll_raw(cx)) {
+                Some(Ok(raw)) => match decode::<C, V>(raw) {
+                    Ok(value) => self.out.push(value),
+                    Err(e) => return Poll::Ready(Err(e)),
+                },
+                Some(Err(e)) => return Poll::Ready(Err(e)),
+                None => return Poll::Ready(Ok(std::mem::take(&mut self.out))),
+            }
+        }
+    }
+}
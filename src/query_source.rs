@@ -6,12 +6,14 @@ use wasm_bindgen::prelude::*;
 pub use get_all::{GetAll, GetAllKeys, GetAllRecords};
 use internal_macros::errdoc;
 
-use crate::internal_utils::SystemRepr;
+use crate::future::UntypedRequest;
+use crate::internal_utils::{Encode, SystemRepr};
 use crate::{KeyPath, KeyRange};
 pub use get::Get;
 pub use get_key::GetKey;
 use internal::QuerySourceInternal;
 
+pub use contains_key::ContainsKey;
 pub use count::Count;
 
 iffeat! {
@@ -20,6 +22,19 @@ iffeat! {
     pub use cursor::{AnyCursorBuilder, CursorBuilder, KeyCursorBuilder};
 }
 
+iffeat! {
+    #[cfg(feature = "cursors")]
+    mod edge;
+    pub use edge::Edge;
+}
+
+iffeat! {
+    #[cfg(feature = "cursors")]
+    mod paginate;
+    pub use paginate::{Page, PageToken, Paginate};
+}
+
+mod contains_key;
 mod count;
 mod get;
 pub(crate) mod get_all;
@@ -30,34 +45,41 @@ mod get_key;
 pub trait QuerySource {
     /// Count the number of documents in the index/object store.
     #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
-    fn count(&self) -> Count<Self>
+    fn count(&self) -> Count<'_, Self>
     where
         Self: Sized;
 
     /// Get one record from the object store or index. Returns the first match if a non-[only](KeyRange::Only) key is
     /// provided and multiple records match.
     #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
-    fn get<V, K, I>(&self, key: I) -> Get<Self, K, V>
+    fn get<V, K, I>(&self, key: I) -> Get<'_, Self, K, V>
     where
         Self: Sized,
         I: Into<KeyRange<K>>;
 
     /// Return the first matching key selected by the specified query.
     #[errdoc(QuerySource(TransactionInactiveError, InvalidStateError, DataError))]
-    fn get_key<K, I>(&self, key_range: I) -> GetKey<Self, K>
+    fn get_key<K, I>(&self, key_range: I) -> GetKey<'_, Self, K>
+    where
+        Self: Sized,
+        I: Into<KeyRange<K>>;
+
+    /// Check whether any record matches the given key or key range.
+    #[errdoc(QuerySource(TransactionInactiveError, InvalidStateError, DataError))]
+    fn contains_key<K, I>(&self, key_range: I) -> ContainsKey<'_, Self, K>
     where
         Self: Sized,
         I: Into<KeyRange<K>>;
 
     /// Get all records in the object store or index.
     #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
-    fn get_all<V>(&self) -> GetAllRecords<Self, V>
+    fn get_all<V>(&self) -> GetAllRecords<'_, Self, V>
     where
         Self: Sized;
 
     /// Get all keys in the object store or index.
     #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
-    fn get_all_keys<K>(&self) -> GetAllKeys<Self, K>
+    fn get_all_keys<K>(&self) -> GetAllKeys<'_, Self, K>
     where
         Self: Sized;
 
@@ -68,8 +90,12 @@ pub trait QuerySource {
     fn name(&self) -> String;
 
     /// Set the index/object store name.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a [driver](crate::backend) rejects the rename. Browsers throw in the same situations.
     #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, ConstraintError))]
-    fn set_name(&self, name: &str);
+    fn set_name(&self, name: &str) -> crate::Result<()>;
 
     iffeat! {
         #[cfg(feature = "cursors")]
@@ -77,12 +103,38 @@ pub trait QuerySource {
         /// Open a cursor that iterates over the records in the index or object store.
         /// Resolves to `None` if the cursor is empty.
         #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
-        fn open_cursor(&self) -> CursorBuilder<Self> where Self: Sized;
+        fn open_cursor(&self) -> CursorBuilder<'_, Self> where Self: Sized;
 
         /// Open a cursor that iterates over the keys in the index or object store.
         /// Resolves to `None` if the cursor is empty.
         #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
-        fn open_key_cursor(&self) -> KeyCursorBuilder<Self> where Self: Sized;
+        fn open_key_cursor(&self) -> KeyCursorBuilder<'_, Self> where Self: Sized;
+
+        /// Read a page of at most `page_size` records. The returned [`Page`] holds [`PageToken`]s for reading the
+        /// following & preceding pages, which remain valid across transactions. A `page_size` of `0` is treated as
+        /// `1`.
+        #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
+        fn paginate<V>(&self, page_size: u32) -> Paginate<'_, Self, V> where Self: Sized;
+
+        /// Get the record with the lowest key, reading one step of a cursor. Resolves to `None` if there are no
+        /// matching records.
+        #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
+        fn first<V>(&self) -> Edge<'_, Self, V> where Self: Sized;
+
+        /// Get the record with the highest key, reading one step of a cursor. Resolves to `None` if there are no
+        /// matching records.
+        #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
+        fn last<V>(&self) -> Edge<'_, Self, V> where Self: Sized;
+
+        /// Get the lowest key, reading one step of a key cursor. This is the primary key for object stores and the
+        /// index key for indices. Resolves to `None` if there are no matching records.
+        #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
+        fn first_key<K>(&self) -> Edge<'_, Self, K> where Self: Sized;
+
+        /// Get the highest key, reading one step of a key cursor. This is the primary key for object stores and the
+        /// index key for indices. Resolves to `None` if there are no matching records.
+        #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
+        fn last_key<K>(&self) -> Edge<'_, Self, K> where Self: Sized;
     }
 }
 
@@ -94,62 +146,94 @@ impl<T: SystemRepr<Repr = R>, R: QuerySourceInternal> QuerySource for T {
     }
 
     #[inline]
-    fn set_name(&self, name: &str) {
-        self.as_sys().set_name(name);
+    fn set_name(&self, name: &str) -> crate::Result<()> {
+        self.as_sys().set_name(name)
     }
 
     #[inline]
-    fn count(&self) -> Count<Self> {
+    fn count(&self) -> Count<'_, Self> {
         Count::new(self)
     }
 
+    #[inline]
     fn key_path(&self) -> Option<KeyPath> {
-        match self.as_sys().key_path() {
-            Ok(path) if !path.is_null() => Some(path.into()),
-            _ => None,
-        }
+        self.as_sys().key_path()
     }
 
-    fn get<V, K, I>(&self, key: I) -> Get<Self, K, V>
+    fn get<V, K, I>(&self, key: I) -> Get<'_, Self, K, V>
     where
         I: Into<KeyRange<K>>,
     {
         Get::new(self, key.into())
     }
 
-    fn get_key<K, I>(&self, key_range: I) -> GetKey<Self, K>
+    fn get_key<K, I>(&self, key_range: I) -> GetKey<'_, Self, K>
     where
         I: Into<KeyRange<K>>,
     {
         GetKey::new(self, key_range.into())
     }
 
+    fn contains_key<K, I>(&self, key_range: I) -> ContainsKey<'_, Self, K>
+    where
+        I: Into<KeyRange<K>>,
+    {
+        ContainsKey::new(self, key_range.into())
+    }
+
     #[inline]
-    fn get_all<V>(&self) -> GetAllRecords<Self, V> {
+    fn get_all<V>(&self) -> GetAllRecords<'_, Self, V> {
         GetAllRecords::new(self)
     }
 
     #[inline]
-    fn get_all_keys<K>(&self) -> GetAllKeys<Self, K> {
+    fn get_all_keys<K>(&self) -> GetAllKeys<'_, Self, K> {
         GetAllKeys::new(self)
     }
 
     iffeat! {
         #[cfg(feature = "cursors")]
         #[inline]
-        fn open_cursor(&self) -> CursorBuilder<Self> {
+        fn open_cursor(&self) -> CursorBuilder<'_, Self> {
             CursorBuilder::new(self)
         }
 
         #[inline]
-        fn open_key_cursor(&self) -> KeyCursorBuilder<Self> {
+        fn open_key_cursor(&self) -> KeyCursorBuilder<'_, Self> {
             KeyCursorBuilder::new(self)
         }
+
+        #[inline]
+        fn paginate<V>(&self, page_size: u32) -> Paginate<'_, Self, V> {
+            Paginate::new(self, page_size)
+        }
+
+        #[inline]
+        fn first<V>(&self) -> Edge<'_, Self, V> {
+            Edge::new(self, crate::cursor::CursorDirection::Next, false)
+        }
+
+        #[inline]
+        fn last<V>(&self) -> Edge<'_, Self, V> {
+            Edge::new(self, crate::cursor::CursorDirection::Prev, false)
+        }
+
+        #[inline]
+        fn first_key<K>(&self) -> Edge<'_, Self, K> {
+            Edge::new(self, crate::cursor::CursorDirection::Next, true)
+        }
+
+        #[inline]
+        fn last_key<K>(&self) -> Edge<'_, Self, K> {
+            Edge::new(self, crate::cursor::CursorDirection::Prev, true)
+        }
     }
 }
 
 pub(crate) mod internal {
-    use wasm_bindgen::prelude::*;
+    use crate::future::UntypedRequest;
+    use crate::internal_utils::Encode;
+    use crate::KeyPath;
 
     /// Internal representation of a [`QuerySource`](super::QuerySource).
     #[::sealed::sealed(pub(super))]
@@ -158,202 +242,232 @@ pub(crate) mod internal {
         fn name(&self) -> String;
 
         #[doc(hidden)]
-        fn set_name(&self, name: &str);
-
-        #[doc(hidden)]
-        fn count(&self) -> Result<web_sys::IdbRequest, JsValue>;
+        fn set_name(&self, name: &str) -> crate::Result<()>;
 
         #[doc(hidden)]
-        fn count_with_key(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue>;
+        fn key_path(&self) -> Option<KeyPath>;
 
+        /// Whether this is an index rather than an object store.
         #[doc(hidden)]
-        fn key_path(&self) -> Result<JsValue, JsValue>;
+        fn is_index(&self) -> bool;
 
         #[doc(hidden)]
-        fn get(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue>;
+        fn count(&self, query: Option<&dyn Encode>) -> crate::Result<UntypedRequest>;
 
         #[doc(hidden)]
-        fn get_key(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue>;
+        fn get(&self, query: &dyn Encode) -> crate::Result<UntypedRequest>;
 
         #[doc(hidden)]
-        fn get_all(&self) -> Result<web_sys::IdbRequest, JsValue>;
+        fn get_key(&self, query: &dyn Encode) -> crate::Result<UntypedRequest>;
 
         #[doc(hidden)]
-        fn get_all_with_key(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue>;
-
-        #[doc(hidden)]
-        fn get_all_with_key_and_limit(
+        fn get_all(
             &self,
-            key: &JsValue,
-            limit: u32,
-        ) -> Result<web_sys::IdbRequest, JsValue>;
-
-        #[doc(hidden)]
-        fn get_all_keys(&self) -> Result<web_sys::IdbRequest, JsValue>;
+            query: Option<&dyn Encode>,
+            limit: Option<u32>,
+        ) -> crate::Result<UntypedRequest>;
 
         #[doc(hidden)]
-        fn get_all_keys_with_key(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue>;
+        fn get_all_keys(
+            &self,
+            query: Option<&dyn Encode>,
+            limit: Option<u32>,
+        ) -> crate::Result<UntypedRequest>;
 
         #[doc(hidden)]
-        fn get_all_keys_with_key_and_limit(
+        #[cfg(feature = "cursors")]
+        fn open_cursor(
             &self,
-            key: &JsValue,
-            limit: u32,
-        ) -> Result<web_sys::IdbRequest, JsValue>;
-
-        iffeat! {
-            #[cfg(feature = "cursors")]
-            #[doc(hidden)]
-            fn open_cursor(&self) -> Result<web_sys::IdbRequest, JsValue>;
-
-            #[doc(hidden)]
-            fn open_cursor_with_range(&self, range: &JsValue) -> Result<web_sys::IdbRequest, JsValue>;
-
-            #[doc(hidden)]
-            fn open_cursor_with_range_and_direction(
-                &self,
-                range: &JsValue,
-                direction: web_sys::IdbCursorDirection,
-            ) -> Result<web_sys::IdbRequest, JsValue>;
-
-            #[doc(hidden)]
-            fn open_key_cursor(&self) -> Result<web_sys::IdbRequest, JsValue>;
-
-            #[doc(hidden)]
-            fn open_key_cursor_with_range(&self, range: &JsValue) -> Result<web_sys::IdbRequest, JsValue>;
-
-            #[doc(hidden)]
-            fn open_key_cursor_with_range_and_direction(
-                &self,
-                range: &JsValue,
-                direction: web_sys::IdbCursorDirection,
-            ) -> Result<web_sys::IdbRequest, JsValue>;
-        }
+            query: Option<&dyn Encode>,
+            direction: Option<crate::cursor::CursorDirection>,
+            key_only: bool,
+        ) -> crate::Result<UntypedRequest>;
     }
 }
 
 macro_rules! impl_internal {
-    ($for: ty) => {
+    ($for: ty, is_index: $is_index: literal) => {
         #[::sealed::sealed]
         impl internal::QuerySourceInternal for $for {
-            #[inline]
             fn name(&self) -> String {
-                <$for>::name(self)
-            }
-
-            #[inline]
-            fn set_name(&self, name: &str) {
-                <$for>::set_name(self, name);
-            }
-
-            #[inline]
-            fn count(&self) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::count(self)
+                match self {
+                    Self::Web(sys) => sys.name(),
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => driver.name(),
+                }
             }
 
-            #[inline]
-            fn count_with_key(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::count_with_key(self, key)
+            fn set_name(&self, name: &str) -> crate::Result<()> {
+                match self {
+                    Self::Web(sys) => {
+                        sys.set_name(name);
+                        Ok(())
+                    }
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => driver.set_name(name),
+                }
             }
 
-            #[inline]
-            fn key_path(&self) -> Result<JsValue, JsValue> {
-                <$for>::key_path(self)
+            fn key_path(&self) -> Option<KeyPath> {
+                match self {
+                    Self::Web(sys) => match sys.key_path() {
+                        Ok(path) if !path.is_null() => Some(path.into()),
+                        _ => None,
+                    },
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => driver.key_path(),
+                }
             }
 
             #[inline]
-            fn get(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::get(self, key)
+            fn is_index(&self) -> bool {
+                $is_index
             }
 
-            #[inline]
-            fn get_key(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::get_key(self, key)
+            fn count(&self, query: Option<&dyn Encode>) -> crate::Result<UntypedRequest> {
+                match self {
+                    Self::Web(sys) => Ok(match query {
+                        Some(query) => sys.count_with_key(&query.to_js()?),
+                        None => sys.count(),
+                    }?
+                    .into()),
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => {
+                        let query = crate::repr::driver_query(query)?;
+                        Ok(UntypedRequest::driver_value(driver.count(query)))
+                    }
+                }
             }
 
-            #[inline]
-            fn get_all(&self) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::get_all(self)
+            fn get(&self, query: &dyn Encode) -> crate::Result<UntypedRequest> {
+                match self {
+                    Self::Web(sys) => Ok(sys.get(&query.to_js()?)?.into()),
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => {
+                        let query = query.to_key_range()?;
+                        Ok(UntypedRequest::driver_value(driver.get(query)))
+                    }
+                }
             }
 
-            #[inline]
-            fn get_all_with_key(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::get_all_with_key(self, key)
+            fn get_key(&self, query: &dyn Encode) -> crate::Result<UntypedRequest> {
+                match self {
+                    Self::Web(sys) => Ok(sys.get_key(&query.to_js()?)?.into()),
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => {
+                        let query = query.to_key_range()?;
+                        Ok(UntypedRequest::driver_value(driver.get_key(query)))
+                    }
+                }
             }
 
-            #[inline]
-            fn get_all_with_key_and_limit(
+            fn get_all(
                 &self,
-                key: &JsValue,
-                limit: u32,
-            ) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::get_all_with_key_and_limit(self, key, limit)
-            }
-
-            #[inline]
-            fn get_all_keys(&self) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::get_all_keys(self)
-            }
-
-            #[inline]
-            fn get_all_keys_with_key(&self, key: &JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::get_all_keys_with_key(self, key)
+                query: Option<&dyn Encode>,
+                limit: Option<u32>,
+            ) -> crate::Result<UntypedRequest> {
+                match self {
+                    Self::Web(sys) => Ok(match (query, limit) {
+                        (None, None) => sys.get_all(),
+                        (Some(query), None) => sys.get_all_with_key(&query.to_js()?),
+                        (query, Some(limit)) => {
+                            sys.get_all_with_key_and_limit(&web_query(query)?, limit)
+                        }
+                    }?
+                    .into()),
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => {
+                        let query = crate::repr::driver_query(query)?;
+                        Ok(UntypedRequest::driver_value(driver.get_all(query, limit)))
+                    }
+                }
             }
 
-            #[inline]
-            fn get_all_keys_with_key_and_limit(
+            fn get_all_keys(
                 &self,
-                key: &JsValue,
-                limit: u32,
-            ) -> Result<web_sys::IdbRequest, JsValue> {
-                <$for>::get_all_keys_with_key_and_limit(self, key, limit)
-            }
-
-            iffeat! {
-                #[cfg(feature = "cursors")]
-                #[inline]
-                fn open_cursor(&self) -> Result<web_sys::IdbRequest, JsValue> {
-                    <$for>::open_cursor(self)
-                }
-
-                #[inline]
-                fn open_cursor_with_range(&self, range: &JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-                    <$for>::open_cursor_with_range(self, range)
-                }
-
-                #[inline]
-                fn open_cursor_with_range_and_direction(
-                    &self,
-                    range: &JsValue,
-                    direction: web_sys::IdbCursorDirection,
-                ) -> Result<web_sys::IdbRequest, JsValue> {
-                    <$for>::open_cursor_with_range_and_direction(self, range, direction)
-                }
-
-                #[inline]
-                fn open_key_cursor(&self) -> Result<web_sys::IdbRequest, JsValue> {
-                    <$for>::open_key_cursor(self)
-                }
-
-                #[inline]
-                 fn open_key_cursor_with_range(&self, range: &JsValue) -> Result<web_sys::IdbRequest, JsValue> {
-                    <$for>::open_key_cursor_with_range(self, range)
+                query: Option<&dyn Encode>,
+                limit: Option<u32>,
+            ) -> crate::Result<UntypedRequest> {
+                match self {
+                    Self::Web(sys) => Ok(match (query, limit) {
+                        (None, None) => sys.get_all_keys(),
+                        (Some(query), None) => sys.get_all_keys_with_key(&query.to_js()?),
+                        (query, Some(limit)) => {
+                            sys.get_all_keys_with_key_and_limit(&web_query(query)?, limit)
+                        }
+                    }?
+                    .into()),
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => {
+                        let query = crate::repr::driver_query(query)?;
+                        let keys = driver.get_all_keys(query, limit).map(|keys| {
+                            keys.into_iter()
+                                .map(crate::backend::Value::from)
+                                .collect::<Vec<_>>()
+                        });
+
+                        Ok(UntypedRequest::driver_value(keys))
+                    }
                 }
+            }
 
-                #[inline]
-                fn open_key_cursor_with_range_and_direction(
-                    &self,
-                    range: &JsValue,
-                    direction: web_sys::IdbCursorDirection,
-                ) -> Result<web_sys::IdbRequest, JsValue> {
-                    <$for>::open_key_cursor_with_range_and_direction(self, range, direction)
+            #[cfg(feature = "cursors")]
+            fn open_cursor(
+                &self,
+                query: Option<&dyn Encode>,
+                direction: Option<crate::cursor::CursorDirection>,
+                key_only: bool,
+            ) -> crate::Result<UntypedRequest> {
+                match self {
+                    Self::Web(sys) => Ok(match (query, direction, key_only) {
+                        (None, None, false) => sys.open_cursor(),
+                        (None, None, true) => sys.open_key_cursor(),
+                        (Some(query), None, false) => sys.open_cursor_with_range(&query.to_js()?),
+                        (Some(query), None, true) => {
+                            sys.open_key_cursor_with_range(&query.to_js()?)
+                        }
+                        (query, Some(direction), false) => {
+                            sys.open_cursor_with_range_and_direction(&web_query(query)?, direction)
+                        }
+                        (query, Some(direction), true) => sys
+                            .open_key_cursor_with_range_and_direction(
+                                &web_query(query)?,
+                                direction,
+                            ),
+                    }?
+                    .into()),
+                    #[cfg(feature = "backend")]
+                    Self::Driver(driver) => {
+                        use crate::future::request::listeners::EventTargetResult;
+
+                        let query = crate::repr::driver_query(query)?;
+                        let direction = direction.unwrap_or(crate::cursor::CursorDirection::Next);
+                        let cursor = driver
+                            .open_cursor(query, direction, key_only)
+                            .map(|cursor| match cursor {
+                                Some(cursor) => EventTargetResult::Cursor(
+                                    crate::repr::CursorRepr::Driver(cursor),
+                                ),
+                                None => EventTargetResult::Null,
+                            });
+
+                        Ok(UntypedRequest::driver(cursor))
+                    }
                 }
             }
         }
     };
 }
 
-impl_internal!(web_sys::IdbObjectStore);
+/// Convert an optional query for a browser request, which takes `undefined` in place of a missing query.
+fn web_query(query: Option<&dyn Encode>) -> crate::Result<JsValue> {
+    match query {
+        Some(query) => query.to_js(),
+        None => Ok(JsValue::UNDEFINED),
+    }
+}
+
+impl_internal!(crate::repr::StoreRepr, is_index: false);
 
 #[cfg(feature = "indices")]
-impl_internal!(web_sys::IdbIndex);
+impl_internal!(crate::repr::IndexRepr, is_index: true);
@@ -0,0 +1,57 @@
+use super::QuerySourceInternal;
+use crate::future::BasicRequest;
+use crate::internal_utils::{Encode, SystemRepr};
+use crate::primitive::TryToJs;
+use crate::KeyRange;
+use fancy_constructor::new;
+use internal_macros::BuildIntoFut;
+use sealed::sealed;
+
+/// Builder for [`QuerySource::contains_key`](super::QuerySource::contains_key).
+#[derive(new, BuildIntoFut)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct ContainsKey<'a, Qs, K> {
+    query_source: &'a Qs,
+    key_range: KeyRange<K>,
+}
+
+impl<Qs, K, Sys> ContainsKey<'_, Qs, K>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+{
+    fn to_req(&self, key: &dyn Encode) -> crate::Result<BasicRequest<bool>> {
+        let req = self.query_source.as_sys().get_key(key)?;
+        Ok(BasicRequest::new(req, |raw| Ok(!raw.is_undefined())))
+    }
+}
+
+#[sealed]
+impl<Sys, Qs, K> crate::BuildPrimitive for ContainsKey<'_, Qs, K>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    KeyRange<K>: TryToJs,
+{
+    type Fut = BasicRequest<bool>;
+
+    fn primitive(self) -> crate::Result<Self::Fut> {
+        self.to_req(&self.key_range)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[sealed]
+impl<Sys, Qs, K> crate::BuildSerde for ContainsKey<'_, Qs, K>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    KeyRange<K>: crate::serde::SerialiseToJs,
+{
+    type Fut = BasicRequest<bool>;
+
+    fn serde(self) -> crate::Result<Self::Fut> {
+        self.to_req(&crate::internal_utils::Ser(&self.key_range))
+    }
+}
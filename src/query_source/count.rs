@@ -50,7 +50,7 @@ where
     type Fut = BasicRequest<u32>;
 
     fn primitive(self) -> crate::Result<Self::Fut> {
-        let req = self.query_source.as_sys().count()?;
+        let req = self.query_source.as_sys().count(None)?;
         Ok(BasicRequest::new_primitive(req))
     }
 }
@@ -65,14 +65,14 @@ where
     type Fut = BasicRequest<u32>;
 
     fn primitive(self) -> crate::Result<Self::Fut> {
-        let js = self.query.try_to_js()?;
-        let req = self.query_source.as_sys().count_with_key(&js)?;
+        let req = self.query_source.as_sys().count(Some(&self.query))?;
         Ok(BasicRequest::new_primitive(req))
     }
 }
 
 #[cfg(feature = "serde")]
 const _: () = {
+    use crate::internal_utils::Ser;
     use crate::{BuildPrimitive, SerialiseToJs};
 
     #[sealed]
@@ -99,8 +99,7 @@ const _: () = {
         type Fut = BasicRequest<u32>;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let js = self.query.serialise_to_js()?;
-            let req = self.query_source.as_sys().count_with_key(&js)?;
+            let req = self.query_source.as_sys().count(Some(&Ser(&self.query)))?;
 
             Ok(BasicRequest::new_primitive(req))
         }
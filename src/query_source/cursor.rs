@@ -9,7 +9,6 @@ use internal_macros::BuildIntoFut;
 use kind::CursorKind;
 use sealed::sealed;
 use std::marker::PhantomData;
-use wasm_bindgen::prelude::*;
 
 /// Builder for a [`Cursor`](crate::cursor::Cursor).
 pub type CursorBuilder<'a, Qs, Q = (), D = ()> = AnyCursorBuilder<'a, kind::Record, Qs, Q, D>;
@@ -105,8 +104,8 @@ const _: () = {
         type Fut = CursorRequest<'qs, K::Cursor<'qs, Qs>, Qs>;
 
         fn primitive(self) -> crate::Result<Self::Fut> {
-            let query = self.query.try_to_js()?;
-            let req = K::open_w_range_n_direction(self.query_source, query, self.direction)?;
+            let req =
+                K::open_w_range_n_direction(self.query_source, Some(&self.query), self.direction)?;
             Ok(CursorRequest::new(req, self.query_source))
         }
     }
@@ -123,8 +122,7 @@ const _: () = {
         type Fut = CursorRequest<'qs, K::Cursor<'qs, Qs>, Qs>;
 
         fn primitive(self) -> crate::Result<Self::Fut> {
-            let query = self.query.try_to_js()?;
-            let req = K::open_w_range(self.query_source, query)?;
+            let req = K::open_w_range(self.query_source, &self.query)?;
             Ok(CursorRequest::new(req, self.query_source))
         }
     }
@@ -140,8 +138,7 @@ const _: () = {
         type Fut = CursorRequest<'qs, K::Cursor<'qs, Qs>, Qs>;
 
         fn primitive(self) -> crate::Result<Self::Fut> {
-            let req =
-                K::open_w_range_n_direction(self.query_source, JsValue::UNDEFINED, self.direction)?;
+            let req = K::open_w_range_n_direction(self.query_source, None, self.direction)?;
             Ok(CursorRequest::new(req, self.query_source))
         }
     }
@@ -165,6 +162,7 @@ const _: () = {
 
 #[cfg(feature = "serde")]
 const _: () = {
+    use crate::internal_utils::Ser;
     use crate::serde::SerialiseToJs;
 
     #[sealed]
@@ -180,8 +178,8 @@ const _: () = {
         type Fut = CursorRequest<'qs, K::Cursor<'qs, Qs>, Qs>;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let query = self.query.serialise_to_js()?;
-            let req = K::open_w_range_n_direction(self.query_source, query, self.direction)?;
+            let query = Ser(&self.query);
+            let req = K::open_w_range_n_direction(self.query_source, Some(&query), self.direction)?;
             Ok(CursorRequest::new(req, self.query_source))
         }
     }
@@ -198,8 +196,7 @@ const _: () = {
         type Fut = CursorRequest<'qs, K::Cursor<'qs, Qs>, Qs>;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let query = self.query.serialise_to_js()?;
-            let req = K::open_w_range(self.query_source, query)?;
+            let req = K::open_w_range(self.query_source, &Ser(&self.query))?;
             Ok(CursorRequest::new(req, self.query_source))
         }
     }
@@ -245,9 +242,9 @@ pub(crate) mod kind {
 
     use super::super::QuerySourceInternal;
     use crate::cursor::CursorDirection;
-    use crate::internal_utils::SystemRepr;
+    use crate::future::UntypedRequest;
+    use crate::internal_utils::{Encode, SystemRepr};
     use sealed::sealed;
-    use wasm_bindgen::prelude::*;
 
     /// The type of cursor being built.
     #[sealed]
@@ -255,100 +252,55 @@ pub(crate) mod kind {
         /// The cursor returned by opening with this type.
         type Cursor<'a, Qs: 'a>;
 
+        /// Whether the cursor only reads keys.
         #[doc(hidden)]
-        fn open<Qs, Sys>(qs: &Qs) -> Result<web_sys::IdbRequest, JsValue>
-        where
-            Qs: SystemRepr<Repr = Sys>,
-            Sys: QuerySourceInternal;
+        const KEY_ONLY: bool;
 
         #[doc(hidden)]
-        fn open_w_range<Qs, Sys>(qs: &Qs, query: JsValue) -> Result<web_sys::IdbRequest, JsValue>
-        where
-            Qs: SystemRepr<Repr = Sys>,
-            Sys: QuerySourceInternal;
-
-        #[doc(hidden)]
-        fn open_w_range_n_direction<Qs, Sys>(
-            qs: &Qs,
-            query: JsValue,
-            direction: CursorDirection,
-        ) -> Result<web_sys::IdbRequest, JsValue>
-        where
-            Qs: SystemRepr<Repr = Sys>,
-            Sys: QuerySourceInternal;
-    }
-
-    #[sealed]
-    impl CursorKind for Record {
-        type Cursor<'a, Qs: 'a> = crate::cursor::Cursor<'a, Qs>;
-
         #[inline]
-        fn open<Qs, Sys>(qs: &Qs) -> Result<web_sys::IdbRequest, JsValue>
+        fn open<Qs, Sys>(qs: &Qs) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
-            qs.as_sys().open_cursor()
+            qs.as_sys().open_cursor(None, None, Self::KEY_ONLY)
         }
 
+        #[doc(hidden)]
         #[inline]
-        fn open_w_range<Qs, Sys>(qs: &Qs, query: JsValue) -> Result<web_sys::IdbRequest, JsValue>
+        fn open_w_range<Qs, Sys>(qs: &Qs, query: &dyn Encode) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
-            qs.as_sys().open_cursor_with_range(&query)
+            qs.as_sys().open_cursor(Some(query), None, Self::KEY_ONLY)
         }
 
+        #[doc(hidden)]
         #[inline]
         fn open_w_range_n_direction<Qs, Sys>(
             qs: &Qs,
-            query: JsValue,
+            query: Option<&dyn Encode>,
             direction: CursorDirection,
-        ) -> Result<web_sys::IdbRequest, JsValue>
+        ) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
             qs.as_sys()
-                .open_cursor_with_range_and_direction(&query, direction)
+                .open_cursor(query, Some(direction), Self::KEY_ONLY)
         }
     }
 
+    #[sealed]
+    impl CursorKind for Record {
+        type Cursor<'a, Qs: 'a> = crate::cursor::Cursor<'a, Qs>;
+        const KEY_ONLY: bool = false;
+    }
+
     #[sealed]
     impl CursorKind for Key {
         type Cursor<'a, Qs: 'a> = crate::cursor::KeyCursor<'a, Qs>;
-
-        #[inline]
-        fn open<Qs, Sys>(qs: &Qs) -> Result<web_sys::IdbRequest, JsValue>
-        where
-            Qs: SystemRepr<Repr = Sys>,
-            Sys: QuerySourceInternal,
-        {
-            qs.as_sys().open_key_cursor()
-        }
-
-        #[inline]
-        fn open_w_range<Qs, Sys>(qs: &Qs, query: JsValue) -> Result<web_sys::IdbRequest, JsValue>
-        where
-            Qs: SystemRepr<Repr = Sys>,
-            Sys: QuerySourceInternal,
-        {
-            qs.as_sys().open_key_cursor_with_range(&query)
-        }
-
-        #[inline]
-        fn open_w_range_n_direction<Qs, Sys>(
-            qs: &Qs,
-            query: JsValue,
-            direction: CursorDirection,
-        ) -> Result<web_sys::IdbRequest, JsValue>
-        where
-            Qs: SystemRepr<Repr = Sys>,
-            Sys: QuerySourceInternal,
-        {
-            qs.as_sys()
-                .open_key_cursor_with_range_and_direction(&query, direction)
-        }
+        const KEY_ONLY: bool = true;
     }
 }
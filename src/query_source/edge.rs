@@ -0,0 +1,89 @@
+use super::QuerySourceInternal;
+use crate::cursor::CursorDirection;
+use crate::future::{EdgeRequest, Raw};
+use crate::internal_utils::{Encode, SystemRepr};
+use crate::primitive::{TryFromJs, TryFromJsExt};
+use crate::{IdbKey, KeyRange};
+use internal_macros::BuildIntoFut;
+use sealed::sealed;
+use std::marker::PhantomData;
+
+/// Builder for [`QuerySource::first`](super::QuerySource::first), [`last`](super::QuerySource::last),
+/// [`first_key`](super::QuerySource::first_key) & [`last_key`](super::QuerySource::last_key).
+#[derive(BuildIntoFut)]
+#[must_use]
+pub struct Edge<'a, Qs, T> {
+    query_source: &'a Qs,
+    query: Option<KeyRange<IdbKey>>,
+    direction: CursorDirection,
+    key_only: bool,
+    marker: PhantomData<T>,
+}
+
+impl<'a, Qs, T> Edge<'a, Qs, T> {
+    #[inline]
+    pub(super) fn new(query_source: &'a Qs, direction: CursorDirection, key_only: bool) -> Self {
+        Self {
+            query_source,
+            query: None,
+            direction,
+            key_only,
+            marker: PhantomData,
+        }
+    }
+
+    /// Only consider records matching the given key or key range.
+    pub fn with_query<K, I>(mut self, query: I) -> Self
+    where
+        I: Into<KeyRange<K>>,
+        K: Into<IdbKey>,
+    {
+        self.query = Some(query.into().map(Into::into));
+        self
+    }
+
+    fn open<Sys>(self, map_fn: fn(Raw) -> crate::Result<T>) -> crate::Result<EdgeRequest<T>>
+    where
+        Qs: SystemRepr<Repr = Sys>,
+        Sys: QuerySourceInternal,
+    {
+        let query = self.query.as_ref().map(|query| query as &dyn Encode);
+        let req =
+            self.query_source
+                .as_sys()
+                .open_cursor(query, Some(self.direction), self.key_only)?;
+
+        Ok(EdgeRequest::new(req, self.key_only, map_fn))
+    }
+}
+
+#[sealed]
+impl<Qs, T, Sys> crate::BuildPrimitive for Edge<'_, Qs, T>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    T: TryFromJs,
+{
+    type Fut = EdgeRequest<T>;
+
+    #[inline]
+    fn primitive(self) -> crate::Result<Self::Fut> {
+        self.open(T::from_raw)
+    }
+}
+
+#[sealed]
+#[cfg(feature = "serde")]
+impl<Qs, T, Sys> crate::BuildSerde for Edge<'_, Qs, T>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    T: crate::serde::DeserialiseFromJs,
+{
+    type Fut = EdgeRequest<T>;
+
+    #[inline]
+    fn serde(self) -> crate::Result<Self::Fut> {
+        self.open(crate::serde::deserialise_from_raw)
+    }
+}
@@ -37,8 +37,7 @@ where
             t_value: _,
         } = self;
 
-        let js = key.try_to_js()?;
-        let req = query_source.as_sys().get(&js)?;
+        let req = query_source.as_sys().get(&key)?;
 
         Ok(BasicRequest::new_primitive(req))
     }
@@ -62,8 +61,9 @@ where
             t_value: _,
         } = self;
 
-        let js = crate::serde::SerialiseToJs::serialise_to_js(&key)?;
-        let req = query_source.as_sys().get(&js)?;
+        let req = query_source
+            .as_sys()
+            .get(&crate::internal_utils::Ser(&key))?;
 
         Ok(BasicRequest::new_ser(req))
     }
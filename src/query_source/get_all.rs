@@ -8,7 +8,6 @@ use internal_macros::BuildIntoFut;
 use kind::GetAllKind;
 use sealed::sealed;
 use std::marker::PhantomData;
-use wasm_bindgen::prelude::*;
 
 /// Builder for [`get_all`](super::QuerySource::get_all).
 ///
@@ -126,8 +125,7 @@ const _: () = {
         type Fut = GetAllPrimitiveRequest<T>;
 
         fn primitive(self) -> crate::Result<Self::Fut> {
-            let js = self.query.try_to_js()?;
-            let req = K::get_with_key(self.query_source, js)?;
+            let req = K::get_with_key(self.query_source, &self.query)?;
             Ok(GetAllPrimitiveRequest::get_all_primitive(req))
         }
     }
@@ -143,7 +141,7 @@ const _: () = {
         type Fut = GetAllPrimitiveRequest<T>;
 
         fn primitive(self) -> crate::Result<Self::Fut> {
-            let req = K::get_with_key_limit(self.query_source, JsValue::UNDEFINED, self.limit)?;
+            let req = K::get_with_key_limit(self.query_source, None, self.limit)?;
             Ok(GetAllPrimitiveRequest::get_all_primitive(req))
         }
     }
@@ -160,8 +158,7 @@ const _: () = {
         type Fut = GetAllPrimitiveRequest<T>;
 
         fn primitive(self) -> crate::Result<Self::Fut> {
-            let js = self.query.try_to_js()?;
-            let req = K::get_with_key_limit(self.query_source, js, self.limit)?;
+            let req = K::get_with_key_limit(self.query_source, Some(&self.query), self.limit)?;
             Ok(GetAllPrimitiveRequest::get_all_primitive(req))
         }
     }
@@ -170,6 +167,7 @@ const _: () = {
 #[cfg(feature = "serde")]
 const _: () = {
     use crate::future::GetAllSerdeRequest;
+    use crate::internal_utils::Ser;
     use crate::serde::{DeserialiseFromJs, SerialiseToJs};
 
     #[sealed]
@@ -200,8 +198,7 @@ const _: () = {
         type Fut = GetAllSerdeRequest<T>;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let js = self.query.serialise_to_js()?;
-            let req = K::get_with_key(self.query_source, js)?;
+            let req = K::get_with_key(self.query_source, &Ser(&self.query))?;
             Ok(GetAllSerdeRequest::get_all_serde(req))
         }
     }
@@ -217,7 +214,7 @@ const _: () = {
         type Fut = GetAllSerdeRequest<T>;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let req = K::get_with_key_limit(self.query_source, JsValue::UNDEFINED, self.limit)?;
+            let req = K::get_with_key_limit(self.query_source, None, self.limit)?;
             Ok(GetAllSerdeRequest::get_all_serde(req))
         }
     }
@@ -234,8 +231,8 @@ const _: () = {
         type Fut = GetAllSerdeRequest<T>;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let js = self.query.serialise_to_js()?;
-            let req = K::get_with_key_limit(self.query_source, js, self.limit)?;
+            let req =
+                K::get_with_key_limit(self.query_source, Some(&Ser(&self.query)), self.limit)?;
             Ok(GetAllSerdeRequest::get_all_serde(req))
         }
     }
@@ -243,9 +240,9 @@ const _: () = {
 
 pub(crate) mod kind {
     use super::super::QuerySourceInternal;
-    use crate::internal_utils::SystemRepr;
+    use crate::future::UntypedRequest;
+    use crate::internal_utils::{Encode, SystemRepr};
     use sealed::sealed;
-    use wasm_bindgen::prelude::*;
 
     #[allow(missing_docs)]
     pub struct Record();
@@ -257,13 +254,13 @@ pub(crate) mod kind {
     #[sealed]
     pub trait GetAllKind {
         #[doc(hidden)]
-        fn get<Qs, Sys>(qs: &Qs) -> Result<web_sys::IdbRequest, JsValue>
+        fn get<Qs, Sys>(qs: &Qs) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal;
 
         #[doc(hidden)]
-        fn get_with_key<Qs, Sys>(qs: &Qs, key: JsValue) -> Result<web_sys::IdbRequest, JsValue>
+        fn get_with_key<Qs, Sys>(qs: &Qs, key: &dyn Encode) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal;
@@ -271,9 +268,9 @@ pub(crate) mod kind {
         #[doc(hidden)]
         fn get_with_key_limit<Qs, Sys>(
             qs: &Qs,
-            key: JsValue,
+            key: Option<&dyn Encode>,
             limit: u32,
-        ) -> Result<web_sys::IdbRequest, JsValue>
+        ) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal;
@@ -282,65 +279,65 @@ pub(crate) mod kind {
     #[sealed]
     impl GetAllKind for Record {
         #[inline]
-        fn get<Qs, Sys>(qs: &Qs) -> Result<web_sys::IdbRequest, JsValue>
+        fn get<Qs, Sys>(qs: &Qs) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
-            qs.as_sys().get_all()
+            qs.as_sys().get_all(None, None)
         }
 
         #[inline]
-        fn get_with_key<Qs, Sys>(qs: &Qs, key: JsValue) -> Result<web_sys::IdbRequest, JsValue>
+        fn get_with_key<Qs, Sys>(qs: &Qs, key: &dyn Encode) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
-            qs.as_sys().get_all_with_key(&key)
+            qs.as_sys().get_all(Some(key), None)
         }
 
         #[inline]
         fn get_with_key_limit<Qs, Sys>(
             qs: &Qs,
-            key: JsValue,
+            key: Option<&dyn Encode>,
             limit: u32,
-        ) -> Result<web_sys::IdbRequest, JsValue>
+        ) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
-            qs.as_sys().get_all_with_key_and_limit(&key, limit)
+            qs.as_sys().get_all(key, Some(limit))
         }
     }
 
     #[sealed]
     impl GetAllKind for Key {
-        fn get<Qs, Sys>(qs: &Qs) -> Result<web_sys::IdbRequest, JsValue>
+        fn get<Qs, Sys>(qs: &Qs) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
-            qs.as_sys().get_all_keys()
+            qs.as_sys().get_all_keys(None, None)
         }
 
-        fn get_with_key<Qs, Sys>(qs: &Qs, key: JsValue) -> Result<web_sys::IdbRequest, JsValue>
+        fn get_with_key<Qs, Sys>(qs: &Qs, key: &dyn Encode) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
-            qs.as_sys().get_all_keys_with_key(&key)
+            qs.as_sys().get_all_keys(Some(key), None)
         }
 
         fn get_with_key_limit<Qs, Sys>(
             qs: &Qs,
-            key: JsValue,
+            key: Option<&dyn Encode>,
             limit: u32,
-        ) -> Result<web_sys::IdbRequest, JsValue>
+        ) -> crate::Result<UntypedRequest>
         where
             Qs: SystemRepr<Repr = Sys>,
             Sys: QuerySourceInternal,
         {
-            qs.as_sys().get_all_keys_with_key_and_limit(&key, limit)
+            qs.as_sys().get_all_keys(key, Some(limit))
         }
     }
 }
@@ -43,14 +43,14 @@ where
     type Fut = BasicRequest<Option<KT>>;
 
     fn primitive(self) -> crate::Result<Self::Fut> {
-        let key = self.key_range.try_into_js()?;
-        let req = self.query_source.as_sys().get_key(&key)?;
+        let req = self.query_source.as_sys().get_key(&self.key_range)?;
         Ok(BasicRequest::new_primitive(req))
     }
 }
 
 #[cfg(feature = "serde")]
 const _: () = {
+    use crate::internal_utils::Ser;
     use crate::serde::{DeserialiseFromJs, SerialiseToJs};
 
     #[sealed]
@@ -64,8 +64,7 @@ const _: () = {
         type Fut = BasicRequest<Option<KT>>;
 
         fn serde(self) -> crate::Result<Self::Fut> {
-            let key = self.key_range.serialise_to_js()?;
-            let req = self.query_source.as_sys().get_key(&key)?;
+            let req = self.query_source.as_sys().get_key(&Ser(&self.key_range))?;
             Ok(BasicRequest::new_ser(req))
         }
     }
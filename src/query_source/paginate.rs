@@ -0,0 +1,201 @@
+use super::QuerySourceInternal;
+use crate::cursor::CursorDirection;
+use crate::future::{PageRequest, Raw};
+use crate::internal_utils::{Encode, SystemRepr};
+use crate::primitive::{TryFromJs, TryFromJsExt};
+use crate::{IdbKey, KeyRange};
+use derive_more::Debug;
+use internal_macros::BuildIntoFut;
+use sealed::sealed;
+use std::marker::PhantomData;
+
+pub use token::PageToken;
+
+mod token;
+
+/// Builder for [`QuerySource::paginate`](super::QuerySource::paginate).
+#[derive(Debug, BuildIntoFut)]
+#[must_use]
+pub struct Paginate<'a, Qs, V> {
+    #[debug(skip)]
+    query_source: &'a Qs,
+    page_size: u32,
+    query: Option<KeyRange<IdbKey>>,
+    direction: CursorDirection,
+    resume: Option<Resume>,
+
+    #[debug(skip)]
+    marker: PhantomData<V>,
+}
+
+#[derive(Debug)]
+enum Resume {
+    After(PageToken),
+    Before(PageToken),
+}
+
+/// A page of records returned by [`QuerySource::paginate`](super::QuerySource::paginate).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<V> {
+    records: Vec<V>,
+    next: Option<PageToken>,
+    previous: Option<PageToken>,
+}
+
+impl<'a, Qs, V> Paginate<'a, Qs, V> {
+    #[inline]
+    pub(super) fn new(query_source: &'a Qs, page_size: u32) -> Self {
+        Self {
+            query_source,
+            page_size: page_size.max(1),
+            query: None,
+            direction: CursorDirection::Next,
+            resume: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Set the key or key range to be paginated over.
+    pub fn with_query<K, I>(mut self, query: I) -> Self
+    where
+        I: Into<KeyRange<K>>,
+        K: Into<IdbKey>,
+    {
+        self.query = Some(query.into().map(Into::into));
+        self
+    }
+
+    /// Set the direction pages are read in.
+    #[inline]
+    pub fn with_direction(mut self, direction: CursorDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Read the page following the record the token points at, e.g. one from [`Page::next`].
+    #[inline]
+    pub fn after(mut self, token: PageToken) -> Self {
+        self.resume = Some(Resume::After(token));
+        self
+    }
+
+    /// Read the page preceding the record the token points at, e.g. one from [`Page::previous`].
+    #[inline]
+    pub fn before(mut self, token: PageToken) -> Self {
+        self.resume = Some(Resume::Before(token));
+        self
+    }
+
+    fn open<Sys>(self, map_fn: fn(Raw) -> crate::Result<V>) -> crate::Result<PageRequest<V>>
+    where
+        Qs: SystemRepr<Repr = Sys>,
+        Sys: QuerySourceInternal,
+    {
+        let query = self.query.as_ref().map(|query| query as &dyn Encode);
+        let (seek, backward) = match self.resume {
+            Some(Resume::After(token)) => (Some(token), false),
+            Some(Resume::Before(token)) => (Some(token), true),
+            None => (None, false),
+        };
+        let direction = if backward {
+            reverse(self.direction)
+        } else {
+            self.direction
+        };
+
+        let sys = self.query_source.as_sys();
+        let req = sys.open_cursor(query, Some(direction), false)?;
+
+        Ok(PageRequest::new(
+            req,
+            sys.is_index(),
+            self.page_size,
+            seek,
+            backward,
+            map_fn,
+        ))
+    }
+}
+
+impl<V> Page<V> {
+    pub(crate) fn new(
+        records: Vec<V>,
+        next: Option<PageToken>,
+        previous: Option<PageToken>,
+    ) -> Self {
+        Self {
+            records,
+            next,
+            previous,
+        }
+    }
+
+    /// The records on this page.
+    #[inline]
+    #[must_use]
+    pub fn records(&self) -> &[V] {
+        &self.records
+    }
+
+    /// Consume the page, returning its records.
+    #[inline]
+    #[must_use]
+    pub fn into_records(self) -> Vec<V> {
+        self.records
+    }
+
+    /// Token for reading the following page via [`Paginate::after`]. `None` if this is the last page.
+    #[inline]
+    #[must_use]
+    pub fn next(&self) -> Option<&PageToken> {
+        self.next.as_ref()
+    }
+
+    /// Token for reading the preceding page via [`Paginate::before`]. `None` if this is the first page.
+    #[inline]
+    #[must_use]
+    pub fn previous(&self) -> Option<&PageToken> {
+        self.previous.as_ref()
+    }
+}
+
+fn reverse(direction: CursorDirection) -> CursorDirection {
+    match direction {
+        CursorDirection::Next => CursorDirection::Prev,
+        CursorDirection::Nextunique => CursorDirection::Prevunique,
+        CursorDirection::Prev => CursorDirection::Next,
+        CursorDirection::Prevunique => CursorDirection::Nextunique,
+        other => other,
+    }
+}
+
+#[sealed]
+impl<Qs, V, Sys> crate::BuildPrimitive for Paginate<'_, Qs, V>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    V: TryFromJs + Unpin,
+{
+    type Fut = PageRequest<V>;
+
+    #[inline]
+    fn primitive(self) -> crate::Result<Self::Fut> {
+        self.open(V::from_raw)
+    }
+}
+
+#[sealed]
+#[cfg(feature = "serde")]
+impl<Qs, V, Sys> crate::BuildSerde for Paginate<'_, Qs, V>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    V: crate::serde::DeserialiseFromJs + Unpin,
+{
+    type Fut = PageRequest<V>;
+
+    #[inline]
+    fn serde(self) -> crate::Result<Self::Fut> {
+        self.open(crate::serde::deserialise_from_raw)
+    }
+}
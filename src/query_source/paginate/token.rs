@@ -0,0 +1,205 @@
+use crate::error::PageTokenError;
+use crate::IdbKey;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const TAG_NUMBER: u8 = 0;
+const TAG_DATE: u8 = 1;
+const TAG_STRING: u8 = 2;
+const TAG_BINARY: u8 = 3;
+const TAG_ARRAY: u8 = 4;
+
+/// How deeply array keys may nest within a token. Tokens come from clients, so this bounds the decoder's recursion.
+const MAX_DEPTH: usize = 64;
+
+/// An opaque position within an object store or index, used to resume
+/// [pagination](super::super::QuerySource::paginate) in a later transaction.
+///
+/// Holds the key of the record the token was taken from & - for indices - its primary key. Tokens can be passed around
+/// as strings via their [`Display`] & [`FromStr`] implementations or serialised with `serde` when the `serde` feature
+/// is enabled. Tokens for array keys nested more than 64 levels deep are rejected when parsed.
+///
+/// ```
+/// # use indexed_db_futures::query_source::PageToken;
+/// # use indexed_db_futures::IdbKey;
+/// let token = PageToken::new(IdbKey::from("foo"), Some(IdbKey::from(1)));
+/// let parsed = token.to_string().parse::<PageToken>().unwrap();
+/// assert_eq!(parsed, token);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageToken {
+    key: IdbKey,
+    primary_key: Option<IdbKey>,
+}
+
+impl PageToken {
+    /// Create a token pointing at the record with the given key & primary key.
+    #[must_use]
+    pub fn new(key: IdbKey, primary_key: Option<IdbKey>) -> Self {
+        Self { key, primary_key }
+    }
+
+    /// The key of the record the token points at.
+    #[must_use]
+    pub fn key(&self) -> &IdbKey {
+        &self.key
+    }
+
+    /// The primary key of the record the token points at. Only set when paginating over an index.
+    #[must_use]
+    pub fn primary_key(&self) -> Option<&IdbKey> {
+        self.primary_key.as_ref()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode(&self.key, &mut out);
+        if let Some(ref primary_key) = self.primary_key {
+            encode(primary_key, &mut out);
+        }
+        out
+    }
+
+    fn from_bytes(mut bytes: &[u8]) -> Option<Self> {
+        let key = decode(&mut bytes, 0)?;
+        let primary_key = if bytes.is_empty() {
+            None
+        } else {
+            Some(decode(&mut bytes, 0)?)
+        };
+
+        (bytes.is_empty() && key.is_valid() && primary_key.as_ref().map_or(true, IdbKey::is_valid))
+            .then_some(Self { key, primary_key })
+    }
+}
+
+impl Display for PageToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for byte in self.to_bytes() {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = PageTokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() % 2 != 0 || !s.is_ascii() {
+            return Err(PageTokenError);
+        }
+
+        let bytes = (0..s.len())
+            .step_by(2)
+            .map(|idx| u8::from_str_radix(&s[idx..idx + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| PageTokenError)?;
+
+        Self::from_bytes(&bytes).ok_or(PageTokenError)
+    }
+}
+
+fn encode(key: &IdbKey, out: &mut Vec<u8>) {
+    match key {
+        IdbKey::Number(n) => {
+            out.push(TAG_NUMBER);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        IdbKey::Date(ms) => {
+            out.push(TAG_DATE);
+            out.extend_from_slice(&ms.to_be_bytes());
+        }
+        IdbKey::String(s) => {
+            out.push(TAG_STRING);
+            encode_len(s.len(), out);
+            out.extend_from_slice(s.as_bytes());
+        }
+        IdbKey::Binary(bytes) => {
+            out.push(TAG_BINARY);
+            encode_len(bytes.len(), out);
+            out.extend_from_slice(bytes);
+        }
+        IdbKey::Array(keys) => {
+            out.push(TAG_ARRAY);
+            encode_len(keys.len(), out);
+            for key in keys {
+                encode(key, out);
+            }
+        }
+    }
+}
+
+fn encode_len(len: usize, out: &mut Vec<u8>) {
+    // Keys are bounded by JS' own limits, so anything past `u32::MAX` can't occur in practice.
+    let len = u32::try_from(len).unwrap_or(u32::MAX);
+    out.extend_from_slice(&len.to_be_bytes());
+}
+
+fn decode(bytes: &mut &[u8], depth: usize) -> Option<IdbKey> {
+    let (&tag, rest) = bytes.split_first()?;
+    *bytes = rest;
+
+    Some(match tag {
+        TAG_NUMBER => IdbKey::Number(f64::from_be_bytes(take(bytes)?)),
+        TAG_DATE => IdbKey::Date(f64::from_be_bytes(take(bytes)?)),
+        TAG_STRING => {
+            let len = decode_len(bytes)?;
+            IdbKey::String(String::from_utf8(take_slice(bytes, len)?.to_vec()).ok()?)
+        }
+        TAG_BINARY => {
+            let len = decode_len(bytes)?;
+            IdbKey::Binary(take_slice(bytes, len)?.to_vec())
+        }
+        TAG_ARRAY => {
+            if depth == MAX_DEPTH {
+                return None;
+            }
+
+            let len = decode_len(bytes)?;
+            let mut keys = Vec::with_capacity(len.min(bytes.len()));
+            for _ in 0..len {
+                keys.push(decode(bytes, depth + 1)?);
+            }
+            IdbKey::Array(keys)
+        }
+        _ => return None,
+    })
+}
+
+fn decode_len(bytes: &mut &[u8]) -> Option<usize> {
+    usize::try_from(u32::from_be_bytes(take(bytes)?)).ok()
+}
+
+fn take<const N: usize>(bytes: &mut &[u8]) -> Option<[u8; N]> {
+    take_slice(bytes, N)?.try_into().ok()
+}
+
+fn take_slice<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if bytes.len() < len {
+        return None;
+    }
+
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Some(head)
+}
+
+#[cfg(feature = "serde")]
+const _: () = {
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    impl Serialize for PageToken {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PageToken {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(D::Error::custom)
+        }
+    }
+};
@@ -0,0 +1,613 @@
+//! Handles backed by either [`web_sys`] or a [driver](crate::backend).
+
+use crate::database::DbSys;
+use crate::error::Error;
+use crate::factory::FactorySys;
+use crate::future::UntypedRequest;
+use crate::internal_utils::Encode;
+use crate::iter::DomStringIter;
+use crate::transaction::{TransactionMode, TransactionSys};
+use crate::KeyPath;
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "backend")]
+use crate::backend::{
+    DatabaseDriver, FactoryDriver, IndexDriver, StoreDriver, TransactionDriver, Value,
+};
+#[cfg(feature = "backend")]
+use std::rc::Rc;
+
+/// [`DBFactory`](crate::factory::DBFactory) representation.
+#[derive(Clone, Debug)]
+pub enum FactoryRepr {
+    /// A browser factory.
+    Web(FactorySys),
+
+    /// A driver.
+    #[cfg(feature = "backend")]
+    Driver(Rc<dyn FactoryDriver>),
+}
+
+impl PartialEq for FactoryRepr {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Web(a), Self::Web(b)) => a == b,
+            #[cfg(feature = "backend")]
+            (Self::Driver(a), Self::Driver(b)) => Rc::ptr_eq(a, b),
+            #[cfg(feature = "backend")]
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FactoryRepr {}
+
+/// [`Database`](crate::database::Database) representation.
+#[derive(Clone, Debug)]
+pub enum DatabaseRepr {
+    /// A browser connection.
+    Web(DbSys),
+
+    /// A driver connection.
+    #[cfg(feature = "backend")]
+    Driver(DriverDatabase),
+}
+
+/// A [driver](crate::backend) connection along with the factory that opened it.
+#[cfg(feature = "backend")]
+#[derive(Clone, Debug)]
+pub struct DriverDatabase {
+    pub(crate) database: Rc<dyn DatabaseDriver>,
+    pub(crate) factory: Rc<dyn FactoryDriver>,
+
+    /// The `versionchange` transaction while the connection is being upgraded.
+    pub(crate) upgrade: Option<Rc<dyn TransactionDriver>>,
+}
+
+impl DatabaseRepr {
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Self::Web(db) => db.name(),
+            #[cfg(feature = "backend")]
+            Self::Driver(db) => db.database.name(),
+        }
+    }
+
+    pub(crate) fn version(&self) -> f64 {
+        match self {
+            Self::Web(db) => db.version(),
+            #[cfg(feature = "backend")]
+            Self::Driver(db) => db.database.version().into(),
+        }
+    }
+
+    pub(crate) fn object_store_names(&self) -> DomStringIter<'_> {
+        match self {
+            Self::Web(db) => DomStringIter::new(db.object_store_names()),
+            #[cfg(feature = "backend")]
+            Self::Driver(db) => DomStringIter::from_vec(db.database.object_store_names()),
+        }
+    }
+
+    pub(crate) fn close(&self) {
+        match self {
+            Self::Web(db) => db.close(),
+            #[cfg(feature = "backend")]
+            Self::Driver(db) => db.database.close(),
+        }
+    }
+
+    pub(crate) fn create_object_store(
+        &self,
+        name: &str,
+        key_path: Option<KeyPath>,
+        auto_increment: Option<bool>,
+    ) -> crate::Result<StoreRepr> {
+        match self {
+            Self::Web(db) => {
+                let sys = if key_path.is_none() && auto_increment.is_none() {
+                    db.create_object_store(name)
+                } else {
+                    let params = web_sys::IdbObjectStoreParameters::new();
+                    if let Some(auto_increment) = auto_increment {
+                        params.set_auto_increment(auto_increment);
+                    }
+                    if let Some(key_path) = key_path {
+                        params.set_key_path(&key_path.to_js());
+                    }
+                    db.create_object_store_with_optional_parameters(name, &params)
+                }?;
+
+                Ok(StoreRepr::Web(sys))
+            }
+            #[cfg(feature = "backend")]
+            Self::Driver(db) => db
+                .database
+                .create_object_store(
+                    name,
+                    key_path.map(driver_key_path).transpose()?,
+                    auto_increment.unwrap_or_default(),
+                )
+                .map(StoreRepr::Driver),
+        }
+    }
+
+    pub(crate) fn delete_object_store(&self, name: &str) -> crate::Result<()> {
+        match self {
+            Self::Web(db) => db.delete_object_store(name).map_err(Into::into),
+            #[cfg(feature = "backend")]
+            Self::Driver(db) => db.database.delete_object_store(name),
+        }
+    }
+
+    /// The `versionchange` transaction of a driver connection that's being upgraded.
+    #[cfg(feature = "backend")]
+    pub(crate) fn upgrade_transaction(&self) -> crate::Result<Rc<dyn TransactionDriver>> {
+        match self {
+            Self::Driver(DriverDatabase {
+                upgrade: Some(tx), ..
+            }) => Ok(tx.clone()),
+            _ => Err(crate::error::UnexpectedDataError::NoUpgradeTransaction.into()),
+        }
+    }
+}
+
+/// [`Transaction`](crate::transaction::Transaction) representation.
+#[derive(Clone, Debug)]
+pub enum TransactionRepr {
+    /// A browser transaction.
+    Web(TransactionSys),
+
+    /// A driver transaction.
+    #[cfg(feature = "backend")]
+    Driver(Rc<dyn TransactionDriver>),
+}
+
+impl TransactionRepr {
+    pub(crate) fn object_store(&self, name: &str) -> crate::Result<StoreRepr> {
+        match self {
+            Self::Web(tx) => Ok(StoreRepr::Web(tx.object_store(name)?)),
+            #[cfg(feature = "backend")]
+            Self::Driver(tx) => tx.object_store(name).map(StoreRepr::Driver),
+        }
+    }
+
+    pub(crate) fn object_store_names(&self) -> DomStringIter<'_> {
+        match self {
+            Self::Web(tx) => DomStringIter::new(tx.object_store_names()),
+            #[cfg(feature = "backend")]
+            Self::Driver(tx) => DomStringIter::from_vec(tx.object_store_names()),
+        }
+    }
+
+    pub(crate) fn mode(&self) -> TransactionMode {
+        match self {
+            Self::Web(tx) => tx.mode().unwrap_or(TransactionMode::Readonly),
+            #[cfg(feature = "backend")]
+            Self::Driver(tx) => tx.mode(),
+        }
+    }
+
+    pub(crate) fn error(&self) -> Option<Error> {
+        match self {
+            Self::Web(tx) => tx.error().map(Into::into),
+            #[cfg(feature = "backend")]
+            Self::Driver(_) => None,
+        }
+    }
+
+    pub(crate) fn abort(&self) -> crate::Result<()> {
+        match self {
+            Self::Web(tx) => tx.abort().map_err(Into::into),
+            #[cfg(feature = "backend")]
+            Self::Driver(tx) => tx.abort(),
+        }
+    }
+
+    pub(crate) fn commit(&self) -> crate::Result<()> {
+        match self {
+            Self::Web(tx) => tx.do_commit().map_err(Into::into),
+            #[cfg(feature = "backend")]
+            Self::Driver(tx) => tx.commit(),
+        }
+    }
+}
+
+impl From<web_sys::IdbTransaction> for TransactionRepr {
+    #[inline]
+    fn from(tx: web_sys::IdbTransaction) -> Self {
+        Self::Web(tx.unchecked_into())
+    }
+}
+
+/// [`ObjectStore`](crate::object_store::ObjectStore) representation.
+#[derive(Clone, Debug)]
+pub enum StoreRepr {
+    /// A browser object store.
+    Web(web_sys::IdbObjectStore),
+
+    /// A driver object store.
+    #[cfg(feature = "backend")]
+    Driver(Rc<dyn StoreDriver>),
+}
+
+impl StoreRepr {
+    pub(crate) fn auto_increment(&self) -> bool {
+        match self {
+            Self::Web(store) => store.auto_increment(),
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => store.auto_increment(),
+        }
+    }
+
+    pub(crate) fn add(
+        &self,
+        value: &dyn Encode,
+        key: Option<&dyn Encode>,
+    ) -> crate::Result<UntypedRequest> {
+        match self {
+            Self::Web(store) => Ok(match key {
+                Some(key) => store.add_with_key(&value.to_js()?, &key.to_js()?),
+                None => store.add(&value.to_js()?),
+            }?
+            .into()),
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => {
+                let (value, key) = driver_record(value, key)?;
+                Ok(UntypedRequest::driver_value(store.add(value, key)))
+            }
+        }
+    }
+
+    pub(crate) fn put(
+        &self,
+        value: &dyn Encode,
+        key: Option<&dyn Encode>,
+    ) -> crate::Result<UntypedRequest> {
+        match self {
+            Self::Web(store) => Ok(match key {
+                Some(key) => store.put_with_key(&value.to_js()?, &key.to_js()?),
+                None => store.put(&value.to_js()?),
+            }?
+            .into()),
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => {
+                let (value, key) = driver_record(value, key)?;
+                Ok(UntypedRequest::driver_value(store.put(value, key)))
+            }
+        }
+    }
+
+    pub(crate) fn delete(&self, query: &dyn Encode) -> crate::Result<UntypedRequest> {
+        match self {
+            Self::Web(store) => Ok(store.delete(&query.to_js()?)?.into()),
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => {
+                let query = query.to_key_range()?;
+                Ok(UntypedRequest::driver_value(
+                    store.delete(query).map(|()| Value::Undefined),
+                ))
+            }
+        }
+    }
+
+    pub(crate) fn clear(&self) -> crate::Result<UntypedRequest> {
+        match self {
+            Self::Web(store) => Ok(store.clear()?.into()),
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => Ok(UntypedRequest::driver_value(
+                store.clear().map(|()| Value::Undefined),
+            )),
+        }
+    }
+
+    #[cfg(feature = "indices")]
+    pub(crate) fn index(&self, name: &str) -> crate::Result<IndexRepr> {
+        match self {
+            Self::Web(store) => Ok(IndexRepr::Web(store.index(name)?)),
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => store.index(name).map(IndexRepr::Driver),
+        }
+    }
+
+    #[cfg(feature = "indices")]
+    pub(crate) fn index_names(&self) -> DomStringIter<'_> {
+        match self {
+            Self::Web(store) => DomStringIter::new(store.index_names()),
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => DomStringIter::from_vec(store.index_names()),
+        }
+    }
+
+    #[cfg(feature = "indices")]
+    #[cfg_attr(not(feature = "backend"), allow(clippy::needless_pass_by_value))]
+    pub(crate) fn create_index(
+        &self,
+        name: &str,
+        key_path: KeyPath,
+        unique: Option<bool>,
+        multi_entry: Option<bool>,
+    ) -> crate::Result<IndexRepr> {
+        match self {
+            Self::Web(store) => {
+                let key_path = key_path.to_js();
+                let sys = if unique.is_none() && multi_entry.is_none() {
+                    store.create_index_with_str_sequence(name, &key_path)
+                } else {
+                    let params = web_sys::IdbIndexParameters::new();
+                    if let Some(unique) = unique {
+                        params.set_unique(unique);
+                    }
+                    if let Some(multi_entry) = multi_entry {
+                        params.set_multi_entry(multi_entry);
+                    }
+                    store.create_index_with_str_sequence_and_optional_parameters(
+                        name, &key_path, &params,
+                    )
+                }?;
+
+                Ok(IndexRepr::Web(sys))
+            }
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => store
+                .create_index(
+                    name,
+                    driver_key_path(key_path)?,
+                    unique.unwrap_or_default(),
+                    multi_entry.unwrap_or_default(),
+                )
+                .map(IndexRepr::Driver),
+        }
+    }
+
+    #[cfg(feature = "indices")]
+    pub(crate) fn delete_index(&self, name: &str) -> crate::Result<()> {
+        match self {
+            Self::Web(store) => store.delete_index(name).map_err(Into::into),
+            #[cfg(feature = "backend")]
+            Self::Driver(store) => store.delete_index(name),
+        }
+    }
+}
+
+/// [`Index`](crate::index::Index) representation.
+#[cfg(feature = "indices")]
+#[derive(Clone, Debug)]
+pub enum IndexRepr {
+    /// A browser index.
+    Web(web_sys::IdbIndex),
+
+    /// A driver index.
+    #[cfg(feature = "backend")]
+    Driver(Rc<dyn IndexDriver>),
+}
+
+#[cfg(feature = "indices")]
+impl IndexRepr {
+    #[cfg(feature = "schema")]
+    pub(crate) fn unique(&self) -> bool {
+        match self {
+            Self::Web(index) => index.unique(),
+            #[cfg(feature = "backend")]
+            Self::Driver(index) => index.unique(),
+        }
+    }
+
+    #[cfg(any(feature = "schema", feature = "query"))]
+    pub(crate) fn multi_entry(&self) -> bool {
+        match self {
+            Self::Web(index) => index.multi_entry(),
+            #[cfg(feature = "backend")]
+            Self::Driver(index) => index.multi_entry(),
+        }
+    }
+}
+
+/// Split a record into the value & optional out-of-line key a driver expects.
+#[cfg(feature = "backend")]
+fn driver_record(
+    value: &dyn Encode,
+    key: Option<&dyn Encode>,
+) -> crate::Result<(Value, Option<crate::IdbKey>)> {
+    let key = match key {
+        Some(key) => Some(key.to_key()?),
+        None => None,
+    };
+
+    Ok((value.to_value()?, key))
+}
+
+/// Drivers can't evaluate raw JS key paths.
+#[cfg(feature = "backend")]
+pub(crate) fn driver_key_path(key_path: KeyPath) -> crate::Result<KeyPath> {
+    match key_path {
+        KeyPath::JsValue(_) => Err(crate::backend::not_supported("a raw JS key path")),
+        key_path => Ok(key_path),
+    }
+}
+
+/// Convert an optional query for a driver.
+#[cfg(feature = "backend")]
+pub(crate) fn driver_query(
+    query: Option<&dyn Encode>,
+) -> crate::Result<Option<crate::KeyRange<crate::IdbKey>>> {
+    match query {
+        Some(query) => query.to_key_range().map(Some),
+        None => Ok(None),
+    }
+}
+
+iffeat! {
+    #[cfg(feature = "cursors")]
+    pub use cursor::CursorRepr;
+}
+
+#[cfg(feature = "cursors")]
+mod cursor {
+    use crate::cursor::{CursorDirection, CursorSys};
+    use crate::future::{Raw, UntypedRequest};
+    use crate::internal_utils::Encode;
+
+    #[cfg(feature = "backend")]
+    use {
+        crate::backend::CursorDriver, crate::future::request::listeners::EventTargetResult,
+        std::rc::Rc,
+    };
+
+    /// [`Cursor`](crate::cursor::Cursor) & [`KeyCursor`](crate::cursor::KeyCursor) representation.
+    #[derive(Clone, Debug)]
+    pub enum CursorRepr {
+        /// A browser cursor.
+        Web(CursorSys),
+
+        /// A driver cursor.
+        #[cfg(feature = "backend")]
+        Driver(Rc<dyn CursorDriver>),
+    }
+
+    impl CursorRepr {
+        pub(crate) fn direction(&self) -> CursorDirection {
+            match self {
+                Self::Web(cur) => cur.direction(),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => cur.direction(),
+            }
+        }
+
+        pub(crate) fn key(&self) -> crate::Result<Raw> {
+            match self {
+                Self::Web(cur) => Ok(cur.key()?.into()),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => Ok(Raw::Value(cur.key().into())),
+            }
+        }
+
+        pub(crate) fn primary_key(&self) -> crate::Result<Raw> {
+            match self {
+                Self::Web(cur) => Ok(cur.primary_key()?.into()),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => Ok(Raw::Value(cur.primary_key().into())),
+            }
+        }
+
+        pub(crate) fn value(&self) -> crate::Result<Raw> {
+            match self {
+                Self::Web(cur) => Ok(cur.value()?.into()),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => Ok(Raw::Value(cur.value()?.into())),
+            }
+        }
+
+        /// Whether the cursor is positioned on a record.
+        pub(crate) fn has_key(&self) -> bool {
+            match self {
+                Self::Web(cur) => match cur.key() {
+                    Ok(k) => !k.is_undefined() && !k.is_null(),
+                    Err(_) => false,
+                },
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => cur.key().is_some(),
+            }
+        }
+
+        /// Whether the cursor still has a request to move it along.
+        pub(crate) fn has_req(&self) -> bool {
+            match self {
+                Self::Web(cur) => {
+                    let req = cur.maybe_req();
+                    !req.is_undefined() && !req.is_null()
+                }
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => cur.key().is_some(),
+            }
+        }
+
+        /// The request that resolves once the cursor has moved. Driver cursors move eagerly, so their requests
+        /// are already complete & resolve to the cursor itself, like a browser request's `result` would.
+        pub(crate) fn req(&self) -> UntypedRequest {
+            match self {
+                Self::Web(cur) => cur.req().into(),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => UntypedRequest::driver(Ok(if cur.key().is_some() {
+                    EventTargetResult::Cursor(Self::Driver(Rc::clone(cur)))
+                } else {
+                    EventTargetResult::Null
+                })),
+            }
+        }
+
+        pub(crate) fn advance(&self, count: u32) -> crate::Result<()> {
+            match self {
+                Self::Web(cur) => cur.advance(count).map_err(Into::into),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => cur.advance(count),
+            }
+        }
+
+        pub(crate) fn continue_(&self) -> crate::Result<()> {
+            match self {
+                Self::Web(cur) => cur.continue_().map_err(Into::into),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => cur.continue_to_key(None),
+            }
+        }
+
+        pub(crate) fn continue_with_key(&self, key: &dyn Encode) -> crate::Result<()> {
+            match self {
+                Self::Web(cur) => cur.continue_with_key(&key.to_js()?).map_err(Into::into),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => cur.continue_to_key(Some(key.to_key()?)),
+            }
+        }
+
+        #[cfg(feature = "indices")]
+        pub(crate) fn continue_primary_key(
+            &self,
+            key: &dyn Encode,
+            primary_key: &dyn Encode,
+        ) -> crate::Result<()> {
+            match self {
+                Self::Web(cur) => cur
+                    .continue_primary_key(&key.to_js()?, &primary_key.to_js()?)
+                    .map_err(Into::into),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => {
+                    cur.continue_to_primary_key(key.to_key()?, primary_key.to_key()?)
+                }
+            }
+        }
+
+        pub(crate) fn update(&self, value: &dyn Encode) -> crate::Result<UntypedRequest> {
+            match self {
+                Self::Web(cur) => Ok(cur.update(&value.to_js()?)?.into()),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => {
+                    Ok(UntypedRequest::driver_value(cur.update(value.to_value()?)))
+                }
+            }
+        }
+
+        pub(crate) fn delete(&self) -> crate::Result<UntypedRequest> {
+            match self {
+                Self::Web(cur) => Ok(cur.delete()?.into()),
+                #[cfg(feature = "backend")]
+                Self::Driver(cur) => Ok(UntypedRequest::driver_value(
+                    cur.delete().map(|()| crate::backend::Value::Undefined),
+                )),
+            }
+        }
+
+        /// Read the key, primary key & value at the cursor's current position.
+        #[cfg(feature = "streams")]
+        pub(crate) fn entry(&self) -> crate::Result<[Raw; 3]> {
+            Ok([self.key()?, self.primary_key()?, self.value()?])
+        }
+    }
+
+    impl From<CursorSys> for CursorRepr {
+        #[inline]
+        fn from(cur: CursorSys) -> Self {
+            Self::Web(cur)
+        }
+    }
+}
@@ -0,0 +1,666 @@
+//! Declarative database schemas.
+//!
+//! A [`Schema`] describes the object stores & indices a database should have. Passing it to
+//! [`OpenDbRequestBuilder::with_schema`](crate::factory::OpenDbRequestBuilder::with_schema) diffs it against the
+//! live database inside the `upgradeneeded` event's version-change transaction and creates, deletes or alters stores
+//! & indices to match.
+//!
+//! Indexed DB has no way of altering an existing object store's key path or key generator; object stores whose
+//! [`key_path`](StoreSchema::with_key_path) or [`auto_increment`](StoreSchema::with_auto_increment) flag changed are
+//! deleted & recreated, **dropping their records**. Altered indices are recreated too, but that doesn't lose any data.
+//!
+//! [`Database::schema_snapshot`](crate::database::Database::schema_snapshot) goes the other way, reading the shape
+//! of an open database into a [`SchemaSnapshot`] that can be logged, [diffed](Schema::diff) or, with the `serde`
+//! feature, serialised.
+//! [`OpenDbRequestBuilder::with_expected_schema`](crate::factory::OpenDbRequestBuilder::with_expected_schema) uses
+//! one to verify a database after opening it, failing with a [list of mismatches](SchemaMismatch) if it doesn't match.
+//!
+//! # Example
+//!
+//! ```
+//! use indexed_db_futures::database::Database;
+//! use indexed_db_futures::schema::{IndexSchema, Schema, StoreSchema};
+//! use indexed_db_futures::prelude::*;
+//!
+//! # async fn example() -> indexed_db_futures::OpenDbResult<()> {
+//! let schema = Schema::new()
+//!     .with_store(
+//!         StoreSchema::new("users")
+//!             .with_key_path("id".into())
+//!             .with_index(IndexSchema::new("email", "email".into()).with_unique(true)),
+//!     )
+//!     .with_store(StoreSchema::new("sessions").with_auto_increment(true));
+//!
+//! let db = Database::open("my_db")
+//!     .with_version(2u8)
+//!     .with_schema(schema)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::KeyPath;
+use internal_macros::generic_bounds;
+use std::fmt::{Display, Formatter};
+
+mod idb_store;
+mod upgrade;
+
+pub use idb_store::IdbStore;
+#[cfg(feature = "derive")]
+pub use indexed_db_futures_macros::IdbStore;
+pub(crate) use upgrade::UpgradeTarget;
+
+/// The desired layout of a database's object stores & indices.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[must_use]
+pub struct Schema {
+    stores: Vec<StoreSchema>,
+}
+
+/// The desired layout of an object store.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[must_use]
+pub struct StoreSchema {
+    name: String,
+    key_path: Option<KeyPath>,
+    auto_increment: bool,
+    indices: Vec<IndexSchema>,
+}
+
+/// The desired layout of an index.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[must_use]
+pub struct IndexSchema {
+    name: String,
+    key_path: KeyPath,
+    unique: bool,
+    multi_entry: bool,
+}
+
+/// The object stores & indices of an open database, as returned by
+/// [`Database::schema_snapshot`](crate::database::Database::schema_snapshot).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemaSnapshot {
+    name: String,
+    version: f64,
+
+    #[cfg_attr(feature = "serde", serde(rename = "stores"))]
+    schema: Schema,
+}
+
+/// A single step needed to bring a database in line with a [`Schema`]. Produced by [`Schema::diff`] in the order
+/// they should be applied.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaChange {
+    /// Delete an object store.
+    DeleteStore(String),
+
+    /// Create an object store without any indices.
+    CreateStore {
+        /// The object store's name.
+        name: String,
+
+        /// The object store's key path.
+        key_path: Option<KeyPath>,
+
+        /// Whether the object store uses a key generator.
+        auto_increment: bool,
+    },
+
+    /// Delete an index.
+    DeleteIndex {
+        /// The name of the object store the index belongs to.
+        store: String,
+
+        /// The index' name.
+        index: String,
+    },
+
+    /// Create an index.
+    CreateIndex {
+        /// The name of the object store the index belongs to.
+        store: String,
+
+        /// The index to create.
+        index: IndexSchema,
+    },
+}
+
+impl Schema {
+    /// Create an empty schema.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an object store to the schema, replacing any previously added store with the same name.
+    pub fn with_store(mut self, store: StoreSchema) -> Self {
+        match self.stores.iter_mut().find(|s| s.name == store.name) {
+            Some(existing) => *existing = store,
+            None => self.stores.push(store),
+        }
+        self
+    }
+
+    /// The schema's object stores in the order they were added.
+    #[inline]
+    pub fn stores(&self) -> &[StoreSchema] {
+        &self.stores
+    }
+
+    /// Get an object store by name.
+    #[must_use]
+    pub fn store(&self, name: &str) -> Option<&StoreSchema> {
+        self.stores.iter().find(move |s| s.name == name)
+    }
+
+    /// Compute the changes that would turn the `live` schema into this one.
+    #[must_use]
+    pub fn diff(&self, live: &Schema) -> Vec<SchemaChange> {
+        let mut changes = live
+            .stores
+            .iter()
+            .filter(|store| self.store(&store.name).is_none())
+            .map(|store| SchemaChange::DeleteStore(store.name.clone()))
+            .collect::<Vec<_>>();
+
+        for store in &self.stores {
+            match live.store(&store.name) {
+                Some(live_store)
+                    if live_store.key_path == store.key_path
+                        && live_store.auto_increment == store.auto_increment =>
+                {
+                    store.diff_indices(live_store, &mut changes);
+                }
+                Some(_) => {
+                    changes.push(SchemaChange::DeleteStore(store.name.clone()));
+                    store.push_create(&mut changes);
+                }
+                None => store.push_create(&mut changes),
+            }
+        }
+
+        changes
+    }
+
+    /// List the ways the `actual` schema differs from this one: stores first in the order they were added, followed by
+    /// stores that only exist in `actual`.
+    #[must_use]
+    pub fn mismatches(&self, actual: &Schema) -> Vec<SchemaMismatch> {
+        let mut out = Vec::new();
+
+        for store in &self.stores {
+            match actual.store(&store.name) {
+                Some(actual_store) => store.push_mismatches(actual_store, &mut out),
+                None => out.push(SchemaMismatch::MissingStore(store.name.clone())),
+            }
+        }
+
+        for store in &actual.stores {
+            if self.store(&store.name).is_none() {
+                out.push(SchemaMismatch::UnexpectedStore(store.name.clone()));
+            }
+        }
+
+        out
+    }
+
+    /// Diff the schema against `target`'s live schema & apply the resulting changes.
+    pub(crate) fn apply_to<T: SchemaTarget>(&self, target: &T) -> Result<(), T::Error> {
+        for change in self.diff(&target.read_schema()?) {
+            target.apply_change(&change)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StoreSchema {
+    /// Describe an object store with the given name, out-of-line keys & no key generator.
+    #[generic_bounds(store_name(N))]
+    pub fn new<N>(name: N) -> Self {
+        Self {
+            name: name.as_ref().into(),
+            key_path: None,
+            auto_increment: false,
+            indices: Vec::new(),
+        }
+    }
+
+    /// Set the object store's
+    /// [key path](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API/Basic_Terminology#key_path).
+    #[generic_bounds(key_path(KP))]
+    pub fn with_key_path<KP>(mut self, key_path: KeyPath<KP>) -> Self {
+        self.key_path = Some(key_path.into_owned());
+        self
+    }
+
+    /// If `true`, the object store has a
+    /// [key generator](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API/Basic_Terminology#key_generator).
+    ///
+    /// Defaults to `false`.
+    #[inline]
+    pub fn with_auto_increment(mut self, auto_increment: bool) -> Self {
+        self.auto_increment = auto_increment;
+        self
+    }
+
+    /// Add an index to the object store, replacing any previously added index with the same name.
+    pub fn with_index(mut self, index: IndexSchema) -> Self {
+        match self.indices.iter_mut().find(|i| i.name == index.name) {
+            Some(existing) => *existing = index,
+            None => self.indices.push(index),
+        }
+        self
+    }
+
+    /// The object store's name.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The object store's key path.
+    #[inline]
+    #[must_use]
+    pub fn key_path(&self) -> Option<&KeyPath> {
+        self.key_path.as_ref()
+    }
+
+    /// Whether the object store uses a key generator.
+    #[inline]
+    #[must_use]
+    pub fn auto_increment(&self) -> bool {
+        self.auto_increment
+    }
+
+    /// The object store's indices in the order they were added.
+    #[inline]
+    pub fn indices(&self) -> &[IndexSchema] {
+        &self.indices
+    }
+
+    /// Get an index by name.
+    #[must_use]
+    pub fn index(&self, name: &str) -> Option<&IndexSchema> {
+        self.indices.iter().find(move |i| i.name == name)
+    }
+
+    fn push_create(&self, changes: &mut Vec<SchemaChange>) {
+        changes.push(SchemaChange::CreateStore {
+            name: self.name.clone(),
+            key_path: self.key_path.clone(),
+            auto_increment: self.auto_increment,
+        });
+        for index in &self.indices {
+            changes.push(self.create_index(index));
+        }
+    }
+
+    fn diff_indices(&self, live: &StoreSchema, changes: &mut Vec<SchemaChange>) {
+        for index in &live.indices {
+            if self.index(&index.name) != Some(index) {
+                changes.push(SchemaChange::DeleteIndex {
+                    store: self.name.clone(),
+                    index: index.name.clone(),
+                });
+            }
+        }
+
+        for index in &self.indices {
+            if live.index(&index.name) != Some(index) {
+                changes.push(self.create_index(index));
+            }
+        }
+    }
+
+    fn push_mismatches(&self, actual: &StoreSchema, out: &mut Vec<SchemaMismatch>) {
+        if self.key_path != actual.key_path {
+            out.push(SchemaMismatch::StoreKeyPath {
+                store: self.name.clone(),
+                expected: self.key_path.clone(),
+                actual: actual.key_path.clone(),
+            });
+        }
+        if self.auto_increment != actual.auto_increment {
+            out.push(SchemaMismatch::StoreAutoIncrement {
+                store: self.name.clone(),
+                expected: self.auto_increment,
+                actual: actual.auto_increment,
+            });
+        }
+
+        for index in &self.indices {
+            match actual.index(&index.name) {
+                Some(actual_index) => index.push_mismatches(&self.name, actual_index, out),
+                None => out.push(SchemaMismatch::MissingIndex {
+                    store: self.name.clone(),
+                    index: index.name.clone(),
+                }),
+            }
+        }
+
+        for index in &actual.indices {
+            if self.index(&index.name).is_none() {
+                out.push(SchemaMismatch::UnexpectedIndex {
+                    store: self.name.clone(),
+                    index: index.name.clone(),
+                });
+            }
+        }
+    }
+
+    fn create_index(&self, index: &IndexSchema) -> SchemaChange {
+        SchemaChange::CreateIndex {
+            store: self.name.clone(),
+            index: index.clone(),
+        }
+    }
+}
+
+impl IndexSchema {
+    /// Describe a non-unique, non-multi-entry index with the given name & key path.
+    #[generic_bounds(index_name(N), key_path(KP))]
+    pub fn new<N, KP>(name: N, key_path: KeyPath<KP>) -> Self {
+        Self {
+            name: name.as_ref().into(),
+            key_path: key_path.into_owned(),
+            unique: false,
+            multi_entry: false,
+        }
+    }
+
+    /// If `true`, the index will not allow duplicate values for a single key.
+    ///
+    /// Defaults to `false`.
+    #[inline]
+    pub fn with_unique(mut self, unique: bool) -> Self {
+        self.unique = unique;
+        self
+    }
+
+    /// If `true`, the index will add an entry for each array element when the key path resolves to an array.
+    ///
+    /// Defaults to `false`.
+    #[inline]
+    pub fn with_multi_entry(mut self, multi_entry: bool) -> Self {
+        self.multi_entry = multi_entry;
+        self
+    }
+
+    /// The index' name.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The index' key path.
+    #[inline]
+    #[must_use]
+    pub fn key_path(&self) -> &KeyPath {
+        &self.key_path
+    }
+
+    /// Whether the index is unique.
+    #[inline]
+    #[must_use]
+    pub fn unique(&self) -> bool {
+        self.unique
+    }
+
+    /// Whether the index is multi-entry.
+    #[inline]
+    #[must_use]
+    pub fn multi_entry(&self) -> bool {
+        self.multi_entry
+    }
+
+    fn push_mismatches(&self, store: &str, actual: &IndexSchema, out: &mut Vec<SchemaMismatch>) {
+        if self.key_path != actual.key_path {
+            out.push(SchemaMismatch::IndexKeyPath {
+                store: store.into(),
+                index: self.name.clone(),
+                expected: self.key_path.clone(),
+                actual: actual.key_path.clone(),
+            });
+        }
+        if self.unique != actual.unique {
+            out.push(SchemaMismatch::IndexUnique {
+                store: store.into(),
+                index: self.name.clone(),
+                expected: self.unique,
+                actual: actual.unique,
+            });
+        }
+        if self.multi_entry != actual.multi_entry {
+            out.push(SchemaMismatch::IndexMultiEntry {
+                store: store.into(),
+                index: self.name.clone(),
+                expected: self.multi_entry,
+                actual: actual.multi_entry,
+            });
+        }
+    }
+}
+
+impl SchemaSnapshot {
+    /// The database's name.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The database's version.
+    #[inline]
+    #[must_use]
+    pub fn version(&self) -> f64 {
+        self.version
+    }
+
+    /// The database's object stores & indices.
+    #[inline]
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Discard the name & version, keeping the object stores & indices.
+    #[inline]
+    pub fn into_schema(self) -> Schema {
+        self.schema
+    }
+}
+
+/// A difference between an expected [`Schema`] & a database's actual one. Produced by [`Schema::mismatches`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaMismatch {
+    /// An expected object store doesn't exist.
+    MissingStore(String),
+
+    /// The database has an object store that isn't in the expected schema.
+    UnexpectedStore(String),
+
+    /// An object store has a different key path.
+    StoreKeyPath {
+        /// The object store's name.
+        store: String,
+
+        /// The expected key path.
+        expected: Option<KeyPath>,
+
+        /// The object store's actual key path.
+        actual: Option<KeyPath>,
+    },
+
+    /// An object store's key generator presence differs.
+    StoreAutoIncrement {
+        /// The object store's name.
+        store: String,
+
+        /// The expected flag.
+        expected: bool,
+
+        /// The object store's actual flag.
+        actual: bool,
+    },
+
+    /// An expected index doesn't exist.
+    MissingIndex {
+        /// The name of the object store the index belongs to.
+        store: String,
+
+        /// The index' name.
+        index: String,
+    },
+
+    /// An object store has an index that isn't in the expected schema.
+    UnexpectedIndex {
+        /// The name of the object store the index belongs to.
+        store: String,
+
+        /// The index' name.
+        index: String,
+    },
+
+    /// An index has a different key path.
+    IndexKeyPath {
+        /// The name of the object store the index belongs to.
+        store: String,
+
+        /// The index' name.
+        index: String,
+
+        /// The expected key path.
+        expected: KeyPath,
+
+        /// The index' actual key path.
+        actual: KeyPath,
+    },
+
+    /// An index' `unique` flag differs.
+    IndexUnique {
+        /// The name of the object store the index belongs to.
+        store: String,
+
+        /// The index' name.
+        index: String,
+
+        /// The expected flag.
+        expected: bool,
+
+        /// The index' actual flag.
+        actual: bool,
+    },
+
+    /// An index' `multiEntry` flag differs.
+    IndexMultiEntry {
+        /// The name of the object store the index belongs to.
+        store: String,
+
+        /// The index' name.
+        index: String,
+
+        /// The expected flag.
+        expected: bool,
+
+        /// The index' actual flag.
+        actual: bool,
+    },
+}
+
+impl Display for SchemaMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingStore(store) => write!(f, "missing object store {store:?}"),
+            Self::UnexpectedStore(store) => write!(f, "unexpected object store {store:?}"),
+            Self::StoreKeyPath {
+                store,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "object store {store:?} has key path {}, expected {}",
+                OptKeyPathDisplay(actual.as_ref()),
+                OptKeyPathDisplay(expected.as_ref()),
+            ),
+            Self::StoreAutoIncrement {
+                store,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "object store {store:?} has autoIncrement {actual}, expected {expected}"
+            ),
+            Self::MissingIndex { store, index } => {
+                write!(f, "missing index {index:?} on object store {store:?}")
+            }
+            Self::UnexpectedIndex { store, index } => {
+                write!(f, "unexpected index {index:?} on object store {store:?}")
+            }
+            Self::IndexKeyPath {
+                store,
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "index {index:?} on object store {store:?} has key path {}, expected {}",
+                OptKeyPathDisplay(Some(actual)),
+                OptKeyPathDisplay(Some(expected)),
+            ),
+            Self::IndexUnique {
+                store,
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "index {index:?} on object store {store:?} has unique {actual}, expected {expected}"
+            ),
+            Self::IndexMultiEntry {
+                store,
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "index {index:?} on object store {store:?} has multiEntry {actual}, expected {expected}"
+            ),
+        }
+    }
+}
+
+struct OptKeyPathDisplay<'a>(Option<&'a KeyPath>);
+
+impl Display for OptKeyPathDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => f.write_str("none"),
+            Some(KeyPath::One(path)) => write!(f, "{path:?}"),
+            Some(KeyPath::Sequence(seq)) => f.debug_list().entries(seq).finish(),
+            Some(KeyPath::JsValue(js)) => write!(f, "{js:?}"),
+        }
+    }
+}
+
+/// A database in the middle of an upgrade that a [`Schema`] can be applied to.
+pub(crate) trait SchemaTarget {
+    type Error;
+
+    /// Read the database's current schema.
+    fn read_schema(&self) -> Result<Schema, Self::Error>;
+
+    /// Apply a single change.
+    fn apply_change(&self, change: &SchemaChange) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,67 @@
+use super::StoreSchema;
+use crate::object_store::ObjectStore;
+use crate::transaction::TransactionRef;
+
+/// A record type that declares the object store it's kept in.
+///
+/// Usually [derived](macro@super::IdbStore) with the `derive` feature:
+///
+/// | Attribute | Placement | Description |
+/// |-----------|-----------|-------------|
+/// | `name = "..."` | struct | The object store's name. Defaults to the struct's name. |
+/// | `auto_increment` | struct | Give the object store a key generator. |
+/// | `key` | field | Make the field part of the object store's key path. Marking several fields produces a compound key path in field order. |
+/// | `index` | field | Index the field. Also accepts `index(name = "...", unique, multi_entry)`; the index is named after the field by default. |
+/// | `rename = "..."` | field | The name of the JS property the field is stored under, if it differs from the field's name. |
+///
+/// Every indexed field also gets a `<field>_index` accessor that gets its index from an object store.
+///
+/// # Example
+///
+/// ```
+/// use indexed_db_futures::database::Database;
+/// use indexed_db_futures::schema::{IdbStore, Schema};
+/// use indexed_db_futures::prelude::*;
+///
+/// #[derive(IdbStore)]
+/// #[idb_store(name = "users")]
+/// struct User {
+///     #[idb_store(key)]
+///     id: u32,
+///
+///     #[idb_store(index(unique))]
+///     email: String,
+///
+///     #[idb_store(index(name = "by_tag", multi_entry))]
+///     tags: Vec<String>,
+/// }
+///
+/// # async fn example() -> indexed_db_futures::OpenDbResult<()> {
+/// let db = Database::open("my_db")
+///     .with_version(1u8)
+///     .with_schema(Schema::new().with_store(User::store_schema()))
+///     .await?;
+///
+/// let tx = db.transaction(User::STORE_NAME).build()?;
+/// let store = User::object_store(&tx)?;
+/// let by_tag = User::tags_index(&store)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait IdbStore {
+    /// The name of the object store records are kept in.
+    const STORE_NAME: &'static str;
+
+    /// The object store's layout, for use in a [`Schema`](super::Schema).
+    fn store_schema() -> StoreSchema;
+
+    /// Get the object store from a transaction.
+    ///
+    /// # Errors
+    ///
+    /// See [`TransactionRef::object_store`].
+    #[inline]
+    fn object_store<'a>(tx: &'a TransactionRef<'_>) -> crate::Result<ObjectStore<'a>> {
+        tx.object_store(Self::STORE_NAME)
+    }
+}
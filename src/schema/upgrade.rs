@@ -0,0 +1,112 @@
+use super::{IndexSchema, Schema, SchemaChange, SchemaSnapshot, SchemaTarget, StoreSchema};
+use crate::database::{Database, VersionChangeEvent};
+use crate::internal_utils::SystemRepr;
+use crate::query_source::internal::QuerySourceInternal;
+use crate::repr::{DatabaseRepr, TransactionRepr};
+use crate::{Build, KeyPath};
+
+/// A [`SchemaTarget`] for the `upgradeneeded` event of an open request.
+pub(crate) struct UpgradeTarget {
+    db: DatabaseRepr,
+    tx: TransactionRepr,
+}
+
+impl UpgradeTarget {
+    pub(crate) fn new(event: &VersionChangeEvent, db: &Database) -> crate::Result<Self> {
+        Ok(Self {
+            db: db.as_sys().clone(),
+            tx: event.upgrade_transaction()?,
+        })
+    }
+}
+
+impl Database {
+    /// Take a [snapshot](SchemaSnapshot) of the database's object stores & indices.
+    ///
+    /// Starts a read-only transaction over every object store, so it can't be called during an upgrade - use the
+    /// [schema](crate::schema) APIs for that.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the transaction can't be started, e.g. if the database connection is closed or an upgrade is in
+    /// progress.
+    pub fn schema_snapshot(&self) -> crate::Result<SchemaSnapshot> {
+        let names = self.as_sys().object_store_names().collect::<Vec<_>>();
+        let schema = if names.is_empty() {
+            Schema::new()
+        } else {
+            let tx = self.transaction(&names[..]).build()?;
+            read_schema(tx.as_sys(), names)?
+        };
+
+        Ok(SchemaSnapshot {
+            name: self.name(),
+            version: self.version(),
+            schema,
+        })
+    }
+}
+
+fn read_schema(tx: &TransactionRepr, names: Vec<String>) -> crate::Result<Schema> {
+    let stores = names
+        .into_iter()
+        .map(move |name| read_store(tx, name))
+        .collect::<crate::Result<_>>()?;
+
+    Ok(Schema { stores })
+}
+
+fn read_store(tx: &TransactionRepr, name: String) -> crate::Result<StoreSchema> {
+    let store = tx.object_store(&name)?;
+    let mut out = StoreSchema::new(name).with_auto_increment(store.auto_increment());
+    out.key_path = store.key_path();
+
+    for name in store.index_names() {
+        let index = store.index(&name)?;
+        let key_path = index.key_path().unwrap_or(KeyPath::One(String::new()));
+
+        out.indices.push(
+            IndexSchema::new(name, key_path)
+                .with_unique(index.unique())
+                .with_multi_entry(index.multi_entry()),
+        );
+    }
+
+    Ok(out)
+}
+
+impl SchemaTarget for UpgradeTarget {
+    type Error = crate::error::Error;
+
+    #[inline]
+    fn read_schema(&self) -> crate::Result<Schema> {
+        read_schema(&self.tx, self.db.object_store_names().collect())
+    }
+
+    fn apply_change(&self, change: &SchemaChange) -> crate::Result<()> {
+        match change {
+            SchemaChange::DeleteStore(name) => self.db.delete_object_store(name)?,
+            SchemaChange::CreateStore {
+                name,
+                key_path,
+                auto_increment,
+            } => {
+                self.db
+                    .create_object_store(name, key_path.clone(), Some(*auto_increment))?;
+            }
+            SchemaChange::DeleteIndex { store, index } => {
+                self.tx.object_store(store)?.delete_index(index)?;
+            }
+            SchemaChange::CreateIndex { store, index } => {
+                self.tx.object_store(store)?.create_index(
+                    &index.name,
+                    index.key_path.clone(),
+                    Some(index.unique),
+                    Some(index.multi_entry),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 //! Serde-specific utilities
 
+#[cfg(feature = "backend")]
+use crate::backend::Value;
+use crate::future::Raw;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_wasm_bindgen::Serializer;
@@ -11,6 +14,29 @@ pub trait SerialiseToJs {
     /// Convert the type to a [`JsValue`], most likely using [`serde_wasm_bindgen`].
     #[allow(clippy::missing_errors_doc)]
     fn serialise_to_js(&self) -> crate::Result<JsValue>;
+
+    /// Convert the type to a [`Value`](crate::backend::Value) for a [driver](crate::backend). Implemented for all
+    /// [`Serialize`] types; the default implementation converts the output of
+    /// [`serialise_to_js`](SerialiseToJs::serialise_to_js) on `wasm32` targets.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the value can't be serialised. The default implementation fails with
+    /// [`NotSupportedError`](crate::error::BackendError::NotSupportedError) outside `wasm32` targets.
+    #[cfg(feature = "backend")]
+    fn serialise_to_value(&self) -> crate::Result<Value> {
+        Value::via_js(|| self.serialise_to_js())
+    }
+
+    /// Convert to a key range for a [driver](crate::backend). Only [`KeyRange`](crate::KeyRange)s produce anything
+    /// other than [`Only`](crate::KeyRange::Only).
+    #[cfg(feature = "backend")]
+    #[doc(hidden)]
+    fn serialise_to_key_range(&self) -> crate::Result<crate::KeyRange<crate::IdbKey>> {
+        self.serialise_to_value()?
+            .into_key()
+            .map(crate::KeyRange::Only)
+    }
 }
 
 /// A type that's convertible from [`JsValue`] using `serde`, but not necessarily
@@ -21,6 +47,26 @@ pub trait DeserialiseFromJs {
     fn deserialise_from_js(js: JsValue) -> crate::Result<Self>
     where
         Self: Sized;
+
+    /// Deserialise a [`Value`](crate::backend::Value) returned by a [driver](crate::backend). Implemented for all
+    /// [`Deserialize`](serde::Deserialize) types; the default implementation converts the value to JS & passes it to
+    /// [`deserialise_from_js`](DeserialiseFromJs::deserialise_from_js) on `wasm32` targets.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the value can't be deserialised. The default implementation fails with
+    /// [`UnexpectedValue`](crate::error::SimpleValueError::UnexpectedValue) outside `wasm32` targets.
+    #[cfg(feature = "backend")]
+    fn deserialise_from_value(value: Value) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        if cfg!(target_arch = "wasm32") {
+            Self::deserialise_from_js(value.to_js())
+        } else {
+            Err(crate::error::SimpleValueError::UnexpectedValue(value).into())
+        }
+    }
 }
 
 impl<T: Serialize> SerialiseToJs for T {
@@ -28,10 +74,31 @@ impl<T: Serialize> SerialiseToJs for T {
         self.serialize(&Serializer::new().serialize_maps_as_objects(true))
             .map_err(Into::into)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn serialise_to_value(&self) -> crate::Result<Value> {
+        Value::from_serde(self)
+    }
 }
 
 impl<T: DeserializeOwned> DeserialiseFromJs for T {
     fn deserialise_from_js(js: JsValue) -> crate::Result<Self> {
         serde_wasm_bindgen::from_value(js).map_err(Into::into)
     }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn deserialise_from_value(value: Value) -> crate::Result<Self> {
+        value.into_serde()
+    }
+}
+
+/// Deserialise a request's result.
+pub(crate) fn deserialise_from_raw<T: DeserialiseFromJs>(raw: Raw) -> crate::Result<T> {
+    match raw {
+        Raw::Js(js) => T::deserialise_from_js(js),
+        #[cfg(feature = "backend")]
+        Raw::Value(value) => T::deserialise_from_value(value),
+    }
 }
@@ -3,6 +3,7 @@
 use crate::database::Database;
 use crate::error::Error;
 use crate::internal_utils::{StructName, SystemRepr};
+use crate::repr::TransactionRepr;
 pub use base::TransactionRef;
 use listeners::TxListeners;
 pub(crate) use options::TransactionOptionsSys;
@@ -61,9 +62,9 @@ macro_rules! map_result {
 }
 
 impl<'a> Transaction<'a> {
-    pub(crate) fn new(db: &'a Database, inner: web_sys::IdbTransaction) -> Self {
+    pub(crate) fn new(db: &'a Database, inner: impl Into<TransactionRepr>) -> Self {
         Self {
-            listeners: TxListeners::new(db, inner),
+            listeners: TxListeners::new(db, inner.into()),
             done: false,
         }
     }
@@ -81,23 +82,37 @@ impl<'a> Transaction<'a> {
         self.done = true;
         self.as_sys().abort()?;
 
-        map_result!(self.listeners.recv().await, ok: Abort, unexpected: Ok => TransactionCommitted)
+        // Driver transactions abort synchronously
+        let Some(res) = self.listeners.recv().await else {
+            return Ok(());
+        };
+        map_result!(res, ok: Abort, unexpected: Ok => TransactionCommitted)
     }
 
     /// Commits all the changes made to objects in the database associated with this transaction.
     #[allow(clippy::missing_errors_doc)]
     pub async fn commit(mut self) -> crate::Result<()> {
         self.done = true;
-        self.as_sys().do_commit()?;
+        self.as_sys().commit()?;
 
-        map_result!(self.listeners.recv().await, ok: Ok, unexpected: Abort => TransactionAborted)
+        // Driver transactions commit synchronously
+        let Some(res) = self.listeners.recv().await else {
+            return Ok(());
+        };
+        map_result!(res, ok: Ok, unexpected: Abort => TransactionAborted)
+    }
+
+    /// Let go of a `versionchange` transaction without aborting it. It'll commit once the upgrade finishes.
+    #[cfg(feature = "migrations")]
+    pub(crate) fn release(mut self) {
+        self.done = true;
     }
 }
 
 #[::sealed::sealed]
 #[allow(unused_qualifications)]
 impl crate::internal_utils::SystemRepr for Transaction<'_> {
-    type Repr = TransactionSys;
+    type Repr = TransactionRepr;
 
     #[inline]
     fn as_sys(&self) -> &Self::Repr {
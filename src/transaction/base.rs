@@ -1,14 +1,14 @@
-use super::{TransactionMode, TransactionSys};
+use super::TransactionMode;
 use crate::database::Database;
 use crate::error::Error;
 use crate::internal_utils::{StructName, SystemRepr};
 use crate::iter::DomStringIter;
 use crate::object_store::ObjectStore;
+use crate::repr::TransactionRepr;
 use accessory::Accessors;
 use internal_macros::errdoc;
 use sealed::sealed;
 use std::fmt::{Debug, Formatter};
-use wasm_bindgen::prelude::*;
 
 /// Reference to a [`Transaction`](super::Transaction) that cannot be explicitly committed or rolled back.
 #[derive(Accessors, StructName)]
@@ -18,38 +18,36 @@ pub struct TransactionRef<'a> {
     db: &'a Database,
 
     #[access(all(vis(pub(super))), get)]
-    transaction: TransactionSys,
+    transaction: TransactionRepr,
 }
 
 impl<'a> TransactionRef<'a> {
-    pub(crate) fn new(db: &'a Database, transaction: web_sys::IdbTransaction) -> Self {
+    pub(crate) fn new(db: &'a Database, transaction: impl Into<TransactionRepr>) -> Self {
         Self {
             db,
-            transaction: transaction.unchecked_into(),
+            transaction: transaction.into(),
         }
     }
 
     /// Get an object store that's part of the transaction.
     #[errdoc(Transaction(NotFoundError, InvalidStateError))]
     #[allow(clippy::missing_errors_doc)]
-    pub fn object_store(&self, name: &str) -> crate::Result<ObjectStore> {
-        match self.as_sys().object_store(name) {
-            Ok(store) => Ok(ObjectStore::new(store, self)),
-            Err(e) => Err(e.into()),
-        }
+    pub fn object_store(&self, name: &str) -> crate::Result<ObjectStore<'_>> {
+        let store = self.as_sys().object_store(name)?;
+        Ok(ObjectStore::new(store, self))
     }
 
     /// Get an iterator of the names of [`IdbObjectStore`](ObjectStore) objects
     /// associated with the transaction.
-    pub fn object_store_names(&self) -> DomStringIter {
-        DomStringIter::new(self.as_sys().object_store_names())
+    pub fn object_store_names(&self) -> DomStringIter<'_> {
+        self.as_sys().object_store_names()
     }
 
     /// The mode for isolating access to data in the object stores that are in the scope of the
     /// transaction.
     #[must_use]
     pub fn mode(&self) -> TransactionMode {
-        self.as_sys().mode().unwrap_or(TransactionMode::Readonly)
+        self.as_sys().mode()
     }
 
     /// # Returns
@@ -57,17 +55,25 @@ impl<'a> TransactionRef<'a> {
     /// or was aborted with `abort()` function.
     #[must_use]
     pub fn error(&self) -> Option<Error> {
-        self.as_sys().error().map(Into::into)
+        self.as_sys().error()
     }
 
     /// Return a Future that resolves when the transaction finishes, successfully or not.
     ///
     /// The primary use case is awaiting a `versionchange` transaction. You must make sure that the transaction hasn't
     /// finished already before you call this fn otherwise the future will never complete.
+    ///
+    /// Not supported by [drivers](crate::backend).
     #[cfg(feature = "tx-done")]
     #[allow(clippy::missing_errors_doc)]
     pub fn on_done(&self) -> crate::Result<super::TransactionDone> {
-        super::TransactionDone::new(self.as_sys().clone())
+        match self.as_sys() {
+            TransactionRepr::Web(tx) => super::TransactionDone::new(tx.clone()),
+            #[cfg(feature = "backend")]
+            TransactionRepr::Driver(_) => {
+                Err(crate::backend::not_supported("Transaction::on_done"))
+            }
+        }
     }
 }
 
@@ -83,7 +89,7 @@ impl Debug for TransactionRef<'_> {
 #[sealed]
 #[allow(unused_qualifications)]
 impl crate::internal_utils::SystemRepr for TransactionRef<'_> {
-    type Repr = TransactionSys;
+    type Repr = TransactionRepr;
 
     #[inline]
     fn as_sys(&self) -> &Self::Repr {
@@ -1,6 +1,7 @@
 use super::{TransactionRef, TransactionResult};
 use crate::database::Database;
 use crate::error::{DomException, SimpleValueError, UnexpectedDataError};
+use crate::repr::TransactionRepr;
 use accessory::Accessors;
 use tokio::sync::mpsc;
 use wasm_bindgen::prelude::*;
@@ -13,7 +14,9 @@ type ErrCb = dyn FnMut(web_sys::Event) + 'static;
 pub(super) struct TxListeners<'a> {
     #[access(get)]
     tx_ref: TransactionRef<'a>,
-    closures: Closures,
+
+    /// `None` for driver transactions, which finish synchronously.
+    closures: Option<Closures>,
 }
 
 pub(super) struct Closures {
@@ -24,8 +27,12 @@ pub(super) struct Closures {
 }
 
 impl<'a> TxListeners<'a> {
-    pub(super) fn new(db: &'a Database, transaction: web_sys::IdbTransaction) -> Self {
-        let closures = Closures::new(&transaction);
+    pub(super) fn new(db: &'a Database, transaction: TransactionRepr) -> Self {
+        let closures = match transaction {
+            TransactionRepr::Web(ref tx) => Some(Closures::new(tx)),
+            #[cfg(feature = "backend")]
+            TransactionRepr::Driver(_) => None,
+        };
 
         Self {
             closures,
@@ -33,18 +40,26 @@ impl<'a> TxListeners<'a> {
         }
     }
 
-    pub(super) async fn recv(&mut self) -> TransactionResult {
-        if let Some(res) = self.closures.rx.recv().await {
-            res
+    /// Wait for the transaction to finish. Resolves to `None` for driver transactions.
+    pub(super) async fn recv(&mut self) -> Option<TransactionResult> {
+        let closures = self.closures.as_mut()?;
+        if let Some(res) = closures.rx.recv().await {
+            Some(res)
         } else {
             unreachable!("Transaction listener channel closed");
         }
     }
 
     pub(super) fn free_listeners(&self) {
-        self.tx_ref().transaction().set_onerror(None);
-        self.tx_ref().transaction().set_oncomplete(None);
-        self.tx_ref().transaction().set_onabort(None);
+        match self.tx_ref().transaction() {
+            TransactionRepr::Web(tx) => {
+                tx.set_onerror(None);
+                tx.set_oncomplete(None);
+                tx.set_onabort(None);
+            }
+            #[cfg(feature = "backend")]
+            TransactionRepr::Driver(_) => {}
+        }
     }
 }
 
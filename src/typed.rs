@@ -0,0 +1,328 @@
+//! Object store & index handles with their key & value types bound once.
+//!
+//! A [`TypedStore`] wraps an [`ObjectStore`], fixing its key type, value type & the [`Codec`] used to convert values
+//! so that call sites no longer need turbofishes or a choice between [`primitive`](crate::BuildPrimitive::primitive)
+//! & [`serde`](crate::BuildSerde::serde). Keys always use [`TryToJs`](crate::primitive::TryToJs) &
+//! [`TryFromJs`](crate::primitive::TryFromJs).
+//!
+//! The codec can also be overridden for an individual request by calling `with_codec` on its builder. See [`Codec`] for
+//! the built-in implementations.
+//!
+//! ```
+//! # use indexed_db_futures::object_store::ObjectStore;
+//! # use indexed_db_futures::typed::TypedStore;
+//! # use indexed_db_futures::typed::Serde;
+//! # use serde::{Deserialize, Serialize};
+//! #
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!   id: u32,
+//!   name: String,
+//! }
+//!
+//! # #[allow(dead_code)]
+//! # async fn example(object_store: ObjectStore<'_>) -> indexed_db_futures::Result<()> {
+//! let users = TypedStore::<u32, User, Serde>::new(object_store);
+//!
+//! users.put(&User { id: 1, name: "Bobby Tables".into() }).await?;
+//! let user: Option<User> = users.get(1).await?;
+//! let count = users.count().with_query(1..10).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::future::VoidRequest;
+use crate::object_store::ObjectStore;
+use crate::KeyRange;
+use internal_macros::errdoc;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+#[cfg(feature = "query")]
+pub(crate) use codec::decode;
+#[cfg(any(feature = "compression", feature = "encryption"))]
+pub use codec::ByteCodec;
+#[cfg(feature = "compression")]
+pub use codec::Compressed;
+#[cfg(feature = "json")]
+pub use codec::Json;
+#[cfg(feature = "postcard")]
+pub use codec::Postcard;
+#[cfg(feature = "serde")]
+pub use codec::Serde;
+pub use codec::{Codec, Primitive};
+#[cfg(feature = "encryption")]
+pub use codec::{Encrypted, EncryptionKey, KeyRing};
+pub use request::{TypedCount, TypedDelete, TypedGet, TypedGetAll, TypedPut};
+
+mod codec;
+mod request;
+
+/// Binds type parameters without affecting auto traits.
+type Marker<T> = PhantomData<fn() -> T>;
+
+iffeat! {
+    #[cfg(feature = "cursors")]
+    mod cursor;
+    pub use cursor::{TypedCursor, TypedCursorBuilder};
+}
+
+/// An [`ObjectStore`] with its keys bound to `K` & values bound to `V`, converted using the `C` [`Codec`].
+pub struct TypedStore<'a, K, V, C = Primitive> {
+    store: ObjectStore<'a>,
+    marker: Marker<(K, V, C)>,
+}
+
+/// An [`Index`](crate::index::Index) with its index keys bound to `IK`, primary keys bound to `K` & values bound to
+/// `V`, converted using the `C` [`Codec`].
+#[cfg(feature = "indices")]
+pub struct TypedIndex<'a, IK, K, V, C = Primitive> {
+    index: crate::index::Index<'a>,
+    marker: Marker<(IK, K, V, C)>,
+}
+
+impl<'a, K, V, C> TypedStore<'a, K, V, C> {
+    /// Wrap the object store.
+    #[inline]
+    #[must_use]
+    pub fn new(store: ObjectStore<'a>) -> Self {
+        Self {
+            store,
+            marker: PhantomData,
+        }
+    }
+
+    /// Switch to a different [`Codec`].
+    #[inline]
+    #[must_use]
+    pub fn with_codec<C2>(self) -> TypedStore<'a, K, V, C2> {
+        TypedStore::new(self.store)
+    }
+
+    /// Get the record matching the given key or the first record in the given key range.
+    #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
+    pub fn get<I>(&self, key: I) -> TypedGet<'_, ObjectStore<'a>, K, V, C>
+    where
+        I: Into<KeyRange<K>>,
+    {
+        TypedGet::new(&self.store, key.into())
+    }
+
+    /// Get all records in the object store.
+    #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
+    #[inline]
+    pub fn get_all(&self) -> TypedGetAll<'_, ObjectStore<'a>, K, V, C> {
+        TypedGetAll::new(&self.store, false)
+    }
+
+    /// Get all keys in the object store.
+    #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
+    #[inline]
+    pub fn get_all_keys(&self) -> TypedGetAll<'_, ObjectStore<'a>, K, K, Primitive> {
+        TypedGetAll::new(&self.store, true)
+    }
+
+    /// Count the number of records in the object store.
+    #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
+    #[inline]
+    pub fn count(&self) -> TypedCount<'_, ObjectStore<'a>, K> {
+        TypedCount::new(&self.store)
+    }
+
+    /// Add the value to the object store. Throws if the computed key already exists - use the
+    /// [`put`](Self::put) method if you want to update the value.
+    ///
+    /// # Returns
+    ///
+    /// A builder that, when built and `await`ed, resolves to the key for the added record.
+    #[errdoc(ObjectStore(
+        ReadOnlyError,
+        TransactionInactiveError,
+        DataErrorAdd,
+        InvalidStateError,
+        DataCloneError,
+        ConstraintError,
+    ))]
+    pub fn add<'b>(&'b self, value: &'b V) -> TypedPut<'b, K, V, C> {
+        TypedPut::new(&self.store, value, false)
+    }
+
+    /// Add the value to the object store. Overwrites the record if the computed key already exists - use the
+    /// [`add`](Self::add) method if you want to throw an error instead.
+    ///
+    /// # Returns
+    ///
+    /// A builder that, when built and `await`ed, resolves to the key for the added record.
+    #[errdoc(ObjectStore(
+        ReadOnlyError,
+        TransactionInactiveError,
+        DataErrorAdd,
+        InvalidStateError,
+        DataCloneError,
+        ConstraintError,
+    ))]
+    pub fn put<'b>(&'b self, value: &'b V) -> TypedPut<'b, K, V, C> {
+        TypedPut::new(&self.store, value, true)
+    }
+
+    /// Delete the record(s) matching the given key or key range.
+    #[errdoc(ObjectStore(
+        ReadOnlyError,
+        TransactionInactiveError,
+        InvalidStateError,
+        DataErrorDelete,
+    ))]
+    pub fn delete<I>(&self, key_range: I) -> TypedDelete<'_, K>
+    where
+        I: Into<KeyRange<K>>,
+    {
+        TypedDelete::new(&self.store, key_range.into())
+    }
+
+    /// Clear all records from the object store.
+    #[errdoc(ObjectStore(ReadOnlyError, TransactionInactiveError))]
+    #[inline]
+    #[allow(clippy::missing_errors_doc)]
+    pub fn clear(&self) -> crate::Result<VoidRequest> {
+        self.store.clear()
+    }
+
+    /// Open a cursor that iterates over the records in the object store.
+    /// Resolves to `None` if the cursor is empty.
+    #[cfg(feature = "cursors")]
+    #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
+    #[inline]
+    pub fn open_cursor(&self) -> TypedCursorBuilder<'_, ObjectStore<'a>, K, K, V, C> {
+        TypedCursorBuilder::new(&self.store)
+    }
+
+    /// Query the records in this object store. See the [`query`](crate::query) module for more information.
+    #[cfg(feature = "query")]
+    #[inline]
+    pub fn query(&self) -> crate::query::Query<'_, V, C> {
+        self.store.query().with_codec()
+    }
+
+    /// Open the index with the given name, binding its keys to `IK`.
+    #[cfg(feature = "indices")]
+    #[errdoc(Index(InvalidStateErrorIndex, NotFoundError))]
+    #[allow(clippy::missing_errors_doc)]
+    pub fn index<IK>(&self, name: &str) -> crate::Result<TypedIndex<'_, IK, K, V, C>> {
+        self.store.index(name).map(TypedIndex::new)
+    }
+
+    /// Reference to the underlying untyped object store.
+    #[inline]
+    #[must_use]
+    pub fn as_inner(&self) -> &ObjectStore<'a> {
+        &self.store
+    }
+
+    /// Unwrap the underlying untyped object store.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> ObjectStore<'a> {
+        self.store
+    }
+}
+
+impl<'a, K, V, C> From<ObjectStore<'a>> for TypedStore<'a, K, V, C> {
+    #[inline]
+    fn from(store: ObjectStore<'a>) -> Self {
+        Self::new(store)
+    }
+}
+
+impl<K, V, C> Debug for TypedStore<'_, K, V, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedStore").field(&self.store).finish()
+    }
+}
+
+#[cfg(feature = "indices")]
+impl<'a, IK, K, V, C> TypedIndex<'a, IK, K, V, C> {
+    /// Wrap the index.
+    #[inline]
+    #[must_use]
+    pub fn new(index: crate::index::Index<'a>) -> Self {
+        Self {
+            index,
+            marker: PhantomData,
+        }
+    }
+
+    /// Switch to a different [`Codec`].
+    #[inline]
+    #[must_use]
+    pub fn with_codec<C2>(self) -> TypedIndex<'a, IK, K, V, C2> {
+        TypedIndex::new(self.index)
+    }
+
+    /// Get the first record matching the given index key or key range.
+    #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
+    pub fn get<I>(&self, key: I) -> TypedGet<'_, crate::index::Index<'a>, IK, V, C>
+    where
+        I: Into<KeyRange<IK>>,
+    {
+        TypedGet::new(&self.index, key.into())
+    }
+
+    /// Get all records in the index.
+    #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
+    #[inline]
+    pub fn get_all(&self) -> TypedGetAll<'_, crate::index::Index<'a>, IK, V, C> {
+        TypedGetAll::new(&self.index, false)
+    }
+
+    /// Get the primary keys of all records in the index.
+    #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
+    #[inline]
+    pub fn get_all_keys(&self) -> TypedGetAll<'_, crate::index::Index<'a>, IK, K, Primitive> {
+        TypedGetAll::new(&self.index, true)
+    }
+
+    /// Count the number of records in the index.
+    #[errdoc(QuerySource(InvalidStateError, TransactionInactiveError, DataError))]
+    #[inline]
+    pub fn count(&self) -> TypedCount<'_, crate::index::Index<'a>, IK> {
+        TypedCount::new(&self.index)
+    }
+
+    /// Open a cursor that iterates over the records in the index.
+    /// Resolves to `None` if the cursor is empty.
+    #[cfg(feature = "cursors")]
+    #[errdoc(Cursor(TransactionInactiveError, DataErrorOpen, InvalidStateErrorOpen))]
+    #[inline]
+    pub fn open_cursor(&self) -> TypedCursorBuilder<'_, crate::index::Index<'a>, IK, K, V, C> {
+        TypedCursorBuilder::new(&self.index)
+    }
+
+    /// Reference to the underlying untyped index.
+    #[inline]
+    #[must_use]
+    pub fn as_inner(&self) -> &crate::index::Index<'a> {
+        &self.index
+    }
+
+    /// Unwrap the underlying untyped index.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> crate::index::Index<'a> {
+        self.index
+    }
+}
+
+#[cfg(feature = "indices")]
+impl<'a, IK, K, V, C> From<crate::index::Index<'a>> for TypedIndex<'a, IK, K, V, C> {
+    #[inline]
+    fn from(index: crate::index::Index<'a>) -> Self {
+        Self::new(index)
+    }
+}
+
+#[cfg(feature = "indices")]
+impl<IK, K, V, C> Debug for TypedIndex<'_, IK, K, V, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedIndex").field(&self.index).finish()
+    }
+}
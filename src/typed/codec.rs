@@ -0,0 +1,215 @@
+#[cfg(feature = "backend")]
+use crate::backend::Value;
+use crate::future::Raw;
+use crate::internal_utils::Encode;
+use crate::primitive::{TryFromJs, TryToJs};
+use std::marker::PhantomData;
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "compression")]
+pub use compressed::Compressed;
+#[cfg(feature = "encryption")]
+pub use encrypted::{Encrypted, EncryptionKey, KeyRing};
+#[cfg(feature = "json")]
+pub use json::Json;
+#[cfg(feature = "postcard")]
+pub use postcard::Postcard;
+
+#[cfg(feature = "compression")]
+mod compressed;
+#[cfg(feature = "encryption")]
+mod encrypted;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "postcard")]
+mod postcard;
+#[cfg(any(feature = "compression", feature = "encryption"))]
+mod raw;
+
+/// How a [typed store](super::TypedStore) converts values of type `T` to & from JS.
+///
+/// Built-in implementations:
+///
+/// | Codec | Stored as | Feature |
+/// |-------|-----------|---------|
+/// | [`Primitive`] | Whatever [`TryToJs`] produces | - |
+/// | [`Serde`] | A structured-cloneable JS value via [`serde_wasm_bindgen`] | `serde` |
+/// | [`Json`] | A JSON string via [`serde_json`] | `json` |
+/// | [`Postcard`] | A `Uint8Array` via [`postcard`](::postcard) | `postcard` |
+/// | [`Compressed`] | The wrapped codec's output, LZ4-compressed into a `Uint8Array` | `compression` |
+/// | [`Encrypted`] | The wrapped codec's output, AES-GCM-encrypted into a `Uint8Array` | `encryption` |
+///
+/// Binary codecs such as [`Postcard`] are considerably faster than the structured-clone path for large records that
+/// only ever get read back by Rust, but their records can't be used in key paths or indices.
+///
+/// Custom codecs can report errors using [`CodecError`](crate::error::CodecError).
+pub trait Codec<T> {
+    /// Convert the value to JS.
+    #[allow(clippy::missing_errors_doc)]
+    fn encode(value: &T) -> crate::Result<JsValue>;
+
+    /// Convert the value from JS.
+    #[allow(clippy::missing_errors_doc)]
+    fn decode(js: JsValue) -> crate::Result<T>;
+
+    /// Convert the value to a [`Value`](crate::backend::Value) for a [driver](crate::backend). The default
+    /// implementation converts the output of [`encode`](Codec::encode) on `wasm32` targets.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the value can't be encoded. The default implementation fails with
+    /// [`NotSupportedError`](crate::error::BackendError::NotSupportedError) outside `wasm32` targets.
+    #[cfg(feature = "backend")]
+    fn encode_value(value: &T) -> crate::Result<Value> {
+        Value::via_js(|| Self::encode(value))
+    }
+
+    /// Convert a [`Value`](crate::backend::Value) returned by a [driver](crate::backend). The default
+    /// implementation converts the value to JS & passes it to [`decode`](Codec::decode) on `wasm32` targets.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the value can't be decoded. The default implementation fails with
+    /// [`UnexpectedValue`](crate::error::SimpleValueError::UnexpectedValue) outside `wasm32` targets.
+    #[cfg(feature = "backend")]
+    fn decode_value(value: Value) -> crate::Result<T> {
+        if cfg!(target_arch = "wasm32") {
+            Self::decode(value.to_js())
+        } else {
+            Err(crate::error::SimpleValueError::UnexpectedValue(value).into())
+        }
+    }
+}
+
+/// A [`Codec`] whose output is always a string or a `Uint8Array`, which is what codecs that post-process bytes, such as
+/// [`Compressed`], require of the codec they wrap.
+///
+/// Implemented by [`Json`], [`Postcard`] & the wrapping codecs themselves. The structured-clone codecs, [`Primitive`] &
+/// [`Serde`], produce arbitrary JS values, so they can't be wrapped:
+///
+/// ```compile_fail
+/// # use indexed_db_futures::typed::{Codec, Compressed, Primitive};
+/// # use wasm_bindgen::JsValue;
+/// fn encode(value: &u32) -> indexed_db_futures::Result<JsValue> {
+///     <Compressed<Primitive> as Codec<u32>>::encode(value)
+/// }
+/// ```
+///
+/// A custom codec implementing this trait while producing anything else makes the wrapping codec fail with a
+/// [`CodecError`](crate::error::CodecError).
+#[cfg(any(feature = "compression", feature = "encryption"))]
+pub trait ByteCodec {}
+
+/// A [`Codec`] using [`TryToJs`] & [`TryFromJs`]; the typed equivalent of calling
+/// [`primitive`](crate::BuildPrimitive::primitive) on a builder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Primitive;
+
+/// A [`Codec`] using [`serde_wasm_bindgen`] to produce structured-cloneable JS values; the typed equivalent of calling
+/// [`serde`](crate::BuildSerde::serde) on a builder.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Serde;
+
+impl<T: TryToJs + TryFromJs> Codec<T> for Primitive {
+    #[inline]
+    fn encode(value: &T) -> crate::Result<JsValue> {
+        value.try_to_js()
+    }
+
+    #[inline]
+    fn decode(js: JsValue) -> crate::Result<T> {
+        T::from_js(js).map_err(Into::into)
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn encode_value(value: &T) -> crate::Result<Value> {
+        value.try_to_value()
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn decode_value(value: Value) -> crate::Result<T> {
+        T::from_value(value).map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Codec<T> for Serde
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    #[inline]
+    fn encode(value: &T) -> crate::Result<JsValue> {
+        crate::serde::SerialiseToJs::serialise_to_js(value)
+    }
+
+    #[inline]
+    fn decode(js: JsValue) -> crate::Result<T> {
+        crate::serde::DeserialiseFromJs::deserialise_from_js(js)
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn encode_value(value: &T) -> crate::Result<Value> {
+        Value::from_serde(value)
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn decode_value(value: Value) -> crate::Result<T> {
+        value.into_serde()
+    }
+}
+
+/// [`Encode`]s a value with the `C` codec.
+pub(super) struct Encoded<'a, C, T>(&'a T, PhantomData<C>);
+
+impl<'a, C, T> Encoded<'a, C, T> {
+    pub(super) fn new(value: &'a T) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+impl<C: Codec<T>, T> Encode for Encoded<'_, C, T> {
+    #[inline]
+    fn to_js(&self) -> crate::Result<JsValue> {
+        C::encode(self.0)
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn to_value(&self) -> crate::Result<Value> {
+        C::encode_value(self.0)
+    }
+
+    #[cfg(feature = "backend")]
+    fn to_key_range(&self) -> crate::Result<crate::KeyRange<crate::IdbKey>> {
+        self.to_key().map(crate::KeyRange::Only)
+    }
+}
+
+/// Decode a request's result.
+pub(crate) fn decode<C: Codec<T>, T>(raw: Raw) -> crate::Result<T> {
+    match raw {
+        Raw::Js(js) => C::decode(js),
+        #[cfg(feature = "backend")]
+        Raw::Value(value) => C::decode_value(value),
+    }
+}
+
+/// Decode a possibly-missing value.
+pub(super) fn decode_opt<C: Codec<T>, T>(raw: Raw) -> crate::Result<Option<T>> {
+    match raw {
+        Raw::Js(ref js) if js.is_undefined() || js.is_null() => Ok(None),
+        #[cfg(feature = "backend")]
+        Raw::Value(Value::Undefined | Value::Null) => Ok(None),
+        raw => decode::<C, T>(raw).map(Some),
+    }
+}
+
+/// Decode an array of values.
+pub(super) fn decode_vec<C: Codec<T>, T>(raw: Raw) -> crate::Result<Vec<T>> {
+    crate::iter::ArrayMapIter::from_raw(raw, decode::<C, T>)?.collect()
+}
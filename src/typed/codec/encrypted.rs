@@ -0,0 +1,184 @@
+use super::raw::Output;
+use super::{ByteCodec, Codec};
+#[cfg(feature = "backend")]
+use crate::backend::Value;
+use crate::error::CodecError;
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use wasm_bindgen::prelude::*;
+
+/// Marks a record as written by [`Encrypted`].
+const MAGIC: [u8; 3] = [0, b'i', b'e'];
+
+/// Magic, format byte & big-endian key version.
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4;
+
+/// AES-GCM nonce length.
+const NONCE_LEN: usize = 12;
+
+/// Name used in [`CodecError`]s.
+const NAME: &str = "encryption";
+
+/// A 256-bit AES key.
+pub type EncryptionKey = [u8; 32];
+
+/// Supplies the keys used by an [`Encrypted`] codec.
+///
+/// Every key is identified by a version that gets stored alongside each record, so keys can be rotated by bumping
+/// [`current_version`](Self::current_version) while keeping older versions available through [`key`](Self::key)
+/// until all records encrypted with them have been rewritten.
+///
+/// ```
+/// # use indexed_db_futures::typed::{EncryptionKey, KeyRing};
+/// # use std::cell::RefCell;
+/// # use std::collections::HashMap;
+/// #
+/// thread_local! {
+///   static KEYS: RefCell<HashMap<u32, EncryptionKey>> = RefCell::default();
+/// }
+///
+/// struct AppKeys;
+///
+/// impl KeyRing for AppKeys {
+///   fn current_version() -> u32 {
+///     KEYS.with_borrow(|keys| keys.keys().copied().max().unwrap_or_default())
+///   }
+///
+///   fn key(version: u32) -> Option<EncryptionKey> {
+///     KEYS.with_borrow(|keys| keys.get(&version).copied())
+///   }
+/// }
+/// ```
+pub trait KeyRing {
+    /// The version of the key to encrypt new records with.
+    fn current_version() -> u32;
+
+    /// Get the key with the given version.
+    fn key(version: u32) -> Option<EncryptionKey>;
+}
+
+/// A [`Codec`] that encrypts the output of the `C` codec with AES-256-GCM, using keys supplied by `K`, & stores it as a
+/// [`Uint8Array`](crate::typed_array::Uint8Array).
+///
+/// Every record gets a fresh random nonce from
+/// [`crypto.getRandomValues`](https://developer.mozilla.org/en-US/docs/Web/API/Crypto/getRandomValues) and a header
+/// holding the [key version](KeyRing::current_version) it was encrypted with; the header is authenticated along with
+/// the value.
+///
+/// The inner codec must be a [`ByteCodec`], i.e. produce strings or `Uint8Array`s, such as [`Json`](super::Json) &
+/// [`Postcard`](super::Postcard) do. Unlike [`Compressed`](super::Compressed), records that weren't written by this
+/// codec are rejected rather than passed to the inner codec, so plaintext can't be substituted for encrypted data.
+pub struct Encrypted<C, K>(PhantomData<fn() -> (C, K)>);
+
+impl<C, K> ByteCodec for Encrypted<C, K> {}
+
+impl<T, C, K> Codec<T> for Encrypted<C, K>
+where
+    C: Codec<T> + ByteCodec,
+    K: KeyRing,
+{
+    #[inline]
+    fn encode(value: &T) -> crate::Result<JsValue> {
+        encrypt::<_, K>(C::encode(value)?)
+    }
+
+    #[inline]
+    fn decode(js: JsValue) -> crate::Result<T> {
+        C::decode(decrypt::<_, K>(&js)?)
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn encode_value(value: &T) -> crate::Result<Value> {
+        encrypt::<_, K>(C::encode_value(value)?)
+    }
+
+    #[cfg(feature = "backend")]
+    #[inline]
+    fn decode_value(value: Value) -> crate::Result<T> {
+        C::decode_value(decrypt::<_, K>(&value)?)
+    }
+}
+
+fn encrypt<O: Output, K: KeyRing>(inner: O) -> crate::Result<O> {
+    let (format, raw) = inner.into_raw(NAME)?;
+
+    let version = K::current_version();
+    let cipher = cipher::<K>(version)?;
+
+    let mut out = Vec::with_capacity(HEADER_LEN + NONCE_LEN + raw.len() + 16);
+    out.extend_from_slice(&MAGIC);
+    out.push(format);
+    out.extend_from_slice(&version.to_be_bytes());
+
+    let mut nonce = [0; NONCE_LEN];
+    random_bytes(&mut nonce)?;
+
+    let payload = Payload {
+        msg: &raw,
+        aad: &out,
+    };
+    let Ok(ciphertext) = cipher.encrypt(Nonce::from_slice(&nonce), payload) else {
+        return Err(CodecError::new(NAME, "encryption failed").into());
+    };
+
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+
+    O::from_bytes(out)
+}
+
+fn decrypt<O: Output, K: KeyRing>(output: &O) -> crate::Result<O> {
+    let Some(bytes) = output.strip_header(&MAGIC)? else {
+        return Err(CodecError::new(NAME, "the record is not encrypted").into());
+    };
+    if bytes.len() < HEADER_LEN - MAGIC.len() + NONCE_LEN {
+        return Err(CodecError::new(NAME, "truncated record").into());
+    }
+
+    let format = bytes[0];
+    let version = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+    let (nonce, ciphertext) = bytes[5..].split_at(NONCE_LEN);
+
+    let mut aad = [0; HEADER_LEN];
+    aad[..MAGIC.len()].copy_from_slice(&MAGIC);
+    aad[MAGIC.len()..].copy_from_slice(&bytes[..5]);
+
+    let payload = Payload {
+        msg: ciphertext,
+        aad: &aad,
+    };
+    let Ok(raw) = cipher::<K>(version)?.decrypt(Nonce::from_slice(nonce), payload) else {
+        return Err(CodecError::new(NAME, "decryption failed").into());
+    };
+
+    O::from_raw(NAME, format, raw)
+}
+
+fn cipher<K: KeyRing>(version: u32) -> crate::Result<Aes256Gcm> {
+    match K::key(version) {
+        Some(key) => Ok(Aes256Gcm::new(&key.into())),
+        None => Err(CodecError::new(NAME, format!("missing key version {version}")).into()),
+    }
+}
+
+fn random_bytes(buf: &mut [u8]) -> crate::Result<()> {
+    if !cfg!(target_arch = "wasm32") {
+        let msg = "crypto.getRandomValues is only available on wasm32 targets";
+        return Err(CodecError::new(NAME, msg).into());
+    }
+
+    let crypto = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("crypto"))?;
+    crypto
+        .unchecked_into::<web_sys::Crypto>()
+        .get_random_values_with_u8_array(buf)?;
+    Ok(())
+}
+
+impl<C, K> Debug for Encrypted<C, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Encrypted")
+    }
+}
@@ -0,0 +1,52 @@
+use super::Codec;
+#[cfg(feature = "backend")]
+use crate::backend::Value;
+use crate::error::{CodecError, SimpleValueError};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// A [`Codec`] storing values as JSON strings via [`serde_json`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Json;
+
+impl Json {
+    const NAME: &'static str = "JSON";
+}
+
+#[cfg(any(feature = "compression", feature = "encryption"))]
+impl super::ByteCodec for Json {}
+
+impl<T: Serialize + DeserializeOwned> Codec<T> for Json {
+    fn encode(value: &T) -> crate::Result<JsValue> {
+        match serde_json::to_string(value) {
+            Ok(json) => Ok(JsValue::from(json)),
+            Err(e) => Err(CodecError::new(Self::NAME, e).into()),
+        }
+    }
+
+    fn decode(js: JsValue) -> crate::Result<T> {
+        let Some(json) = js.as_string() else {
+            return Err(SimpleValueError::NotAString(js).into());
+        };
+
+        serde_json::from_str(&json).map_err(|e| CodecError::new(Self::NAME, e).into())
+    }
+
+    #[cfg(feature = "backend")]
+    fn encode_value(value: &T) -> crate::Result<Value> {
+        match serde_json::to_string(value) {
+            Ok(json) => Ok(Value::String(json)),
+            Err(e) => Err(CodecError::new(Self::NAME, e).into()),
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    fn decode_value(value: Value) -> crate::Result<T> {
+        let Value::String(json) = value else {
+            return Err(SimpleValueError::UnexpectedValue(value).into());
+        };
+
+        serde_json::from_str(&json).map_err(|e| CodecError::new(Self::NAME, e).into())
+    }
+}
@@ -0,0 +1,54 @@
+use super::Codec;
+#[cfg(feature = "backend")]
+use crate::backend::Value;
+use crate::error::{CodecError, SimpleValueError};
+use js_sys::Uint8Array;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// A [`Codec`] storing values as [`postcard`]-encoded `Uint8Array`s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Postcard;
+
+impl Postcard {
+    const NAME: &'static str = "postcard";
+}
+
+#[cfg(any(feature = "compression", feature = "encryption"))]
+impl super::ByteCodec for Postcard {}
+
+impl<T: Serialize + DeserializeOwned> Codec<T> for Postcard {
+    fn encode(value: &T) -> crate::Result<JsValue> {
+        match postcard::to_allocvec(value) {
+            Ok(bytes) => Ok(Uint8Array::from(bytes.as_slice()).into()),
+            Err(e) => Err(CodecError::new(Self::NAME, e).into()),
+        }
+    }
+
+    fn decode(js: JsValue) -> crate::Result<T> {
+        let bytes = match js.dyn_into::<Uint8Array>() {
+            Ok(arr) => arr.to_vec(),
+            Err(js) => return Err(SimpleValueError::DynCast(js).into()),
+        };
+
+        postcard::from_bytes(&bytes).map_err(|e| CodecError::new(Self::NAME, e).into())
+    }
+
+    #[cfg(feature = "backend")]
+    fn encode_value(value: &T) -> crate::Result<Value> {
+        match postcard::to_allocvec(value) {
+            Ok(bytes) => Ok(Value::Binary(bytes)),
+            Err(e) => Err(CodecError::new(Self::NAME, e).into()),
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    fn decode_value(value: Value) -> crate::Result<T> {
+        let Value::Binary(bytes) = value else {
+            return Err(SimpleValueError::UnexpectedValue(value).into());
+        };
+
+        postcard::from_bytes(&bytes).map_err(|e| CodecError::new(Self::NAME, e).into())
+    }
+}
@@ -0,0 +1,121 @@
+//! Byte-level access to the output of codecs that produce strings or `Uint8Array`s.
+
+#[cfg(feature = "backend")]
+use crate::backend::Value;
+use crate::error::CodecError;
+use crate::primitive::{TryFromJs, TryToJs};
+use crate::typed_array::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+/// The codec produced a `Uint8Array`.
+const FORMAT_BYTES: u8 = 0;
+
+/// The codec produced a string.
+const FORMAT_STRING: u8 = 1;
+
+/// A codec's output: JS for browsers or a [`Value`](crate::backend::Value) for [drivers](crate::backend).
+pub(super) trait Output: Sized {
+    /// Convert a codec's output into a format byte & the raw bytes. Errors if it's neither a string nor binary.
+    fn into_raw(self, codec: &'static str) -> crate::Result<(u8, Vec<u8>)>;
+
+    /// Wrap bytes.
+    fn from_bytes(bytes: Vec<u8>) -> crate::Result<Self>;
+
+    /// The bytes if the output is binary.
+    fn to_bytes(&self) -> crate::Result<Option<Vec<u8>>>;
+
+    /// Reverse of [`into_raw`](Output::into_raw).
+    fn from_raw(codec: &'static str, format: u8, raw: Vec<u8>) -> crate::Result<Self> {
+        match format {
+            FORMAT_BYTES => Self::from_bytes(raw),
+            FORMAT_STRING => match String::from_utf8(raw) {
+                Ok(string) => Ok(Self::from_string(string)),
+                Err(e) => Err(CodecError::new(codec, e).into()),
+            },
+            other => {
+                let msg = format!("unknown record format: {other}");
+                Err(CodecError::new(codec, msg).into())
+            }
+        }
+    }
+
+    /// Wrap a string.
+    fn from_string(string: String) -> Self;
+
+    /// If the output is binary & starts with the given header, return the bytes following it.
+    fn strip_header(&self, header: &[u8]) -> crate::Result<Option<Vec<u8>>> {
+        let Some(mut bytes) = self.to_bytes()? else {
+            return Ok(None);
+        };
+
+        if bytes.starts_with(header) {
+            bytes.drain(..header.len());
+            Ok(Some(bytes))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn unsupported_output(codec: &'static str) -> crate::error::Error {
+    let msg = "the inner codec must produce strings or Uint8Arrays";
+    CodecError::new(codec, msg).into()
+}
+
+impl Output for JsValue {
+    fn into_raw(self, codec: &'static str) -> crate::Result<(u8, Vec<u8>)> {
+        if let Some(string) = self.as_string() {
+            Ok((FORMAT_STRING, string.into_bytes()))
+        } else if self.is_instance_of::<js_sys::Uint8Array>() {
+            Ok((FORMAT_BYTES, Uint8Array::from_js(self)?.into()))
+        } else {
+            Err(unsupported_output(codec))
+        }
+    }
+
+    #[inline]
+    fn from_bytes(bytes: Vec<u8>) -> crate::Result<Self> {
+        Uint8Array::from(bytes).try_to_js()
+    }
+
+    fn to_bytes(&self) -> crate::Result<Option<Vec<u8>>> {
+        if self.is_instance_of::<js_sys::Uint8Array>() {
+            Ok(Some(Uint8Array::from_js(self.clone())?.into()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline]
+    fn from_string(string: String) -> Self {
+        Self::from(string)
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Output for Value {
+    fn into_raw(self, codec: &'static str) -> crate::Result<(u8, Vec<u8>)> {
+        match self {
+            Self::String(string) => Ok((FORMAT_STRING, string.into_bytes())),
+            Self::Binary(bytes) => Ok((FORMAT_BYTES, bytes)),
+            _ => Err(unsupported_output(codec)),
+        }
+    }
+
+    #[inline]
+    fn from_bytes(bytes: Vec<u8>) -> crate::Result<Self> {
+        Ok(Self::Binary(bytes))
+    }
+
+    fn to_bytes(&self) -> crate::Result<Option<Vec<u8>>> {
+        match self {
+            Self::Binary(bytes) => Ok(Some(bytes.clone())),
+            _ => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn from_string(string: String) -> Self {
+        Self::String(string)
+    }
+}
@@ -0,0 +1,197 @@
+use super::codec::{decode, Codec, Encoded};
+use super::Marker;
+use crate::cursor::{BaseCursor, Cursor, CursorDirection};
+use crate::future::{CursorNextRequest, CursorRequest, VoidRequest};
+use crate::internal_utils::{Encode, SystemRepr};
+use crate::primitive::{TryFromJs, TryToJs};
+use crate::query_source::internal::QuerySourceInternal;
+use crate::repr::CursorRepr;
+use crate::KeyRange;
+use fancy_constructor::new;
+use internal_macros::{errdoc, BuildIntoFut};
+use sealed::sealed;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// Builder for [`TypedStore::open_cursor`](super::TypedStore::open_cursor) &
+/// [`TypedIndex::open_cursor`](super::TypedIndex::open_cursor).
+#[derive(new, BuildIntoFut)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct TypedCursorBuilder<'a, Qs, QK, K, V, C> {
+    query_source: &'a Qs,
+
+    #[new(val(None))]
+    query: Option<KeyRange<QK>>,
+
+    #[new(val(CursorDirection::Next))]
+    direction: CursorDirection,
+
+    #[new(val(PhantomData))]
+    marker: Marker<(K, V, C)>,
+}
+
+/// A [`Cursor`] whose keys, primary keys & values have been bound to `QK`, `K` & `V` respectively. Values are
+/// converted using the `C` [`Codec`].
+pub struct TypedCursor<'a, Qs, QK, K, V, C> {
+    cursor: Cursor<'a, Qs>,
+    marker: Marker<(QK, K, V, C)>,
+}
+
+impl<'a, Qs, QK, K, V, C> TypedCursorBuilder<'a, Qs, QK, K, V, C> {
+    /// Set the key or key range to be queried.
+    pub fn with_query<I: Into<KeyRange<QK>>>(mut self, query: I) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Decode values using a different [`Codec`] than the one the handle was created with.
+    pub fn with_codec<C2>(self) -> TypedCursorBuilder<'a, Qs, QK, K, V, C2> {
+        TypedCursorBuilder {
+            query_source: self.query_source,
+            query: self.query,
+            direction: self.direction,
+            marker: PhantomData,
+        }
+    }
+
+    /// Set the direction of the cursor. Defaults to [`Next`](CursorDirection::Next).
+    #[inline]
+    pub fn with_direction(mut self, direction: CursorDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+}
+
+#[sealed]
+impl<'a, Qs, Sys, QK, K, V, C> crate::Build for TypedCursorBuilder<'a, Qs, QK, K, V, C>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    KeyRange<QK>: TryToJs,
+{
+    type Ok = CursorRequest<'a, TypedCursor<'a, Qs, QK, K, V, C>, Qs>;
+    type Err = crate::error::Error;
+
+    fn build(self) -> crate::Result<Self::Ok> {
+        let query = self.query.as_ref().map(|query| query as &dyn Encode);
+        let req = self
+            .query_source
+            .as_sys()
+            .open_cursor(query, Some(self.direction), false)?;
+
+        Ok(CursorRequest::new(req, self.query_source))
+    }
+}
+
+impl<'a, Qs, QK, K, V, C> TypedCursor<'a, Qs, QK, K, V, C> {
+    /// Get the key at the cursor's current position: the primary key when iterating over an object store and the
+    /// index key when iterating over an index.
+    ///
+    /// Returns `None` if the cursor's iterated past its end.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn key(&self) -> crate::Result<Option<QK>>
+    where
+        Option<QK>: TryFromJs,
+    {
+        self.cursor.key()
+    }
+
+    /// Get the primary key at the cursor's current position.
+    ///
+    /// Returns `None` if the cursor's iterated past its end.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn primary_key(&self) -> crate::Result<Option<K>>
+    where
+        Option<K>: TryFromJs,
+    {
+        self.cursor.primary_key()
+    }
+
+    /// Get the next record in the cursor.
+    ///
+    /// See [`Cursor::next_record`].
+    #[errdoc(Cursor(TransactionInactiveError, InvalidStateError))]
+    #[inline]
+    pub fn next_record(&mut self) -> CursorNextRequest<'_, V>
+    where
+        C: Codec<V>,
+    {
+        CursorNextRequest::new(self.cursor.as_mut(), BaseCursor::value_sys, decode::<C, V>)
+    }
+
+    /// Overwrite the value at the cursor's current position.
+    ///
+    /// See [`Cursor::update`].
+    #[errdoc(Cursor(
+        TransactionInactiveError,
+        ReadOnlyError,
+        InvalidStateError,
+        DataErrorUpdate,
+        DataCloneError,
+    ))]
+    #[allow(clippy::missing_errors_doc)]
+    pub fn update(&self, value: &V) -> crate::Result<VoidRequest>
+    where
+        C: Codec<V>,
+    {
+        let req = self.cursor.as_sys().update(&Encoded::<C, V>::new(value))?;
+        Ok(VoidRequest::new(req))
+    }
+
+    /// Delete the record at the cursor's current position.
+    ///
+    /// See [`Cursor::delete`].
+    #[errdoc(Cursor(TransactionInactiveError, ReadOnlyError, InvalidStateError))]
+    #[inline]
+    #[allow(clippy::missing_errors_doc)]
+    pub fn delete(&mut self) -> crate::Result<VoidRequest> {
+        self.cursor.delete()
+    }
+
+    /// The direction of the cursor.
+    #[inline]
+    #[must_use]
+    pub fn direction(&self) -> CursorDirection {
+        self.cursor.direction()
+    }
+
+    /// Convert this cursor into a stream of values.
+    #[cfg(feature = "streams")]
+    #[must_use]
+    pub fn stream(self) -> crate::cursor::Stream<Cursor<'a, Qs>, V>
+    where
+        C: Codec<V>,
+    {
+        crate::cursor::Stream::new_with(self.cursor, |cursor| decode::<C, V>(cursor.value_sys()?))
+    }
+
+    /// Unwrap the underlying untyped cursor.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> Cursor<'a, Qs> {
+        self.cursor
+    }
+}
+
+#[sealed]
+impl<'a, Qs, QK, K, V, C> crate::future::cursor::CursorKind<'a, Qs>
+    for TypedCursor<'a, Qs, QK, K, V, C>
+{
+    #[inline]
+    fn construct(repr: CursorRepr, query_source: &'a Qs) -> Self {
+        Self {
+            cursor: Cursor::new(repr, query_source),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Qs, QK, K, V, C> Debug for TypedCursor<'_, Qs, QK, K, V, C>
+where
+    Qs: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedCursor").field(&self.cursor).finish()
+    }
+}
@@ -0,0 +1,242 @@
+use super::codec::{decode, decode_opt, decode_vec, Codec, Encoded, Primitive};
+use super::Marker;
+use crate::future::{BasicRequest, VoidRequest};
+use crate::internal_utils::Encode;
+use crate::internal_utils::SystemRepr;
+use crate::object_store::ObjectStore;
+use crate::primitive::{TryFromJs, TryFromJsExt, TryToJs};
+use crate::query_source::internal::QuerySourceInternal;
+use crate::KeyRange;
+use fancy_constructor::new;
+use internal_macros::BuildIntoFut;
+use sealed::sealed;
+use std::marker::PhantomData;
+
+/// Builder for [`TypedStore::get`](super::TypedStore::get) & [`TypedIndex::get`](super::TypedIndex::get).
+#[derive(new, BuildIntoFut)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct TypedGet<'a, Qs, QK, V, C> {
+    query_source: &'a Qs,
+    key: KeyRange<QK>,
+
+    #[new(val(PhantomData))]
+    marker: Marker<(V, C)>,
+}
+
+/// Builder for [`TypedStore::get_all`](super::TypedStore::get_all),
+/// [`TypedStore::get_all_keys`](super::TypedStore::get_all_keys) & their [`TypedIndex`](super::TypedIndex)
+/// equivalents.
+///
+/// Retrieves all the keys/records in the index or object store if no [query](Self::with_query) is provided.
+#[derive(new, BuildIntoFut)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct TypedGetAll<'a, Qs, QK, T, C> {
+    query_source: &'a Qs,
+    keys: bool,
+
+    #[new(val(None))]
+    query: Option<KeyRange<QK>>,
+
+    #[new(val(None))]
+    limit: Option<u32>,
+
+    #[new(val(PhantomData))]
+    marker: Marker<(T, C)>,
+}
+
+/// Builder for [`TypedStore::count`](super::TypedStore::count) & [`TypedIndex::count`](super::TypedIndex::count).
+#[derive(new, BuildIntoFut)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct TypedCount<'a, Qs, QK> {
+    query_source: &'a Qs,
+
+    #[new(val(None))]
+    query: Option<KeyRange<QK>>,
+}
+
+/// Builder for [`TypedStore::add`](super::TypedStore::add) & [`TypedStore::put`](super::TypedStore::put).
+#[derive(new, BuildIntoFut)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct TypedPut<'a, K, V, C> {
+    store: &'a ObjectStore<'a>,
+    value: &'a V,
+    overwrite: bool,
+
+    #[new(val(None))]
+    key: Option<K>,
+
+    #[new(val(PhantomData))]
+    marker: Marker<C>,
+}
+
+/// Builder for [`TypedStore::delete`](super::TypedStore::delete).
+#[derive(new, BuildIntoFut)]
+#[new(vis(pub(super)))]
+#[must_use]
+pub struct TypedDelete<'a, K> {
+    store: &'a ObjectStore<'a>,
+    key: KeyRange<K>,
+}
+
+impl<'a, Qs, QK, V, C> TypedGet<'a, Qs, QK, V, C> {
+    /// Decode the record using a different [`Codec`] than the one the handle was created with.
+    pub fn with_codec<C2>(self) -> TypedGet<'a, Qs, QK, V, C2> {
+        TypedGet::new(self.query_source, self.key)
+    }
+}
+
+impl<'a, Qs, QK, T, C> TypedGetAll<'a, Qs, QK, T, C> {
+    /// Decode the results using a different [`Codec`] than the one the handle was created with.
+    pub fn with_codec<C2>(self) -> TypedGetAll<'a, Qs, QK, T, C2> {
+        TypedGetAll {
+            query_source: self.query_source,
+            keys: self.keys,
+            query: self.query,
+            limit: self.limit,
+            marker: PhantomData,
+        }
+    }
+
+    /// Set the key or key range to be queried.
+    pub fn with_query<I: Into<KeyRange<QK>>>(mut self, query: I) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Set the maximum number of results to return.
+    #[inline]
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<Qs, QK> TypedCount<'_, Qs, QK> {
+    /// Set the key or key range to be counted.
+    pub fn with_query<I: Into<KeyRange<QK>>>(mut self, query: I) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+}
+
+impl<'a, K, V, C> TypedPut<'a, K, V, C> {
+    /// Set the record's key. Only applicable to object stores with out-of-line keys.
+    #[inline]
+    pub fn with_key(mut self, key: K) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Encode the value using a different [`Codec`] than the one the handle was created with.
+    pub fn with_codec<C2>(self) -> TypedPut<'a, K, V, C2> {
+        TypedPut {
+            store: self.store,
+            value: self.value,
+            overwrite: self.overwrite,
+            key: self.key,
+            marker: PhantomData,
+        }
+    }
+}
+
+#[sealed]
+impl<Qs, Sys, QK, V, C> crate::Build for TypedGet<'_, Qs, QK, V, C>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    KeyRange<QK>: TryToJs,
+    C: Codec<V>,
+{
+    type Ok = BasicRequest<Option<V>>;
+    type Err = crate::error::Error;
+
+    fn build(self) -> crate::Result<Self::Ok> {
+        let req = self.query_source.as_sys().get(&self.key)?;
+
+        Ok(BasicRequest::new(req, decode_opt::<C, V>))
+    }
+}
+
+#[sealed]
+impl<Qs, Sys, QK, T, C> crate::Build for TypedGetAll<'_, Qs, QK, T, C>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    KeyRange<QK>: TryToJs,
+    C: Codec<T>,
+{
+    type Ok = BasicRequest<Vec<T>>;
+    type Err = crate::error::Error;
+
+    fn build(self) -> crate::Result<Self::Ok> {
+        let sys = self.query_source.as_sys();
+        let query = self.query.as_ref().map(|query| query as &dyn Encode);
+
+        let req = if self.keys {
+            sys.get_all_keys(query, self.limit)
+        } else {
+            sys.get_all(query, self.limit)
+        }?;
+
+        Ok(BasicRequest::new(req, decode_vec::<C, T>))
+    }
+}
+
+#[sealed]
+impl<Qs, Sys, QK> crate::Build for TypedCount<'_, Qs, QK>
+where
+    Qs: SystemRepr<Repr = Sys>,
+    Sys: QuerySourceInternal,
+    KeyRange<QK>: TryToJs,
+{
+    type Ok = BasicRequest<u32>;
+    type Err = crate::error::Error;
+
+    fn build(self) -> crate::Result<Self::Ok> {
+        let query = self.query.as_ref().map(|query| query as &dyn Encode);
+        let req = self.query_source.as_sys().count(query)?;
+
+        Ok(BasicRequest::new(req, decode::<Primitive, u32>))
+    }
+}
+
+#[sealed]
+impl<K, V, C> crate::Build for TypedPut<'_, K, V, C>
+where
+    K: TryToJs + TryFromJs,
+    C: Codec<V>,
+{
+    type Ok = BasicRequest<K>;
+    type Err = crate::error::Error;
+
+    fn build(self) -> crate::Result<Self::Ok> {
+        let value = Encoded::<C, V>::new(self.value);
+        let key = self.key.as_ref().map(|key| key as &dyn Encode);
+        let sys = self.store.as_sys();
+        let req = if self.overwrite {
+            sys.put(&value, key)
+        } else {
+            sys.add(&value, key)
+        }?;
+
+        Ok(BasicRequest::new(req, K::from_raw))
+    }
+}
+
+#[sealed]
+impl<K> crate::Build for TypedDelete<'_, K>
+where
+    KeyRange<K>: TryToJs,
+{
+    type Ok = VoidRequest;
+    type Err = crate::error::Error;
+
+    fn build(self) -> crate::Result<Self::Ok> {
+        let req = self.store.as_sys().delete(&self.key)?;
+        Ok(VoidRequest::new(req))
+    }
+}
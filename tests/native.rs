@@ -0,0 +1,35 @@
+//! Tests that don't need a JS environment and run under a plain `cargo test`.
+
+#![cfg(not(target_arch = "wasm32"))]
+#![allow(clippy::uninlined_format_args)]
+
+#[cfg(feature = "memory")]
+#[path = "native/memory.rs"]
+mod memory;
+
+#[cfg(all(feature = "backend", feature = "memory"))]
+#[path = "native/backend.rs"]
+mod backend;
+
+#[cfg(feature = "schema")]
+#[path = "native/schema.rs"]
+mod schema;
+
+#[cfg(feature = "migrations")]
+#[path = "native/migration.rs"]
+mod migration;
+
+#[cfg(feature = "derive")]
+#[path = "native/derive.rs"]
+mod derive;
+
+#[path = "native/key_range.rs"]
+mod key_range;
+
+#[cfg(feature = "cursors")]
+#[path = "native/page_token.rs"]
+mod page_token;
+
+#[cfg(all(feature = "cursors", feature = "streams"))]
+#[path = "native/merge.rs"]
+mod merge;
@@ -0,0 +1,320 @@
+//! Drivers plugged in underneath the regular API.
+
+use futures::executor::block_on;
+use indexed_db_futures::backend::*;
+use indexed_db_futures::cursor::CursorDirection;
+use indexed_db_futures::error::{BackendError, Error};
+use indexed_db_futures::factory::DBFactory;
+use indexed_db_futures::memory::MemoryFactory;
+use indexed_db_futures::prelude::*;
+use indexed_db_futures::transaction::TransactionMode;
+use indexed_db_futures::{IdbKey, KeyPath, KeyRange, Result};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const STORE: &str = "store";
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// Wraps the in-memory driver, logging every call that reaches it.
+#[derive(Debug)]
+struct Logged<T> {
+    inner: T,
+    log: Log,
+}
+
+impl<T> Logged<T> {
+    fn wrap<U>(&self, inner: U) -> Logged<U> {
+        Logged {
+            inner,
+            log: Rc::clone(&self.log),
+        }
+    }
+
+    fn record(&self, call: impl Into<String>) {
+        self.log.borrow_mut().push(call.into());
+    }
+}
+
+impl FactoryDriver for Logged<MemoryFactory> {
+    fn open(
+        &self,
+        name: &str,
+        version: Option<u32>,
+        on_upgrade: Option<UpgradeHandler>,
+    ) -> DriverFuture<Rc<dyn DatabaseDriver>> {
+        self.record(format!("open {name}"));
+        let log = Rc::clone(&self.log);
+        let fut = self.inner.open(name, version, on_upgrade);
+
+        Box::pin(async move {
+            let inner = fut.await?;
+            Ok(Rc::new(Logged { inner, log }) as Rc<dyn DatabaseDriver>)
+        })
+    }
+
+    fn delete(&self, name: &str) -> DriverFuture<()> {
+        self.record(format!("delete {name}"));
+        self.inner.delete(name)
+    }
+}
+
+impl DatabaseDriver for Logged<Rc<dyn DatabaseDriver>> {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn version(&self) -> u32 {
+        self.inner.version()
+    }
+
+    fn object_store_names(&self) -> Vec<String> {
+        self.inner.object_store_names()
+    }
+
+    fn create_object_store(
+        &self,
+        name: &str,
+        key_path: Option<KeyPath>,
+        auto_increment: bool,
+    ) -> Result<Rc<dyn StoreDriver>> {
+        self.inner
+            .create_object_store(name, key_path, auto_increment)
+    }
+
+    fn delete_object_store(&self, name: &str) -> Result<()> {
+        self.inner.delete_object_store(name)
+    }
+
+    fn transaction(
+        &self,
+        store_names: &[String],
+        mode: TransactionMode,
+    ) -> Result<Rc<dyn TransactionDriver>> {
+        self.record(format!("transaction {store_names:?} {mode:?}"));
+        let tx = self.inner.transaction(store_names, mode)?;
+        Ok(Rc::new(self.wrap(tx)))
+    }
+
+    fn close(&self) {
+        self.inner.close();
+    }
+}
+
+impl TransactionDriver for Logged<Rc<dyn TransactionDriver>> {
+    fn mode(&self) -> TransactionMode {
+        self.inner.mode()
+    }
+
+    fn object_store_names(&self) -> Vec<String> {
+        self.inner.object_store_names()
+    }
+
+    fn object_store(&self, name: &str) -> Result<Rc<dyn StoreDriver>> {
+        let store = self.inner.object_store(name)?;
+        Ok(Rc::new(self.wrap(store)))
+    }
+
+    fn commit(&self) -> Result<()> {
+        self.record("commit");
+        self.inner.commit()
+    }
+
+    fn abort(&self) -> Result<()> {
+        self.record("abort");
+        self.inner.abort()
+    }
+}
+
+impl QueryDriver for Logged<Rc<dyn StoreDriver>> {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn set_name(&self, name: &str) -> Result<()> {
+        self.inner.set_name(name)
+    }
+
+    fn key_path(&self) -> Option<KeyPath> {
+        self.inner.key_path()
+    }
+
+    fn count(&self, query: Option<KeyRange<IdbKey>>) -> Result<u32> {
+        self.record("count");
+        self.inner.count(query)
+    }
+
+    fn get(&self, query: KeyRange<IdbKey>) -> Result<Option<Value>> {
+        self.record(format!("get {query:?}"));
+        self.inner.get(query)
+    }
+
+    fn get_key(&self, query: KeyRange<IdbKey>) -> Result<Option<IdbKey>> {
+        self.record("get_key");
+        self.inner.get_key(query)
+    }
+
+    fn get_all(&self, query: Option<KeyRange<IdbKey>>, limit: Option<u32>) -> Result<Vec<Value>> {
+        self.record(format!("get_all {limit:?}"));
+        self.inner.get_all(query, limit)
+    }
+
+    fn get_all_keys(
+        &self,
+        query: Option<KeyRange<IdbKey>>,
+        limit: Option<u32>,
+    ) -> Result<Vec<IdbKey>> {
+        self.record("get_all_keys");
+        self.inner.get_all_keys(query, limit)
+    }
+
+    fn open_cursor(
+        &self,
+        query: Option<KeyRange<IdbKey>>,
+        direction: CursorDirection,
+        key_only: bool,
+    ) -> Result<Option<Rc<dyn CursorDriver>>> {
+        self.record(format!("open_cursor {direction:?}"));
+        self.inner.open_cursor(query, direction, key_only)
+    }
+}
+
+impl StoreDriver for Logged<Rc<dyn StoreDriver>> {
+    fn auto_increment(&self) -> bool {
+        self.inner.auto_increment()
+    }
+
+    fn index_names(&self) -> Vec<String> {
+        self.inner.index_names()
+    }
+
+    fn index(&self, name: &str) -> Result<Rc<dyn IndexDriver>> {
+        self.inner.index(name)
+    }
+
+    fn create_index(
+        &self,
+        name: &str,
+        key_path: KeyPath,
+        unique: bool,
+        multi_entry: bool,
+    ) -> Result<Rc<dyn IndexDriver>> {
+        self.inner.create_index(name, key_path, unique, multi_entry)
+    }
+
+    fn delete_index(&self, name: &str) -> Result<()> {
+        self.inner.delete_index(name)
+    }
+
+    fn add(&self, value: Value, key: Option<IdbKey>) -> Result<IdbKey> {
+        self.record(format!("add {value:?} {key:?}"));
+        self.inner.add(value, key)
+    }
+
+    fn put(&self, value: Value, key: Option<IdbKey>) -> Result<IdbKey> {
+        self.record(format!("put {value:?} {key:?}"));
+        self.inner.put(value, key)
+    }
+
+    fn delete(&self, query: KeyRange<IdbKey>) -> Result<()> {
+        self.record("delete");
+        self.inner.delete(query)
+    }
+
+    fn clear(&self) -> Result<()> {
+        self.record("clear");
+        self.inner.clear()
+    }
+}
+
+fn logged_factory() -> (DBFactory, Log) {
+    let log = Log::default();
+    let driver = Logged {
+        inner: MemoryFactory::new(),
+        log: Rc::clone(&log),
+    };
+    (DBFactory::from_driver(driver), log)
+}
+
+#[test]
+fn builders_dispatch_to_the_driver() {
+    let (factory, log) = logged_factory();
+
+    block_on(async {
+        let db = factory
+            .open_db("db")
+            .with_version(1u32)
+            .with_on_upgrade_needed(|_, db| {
+                db.create_object_store(STORE).build()?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let tx = db
+            .transaction(STORE)
+            .with_mode(TransactionMode::Readwrite)
+            .build()
+            .unwrap();
+        let store = tx.object_store(STORE).unwrap();
+        store.put("a").with_key(1u32).await.unwrap();
+        store.add("b").with_key(2u32).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let tx = db.transaction(STORE).build().unwrap();
+        let store = tx.object_store(STORE).unwrap();
+        assert_eq!(store.get(2u32).await, Ok(Some(String::from("b"))));
+
+        let all = store.get_all::<String>().with_limit(5).await.unwrap();
+        assert_eq!(
+            all.collect::<Result<Vec<_>>>(),
+            Ok(vec!["a".into(), "b".into()])
+        );
+
+        let mut cursor = store
+            .open_cursor()
+            .with_direction(CursorDirection::Prev)
+            .await
+            .unwrap()
+            .expect("cursor");
+        assert_eq!(cursor.next_record().await, Ok(Some(String::from("b"))));
+    });
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "open db",
+            "transaction [\"store\"] Readwrite",
+            "put String(\"a\") Some(Number(1.0))",
+            "add String(\"b\") Some(Number(2.0))",
+            "commit",
+            "transaction [\"store\"] Readonly",
+            "get Only(Number(2.0))",
+            "get_all Some(5)",
+            "open_cursor Prev",
+            // Dropping a transaction without committing it rolls it back
+            "abort",
+        ]
+    );
+}
+
+#[test]
+fn driver_errors_surface_through_builders() {
+    let (factory, log) = logged_factory();
+
+    block_on(async {
+        let db = factory.open_db("db").await.unwrap();
+        let err = db.transaction(STORE).build().unwrap_err();
+        assert!(
+            matches!(err, Error::Backend(BackendError::NotFoundError(_))),
+            "{err:?}"
+        );
+
+        factory.delete_db("db").unwrap().await.unwrap();
+    });
+
+    assert_eq!(
+        *log.borrow(),
+        ["open db", "transaction [\"store\"] Readonly", "delete db"]
+    );
+}
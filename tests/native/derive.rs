@@ -0,0 +1,105 @@
+use indexed_db_futures::primitive::{CompoundKey, TryFromJs, TryToJs};
+use indexed_db_futures::schema::{IdbStore, IndexSchema, StoreSchema};
+use indexed_db_futures::KeyPath;
+
+#[derive(IdbStore)]
+#[idb_store(name = "users", auto_increment)]
+#[allow(dead_code)]
+pub struct User {
+    #[idb_store(key)]
+    id: u32,
+
+    #[idb_store(index(unique), rename = "emailAddress")]
+    email: String,
+
+    #[idb_store(index(name = "by_tag", multi_entry))]
+    tags: Vec<String>,
+
+    #[idb_store(index)]
+    r#type: u8,
+
+    name: String,
+}
+
+#[derive(IdbStore)]
+#[allow(dead_code)]
+struct Membership<T> {
+    #[idb_store(key)]
+    org: String,
+
+    #[idb_store(key)]
+    user: u32,
+
+    data: T,
+}
+
+#[derive(CompoundKey)]
+#[allow(dead_code)]
+struct EventKey {
+    user: u32,
+    timestamp: f64,
+}
+
+#[derive(CompoundKey)]
+#[allow(dead_code)]
+struct Pair<T>(String, T);
+
+#[test]
+fn store_schema() {
+    assert_eq!(User::STORE_NAME, "users");
+    assert_eq!(
+        User::store_schema(),
+        StoreSchema::new("users")
+            .with_key_path("id".into())
+            .with_auto_increment(true)
+            .with_index(IndexSchema::new("emailAddress", "emailAddress".into()).with_unique(true))
+            .with_index(IndexSchema::new("by_tag", "tags".into()).with_multi_entry(true))
+            .with_index(IndexSchema::new("type", "type".into()))
+    );
+}
+
+#[test]
+fn compound_key_path() {
+    assert_eq!(Membership::<()>::STORE_NAME, "Membership");
+    assert_eq!(
+        Membership::<()>::store_schema().key_path(),
+        Some(&KeyPath::from(["org", "user"]).into_owned())
+    );
+    assert!(Membership::<()>::store_schema().indices().is_empty());
+}
+
+#[test]
+fn index_accessors() {
+    let _ = [User::email_index, User::tags_index, User::type_index];
+}
+
+#[test]
+fn compound_key_conversions() {
+    fn assert_key<T: TryToJs + TryFromJs>() {}
+
+    assert_key::<EventKey>();
+    assert_key::<Pair<u8>>();
+    assert_key::<Pair<(u8, String)>>();
+}
+
+#[cfg(feature = "memory")]
+#[test]
+fn memory_schema() {
+    use indexed_db_futures::factory::DBFactory;
+    use indexed_db_futures::schema::Schema;
+    use std::future::IntoFuture;
+
+    let schema = Schema::new()
+        .with_store(User::store_schema())
+        .with_store(Membership::<()>::store_schema());
+    let open = DBFactory::memory()
+        .open_db("db")
+        .with_schema(schema.clone())
+        .into_future();
+    let db = futures::executor::block_on(open).unwrap();
+
+    assert_eq!(
+        db.schema_snapshot().unwrap().schema().mismatches(&schema),
+        []
+    );
+}
@@ -0,0 +1,202 @@
+use indexed_db_futures::{IdbKey, KeyRange};
+
+fn bounds(range: KeyRange<IdbKey>) -> (IdbKey, Option<IdbKey>) {
+    match range {
+        KeyRange::Bound(lower, false, upper, true) => (lower, Some(upper)),
+        KeyRange::LowerBound(lower, false) => (lower, None),
+        other => panic!("Unexpected range: {other:?}"),
+    }
+}
+
+fn contains(range: &(IdbKey, Option<IdbKey>), key: &IdbKey) -> bool {
+    *key >= range.0 && range.1.as_ref().map_or(true, |upper| key < upper)
+}
+
+#[test]
+fn string_prefix() {
+    let range = bounds(KeyRange::string_prefix("user:42:"));
+    assert_eq!(range.1, Some(IdbKey::from("user:42;")));
+
+    for key in ["user:42:", "user:42:\u{FFFF}", "user:42:\u{10FFFF}x"] {
+        assert!(contains(&range, &key.into()), "{key:?}");
+    }
+    for key in ["user:42", "user:42;", "user:43:"] {
+        assert!(!contains(&range, &key.into()), "{key:?}");
+    }
+}
+
+#[test]
+fn string_prefix_end() {
+    let cases = [
+        ("a\u{FFFF}", IdbKey::from("b")),
+        ("a\u{D7FF}", IdbKey::from("a\u{10000}")),
+        ("\u{1F600}", IdbKey::from("\u{1F601}")),
+        ("\u{1F3FF}", IdbKey::from("\u{1F400}")),
+        ("\u{10FFFF}", IdbKey::from("\u{E000}")),
+        ("\u{FFFF}", IdbKey::Binary(Vec::new())),
+        ("", IdbKey::Binary(Vec::new())),
+    ];
+
+    for (prefix, expected) in cases {
+        let range = bounds(KeyRange::string_prefix(prefix));
+        assert_eq!(range.1.as_ref(), Some(&expected), "{prefix:?}");
+
+        let next = format!("{prefix}\u{10FFFF}");
+        assert!(contains(&range, &next.as_str().into()), "{prefix:?}");
+    }
+}
+
+#[test]
+fn binary_prefix() {
+    let range = bounds(KeyRange::binary_prefix(&[1, 0xFF]));
+    assert_eq!(range.1, Some(IdbKey::Binary(vec![2])));
+    assert!(contains(&range, &IdbKey::Binary(vec![1, 0xFF, 0xFF])));
+    assert!(!contains(&range, &IdbKey::Binary(vec![1, 0xFE, 0xFF])));
+
+    let range = bounds(KeyRange::binary_prefix(&[0xFF]));
+    assert_eq!(range.1, Some(IdbKey::Array(Vec::new())));
+}
+
+#[test]
+fn array_prefix() {
+    let range = bounds(KeyRange::array_prefix([42]));
+
+    let inside = [
+        IdbKey::from([IdbKey::from(42)]),
+        IdbKey::from([IdbKey::from(42), IdbKey::from("x")]),
+        IdbKey::from([IdbKey::from(42), IdbKey::from([IdbKey::from(1)])]),
+    ];
+    for key in &inside {
+        assert!(contains(&range, key), "{key:?}");
+    }
+
+    let outside = [
+        IdbKey::from([IdbKey::from(41.999_999)]),
+        IdbKey::from([IdbKey::from(42.000_000_1)]),
+        IdbKey::from([IdbKey::from("42")]),
+    ];
+    for key in &outside {
+        assert!(!contains(&range, key), "{key:?}");
+    }
+
+    let range = bounds(KeyRange::array_prefix([
+        IdbKey::from("a"),
+        IdbKey::Number(f64::INFINITY),
+    ]));
+    assert!(contains(
+        &range,
+        &IdbKey::from([
+            IdbKey::from("a"),
+            IdbKey::Number(f64::INFINITY),
+            IdbKey::from(1)
+        ])
+    ));
+    assert!(!contains(
+        &range,
+        &IdbKey::from([IdbKey::from("a"), IdbKey::Date(-8.64e15)])
+    ));
+
+    assert_eq!(
+        KeyRange::array_prefix(Vec::<IdbKey>::new()),
+        KeyRange::LowerBound(IdbKey::Array(Vec::new()), false)
+    );
+}
+
+#[test]
+fn std_range_bounds() {
+    assert_eq!(KeyRange::from(..5), KeyRange::UpperBound(5, true));
+    assert_eq!(KeyRange::from(..=5), KeyRange::UpperBound(5, false));
+    assert_eq!(KeyRange::from(1..5), KeyRange::Bound(1, false, 5, true));
+    assert_eq!(KeyRange::from(1..=5), KeyRange::Bound(1, false, 5, false));
+    assert_eq!(KeyRange::from(1..), KeyRange::LowerBound(1, false));
+}
+
+fn num(n: i32) -> IdbKey {
+    IdbKey::from(n)
+}
+
+#[test]
+fn range_contains() {
+    let range = KeyRange::from(num(1)..num(5));
+    assert!(range.contains(&num(1)));
+    assert!(range.contains(&IdbKey::from(4.9)));
+    assert!(!range.contains(&num(5)));
+    assert!(!range.contains(&IdbKey::from("1")));
+
+    assert!(KeyRange::Only(num(1)).contains(&IdbKey::from(1.0)));
+    assert!(KeyRange::from(..=num(1)).contains(&num(1)));
+    assert!(!KeyRange::from(..num(1)).contains(&num(1)));
+    assert!(KeyRange::LowerBound(num(1), true).contains(&IdbKey::from("a")));
+}
+
+#[test]
+fn is_empty() {
+    assert!(KeyRange::Bound(num(2), false, num(1), false).is_empty());
+    assert!(KeyRange::Bound(num(1), true, num(1), false).is_empty());
+    assert!(!KeyRange::Bound(num(1), false, num(1), false).is_empty());
+    assert!(!KeyRange::UpperBound(num(1), true).is_empty());
+}
+
+#[test]
+fn intersect() {
+    let a = KeyRange::from(num(1)..num(5));
+
+    assert_eq!(
+        a.intersect(&KeyRange::from(num(3)..=num(7))),
+        Some(KeyRange::Bound(num(3), false, num(5), true))
+    );
+    assert_eq!(
+        a.intersect(&KeyRange::LowerBound(num(1), true)),
+        Some(KeyRange::Bound(num(1), true, num(5), true))
+    );
+    assert_eq!(
+        a.intersect(&KeyRange::from(..=num(1))),
+        Some(KeyRange::Only(num(1)))
+    );
+    assert_eq!(a.intersect(&KeyRange::from(num(5)..)), None);
+    assert_eq!(
+        KeyRange::from(num(3)..).intersect(&KeyRange::from(..num(4))),
+        Some(KeyRange::Bound(num(3), false, num(4), true))
+    );
+}
+
+#[test]
+fn union() {
+    let a = KeyRange::from(num(1)..num(3));
+
+    assert_eq!(
+        a.union(&KeyRange::from(num(3)..=num(4))),
+        vec![KeyRange::Bound(num(1), false, num(4), false)]
+    );
+    assert_eq!(
+        a.union(&KeyRange::LowerBound(num(3), true)),
+        vec![a.clone(), KeyRange::LowerBound(num(3), true)]
+    );
+    assert_eq!(
+        a.union(&KeyRange::from(..num(2))),
+        vec![KeyRange::UpperBound(num(3), true)]
+    );
+    assert_eq!(
+        KeyRange::from(..=num(1)).union(&KeyRange::from(num(1)..)),
+        vec![KeyRange::LowerBound(
+            IdbKey::Number(f64::NEG_INFINITY),
+            false
+        )]
+    );
+
+    let all = KeyRange::union_all([
+        KeyRange::from(num(10)..num(20)),
+        KeyRange::Only(IdbKey::from("a")),
+        KeyRange::Bound(num(9), false, num(8), false),
+        KeyRange::from(num(0)..num(5)),
+        KeyRange::from(num(4)..=num(6)),
+    ]);
+    assert_eq!(
+        all,
+        vec![
+            KeyRange::Bound(num(0), false, num(6), false),
+            KeyRange::Bound(num(10), false, num(20), true),
+            KeyRange::Only(IdbKey::from("a")),
+        ]
+    );
+}
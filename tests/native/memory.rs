@@ -0,0 +1,1053 @@
+use futures::executor::block_on;
+use indexed_db_futures::backend::Value;
+use indexed_db_futures::cursor::CursorDirection;
+use indexed_db_futures::database::Database;
+use indexed_db_futures::error::{BackendError, Error, OpenDbError};
+use indexed_db_futures::factory::DBFactory;
+use indexed_db_futures::prelude::*;
+use indexed_db_futures::transaction::TransactionMode;
+use indexed_db_futures::{IdbKey as Key, KeyPath, KeyRange};
+
+const STORE: &str = "store";
+
+fn user(id: u32, name: &str, tags: &[&str]) -> Value {
+    Value::object([
+        ("id", Value::from(id)),
+        ("name", Value::from(name)),
+        (
+            "tags",
+            Value::Array(tags.iter().map(|&t| Value::from(t)).collect()),
+        ),
+    ])
+}
+
+/// Unwrap the driver error out of an error returned by the regular API.
+fn backend_error<E: Into<OpenDbError>>(err: E) -> BackendError {
+    match err.into() {
+        OpenDbError::Base(Error::Backend(err)) => err,
+        other => panic!("Not a driver error: {other:?}"),
+    }
+}
+
+macro_rules! assert_backend_err {
+    ($res: expr, $variant: ident) => {{
+        let err = backend_error($res.unwrap_err());
+        assert!(matches!(err, BackendError::$variant(_)), "{err:?}");
+    }};
+}
+
+fn store_names(db: &Database) -> Vec<String> {
+    db.object_store_names().collect()
+}
+
+/// A database with an in-line keyed store with a unique `name` & multi-entry `tags` index.
+async fn users_db() -> Database {
+    DBFactory::memory()
+        .open_db("users")
+        .with_on_upgrade_needed(|_, db| {
+            let store = db
+                .create_object_store(STORE)
+                .with_key_path("id".into())
+                .build()?;
+            store
+                .create_index("name", "name".into())
+                .with_unique(true)
+                .build()?;
+            store
+                .create_index("tags", "tags".into())
+                .with_multi_entry(true)
+                .build()?;
+            Ok(())
+        })
+        .await
+        .expect("open")
+}
+
+async fn seed_users(db: &Database) {
+    let tx = db
+        .transaction(STORE)
+        .with_mode(TransactionMode::Readwrite)
+        .build()
+        .unwrap();
+    let store = tx.object_store(STORE).unwrap();
+    store.add(user(1, "alice", &["a", "b"])).await.unwrap();
+    store.add(user(2, "bob", &["b"])).await.unwrap();
+    store.add(user(3, "carol", &["c", "b", "b"])).await.unwrap();
+    tx.commit().await.unwrap();
+}
+
+async fn out_of_line_db(auto_increment: bool) -> Database {
+    DBFactory::memory()
+        .open_db("ool")
+        .with_on_upgrade_needed(move |_, db| {
+            db.create_object_store(STORE)
+                .with_auto_increment(auto_increment)
+                .build()?;
+            Ok(())
+        })
+        .await
+        .expect("open")
+}
+
+mod key_order {
+    use super::*;
+
+    #[test]
+    fn type_order() {
+        let keys = [
+            Key::Number(f64::NEG_INFINITY),
+            Key::Number(-1.0),
+            Key::Number(10.0),
+            Key::Date(-5.0),
+            Key::String(String::new()),
+            Key::from("a"),
+            Key::from("b"),
+            Key::Binary(vec![]),
+            Key::Binary(vec![0]),
+            Key::Array(vec![]),
+            Key::Array(vec![Key::from(1)]),
+            Key::Array(vec![Key::from(1), Key::from(0)]),
+            Key::Array(vec![Key::from("a")]),
+        ];
+
+        for pair in keys.windows(2) {
+            assert!(pair[0] < pair[1], "{:?} < {:?}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn utf16_string_order() {
+        // U+FF61 sorts after U+1F600 in UTF-8/char order but before it in UTF-16 code unit order
+        assert!(Key::from("\u{1F600}") < Key::from("\u{FF61}"));
+    }
+
+    #[test]
+    fn zero_equality() {
+        assert_eq!(Key::Number(0.0), Key::Number(-0.0));
+    }
+}
+
+mod open {
+    use super::*;
+
+    #[test]
+    fn version_defaults() {
+        block_on(async {
+            let factory = DBFactory::memory();
+            let db = factory.open_db("db").await.unwrap();
+            assert_eq!(db.version(), 1.0);
+
+            let db = factory.open_db("db").await.unwrap();
+            assert_eq!(db.version(), 1.0);
+        });
+    }
+
+    #[test]
+    fn version_zero() {
+        block_on(async {
+            let res = DBFactory::memory().open_db("db").with_version(0u32).await;
+            assert!(res.is_err(), "{res:?}");
+        });
+    }
+
+    #[test]
+    fn version_error() {
+        block_on(async {
+            let factory = DBFactory::memory();
+            factory.open_db("db").with_version(3u32).await.unwrap();
+
+            let res = factory.open_db("db").with_version(2u32).await;
+            assert_backend_err!(res, VersionError);
+        });
+    }
+
+    #[test]
+    fn upgrade_event() {
+        block_on(async {
+            let factory = DBFactory::memory();
+            factory.open_db("db").with_version(2u32).await.unwrap();
+
+            let db = factory
+                .open_db("db")
+                .with_version(5u32)
+                .with_on_upgrade_needed(|evt, db| {
+                    assert_eq!((evt.old_version(), evt.new_version()), (2.0, Some(5.0)));
+                    assert_eq!(db.version(), 5.0);
+                    Ok(())
+                })
+                .await
+                .unwrap();
+            assert_eq!(db.version(), 5.0);
+        });
+    }
+
+    #[test]
+    fn failed_upgrade_rolls_back() {
+        block_on(async {
+            let factory = DBFactory::memory();
+            let res = factory
+                .open_db("db")
+                .with_version(1u32)
+                .with_on_upgrade_needed(|_, db| {
+                    db.create_object_store(STORE).build()?;
+                    Err(BackendError::AbortError("nope".into()).into())
+                })
+                .await;
+            assert_eq!(
+                backend_error(res.unwrap_err()),
+                BackendError::AbortError("nope".into())
+            );
+
+            let db = factory.open_db("db").await.unwrap();
+            assert!(store_names(&db).is_empty());
+        });
+    }
+
+    #[test]
+    fn schema_changes_outside_upgrade() {
+        block_on(async {
+            let db = out_of_line_db(false).await;
+            assert_backend_err!(db.create_object_store("other").build(), InvalidStateError);
+            assert_backend_err!(db.delete_object_store(STORE), InvalidStateError);
+
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            assert_backend_err!(
+                store.create_index("idx", "foo".into()).build(),
+                InvalidStateError
+            );
+            assert_backend_err!(store.set_name("other"), InvalidStateError);
+        });
+    }
+
+    #[test]
+    fn rename_store() {
+        block_on(async {
+            let db = DBFactory::memory()
+                .open_db("db")
+                .with_on_upgrade_needed(|_, db| {
+                    let store = db.create_object_store(STORE).build()?;
+                    db.create_object_store("other").build()?;
+
+                    assert_backend_err!(store.set_name("other"), ConstraintError);
+                    store.set_name("renamed")?;
+                    assert_eq!(store.name(), "renamed");
+                    Ok(())
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(store_names(&db), ["other", "renamed"]);
+        });
+    }
+
+    #[test]
+    fn delete() {
+        block_on(async {
+            let factory = DBFactory::memory();
+            factory.open_db("db").with_version(3u32).await.unwrap();
+            factory.delete_db("db").unwrap().await.unwrap();
+
+            let db = factory.open_db("db").await.unwrap();
+            assert_eq!(db.version(), 1.0);
+        });
+    }
+}
+
+mod keys {
+    use super::*;
+
+    #[test]
+    fn auto_increment_out_of_line() {
+        block_on(async {
+            let db = out_of_line_db(true).await;
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            let add = |value| store.add(value).with_key_type::<Key>();
+            assert_eq!(add("a").await, Ok(Key::from(1)));
+            assert_eq!(store.add("b").with_key(10.5).await, Ok(10.5));
+            assert_eq!(add("c").await, Ok(Key::from(11)));
+            assert_eq!(
+                store.add("d").with_key("str").with_key_type::<Key>().await,
+                Ok(Key::from("str"))
+            );
+            assert_eq!(add("e").await, Ok(Key::from(12)));
+        });
+    }
+
+    #[test]
+    fn auto_increment_in_line() {
+        block_on(async {
+            let db = DBFactory::memory()
+                .open_db("db")
+                .with_on_upgrade_needed(|_, db| {
+                    db.create_object_store(STORE)
+                        .with_key_path("meta.id".into())
+                        .with_auto_increment(true)
+                        .build()?;
+                    Ok(())
+                })
+                .await
+                .unwrap();
+
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            let key = store
+                .add(Value::object([("v", Value::from(1))]))
+                .with_key_type::<Key>()
+                .await;
+            assert_eq!(key, Ok(Key::from(1)));
+
+            let expected = Value::object([
+                ("v", Value::from(1)),
+                ("meta", Value::object([("id", Value::from(1))])),
+            ]);
+            assert_eq!(store.get(1u32).await, Ok(Some(expected)));
+        });
+    }
+
+    #[test]
+    fn key_generator_rolls_back() {
+        block_on(async {
+            let db = out_of_line_db(true).await;
+            for _ in 0..2 {
+                let tx = db
+                    .transaction(STORE)
+                    .with_mode(TransactionMode::Readwrite)
+                    .build()
+                    .unwrap();
+                let store = tx.object_store(STORE).unwrap();
+                let key = store.add("a").with_key_type::<u32>().await;
+                assert_eq!(key, Ok(1));
+            }
+        });
+    }
+
+    #[test]
+    fn compound_key_path() {
+        block_on(async {
+            let db = DBFactory::memory()
+                .open_db("db")
+                .with_on_upgrade_needed(|_, db| {
+                    db.create_object_store(STORE)
+                        .with_key_path(KeyPath::from(["a", "b"]))
+                        .build()?;
+                    Ok(())
+                })
+                .await
+                .unwrap();
+
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let key = store
+                .put(Value::object([
+                    ("a", Value::from(1)),
+                    ("b", Value::from("x")),
+                ]))
+                .with_key_type::<Key>()
+                .await;
+            assert_eq!(key, Ok(Key::from([Key::from(1), Key::from("x")])));
+
+            let res = store.put(Value::object([("a", Value::from(1))])).await;
+            assert_backend_err!(res, DataError);
+        });
+    }
+
+    #[test]
+    fn in_line_key_with_explicit_key() {
+        block_on(async {
+            let db = users_db().await;
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let res = store.put(user(1, "a", &[])).with_key(1u32).await;
+            assert_backend_err!(res, DataError);
+        });
+    }
+
+    #[test]
+    fn missing_out_of_line_key() {
+        block_on(async {
+            let db = out_of_line_db(false).await;
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let res = tx.object_store(STORE).unwrap().put("a").await;
+            assert_backend_err!(res, DataError);
+        });
+    }
+}
+
+mod transactions {
+    use super::*;
+
+    #[test]
+    fn implicit_rollback() {
+        block_on(async {
+            let db = users_db().await;
+            {
+                let tx = db
+                    .transaction(STORE)
+                    .with_mode(TransactionMode::Readwrite)
+                    .build()
+                    .unwrap();
+                let store = tx.object_store(STORE).unwrap();
+                store.put(user(1, "a", &[])).await.unwrap();
+            }
+
+            let tx = db.transaction(STORE).build().unwrap();
+            assert_eq!(tx.object_store(STORE).unwrap().count().await, Ok(0));
+        });
+    }
+
+    #[test]
+    fn explicit_commit() {
+        block_on(async {
+            let db = users_db().await;
+            seed_users(&db).await;
+
+            let tx = db.transaction(STORE).build().unwrap();
+            assert_eq!(tx.object_store(STORE).unwrap().count().await, Ok(3));
+        });
+    }
+
+    #[test]
+    fn abort() {
+        block_on(async {
+            let db = users_db().await;
+            seed_users(&db).await;
+
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            tx.object_store(STORE)
+                .unwrap()
+                .clear()
+                .unwrap()
+                .await
+                .unwrap();
+            tx.abort().await.unwrap();
+
+            let tx = db.transaction(STORE).build().unwrap();
+            assert_eq!(tx.object_store(STORE).unwrap().count().await, Ok(3));
+        });
+    }
+
+    #[test]
+    fn readonly() {
+        block_on(async {
+            let db = users_db().await;
+            let tx = db.transaction(STORE).build().unwrap();
+            let res = tx.object_store(STORE).unwrap().put(user(1, "a", &[])).await;
+            assert_backend_err!(res, ReadOnlyError);
+        });
+    }
+
+    #[test]
+    fn scope() {
+        block_on(async {
+            let db = DBFactory::memory()
+                .open_db("db")
+                .with_on_upgrade_needed(|_, db| {
+                    db.create_object_store("a").build()?;
+                    db.create_object_store("b").build()?;
+                    Ok(())
+                })
+                .await
+                .unwrap();
+
+            let tx = db.transaction("a").build().unwrap();
+            assert_eq!(tx.object_store_names().collect::<Vec<_>>(), ["a"]);
+            assert_backend_err!(tx.object_store("b"), NotFoundError);
+            assert_backend_err!(db.transaction(["a", "c"]).build(), NotFoundError);
+        });
+    }
+
+    #[test]
+    fn overlapping_writes_fail_the_later_commit() {
+        block_on(async {
+            let db = users_db().await;
+            let write = |name| {
+                let tx = db
+                    .transaction(STORE)
+                    .with_mode(TransactionMode::Readwrite)
+                    .build()
+                    .unwrap();
+                (tx, user(1, name, &[]))
+            };
+
+            let (first, first_user) = write("first");
+            let (second, second_user) = write("second");
+            first
+                .object_store(STORE)
+                .unwrap()
+                .put(first_user)
+                .await
+                .unwrap();
+            second
+                .object_store(STORE)
+                .unwrap()
+                .put(second_user)
+                .await
+                .unwrap();
+
+            first.commit().await.unwrap();
+            assert_backend_err!(second.commit().await, AbortError);
+
+            let tx = db.transaction(STORE).build().unwrap();
+            let stored: Option<Value> = tx.object_store(STORE).unwrap().get(1u32).await.unwrap();
+            assert_eq!(stored, Some(user(1, "first", &[])));
+        });
+    }
+
+    #[test]
+    fn overlapping_reads_commit() {
+        block_on(async {
+            let db = users_db().await;
+            let reader = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            assert_eq!(reader.object_store(STORE).unwrap().count().await, Ok(0));
+
+            seed_users(&db).await;
+            reader.commit().await.unwrap();
+        });
+    }
+}
+
+mod indices {
+    use super::*;
+    use indexed_db_futures::index::Index;
+
+    async fn tag_keys(idx: &Index<'_>, tag: &str) -> indexed_db_futures::Result<Vec<u32>> {
+        idx.get_all_keys().with_query(tag).await?.collect()
+    }
+
+    #[test]
+    fn unique_violation() {
+        block_on(async {
+            let db = users_db().await;
+            seed_users(&db).await;
+
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            assert_backend_err!(store.put(user(4, "alice", &[])).await, ConstraintError);
+
+            // Overwriting the same record is fine
+            store.put(user(1, "alice", &["z"])).await.unwrap();
+            assert_eq!(store.count().await, Ok(3));
+        });
+    }
+
+    #[test]
+    fn multi_entry() {
+        block_on(async {
+            let db = users_db().await;
+            seed_users(&db).await;
+
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let idx = store.index("tags").unwrap();
+
+            assert_eq!(tag_keys(&idx, "b").await, Ok(vec![1, 2, 3]));
+            assert_eq!(tag_keys(&idx, "c").await, Ok(vec![3]));
+            assert_eq!(idx.count().await, Ok(5));
+        });
+    }
+
+    #[test]
+    fn index_updates_on_delete() {
+        block_on(async {
+            let db = users_db().await;
+            seed_users(&db).await;
+
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            store.delete(1u32..=2).await.unwrap();
+
+            let idx = store.index("tags").unwrap();
+            assert_eq!(idx.get_key("b").with_key_type().await, Ok(Some(3u32)));
+            assert_eq!(idx.get_key("a").with_key_type::<u32>().await, Ok(None));
+        });
+    }
+
+    #[test]
+    fn get_by_index() {
+        block_on(async {
+            let db = users_db().await;
+            seed_users(&db).await;
+
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let idx = store.index("name").unwrap();
+            assert_eq!(idx.get("bob").await, Ok(Some(user(2, "bob", &["b"]))));
+            assert_eq!(store.index_names().collect::<Vec<_>>(), ["name", "tags"]);
+        });
+    }
+}
+
+mod queries {
+    use super::*;
+
+    async fn numbers_db() -> Database {
+        let db = out_of_line_db(false).await;
+        let tx = db
+            .transaction(STORE)
+            .with_mode(TransactionMode::Readwrite)
+            .build()
+            .unwrap();
+        let store = tx.object_store(STORE).unwrap();
+        for i in 0..10u32 {
+            store.put(i * 10).with_key(i).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+        db
+    }
+
+    #[test]
+    fn get_all_ranges() {
+        block_on(async {
+            let db = numbers_db().await;
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            let collect = |iter: Result<_, _>| {
+                Result::and_then(
+                    iter,
+                    Iterator::collect::<indexed_db_futures::Result<Vec<u32>>>,
+                )
+            };
+            assert_eq!(
+                collect(store.get_all::<u32>().with_query(2u32..5).await),
+                Ok(vec![20, 30, 40])
+            );
+            assert_eq!(
+                collect(store.get_all::<u32>().with_query(..=1u32).await),
+                Ok(vec![0, 10])
+            );
+            assert_eq!(
+                collect(
+                    store
+                        .get_all::<u32>()
+                        .with_query::<u32, _>(KeyRange::LowerBound(7u32, true))
+                        .await
+                ),
+                Ok(vec![80, 90])
+            );
+            assert_eq!(
+                collect(store.get_all::<u32>().with_limit(2).await),
+                Ok(vec![0, 10])
+            );
+            assert_eq!(store.count().with_query(3u32..).await, Ok(7));
+            assert_eq!(store.get(4u32).await, Ok(Some(40u32)));
+            assert_eq!(
+                store.get_key(KeyRange::LowerBound(4u32, true)).await,
+                Ok(Some(5u32))
+            );
+
+            let res = store
+                .get_all::<u32>()
+                .with_query::<u32, _>(KeyRange::Bound(5u32, false, 2, false))
+                .await;
+            assert_backend_err!(res.map(|_| ()), DataError);
+        });
+    }
+
+    #[test]
+    fn store_cursor() {
+        block_on(async {
+            let db = numbers_db().await;
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            let mut cursor = store
+                .open_cursor()
+                .with_query(2u32..=8)
+                .with_direction(CursorDirection::Prev)
+                .await
+                .unwrap()
+                .expect("cursor");
+            assert_eq!(cursor.key(), Ok(Some(8u32)));
+            assert_eq!(cursor.next_record().await, Ok(Some(80u32)));
+
+            assert_eq!(cursor.next_record().await, Ok(Some(70u32)));
+            cursor.delete().unwrap().await.unwrap();
+
+            cursor.continue_to_key(4u32).await.unwrap();
+            assert_eq!(cursor.next_record().await, Ok(Some(40u32)));
+            cursor.update(-1).await.unwrap();
+
+            assert_eq!(cursor.next_record().await, Ok(Some(30u32)));
+            assert_eq!(cursor.next_record().await, Ok(Some(20u32)));
+            assert_eq!(cursor.next_record::<u32>().await, Ok(None));
+            assert_eq!(cursor.key::<u32>(), Ok(None));
+
+            assert_eq!(store.get::<u32, _, _>(7u32).await, Ok(None));
+            assert_eq!(store.get(4u32).await, Ok(Some(-1)));
+        });
+    }
+
+    #[test]
+    fn key_cursor() {
+        block_on(async {
+            let db = numbers_db().await;
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            let mut cursor = store
+                .open_key_cursor()
+                .with_query(5u32..)
+                .await
+                .unwrap()
+                .expect("cursor");
+            cursor.advance_by(3).await.unwrap();
+            assert_eq!(cursor.next_key().await, Ok(Some(8u32)));
+            assert_eq!(cursor.next_key().await, Ok(Some(9u32)));
+            assert_eq!(cursor.next_key::<u32>().await, Ok(None));
+        });
+    }
+
+    #[test]
+    fn index_cursor_directions() {
+        block_on(async {
+            let db = users_db().await;
+            seed_users(&db).await;
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let idx = store.index("tags").unwrap();
+
+            let collect = |direction| {
+                let idx = &idx;
+                async move {
+                    let mut out = Vec::new();
+                    let mut cursor = idx
+                        .open_cursor()
+                        .with_direction(direction)
+                        .await
+                        .unwrap()
+                        .expect("cursor");
+                    while cursor.next_record::<Value>().await.unwrap().is_some() {
+                        let key: String = cursor.key().unwrap().unwrap();
+                        let primary_key: u32 = cursor.primary_key().unwrap().unwrap();
+                        out.push((key, primary_key));
+                    }
+                    out
+                }
+            };
+            let entries = |e: &[(&str, u32)]| {
+                e.iter()
+                    .map(|&(k, pk)| (k.to_owned(), pk))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(
+                collect(CursorDirection::Next).await,
+                entries(&[("a", 1), ("b", 1), ("b", 2), ("b", 3), ("c", 3)])
+            );
+            assert_eq!(
+                collect(CursorDirection::Prev).await,
+                entries(&[("c", 3), ("b", 3), ("b", 2), ("b", 1), ("a", 1)])
+            );
+            assert_eq!(
+                collect(CursorDirection::Nextunique).await,
+                entries(&[("a", 1), ("b", 1), ("c", 3)])
+            );
+            assert_eq!(
+                collect(CursorDirection::Prevunique).await,
+                entries(&[("c", 3), ("b", 1), ("a", 1)])
+            );
+        });
+    }
+
+    #[test]
+    fn continue_to_primary_key() {
+        block_on(async {
+            let db = users_db().await;
+            seed_users(&db).await;
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let idx = store.index("tags").unwrap();
+
+            let mut cursor = idx.open_cursor().await.unwrap().expect("cursor");
+            cursor.continue_to_primary_key("b", 2u32).await.unwrap();
+            assert_eq!(cursor.primary_key(), Ok(Some(2u32)));
+
+            cursor.continue_to_primary_key("b", 4u32).await.unwrap();
+            assert_eq!(cursor.key(), Ok(Some(String::from("c"))));
+            assert_eq!(cursor.primary_key(), Ok(Some(3u32)));
+        });
+    }
+}
+
+mod bulk {
+    use super::*;
+    use indexed_db_futures::object_store::UpdateAction;
+    use indexed_db_futures::query_source::Page;
+
+    const BY_SCORE: &str = "score";
+
+    /// Records `0..10` keyed by `id` with a `score` of `id % 5`, indexed by score.
+    async fn scores_db() -> Database {
+        let db = DBFactory::memory()
+            .open_db("scores")
+            .with_on_upgrade_needed(|_, db| {
+                let store = db
+                    .create_object_store(STORE)
+                    .with_key_path("id".into())
+                    .build()?;
+                store.create_index(BY_SCORE, "score".into()).build()?;
+                Ok(())
+            })
+            .await
+            .expect("open");
+
+        let tx = db
+            .transaction(STORE)
+            .with_mode(TransactionMode::Readwrite)
+            .build()
+            .unwrap();
+        let store = tx.object_store(STORE).unwrap();
+        for id in 0..10u32 {
+            let record = Value::object([("id", Value::from(id)), ("score", Value::from(id % 5))]);
+            store.put(record).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+        db
+    }
+
+    fn ids(page: &Page<Value>) -> Vec<f64> {
+        page.records()
+            .iter()
+            .map(|record| match record.get("id") {
+                Some(&Value::Number(id)) => id,
+                other => panic!("No id: {other:?}"),
+            })
+            .collect()
+    }
+
+    async fn remaining_ids(db: &Database) -> Vec<u32> {
+        let tx = db.transaction(STORE).build().unwrap();
+        let store = tx.object_store(STORE).unwrap();
+        let keys = store.get_all_keys::<u32>().await.unwrap();
+        keys.collect::<indexed_db_futures::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn paginate_store() {
+        block_on(async {
+            let db = scores_db().await;
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            let mut pages = Vec::new();
+            let mut page: Page<Value> = store.paginate(3).await.unwrap();
+            loop {
+                pages.push(ids(&page));
+                match page.next() {
+                    Some(token) => page = store.paginate(3).after(token.clone()).await.unwrap(),
+                    None => break,
+                }
+            }
+            assert_eq!(
+                pages,
+                [
+                    vec![0.0, 1.0, 2.0],
+                    vec![3.0, 4.0, 5.0],
+                    vec![6.0, 7.0, 8.0],
+                    vec![9.0]
+                ]
+            );
+
+            let back: Page<Value> = store
+                .paginate(3)
+                .before(page.previous().expect("previous").clone())
+                .await
+                .unwrap();
+            assert_eq!(ids(&back), [6.0, 7.0, 8.0]);
+        });
+    }
+
+    #[test]
+    fn paginate_index() {
+        block_on(async {
+            let db = scores_db().await;
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let idx = store.index(BY_SCORE).unwrap();
+
+            let first: Page<Value> = idx.paginate(3).await.unwrap();
+            assert_eq!(ids(&first), [0.0, 5.0, 1.0]);
+
+            let token = first.next().expect("next").clone();
+            let second: Page<Value> = idx.paginate(3).after(token).await.unwrap();
+            assert_eq!(ids(&second), [6.0, 2.0, 7.0]);
+
+            let token = second.next().expect("next").clone();
+            let third: Page<Value> = idx.paginate(4).after(token).await.unwrap();
+            assert_eq!(ids(&third), [3.0, 8.0, 4.0, 9.0]);
+            assert_eq!(third.next(), None);
+        });
+    }
+
+    #[cfg(feature = "query")]
+    #[test]
+    fn query_sorted_in_rust_matches_index_order() {
+        block_on(async {
+            let db = scores_db().await;
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            for direction in [
+                CursorDirection::Next,
+                CursorDirection::Prev,
+                CursorDirection::Nextunique,
+                CursorDirection::Prevunique,
+            ] {
+                let by_index = store.query::<Value>().with_order(BY_SCORE, direction);
+                assert!(!by_index.explain().unwrap().sorts_in_rust());
+
+                // Filtering on the primary key makes the object store the better candidate
+                let in_rust = store
+                    .query::<Value>()
+                    .with_filter("id", 0u32..)
+                    .with_order(BY_SCORE, direction);
+                assert!(in_rust.explain().unwrap().sorts_in_rust());
+
+                assert_eq!(in_rust.await, by_index.await, "{direction:?}");
+            }
+        });
+    }
+
+    #[test]
+    fn update_where() {
+        block_on(async {
+            let db = scores_db().await;
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            let counts = store
+                .update_where(2u32..7, |record: Value| {
+                    match record.get("score").and_then(Value::to_key) {
+                        Some(Key::Number(0.0)) => UpdateAction::Delete,
+                        Some(Key::Number(2.0)) => UpdateAction::Replace(Value::object([
+                            ("id", Value::from(2u32)),
+                            ("score", Value::from(20u32)),
+                        ])),
+                        _ => UpdateAction::Keep,
+                    }
+                })
+                .await
+                .unwrap();
+            assert_eq!((counts.updated(), counts.deleted()), (1, 1));
+            tx.commit().await.unwrap();
+
+            assert_eq!(remaining_ids(&db).await, [0, 1, 2, 3, 4, 6, 7, 8, 9]);
+
+            let tx = db.transaction(STORE).build().unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let idx = store.index(BY_SCORE).unwrap();
+            assert_eq!(idx.count().with_query(20u32).await, Ok(1));
+        });
+    }
+
+    #[test]
+    fn delete_range() {
+        block_on(async {
+            let db = scores_db().await;
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+            let idx = store.index(BY_SCORE).unwrap();
+
+            assert_eq!(idx.delete_range_count(1u32..=3).await, Ok(6));
+            assert_eq!(idx.delete_range_count(1u32..=3).await, Ok(0));
+            idx.delete_range(..1u32).await.unwrap();
+            tx.commit().await.unwrap();
+
+            assert_eq!(remaining_ids(&db).await, [4, 9]);
+        });
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::*;
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Record {
+        id: u32,
+        name: String,
+        score: f64,
+        nickname: Option<String>,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn round_trip() {
+        block_on(async {
+            let db = users_db().await;
+            let tx = db
+                .transaction(STORE)
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            let store = tx.object_store(STORE).unwrap();
+
+            let record = Record {
+                id: 5,
+                name: "dave".into(),
+                score: 1.5,
+                nickname: None,
+                tags: vec!["x".into()],
+            };
+            store.put(&record).serde().unwrap().await.unwrap();
+
+            let idx = store.index("name").unwrap();
+            let value = idx.get("dave").serde().unwrap().await;
+            assert_eq!(value, Ok(Some(record)));
+        });
+    }
+}
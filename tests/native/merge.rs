@@ -0,0 +1,120 @@
+use futures::executor::block_on;
+use futures::stream::{self, Iter, StreamExt, TryStreamExt};
+use indexed_db_futures::cursor::{CursorDirection, MergedStream};
+use std::vec::IntoIter;
+
+type Entry = (u8, u8, &'static str);
+type Source = Iter<IntoIter<Result<Entry, &'static str>>>;
+
+fn source(entries: &[Entry]) -> Source {
+    stream::iter(entries.iter().copied().map(Ok).collect::<Vec<_>>())
+}
+
+fn collect(merged: MergedStream<Source>) -> Result<Vec<Entry>, &'static str> {
+    block_on(merged.try_collect())
+}
+
+#[test]
+fn next() {
+    let merged = MergedStream::new([
+        source(&[(1, 1, "a"), (4, 4, "d")]),
+        source(&[]),
+        source(&[(2, 2, "b"), (3, 3, "c"), (5, 5, "e")]),
+    ]);
+
+    let expect = vec![
+        (1, 1, "a"),
+        (2, 2, "b"),
+        (3, 3, "c"),
+        (4, 4, "d"),
+        (5, 5, "e"),
+    ];
+    assert_eq!(collect(merged), Ok(expect));
+}
+
+#[test]
+fn equal_keys_ordered_by_primary_key() {
+    let merged = MergedStream::new([
+        source(&[(1, 3, "c"), (2, 1, "d")]),
+        source(&[(1, 1, "a"), (1, 2, "b")]),
+    ]);
+
+    let expect = vec![(1, 1, "a"), (1, 2, "b"), (1, 3, "c"), (2, 1, "d")];
+    assert_eq!(collect(merged), Ok(expect));
+}
+
+#[test]
+fn prev() {
+    let merged = MergedStream::new([
+        source(&[(5, 1, "e"), (1, 2, "a")]),
+        source(&[(5, 3, "f"), (3, 4, "c")]),
+    ])
+    .with_direction(CursorDirection::Prev);
+
+    let expect = vec![(5, 3, "f"), (5, 1, "e"), (3, 4, "c"), (1, 2, "a")];
+    assert_eq!(collect(merged), Ok(expect));
+}
+
+#[test]
+fn unique() {
+    let merged = MergedStream::new([
+        source(&[(1, 2, "b"), (2, 4, "d")]),
+        source(&[(1, 1, "a"), (2, 3, "c")]),
+    ])
+    .with_direction(CursorDirection::Nextunique);
+    assert_eq!(collect(merged), Ok(vec![(1, 1, "a"), (2, 3, "c")]));
+
+    let merged = MergedStream::new([
+        source(&[(2, 4, "d"), (1, 2, "b")]),
+        source(&[(2, 3, "c"), (1, 1, "a")]),
+    ])
+    .with_direction(CursorDirection::Prevunique);
+    assert_eq!(collect(merged), Ok(vec![(2, 3, "c"), (1, 1, "a")]));
+}
+
+#[test]
+fn dedup_by_primary_key() {
+    let merged = MergedStream::new([
+        source(&[(1, 10, "a"), (3, 30, "c")]),
+        source(&[(2, 10, "a"), (4, 40, "d")]),
+    ]);
+    let expect = vec![(1, 10, "a"), (2, 10, "a"), (3, 30, "c"), (4, 40, "d")];
+    assert_eq!(collect(merged), Ok(expect));
+
+    let merged = MergedStream::new([
+        source(&[(1, 10, "a"), (3, 30, "c")]),
+        source(&[(2, 10, "a"), (4, 40, "d")]),
+    ])
+    .dedup_by_primary_key();
+    assert_eq!(
+        collect(merged),
+        Ok(vec![(1, 10, "a"), (3, 30, "c"), (4, 40, "d")])
+    );
+}
+
+#[test]
+fn error_drops_source() {
+    let failing = stream::iter(vec![Ok((2, 2, "b")), Err("oops"), Ok((3, 3, "c"))]);
+    let mut merged = MergedStream::new([source(&[(1, 1, "a"), (4, 4, "d")]), failing]);
+
+    block_on(async {
+        assert_eq!(merged.next().await, Some(Ok((1, 1, "a"))));
+        assert_eq!(merged.next().await, Some(Ok((2, 2, "b"))));
+        assert_eq!(merged.next().await, Some(Err("oops")));
+        assert_eq!(merged.next().await, Some(Ok((4, 4, "d"))));
+        assert_eq!(merged.next().await, None);
+    });
+    assert!(futures::stream::FusedStream::is_terminated(&merged));
+}
+
+#[test]
+fn key_order() {
+    // Rust orders strings by UTF-8 bytes, IndexedDB by UTF-16 code units
+    let merged = MergedStream::new([
+        stream::iter(vec![Ok::<_, ()>(("\u{FF61}", 1u8, ()))]),
+        stream::iter(vec![Ok(("a", 2u8, ())), Ok(("\u{1F600}", 3u8, ()))]),
+    ]);
+
+    let keys = block_on(merged.map_ok(|(_, pk, ())| pk).try_collect::<Vec<_>>());
+    assert_eq!(keys, Ok(vec![2, 3, 1]));
+}
@@ -0,0 +1,35 @@
+use indexed_db_futures::migration::Migrations;
+
+fn noop() -> Migrations {
+    Migrations::new()
+}
+
+fn with_versions(versions: &[u32]) -> Migrations {
+    versions.iter().fold(noop(), |migrations, &version| {
+        migrations.with_migration(version, |_, _| Box::pin(async { Ok(()) }))
+    })
+}
+
+#[test]
+fn version() {
+    assert_eq!(noop().version(), None);
+    assert_eq!(with_versions(&[3, 1, 2]).version(), Some(3));
+}
+
+#[test]
+fn pending() {
+    let migrations = with_versions(&[5, 1, 3, 2]);
+
+    assert_eq!(migrations.pending(0).collect::<Vec<_>>(), [1, 2, 3, 5]);
+    assert_eq!(migrations.pending(2).collect::<Vec<_>>(), [3, 5]);
+    assert_eq!(migrations.pending(4).collect::<Vec<_>>(), [5]);
+    assert_eq!(migrations.pending(5).count(), 0);
+    assert_eq!(migrations.pending(u32::MAX).count(), 0);
+}
+
+#[test]
+fn replaces_by_version() {
+    let migrations = with_versions(&[1, 2, 1]);
+    assert_eq!(migrations.pending(0).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(format!("{migrations:?}"), "Migrations { versions: [1, 2] }");
+}
@@ -0,0 +1,65 @@
+use indexed_db_futures::error::PageTokenError;
+use indexed_db_futures::query_source::PageToken;
+use indexed_db_futures::IdbKey;
+
+#[test]
+fn round_trip() {
+    let keys = [
+        IdbKey::Number(-1.5),
+        IdbKey::Number(f64::INFINITY),
+        IdbKey::Date(1_700_000_000_000.0),
+        IdbKey::from(""),
+        IdbKey::from("héllo 😀"),
+        IdbKey::Binary(vec![0, 1, 255]),
+        IdbKey::Array(vec![]),
+        IdbKey::from([IdbKey::from(1), IdbKey::from([IdbKey::from("a")])]),
+    ];
+
+    for key in keys {
+        for primary_key in [None, Some(IdbKey::from(42)), Some(key.clone())] {
+            let token = PageToken::new(key.clone(), primary_key);
+            let parsed = token.to_string().parse::<PageToken>();
+            assert_eq!(parsed.as_ref(), Ok(&token), "{token:?}");
+        }
+    }
+}
+
+#[test]
+fn malformed() {
+    let valid = PageToken::new(IdbKey::from("abc"), Some(IdbKey::from(1))).to_string();
+    let cases = [
+        String::new(),
+        "0".into(),
+        "zz".into(),
+        "ff".into(),
+        valid[..valid.len() - 2].to_string(),
+        format!("{valid}00"),
+        // NaN number
+        format!("00{}", hex(&f64::NAN.to_be_bytes())),
+        // String longer than its payload
+        "02000000ff61".into(),
+    ];
+
+    for case in cases {
+        assert_eq!(case.parse::<PageToken>(), Err(PageTokenError), "{case:?}");
+    }
+}
+
+#[test]
+fn nesting_depth() {
+    let nested = |depth: usize| (0..depth).fold(IdbKey::from(1), |key, _| IdbKey::from([key]));
+
+    let token = PageToken::new(nested(64), None);
+    assert_eq!(token.to_string().parse::<PageToken>(), Ok(token));
+
+    let token = PageToken::new(nested(65), None);
+    assert_eq!(token.to_string().parse::<PageToken>(), Err(PageTokenError));
+
+    // Deep enough to overflow the stack if decoding recursed without a limit
+    let crafted = "0400000001".repeat(200_000);
+    assert_eq!(crafted.parse::<PageToken>(), Err(PageTokenError));
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,466 @@
+use indexed_db_futures::schema::{IndexSchema, Schema, SchemaChange, SchemaMismatch, StoreSchema};
+use indexed_db_futures::KeyPath;
+
+fn users() -> StoreSchema {
+    StoreSchema::new("users")
+        .with_key_path("id".into())
+        .with_index(IndexSchema::new("email", "email".into()).with_unique(true))
+}
+
+fn create_store(name: &str, key_path: Option<KeyPath>, auto_increment: bool) -> SchemaChange {
+    SchemaChange::CreateStore {
+        name: name.into(),
+        key_path,
+        auto_increment,
+    }
+}
+
+fn create_index(store: &str, index: IndexSchema) -> SchemaChange {
+    SchemaChange::CreateIndex {
+        store: store.into(),
+        index,
+    }
+}
+
+fn delete_index(store: &str, index: &str) -> SchemaChange {
+    SchemaChange::DeleteIndex {
+        store: store.into(),
+        index: index.into(),
+    }
+}
+
+#[test]
+fn builders_replace_by_name() {
+    let schema = Schema::new()
+        .with_store(StoreSchema::new("a"))
+        .with_store(StoreSchema::new("b"))
+        .with_store(StoreSchema::new("a").with_auto_increment(true));
+
+    let names = schema
+        .stores()
+        .iter()
+        .map(StoreSchema::name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b"]);
+    assert!(schema.store("a").unwrap().auto_increment());
+
+    let store = users().with_index(IndexSchema::new("email", "mail".into()));
+    assert_eq!(store.indices().len(), 1);
+    assert_eq!(
+        store.index("email").unwrap().key_path(),
+        &KeyPath::One("mail".into())
+    );
+    assert!(!store.index("email").unwrap().unique());
+}
+
+mod diff {
+    use super::*;
+
+    #[test]
+    fn identical() {
+        let schema = Schema::new().with_store(users());
+        assert_eq!(schema.diff(&schema.clone()), []);
+    }
+
+    #[test]
+    fn from_empty() {
+        let schema = Schema::new()
+            .with_store(users())
+            .with_store(StoreSchema::new("log").with_auto_increment(true));
+
+        assert_eq!(
+            schema.diff(&Schema::new()),
+            [
+                create_store("users", Some(KeyPath::One("id".into())), false),
+                create_index(
+                    "users",
+                    IndexSchema::new("email", "email".into()).with_unique(true)
+                ),
+                create_store("log", None, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn removed_store() {
+        let live = Schema::new()
+            .with_store(users())
+            .with_store(StoreSchema::new("old"));
+        let schema = Schema::new().with_store(users());
+
+        assert_eq!(
+            schema.diff(&live),
+            [SchemaChange::DeleteStore("old".into())]
+        );
+    }
+
+    #[test]
+    fn store_key_path_changed() {
+        let live = Schema::new().with_store(users());
+        let schema = Schema::new().with_store(users().with_key_path(KeyPath::from(["org", "id"])));
+
+        assert_eq!(
+            schema.diff(&live),
+            [
+                SchemaChange::DeleteStore("users".into()),
+                create_store(
+                    "users",
+                    Some(KeyPath::from(["org", "id"]).into_owned()),
+                    false
+                ),
+                create_index(
+                    "users",
+                    IndexSchema::new("email", "email".into()).with_unique(true)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn store_auto_increment_changed() {
+        let live = Schema::new().with_store(StoreSchema::new("log"));
+        let schema = Schema::new().with_store(StoreSchema::new("log").with_auto_increment(true));
+
+        assert_eq!(
+            schema.diff(&live),
+            [
+                SchemaChange::DeleteStore("log".into()),
+                create_store("log", None, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn indices() {
+        let live = Schema::new().with_store(
+            users()
+                .with_index(IndexSchema::new("name", "name".into()))
+                .with_index(IndexSchema::new("tags", "tags".into())),
+        );
+        let schema = Schema::new().with_store(
+            users()
+                .with_index(IndexSchema::new("tags", "tags".into()).with_multi_entry(true))
+                .with_index(IndexSchema::new("age", "age".into())),
+        );
+
+        assert_eq!(
+            schema.diff(&live),
+            [
+                delete_index("users", "name"),
+                delete_index("users", "tags"),
+                create_index(
+                    "users",
+                    IndexSchema::new("tags", "tags".into()).with_multi_entry(true)
+                ),
+                create_index("users", IndexSchema::new("age", "age".into())),
+            ]
+        );
+    }
+}
+
+mod mismatches {
+    use super::*;
+
+    #[test]
+    fn identical() {
+        let schema = Schema::new().with_store(users());
+        assert_eq!(schema.mismatches(&schema.clone()), []);
+    }
+
+    #[test]
+    fn stores() {
+        let expected = Schema::new()
+            .with_store(users())
+            .with_store(StoreSchema::new("log").with_auto_increment(true))
+            .with_store(StoreSchema::new("missing"));
+        let actual = Schema::new()
+            .with_store(StoreSchema::new("extra"))
+            .with_store(users().with_key_path(KeyPath::from(["org", "id"])))
+            .with_store(StoreSchema::new("log"));
+
+        let mismatches = expected.mismatches(&actual);
+        assert_eq!(
+            mismatches,
+            [
+                SchemaMismatch::StoreKeyPath {
+                    store: "users".into(),
+                    expected: Some(KeyPath::One("id".into())),
+                    actual: Some(KeyPath::from(["org", "id"]).into_owned()),
+                },
+                SchemaMismatch::StoreAutoIncrement {
+                    store: "log".into(),
+                    expected: true,
+                    actual: false,
+                },
+                SchemaMismatch::MissingStore("missing".into()),
+                SchemaMismatch::UnexpectedStore("extra".into()),
+            ]
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            r#"object store "users" has key path ["org", "id"], expected "id""#
+        );
+    }
+
+    #[test]
+    fn indices() {
+        let expected = Schema::new().with_store(
+            users()
+                .with_index(IndexSchema::new("tags", "tags".into()).with_multi_entry(true))
+                .with_index(IndexSchema::new("name", "name".into())),
+        );
+        let actual = Schema::new().with_store(
+            StoreSchema::new("users")
+                .with_key_path("id".into())
+                .with_index(IndexSchema::new("email", "mail".into()))
+                .with_index(IndexSchema::new("tags", "tags".into()))
+                .with_index(IndexSchema::new("age", "age".into())),
+        );
+
+        let mismatches = expected.mismatches(&actual);
+        assert_eq!(
+            mismatches,
+            [
+                SchemaMismatch::IndexKeyPath {
+                    store: "users".into(),
+                    index: "email".into(),
+                    expected: KeyPath::One("email".into()),
+                    actual: KeyPath::One("mail".into()),
+                },
+                SchemaMismatch::IndexUnique {
+                    store: "users".into(),
+                    index: "email".into(),
+                    expected: true,
+                    actual: false,
+                },
+                SchemaMismatch::IndexMultiEntry {
+                    store: "users".into(),
+                    index: "tags".into(),
+                    expected: true,
+                    actual: false,
+                },
+                SchemaMismatch::MissingIndex {
+                    store: "users".into(),
+                    index: "name".into(),
+                },
+                SchemaMismatch::UnexpectedIndex {
+                    store: "users".into(),
+                    index: "age".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            mismatches[1].to_string(),
+            r#"index "email" on object store "users" has unique false, expected true"#
+        );
+    }
+}
+
+#[cfg(feature = "memory")]
+mod memory {
+    use super::*;
+    use futures::executor::block_on;
+    use indexed_db_futures::backend::Value;
+    use indexed_db_futures::database::Database;
+    use indexed_db_futures::factory::DBFactory;
+    use indexed_db_futures::prelude::*;
+    use indexed_db_futures::transaction::TransactionMode;
+    use std::future::IntoFuture;
+
+    fn open(factory: &DBFactory, version: u32, schema: Schema) -> Database {
+        block_on(
+            factory
+                .open_db("db")
+                .with_version(version)
+                .with_schema(schema)
+                .into_future(),
+        )
+        .unwrap()
+    }
+
+    fn store_names(db: &Database) -> Vec<String> {
+        db.object_store_names().collect()
+    }
+
+    fn count(db: &Database, store: &str) -> u32 {
+        let tx = db.transaction(store).build().unwrap();
+        let out = block_on(tx.object_store(store).unwrap().count().into_future()).unwrap();
+        out
+    }
+
+    fn seed(db: &Database) {
+        let tx = db
+            .transaction(["users", "log"])
+            .with_mode(TransactionMode::Readwrite)
+            .build()
+            .unwrap();
+        block_on(async {
+            let user = Value::object([("id", Value::from(1)), ("email", Value::from("a@b.c"))]);
+            tx.object_store("users").unwrap().put(user).await.unwrap();
+            tx.object_store("log").unwrap().add("hi").await.unwrap();
+            tx.commit().await.unwrap();
+        });
+    }
+
+    #[test]
+    fn create() {
+        let schema = Schema::new()
+            .with_store(users())
+            .with_store(StoreSchema::new("log").with_auto_increment(true));
+        let db = open(&DBFactory::memory(), 1, schema.clone());
+
+        assert_eq!(store_names(&db), ["log", "users"]);
+        assert_eq!(
+            db.schema_snapshot().unwrap().schema().mismatches(&schema),
+            []
+        );
+
+        let tx = db.transaction("users").build().unwrap();
+        let store = tx.object_store("users").unwrap();
+        assert_eq!(store.key_path(), Some(KeyPath::One("id".into())));
+        assert_eq!(
+            store.index("email").unwrap().key_path(),
+            Some(KeyPath::One("email".into()))
+        );
+    }
+
+    #[test]
+    fn upgrade_keeps_unchanged_data() {
+        let factory = DBFactory::memory();
+        let v1 = Schema::new()
+            .with_store(users())
+            .with_store(StoreSchema::new("log").with_auto_increment(true));
+        seed(&open(&factory, 1, v1));
+
+        let v2 = Schema::new()
+            .with_store(users().with_index(IndexSchema::new("email", "email".into())))
+            .with_store(StoreSchema::new("log"));
+        let db = open(&factory, 2, v2.clone());
+        assert_eq!(db.schema_snapshot().unwrap().schema().mismatches(&v2), []);
+
+        assert_eq!(count(&db, "users"), 1, "users store should be retained");
+        assert_eq!(count(&db, "log"), 0, "log store should be recreated");
+
+        let tx = db.transaction("users").build().unwrap();
+        let store = tx.object_store("users").unwrap();
+        let index = store.index("email").unwrap();
+        assert_eq!(block_on(index.count().into_future()), Ok(1));
+    }
+
+    #[test]
+    fn upgrade_deletes() {
+        let factory = DBFactory::memory();
+        drop(open(&factory, 1, Schema::new().with_store(users())));
+
+        let db = open(
+            &factory,
+            2,
+            Schema::new().with_store(StoreSchema::new("users").with_key_path("id".into())),
+        );
+        assert_eq!(store_names(&db), ["users"]);
+
+        let tx = db.transaction("users").build().unwrap();
+        let store = tx.object_store("users").unwrap();
+        assert_eq!(store.index_names().count(), 0);
+
+        let db = open(&factory, 3, Schema::new());
+        assert!(store_names(&db).is_empty());
+    }
+
+    #[test]
+    fn snapshot() {
+        let factory = DBFactory::memory();
+        let schema = Schema::new()
+            .with_store(StoreSchema::new("log").with_auto_increment(true))
+            .with_store(
+                users().with_index(IndexSchema::new("tags", "tags".into()).with_multi_entry(true)),
+            );
+        drop(open(&factory, 3, schema.clone()));
+
+        let snapshot = block_on(factory.open_db("db").into_future())
+            .unwrap()
+            .schema_snapshot()
+            .unwrap();
+        assert_eq!(snapshot.name(), "db");
+        assert_eq!(snapshot.version(), 3.0);
+        assert_eq!(snapshot.schema(), &schema);
+
+        let empty = block_on(DBFactory::memory().open_db("db").into_future()).unwrap();
+        assert_eq!(empty.schema_snapshot().unwrap().schema(), &Schema::new());
+    }
+
+    #[test]
+    fn snapshot_during_upgrade() {
+        let factory = factory_with_users();
+        let db = factory
+            .open_db("db")
+            .with_version(2u32)
+            .with_on_upgrade_needed(|_, db| {
+                db.delete_object_store("users")?;
+                let snapshot = db.schema_snapshot()?;
+                assert_eq!(snapshot.version(), 2.0);
+                assert_eq!(snapshot.into_schema(), Schema::new());
+                Ok(())
+            });
+        assert!(block_on(db.into_future()).is_ok());
+    }
+
+    fn factory_with_users() -> DBFactory {
+        let factory = DBFactory::memory();
+        drop(open(&factory, 1, Schema::new().with_store(users())));
+        factory
+    }
+
+    #[test]
+    fn invalid_schema_rolls_back() {
+        let factory = factory_with_users();
+
+        let res = factory.open_db("db").with_version(2u32).with_schema(
+            Schema::new()
+                .with_store(StoreSchema::new("log"))
+                .with_store(
+                    StoreSchema::new("bad")
+                        .with_key_path(KeyPath::from(["a", "b"]))
+                        .with_auto_increment(true),
+                ),
+        );
+        assert!(block_on(res.into_future()).is_err());
+
+        let db = block_on(factory.open_db("db").into_future()).unwrap();
+        assert_eq!(db.version(), 1.0);
+        assert_eq!(store_names(&db), ["users"]);
+    }
+
+    #[test]
+    fn unique_index_on_duplicates_rolls_back() {
+        let factory = DBFactory::memory();
+        let v1 = Schema::new().with_store(StoreSchema::new("users").with_key_path("id".into()));
+        let db = open(&factory, 1, v1);
+        {
+            let tx = db
+                .transaction("users")
+                .with_mode(TransactionMode::Readwrite)
+                .build()
+                .unwrap();
+            block_on(async {
+                let store = tx.object_store("users").unwrap();
+                for id in 1..=2 {
+                    let user =
+                        Value::object([("id", Value::from(id)), ("email", Value::from("a@b.c"))]);
+                    store.put(user).await.unwrap();
+                }
+                tx.commit().await.unwrap();
+            });
+        }
+
+        let res = factory
+            .open_db("db")
+            .with_version(2u32)
+            .with_schema(Schema::new().with_store(users()));
+        assert!(block_on(res.into_future()).is_err());
+
+        let db = block_on(factory.open_db("db").into_future()).unwrap();
+        assert_eq!(db.version(), 1.0);
+        assert_eq!(count(&db, "users"), 2);
+    }
+}
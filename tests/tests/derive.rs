@@ -0,0 +1,80 @@
+use crate::prelude::*;
+use idb_fut::database::Database;
+use idb_fut::query_source::QuerySource;
+use idb_fut::schema::{IdbStore, Schema};
+use idb_fut::transaction::TransactionMode;
+
+#[derive(IdbStore)]
+#[idb_store(name = "users")]
+#[allow(dead_code)]
+struct User {
+    #[idb_store(key)]
+    id: u32,
+
+    #[idb_store(index(unique))]
+    email: String,
+}
+
+#[wasm_bindgen_test]
+pub async fn derived_schema() {
+    let db = Database::open(&random_str())
+        .with_version(1u8)
+        .with_schema(Schema::new().with_store(User::store_schema()))
+        .await
+        .expect("open");
+
+    let tx = db
+        .transaction(User::STORE_NAME)
+        .with_mode(TransactionMode::Readwrite)
+        .build()
+        .unwrap();
+    let store = User::object_store(&tx).unwrap();
+    assert_eq!(store.name(), "users");
+
+    let index = User::email_index(&store).unwrap();
+    assert_eq!(index.name(), "email");
+    assert_eq!(index.count().await, Ok(0));
+}
+
+#[derive(idb_fut::primitive::CompoundKey, Debug, PartialEq)]
+struct MembershipKey {
+    org: String,
+    user: u32,
+}
+
+#[wasm_bindgen_test]
+pub async fn compound_key() {
+    let db = random_db_with_store().await;
+    open_tx!(db, Readwrite > (tx, store));
+
+    let key = MembershipKey {
+        org: "acme".into(),
+        user: 7,
+    };
+    store
+        .put("member")
+        .with_key(&key)
+        .with_key_type::<MembershipKey>()
+        .primitive()
+        .unwrap()
+        .await
+        .expect("put");
+
+    assert_eq!(
+        store.get(&key).primitive().unwrap().await,
+        Ok(Some("member".to_string()))
+    );
+    assert_eq!(
+        store
+            .get_key(
+                MembershipKey {
+                    org: "acme".into(),
+                    user: 0,
+                }..
+            )
+            .primitive()
+            .unwrap()
+            .await,
+        Ok(Some(key))
+    );
+}
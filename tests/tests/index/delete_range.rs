@@ -0,0 +1,55 @@
+use crate::prelude::*;
+
+#[wasm_bindgen_test]
+pub async fn readonly_error() {
+    let db = random_db_idx_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_idx!(db, Readonly > idx);
+
+    let err = dyn_await!(idx.delete_range(Value::MIN..=Value::MAX)).unwrap_err();
+    assert_dom_exc!(err, ReadOnlyError);
+}
+
+#[wasm_bindgen_test]
+pub async fn delete_range() {
+    let db = random_db_idx_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    {
+        open_tx!(db, Readwrite > (tx, store));
+        let idx = store.index(&store.name()).expect("index()");
+        dyn_await!(idx.delete_range(..Value::new(4))).expect("delete_range");
+
+        drop(idx);
+        drop(store);
+        tx.commit().await.unwrap();
+    }
+
+    open_tx!(db, Readonly > (tx, store));
+    let actual = dyn_await!(store.get_all::<KeyVal>())
+        .expect("get_all")
+        .collect::<idb_fut::Result<Vec<_>>>()
+        .expect("collect");
+    let expected = KeyVal::iter_range()
+        .filter(|kv| *kv.value() >= 4)
+        .collect::<Vec<_>>();
+
+    assert_eq!(actual, expected);
+}
+
+#[wasm_bindgen_test]
+pub async fn delete_range_count() {
+    let db = random_db_idx_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readwrite > (tx, store));
+    let idx = store.index(&store.name()).expect("index()");
+
+    let deleted = dyn_await!(idx.delete_range_count(Value::new(3)..=Value::new(7)));
+    assert_eq!(deleted, Ok(5), "first pass");
+
+    let deleted = dyn_await!(idx.delete_range_count(Value::new(3)..=Value::new(7)));
+    assert_eq!(deleted, Ok(0), "second pass");
+
+    let remaining = dyn_await!(store.count()).expect("count");
+    assert_eq!(remaining, KeyVal::RANGE_LEN - 5);
+}
@@ -6,4 +6,6 @@ macro_rules! open_idx {
 }
 
 pub mod create;
+#[cfg(feature = "cursors")]
+pub mod delete_range;
 pub mod query_source;
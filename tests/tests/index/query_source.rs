@@ -1,5 +1,7 @@
 #[cfg(feature = "cursors")]
 pub mod cursor;
+#[cfg(feature = "cursors")]
+pub mod paginate;
 
 use crate::prelude::*;
 use idb_fut::database::Database;
@@ -69,6 +71,62 @@ pub async fn get_key() {
     assert_eq!(range, Ok(Some(Key::new(-3))));
 }
 
+#[wasm_bindgen_test]
+pub async fn contains_key() {
+    let db = random_db_idx_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    open_idx!(db, Readonly > idx);
+
+    assert_eq!(
+        dyn_await!(idx.contains_key::<Value, _>(KeyRange::Only(Value::MAX))),
+        Ok(true),
+        "only"
+    );
+    assert_eq!(
+        dyn_await!(idx.contains_key(random_str())),
+        Ok(false),
+        "missing"
+    );
+    assert_eq!(
+        dyn_await!(idx.contains_key(Value::new(3)..=Value::new(6))),
+        Ok(true),
+        "range"
+    );
+}
+
+#[wasm_bindgen_test]
+#[cfg(feature = "cursors")]
+pub async fn first_last() {
+    let db = random_db_idx_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    open_idx!(db, Readonly > idx);
+
+    let first = dyn_await!(idx.first::<KeyVal>());
+    assert_eq!(first, Ok(Some(KeyVal::new(Key::MAX, Value::MIN))), "first");
+
+    let last = dyn_await!(idx.last::<KeyVal>());
+    assert_eq!(last, Ok(Some(KeyVal::new(Key::MIN, Value::MAX))), "last");
+
+    assert_eq!(
+        dyn_await!(idx.first_key::<Value>()),
+        Ok(Some(Value::MIN)),
+        "first_key"
+    );
+    assert_eq!(
+        dyn_await!(idx.last_key::<Value>()),
+        Ok(Some(Value::MAX)),
+        "last_key"
+    );
+
+    let ranged = dyn_await!(idx.last::<KeyVal>().with_query(3u8..=6));
+    assert_eq!(ranged, Ok(Some(KeyVal::new(-6, 6))), "ranged last");
+
+    let ranged = dyn_await!(idx.first_key::<Value>().with_query(4u8..));
+    assert_eq!(ranged, Ok(Some(Value::new(4))), "ranged first_key");
+}
+
 #[wasm_bindgen_test]
 pub async fn get_all() {
     let db = random_db_idx_keyval().await;
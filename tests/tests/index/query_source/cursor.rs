@@ -229,6 +229,26 @@ pub mod key_cursor {
 
             assert_eq!(data, expected);
         }
+
+        #[wasm_bindgen_test]
+        pub async fn continue_to_primary_key() {
+            let db = random_db_idx_keyval().await;
+            KeyVal::insert_keyval_docs(&db).await;
+            open_idx!(db, Readonly > idx);
+            let cursor = dyn_await!(idx.open_key_cursor()).unwrap().unwrap();
+            let mut stream = open_kstream!(cursor, Value);
+
+            assert_eq!(stream.try_next().await, Ok(Some(Value::MIN)));
+            assert_eq!(
+                continue_to_pkey!(Value::new(5), Key::new(-5), stream),
+                Ok(())
+            );
+
+            let data = stream.try_collect::<Vec<_>>().await.unwrap();
+            let expected = (5u8..=*Value::MAX).map(Value::new).collect::<Vec<_>>();
+
+            assert_eq!(data, expected);
+        }
     }
 }
 
@@ -296,6 +316,84 @@ pub mod cursor {
         assert_eq!(next, Ok(Some(KeyVal::new(Key::MIN, Value::MAX))));
     }
 
+    #[cfg(feature = "streams")]
+    pub mod entry_stream {
+        use crate::prelude::*;
+        use idb_fut::cursor::{CursorDirection, MergedStream};
+
+        #[wasm_bindgen_test]
+        pub async fn all() {
+            let db = random_db_idx_keyval().await;
+            KeyVal::insert_keyval_docs(&db).await;
+            open_idx!(db, Readonly > idx);
+            let cursor = dyn_await!(idx.open_cursor()).unwrap().unwrap();
+            let stream = open_estream!(cursor, Value, Key, KeyVal);
+
+            let data = stream.try_collect::<Vec<_>>().await.unwrap();
+            let expected = KeyVal::iter_range()
+                .rev()
+                .map(move |kv| (kv.value(), kv.key(), kv))
+                .collect::<Vec<_>>();
+
+            assert_eq!(data, expected);
+        }
+
+        #[wasm_bindgen_test]
+        pub async fn with_query() {
+            let db = random_db_idx_keyval().await;
+            KeyVal::insert_keyval_docs(&db).await;
+            open_idx!(db, Readonly > idx);
+            let cursor = {
+                let req = idx.open_cursor().with_query(Value::new(3)..Value::new(6));
+                dyn_await!(req).unwrap().unwrap()
+            };
+            let stream = open_estream!(cursor, Value, Key, KeyVal);
+
+            let data = stream.try_collect::<Vec<_>>().await.unwrap();
+            let expected = (3u8..6)
+                .map(move |v| {
+                    let k = -(v as i8);
+                    (Value::new(v), Key::new(k), KeyVal::new(k, v))
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(data, expected);
+        }
+
+        #[wasm_bindgen_test]
+        pub async fn merged() {
+            let db = random_db_idx_keyval().await;
+            KeyVal::insert_keyval_docs(&db).await;
+            open_idx!(db, Readonly > idx);
+
+            let mut sources = Vec::new();
+            for range in [Value::new(6)..=Value::new(8), Value::new(2)..=Value::new(7)] {
+                let req = idx
+                    .open_cursor()
+                    .with_query(range)
+                    .with_direction(CursorDirection::Prev);
+                let cursor = dyn_await!(req).unwrap().unwrap();
+                sources.push(open_estream!(cursor, Value, Key, KeyVal));
+            }
+
+            let data = MergedStream::new(sources)
+                .with_direction(CursorDirection::Prev)
+                .dedup_by_primary_key()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+            let expected = (2u8..=8)
+                .rev()
+                .map(move |v| {
+                    let k = -(v as i8);
+                    (Value::new(v), Key::new(k), KeyVal::new(k, v))
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(data, expected);
+        }
+    }
+
     pub mod base_cursor {
         pub use crate::prelude::*;
 
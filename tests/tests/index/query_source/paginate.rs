@@ -0,0 +1,78 @@
+use crate::prelude::*;
+use idb_fut::cursor::CursorDirection;
+use idb_fut::database::Database;
+use idb_fut::query_source::{Page, PageToken};
+
+async fn read(
+    db: &Database,
+    direction: CursorDirection,
+    after: Option<&PageToken>,
+    before: Option<&PageToken>,
+) -> Page<KeyVal> {
+    open_idx!(db, Readonly > idx);
+
+    let mut builder = idx.paginate(2).with_direction(direction);
+    if let Some(token) = after {
+        builder = builder.after(token.clone());
+    }
+    if let Some(token) = before {
+        builder = builder.before(token.clone());
+    }
+
+    dyn_await!(builder).expect("paginate")
+}
+
+fn keys(page: &Page<KeyVal>) -> Vec<i8> {
+    page.records().iter().map(|r| *r.key()).collect()
+}
+
+/// Index keys (values) with duplicates: 1 => [-3, -2, -1], 2 => [-5, -4]
+async fn init() -> Database {
+    let db = random_db_idx_keyval().await;
+    open_tx!(db, Readwrite > (tx, store));
+    for (key, value) in [(-1, 1), (-2, 1), (-3, 1), (-4, 2), (-5, 2)] {
+        store.add(KeyVal::new(key, value)).build_dyn().expect("add");
+    }
+    drop(store);
+    tx.commit().await.expect("commit");
+    db
+}
+
+#[wasm_bindgen_test]
+pub async fn duplicate_index_keys() {
+    let db = init().await;
+    let next = CursorDirection::Next;
+
+    let first = read(&db, next, None, None).await;
+    assert_eq!(keys(&first), [-3, -2]);
+    let token = first.next().expect("first.next");
+    assert_eq!(token.key(), &idb_fut::IdbKey::from(1));
+    assert_eq!(token.primary_key(), Some(&idb_fut::IdbKey::from(-2)));
+
+    let second = read(&db, next, first.next(), None).await;
+    assert_eq!(keys(&second), [-1, -5]);
+
+    let third = read(&db, next, second.next(), None).await;
+    assert_eq!(keys(&third), [-4]);
+    assert_eq!(third.next(), None);
+
+    let back = read(&db, next, None, third.previous()).await;
+    assert_eq!(keys(&back), [-1, -5]);
+    assert!(back.previous().is_some());
+
+    let back = read(&db, next, None, back.previous()).await;
+    assert_eq!(keys(&back), [-3, -2]);
+    assert_eq!(back.previous(), None);
+}
+
+#[wasm_bindgen_test]
+pub async fn unique_direction() {
+    let db = init().await;
+
+    let first = read(&db, CursorDirection::Nextunique, None, None).await;
+    assert_eq!(keys(&first), [-3, -5]);
+    assert_eq!(first.next(), None);
+
+    let first = read(&db, CursorDirection::Prevunique, None, None).await;
+    assert_eq!(keys(&first), [-5, -3]);
+}
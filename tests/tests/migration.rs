@@ -0,0 +1,132 @@
+use crate::prelude::*;
+use idb_fut::database::Database;
+use idb_fut::error::{Error, OpenDbError};
+use idb_fut::migration::Migrations;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Log = Rc<RefCell<Vec<u32>>>;
+
+fn logged(log: &Log, versions: &[u32]) -> Migrations {
+    versions
+        .iter()
+        .fold(Migrations::new(), |migrations, &version| {
+            let log = Rc::clone(log);
+            migrations.with_migration(version, move |db, _| {
+                Box::pin(async move {
+                    log.borrow_mut().push(version);
+                    db.create_object_store(&format!("v{version}")).build()?;
+                    Ok(())
+                })
+            })
+        })
+}
+
+#[wasm_bindgen_test]
+pub async fn runs_pending_steps() {
+    let name = random_str();
+    let log = Log::default();
+
+    let db = Database::open(&name)
+        .with_migrations(logged(&log, &[1, 2]))
+        .await
+        .expect("v2");
+    assert_eq!(db.version(), 2.0);
+    assert_eq!(*log.borrow(), [1, 2]);
+    db.close();
+
+    log.borrow_mut().clear();
+    let db = Database::open(&name)
+        .with_migrations(logged(&log, &[1, 2, 3, 4]))
+        .await
+        .expect("v4");
+    assert_eq!(db.version(), 4.0);
+    assert_eq!(*log.borrow(), [3, 4]);
+
+    let mut stores = db.object_store_names().collect::<Vec<_>>();
+    stores.sort_unstable();
+    assert_eq!(stores, ["v1", "v2", "v3", "v4"]);
+}
+
+#[wasm_bindgen_test]
+pub async fn empty_keeps_current_version() {
+    let name = random_str();
+    let log = Log::default();
+
+    let db = Database::open(&name)
+        .with_migrations(logged(&log, &[1, 2]))
+        .await
+        .expect("v2");
+    db.close();
+
+    let db = Database::open(&name)
+        .with_migrations(Migrations::new())
+        .await
+        .expect("empty");
+    assert_eq!(db.version(), 2.0);
+}
+
+#[wasm_bindgen_test]
+pub async fn uses_upgrade_transaction() {
+    let db = Database::open(random_str())
+        .with_migrations(
+            Migrations::new()
+                .with_migration(1, |db, _| {
+                    Box::pin(async move {
+                        db.create_object_store(&db.name()).build()?;
+                        Ok(())
+                    })
+                })
+                .with_migration(2, |db, tx| {
+                    Box::pin(async move {
+                        tx.object_store(&db.name())?
+                            .put("foo")
+                            .with_key(1u8)
+                            .await?;
+                        Ok(())
+                    })
+                }),
+        )
+        .await
+        .expect("open");
+
+    open_tx!(db, Readonly > (tx, store));
+    let value: Option<String> = store.get(1u8).await.unwrap();
+    assert_eq!(value.as_deref(), Some("foo"));
+}
+
+#[wasm_bindgen_test]
+pub async fn reports_failed_step() {
+    let name = random_str();
+    let err = Database::open(&name)
+        .with_migrations(
+            Migrations::new()
+                .with_migration(1, |db, _| {
+                    Box::pin(async move {
+                        db.create_object_store("a").build()?;
+                        Ok(())
+                    })
+                })
+                .with_migration(2, |db, _| {
+                    Box::pin(async move {
+                        db.create_object_store("a").build()?;
+                        Ok(())
+                    })
+                }),
+        )
+        .await
+        .unwrap_err();
+
+    let OpenDbError::Base(Error::Migration(err)) = err else {
+        panic!("Wrong error: {err:?}");
+    };
+    assert_eq!(err.version(), 2);
+    assert_dom_exc!(err.error(), ConstraintError);
+
+    let db = Database::open(&name).await.unwrap();
+    assert_eq!(
+        db.object_store_names().len(),
+        0,
+        "upgrade should've been rolled back"
+    );
+}
@@ -153,18 +153,41 @@ macro_rules! open_stream {
     }};
 }
 
+#[cfg(all(feature = "streams", feature = "cursors"))]
+macro_rules! open_estream {
+    ($cursor: ident, $key: ty, $pk: ty, $val: ty) => {{
+        ::cfg_if::cfg_if! {
+            if #[cfg(feature = "serde")] {
+                $cursor.entry_stream_ser::<$key, $pk, $val>()
+            } else {
+                $cursor.entry_stream::<$key, $pk, $val>()
+            }
+        }
+    }};
+}
+
 pub mod utils;
 
 pub mod database;
 #[cfg(feature = "dates")]
 pub mod date;
+#[cfg(feature = "derive")]
+pub mod derive;
 pub mod example_reproductions;
 #[cfg(feature = "indices")]
 pub mod index;
 pub mod key_path;
+#[cfg(feature = "migrations")]
+pub mod migration;
 pub mod object_store;
 pub mod primitive;
+#[cfg(feature = "query")]
+pub mod query;
+#[cfg(feature = "schema")]
+pub mod schema;
 pub mod transaction;
+#[cfg(feature = "typed")]
+pub mod typed;
 
 #[allow(unused_imports)]
 pub mod prelude {
@@ -0,0 +1,64 @@
+use crate::prelude::*;
+use idb_fut::primitive::BinaryKey;
+
+#[wasm_bindgen_test]
+pub async fn round_trip_and_order() {
+    let db = random_db_with_store().await;
+    open_tx!(db, Readwrite > (tx, store));
+
+    let keys = [
+        BinaryKey::from([0x01, 0xff]),
+        BinaryKey::from([0x02]),
+        BinaryKey::from(vec![0x01]),
+        BinaryKey::from(&[0x01, 0x00][..]),
+    ];
+    for (idx, key) in keys.iter().enumerate() {
+        let res = store.put(idx as u8).with_key(key.clone()).await;
+        assert_eq!(res.as_ref(), Ok(key));
+    }
+
+    let mut sorted = keys.to_vec();
+    sorted.sort();
+    let all_keys = store
+        .get_all_keys::<BinaryKey>()
+        .primitive()
+        .unwrap()
+        .await
+        .expect("get_all_keys")
+        .collect::<idb_fut::Result<Vec<_>>>();
+    assert_eq!(all_keys, Ok(sorted));
+
+    assert_eq!(store.get(BinaryKey::from([0x02])).await, Ok(Some(1u8)));
+    assert_eq!(
+        store
+            .get_key(BinaryKey::from([0x01, 0x00])..BinaryKey::from([0x02]))
+            .primitive()
+            .unwrap()
+            .await,
+        Ok(Some(BinaryKey::from([0x01, 0x00])))
+    );
+
+    store
+        .delete(BinaryKey::from([0x01])..BinaryKey::from([0x02]))
+        .await
+        .expect("delete");
+    assert_eq!(store.count().await, Ok(2));
+}
+
+#[cfg(feature = "cursors")]
+#[wasm_bindgen_test]
+pub async fn cursor_keys() {
+    let db = random_db_with_store().await;
+    open_tx!(db, Readwrite > (tx, store));
+
+    let key = BinaryKey::from([0xde, 0xad, 0xbe, 0xef]);
+    store.put("value").with_key(key.clone()).await.expect("put");
+
+    let cursor = store
+        .open_cursor()
+        .await
+        .expect("open_cursor")
+        .expect("cursor empty");
+    assert_eq!(cursor.key(), Ok(Some(key.clone())));
+    assert_eq!(cursor.primary_key(), Ok(Some(key)));
+}
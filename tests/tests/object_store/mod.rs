@@ -1,4 +1,9 @@
 pub mod add_put;
+pub mod binary_key;
 pub mod clear;
 pub mod delete;
 pub mod query_source;
+#[cfg(feature = "streams")]
+pub mod stream_chunked;
+#[cfg(feature = "cursors")]
+pub mod update_where;
@@ -2,6 +2,8 @@ pub mod key_path;
 
 #[cfg(feature = "cursors")]
 pub mod cursor;
+#[cfg(feature = "cursors")]
+pub mod paginate;
 
 use crate::prelude::*;
 use idb_fut::database::Database;
@@ -69,6 +71,72 @@ pub async fn get_key() {
     assert_eq!(range, Ok(Some(Key::new(-3))));
 }
 
+#[wasm_bindgen_test]
+pub async fn contains_key() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    open_tx!(db, Readonly > (tx, store));
+
+    assert_eq!(
+        dyn_await!(store.contains_key::<Key, _>(KeyRange::Only(Key::MAX))),
+        Ok(true),
+        "only"
+    );
+    assert_eq!(dyn_await!(store.contains_key(0i8)), Ok(false), "missing");
+    assert_eq!(
+        dyn_await!(store.contains_key(0i8..=5)),
+        Ok(false),
+        "empty range"
+    );
+    assert_eq!(dyn_await!(store.contains_key(-3i8..=5)), Ok(true), "range");
+}
+
+#[wasm_bindgen_test]
+#[cfg(feature = "cursors")]
+pub async fn first_last() {
+    let db = random_db_keyval().await;
+
+    {
+        open_tx!(db, Readonly > (tx, store));
+        assert_eq!(dyn_await!(store.first::<KeyVal>()), Ok(None), "empty first");
+        assert_eq!(
+            dyn_await!(store.last_key::<Key>()),
+            Ok(None),
+            "empty last_key"
+        );
+    }
+
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+
+    let first = dyn_await!(store.first::<KeyVal>());
+    assert_eq!(first, Ok(Some(KeyVal::new(Key::MIN, Value::MAX))), "first");
+
+    let last = dyn_await!(store.last::<KeyVal>());
+    assert_eq!(last, Ok(Some(KeyVal::new(Key::MAX, Value::MIN))), "last");
+
+    assert_eq!(
+        dyn_await!(store.first_key::<Key>()),
+        Ok(Some(Key::MIN)),
+        "first_key"
+    );
+    assert_eq!(
+        dyn_await!(store.last_key::<Key>()),
+        Ok(Some(Key::MAX)),
+        "last_key"
+    );
+
+    let ranged = dyn_await!(store.first::<KeyVal>().with_query(-7i8..=-4));
+    assert_eq!(ranged, Ok(Some(KeyVal::new(-7, 7))), "ranged first");
+
+    let ranged = dyn_await!(store.last_key::<Key>().with_query(-7i8..-4));
+    assert_eq!(ranged, Ok(Some(Key::new(-5))), "ranged last_key");
+
+    let ranged = dyn_await!(store.last::<KeyVal>().with_query(0i8..));
+    assert_eq!(ranged, Ok(None), "empty range");
+}
+
 #[wasm_bindgen_test]
 pub async fn get_all() {
     let db = random_db_keyval().await;
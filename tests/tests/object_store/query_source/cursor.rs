@@ -557,6 +557,51 @@ pub mod cursor {
         }
     }
 
+    #[cfg(feature = "streams")]
+    pub mod entry_stream {
+        use crate::prelude::*;
+        use idb_fut::cursor::CursorDirection;
+
+        #[wasm_bindgen_test]
+        pub async fn all() {
+            let db = random_db_keyval().await;
+            KeyVal::insert_keyval_docs(&db).await;
+            open_tx!(db, Readonly > (tx, store));
+            let cursor = dyn_await!(store.open_cursor()).unwrap().unwrap();
+            let stream = open_estream!(cursor, Key, Key, KeyVal);
+
+            let data = stream.try_collect::<Vec<_>>().await.unwrap();
+            let expected = KeyVal::iter_range()
+                .map(move |kv| (kv.key(), kv.key(), kv))
+                .collect::<Vec<_>>();
+
+            assert_eq!(data, expected);
+        }
+
+        #[wasm_bindgen_test]
+        pub async fn with_query_and_direction() {
+            let db = random_db_keyval().await;
+            KeyVal::insert_keyval_docs(&db).await;
+            open_tx!(db, Readonly > (tx, store));
+            let cursor = {
+                let req = store
+                    .open_cursor()
+                    .with_query(Key::new(-7)..=Key::new(-4))
+                    .with_direction(CursorDirection::Prev);
+                dyn_await!(req).unwrap().unwrap()
+            };
+            let stream = open_estream!(cursor, Key, Key, KeyVal);
+
+            let data = stream.try_collect::<Vec<_>>().await.unwrap();
+            let expected = (-7i8..=-4)
+                .rev()
+                .map(move |k| (Key::new(k), Key::new(k), KeyVal::new(k, -k as u8)))
+                .collect::<Vec<_>>();
+
+            assert_eq!(data, expected);
+        }
+    }
+
     #[cfg(feature = "streams")]
     pub mod key_stream {
         use crate::prelude::*;
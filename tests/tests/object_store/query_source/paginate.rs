@@ -0,0 +1,105 @@
+use crate::prelude::*;
+use idb_fut::cursor::CursorDirection;
+use idb_fut::database::Database;
+use idb_fut::query_source::{Page, PageToken};
+
+async fn read(
+    db: &Database,
+    direction: CursorDirection,
+    after: Option<&PageToken>,
+    before: Option<&PageToken>,
+) -> Page<KeyVal> {
+    open_tx!(db, Readonly > (tx, store));
+
+    let mut builder = store.paginate(4).with_direction(direction);
+    if let Some(token) = after {
+        builder = builder.after(token.clone());
+    }
+    if let Some(token) = before {
+        builder = builder.before(token.clone());
+    }
+
+    dyn_await!(builder).expect("paginate")
+}
+
+fn keys(page: &Page<KeyVal>) -> Vec<i8> {
+    page.records().iter().map(|r| *r.key()).collect()
+}
+
+#[wasm_bindgen_test]
+pub async fn forward_and_backward() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    let next = CursorDirection::Next;
+
+    let first = read(&db, next, None, None).await;
+    assert_eq!(keys(&first), [-10, -9, -8, -7]);
+    assert_eq!(first.previous(), None);
+
+    // Tokens survive a round trip through a string
+    let token = first.next().expect("first.next").to_string();
+    let token = token.parse::<PageToken>().expect("parse");
+
+    let second = read(&db, next, Some(&token), None).await;
+    assert_eq!(keys(&second), [-6, -5, -4, -3]);
+
+    let third = read(&db, next, second.next(), None).await;
+    assert_eq!(keys(&third), [-2, -1]);
+    assert_eq!(third.next(), None);
+
+    let back = read(&db, next, None, third.previous()).await;
+    assert_eq!(keys(&back), [-6, -5, -4, -3]);
+    assert_eq!(back.next(), second.next());
+
+    let back = read(&db, next, None, back.previous()).await;
+    assert_eq!(keys(&back), [-10, -9, -8, -7]);
+    assert_eq!(back.previous(), None);
+}
+
+#[wasm_bindgen_test]
+pub async fn reverse_direction() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    let prev = CursorDirection::Prev;
+
+    let first = read(&db, prev, None, None).await;
+    assert_eq!(keys(&first), [-1, -2, -3, -4]);
+
+    let second = read(&db, prev, first.next(), None).await;
+    assert_eq!(keys(&second), [-5, -6, -7, -8]);
+
+    let back = read(&db, prev, None, second.previous()).await;
+    assert_eq!(keys(&back), [-1, -2, -3, -4]);
+    assert_eq!(back.previous(), None);
+}
+
+#[wasm_bindgen_test]
+pub async fn deleted_token_record() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    let first = read(&db, CursorDirection::Next, None, None).await;
+    {
+        open_tx!(db, Readwrite > (tx, store));
+        dyn_await!(store.delete(-7i8)).expect("delete");
+        drop(store);
+        tx.commit().await.expect("commit");
+    }
+
+    let second = read(&db, CursorDirection::Next, first.next(), None).await;
+    assert_eq!(keys(&second), [-6, -5, -4, -3]);
+}
+
+#[wasm_bindgen_test]
+pub async fn zero_page_size() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+
+    let page: Page<KeyVal> = dyn_await!(store.paginate(0)).expect("paginate");
+    assert_eq!(keys(&page), [-10]);
+
+    let next = page.next().expect("page.next").clone();
+    let page: Page<KeyVal> = dyn_await!(store.paginate(0).after(next)).expect("paginate");
+    assert_eq!(keys(&page), [-9]);
+}
@@ -0,0 +1,53 @@
+use crate::prelude::*;
+use idb_fut::object_store::{ChunkedStream, StreamChunked};
+
+fn open(builder: StreamChunked<'_>) -> ChunkedStream<'_, KeyVal> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "serde")] {
+            builder.stream_ser().expect("stream_ser")
+        } else {
+            builder.stream().expect("stream")
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+pub async fn all() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+    let expected = KeyVal::iter_range().collect::<Vec<_>>();
+
+    for chunk_size in [0, 1, 3, 5, 10, 100] {
+        let data = open(store.stream_chunked(chunk_size))
+            .try_collect::<Vec<_>>()
+            .await;
+        assert_eq!(data.as_ref(), Ok(&expected), "chunk_size {chunk_size}");
+    }
+}
+
+#[wasm_bindgen_test]
+pub async fn with_query() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+
+    let data = open(store.stream_chunked(2).with_query(-8i8..-3))
+        .try_collect::<Vec<_>>()
+        .await;
+    let expected = KeyVal::iter_range()
+        .filter(|r| (-8..-3).contains(&*r.key()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(data, Ok(expected));
+}
+
+#[wasm_bindgen_test]
+pub async fn empty() {
+    let db = random_db_keyval().await;
+    open_tx!(db, Readonly > (tx, store));
+
+    let mut stream = open(store.stream_chunked(4));
+    assert_eq!(stream.try_next().await, Ok(None));
+    assert!(futures::stream::FusedStream::is_terminated(&stream));
+}
@@ -0,0 +1,69 @@
+use crate::prelude::*;
+use idb_fut::object_store::UpdateAction;
+
+#[wasm_bindgen_test]
+pub async fn readonly_error() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+
+    let err = dyn_await!(store.update_where(Key::MIN..=Key::MAX, |kv: KeyVal| {
+        UpdateAction::Replace(kv)
+    }))
+    .unwrap_err();
+
+    assert_dom_exc!(err, ReadOnlyError);
+}
+
+#[wasm_bindgen_test]
+pub async fn keep_replace_delete() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    let counts = {
+        open_tx!(db, Readwrite > (tx, store));
+        let req = store.update_where(Key::new(-8)..=Key::new(-3), |kv: KeyVal| {
+            match *kv.key() % 3 {
+                0 => UpdateAction::Delete,
+                -1 => UpdateAction::Replace(KeyVal::new(kv.key(), Value::new(*kv.value() + 10))),
+                _ => UpdateAction::Keep,
+            }
+        });
+        let counts = dyn_await!(req).expect("update_where");
+
+        drop(store);
+        tx.commit().await.unwrap();
+        counts
+    };
+
+    assert_eq!(counts.updated(), 2, "updated");
+    assert_eq!(counts.deleted(), 2, "deleted");
+
+    open_tx!(db, Readonly > (tx, store));
+    let actual = dyn_await!(store.get_all::<KeyVal>())
+        .expect("get_all")
+        .collect::<idb_fut::Result<Vec<_>>>()
+        .expect("collect");
+
+    let expected = KeyVal::iter_range()
+        .filter_map(|kv| match *kv.key() {
+            -6 | -3 => None,
+            -7 | -4 => Some(KeyVal::new(kv.key(), Value::new(*kv.value() + 10))),
+            _ => Some(kv),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(actual, expected);
+}
+
+#[wasm_bindgen_test]
+pub async fn empty_range() {
+    let db = random_db_keyval().await;
+    open_tx!(db, Readwrite > (tx, store));
+
+    let counts =
+        dyn_await!(store.update_where(Key::MIN..=Key::MAX, |_: KeyVal| { UpdateAction::Delete }))
+            .expect("update_where");
+
+    assert_eq!(counts, Default::default());
+}
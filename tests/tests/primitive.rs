@@ -118,3 +118,91 @@ pub fn options() {
         "null from js cmp"
     );
 }
+
+#[wasm_bindgen_test]
+pub fn tuples() {
+    let value = (random::<u32>(), random_str());
+    check!(value, (u32, String));
+
+    let value = (random::<u8>(), random::<i16>(), random::<bool>(), 'x');
+    check!(value, (u8, i16, bool, char));
+
+    let js = (1u8, 2u8, 3u8).try_to_js().expect("triple to js");
+    assert_eq!(
+        <(u8, u8)>::from_js(js),
+        Err(idb_fut::error::SimpleValueError::ArrayLength {
+            expected: 2,
+            actual: 3
+        }),
+        "length mismatch"
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn idb_key_matches_factory_cmp() {
+    use idb_fut::factory::DBFactory;
+    use idb_fut::IdbKey;
+
+    let keys = [
+        IdbKey::Number(f64::NEG_INFINITY),
+        IdbKey::Number(-1.0),
+        IdbKey::Number(0.0),
+        IdbKey::Number(10.0),
+        IdbKey::Date(-5.0),
+        IdbKey::Date(1_700_000_000_000.0),
+        IdbKey::from(""),
+        IdbKey::from("a"),
+        IdbKey::from("\u{1F600}"),
+        IdbKey::from("\u{FF61}"),
+        IdbKey::Binary(vec![]),
+        IdbKey::Binary(vec![0, 1]),
+        IdbKey::Binary(vec![1]),
+        IdbKey::from([]),
+        IdbKey::from([IdbKey::from(1)]),
+        IdbKey::from([IdbKey::from(1), IdbKey::from("a")]),
+        IdbKey::from([IdbKey::from("a")]),
+    ];
+
+    let factory = DBFactory::new().expect("factory");
+    for a in &keys {
+        let value = a.clone();
+        check!(value, IdbKey);
+
+        for b in &keys {
+            assert_eq!(factory.cmp(a, b), Ok(a.cmp(b)), "{a:?} <=> {b:?}");
+        }
+    }
+
+    assert!(IdbKey::from_js(JsValue::from_f64(f64::NAN)).is_err());
+    assert!(IdbKey::from_js(JsValue::TRUE).is_err());
+    assert_dom_exc!(
+        factory.cmp(&JsValue::NULL, &IdbKey::from(1)).unwrap_err(),
+        DataError
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn idb_key_range() {
+    use idb_fut::{IdbKey, KeyRange};
+
+    let ranges = [
+        KeyRange::Bound(IdbKey::from(1), true, IdbKey::from("a"), false),
+        KeyRange::LowerBound(IdbKey::from(1), false),
+        KeyRange::UpperBound(IdbKey::from([IdbKey::from(1)]), true),
+    ];
+    for range in ranges {
+        let sys = range.to_idb_key_range().expect("to_idb_key_range");
+        let js: JsValue = sys.into();
+        assert_eq!(KeyRange::<IdbKey>::from_js(js), Ok(range));
+    }
+
+    let only = KeyRange::Only(IdbKey::from(1)).to_idb_key_range();
+    let only = KeyRange::<IdbKey>::from_js(only.expect("only").into()).expect("from_js");
+    assert!(only.contains(&IdbKey::from(1)));
+    assert!(!only.contains(&IdbKey::from(2)));
+
+    assert_eq!(
+        KeyRange::<IdbKey>::from_js(JsValue::from_str("a")),
+        Ok(KeyRange::Only(IdbKey::from("a")))
+    );
+}
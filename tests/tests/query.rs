@@ -0,0 +1,185 @@
+use crate::prelude::*;
+use idb_fut::cursor::CursorDirection;
+use idb_fut::{IdbKey, KeyRange};
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "serde")] {
+        type Codec = idb_fut::typed::Serde;
+    } else {
+        type Codec = idb_fut::typed::Primitive;
+    }
+}
+
+#[wasm_bindgen_test]
+pub async fn index_range_order_offset_limit() {
+    let db = random_db_idx_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+
+    let query = store
+        .query::<KeyVal>()
+        .with_codec::<Codec>()
+        .with_filter(Value::PATH, 3u8..=8)
+        .with_order(Value::PATH, CursorDirection::Next)
+        .with_offset(1)
+        .with_limit(3);
+
+    let plan = query.explain().expect("explain");
+    assert_eq!(plan.index(), Some(db.name().as_str()));
+    assert_eq!(
+        plan.range(),
+        Some(&KeyRange::Bound(
+            IdbKey::from(3),
+            false,
+            IdbKey::from(8),
+            false
+        ))
+    );
+    assert_eq!(plan.direction(), CursorDirection::Next);
+    assert!(plan.residual_filters().is_empty());
+    assert!(!plan.sorts_in_rust());
+
+    let expected = vec![KeyVal::new(-4, 4), KeyVal::new(-5, 5), KeyVal::new(-6, 6)];
+    assert_eq!(query.await, Ok(expected));
+}
+
+#[wasm_bindgen_test]
+pub async fn residual_filter_sorted_in_rust() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+
+    let query = store
+        .query::<KeyVal>()
+        .with_codec::<Codec>()
+        .with_filter(Key::PATH, ..-1i8)
+        .with_filter(Value::PATH, 2u8..=5)
+        .with_order(Value::PATH, CursorDirection::Prev)
+        .with_limit(2);
+
+    let plan = query.explain().expect("explain");
+    assert_eq!(plan.index(), None);
+    assert_eq!(
+        plan.range(),
+        Some(&KeyRange::UpperBound(IdbKey::from(-1), true))
+    );
+    assert_eq!(plan.residual_filters(), [Value::PATH]);
+    assert!(plan.sorts_in_rust());
+
+    let stream = query.stream().expect("stream");
+    let records = stream.try_collect::<Vec<_>>().await;
+    assert_eq!(records, Ok(vec![KeyVal::new(-5, 5), KeyVal::new(-4, 4)]));
+}
+
+#[wasm_bindgen_test]
+pub async fn contradicting_filters() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+
+    let query = store
+        .query::<KeyVal>()
+        .with_codec::<Codec>()
+        .with_filter(Key::PATH, ..-5i8)
+        .with_filter(Key::PATH, -3i8..);
+
+    assert!(query.explain().expect("explain").is_empty());
+    assert_eq!(query.await, Ok(Vec::new()));
+}
+
+#[wasm_bindgen_test]
+pub async fn typed_store() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+    open_tx!(db, Readonly > (tx, store));
+    let store = idb_fut::typed::TypedStore::<i8, KeyVal, Codec>::new(store);
+
+    let records = store.query().with_filter(Key::PATH, -3i8).await;
+    assert_eq!(records, Ok(vec![KeyVal::new(-3, 3)]));
+}
+
+fn record(fields: &[(&str, i32)]) -> JsValue {
+    let obj = js_sys::Object::new();
+    for (field, value) in fields {
+        js_sys::Reflect::set(&obj, &(*field).into(), &(*value).into()).unwrap();
+    }
+    obj.into()
+}
+
+fn ids(records: Vec<JsValue>) -> Vec<i32> {
+    let id = |record: &JsValue| js_sys::Reflect::get(record, &"id".into()).unwrap();
+    records
+        .iter()
+        .map(move |r| id(r).as_f64().unwrap() as i32)
+        .collect()
+}
+
+#[wasm_bindgen_test]
+pub async fn partially_filtered_compound_index() {
+    let db = random_db_with_init(|_, db| {
+        let store = db
+            .create_object_store(&db.name())
+            .with_key_path("id".into())
+            .build()?;
+        store.create_index("ab", ["a", "b"].into()).build()?;
+        Ok(())
+    })
+    .await;
+
+    let records = [
+        record(&[("id", 1), ("a", 1), ("b", 1)]),
+        record(&[("id", 2), ("a", 1)]),
+        record(&[("id", 3), ("a", 2), ("b", 1)]),
+    ];
+    open_tx!(db, Readwrite > (tx, store));
+    for record in records {
+        store.put(record).await.expect("put");
+    }
+
+    // Record 2 isn't in the index
+    let query = store.query::<JsValue>().with_filter("a", 1);
+    let plan = query.explain().expect("explain");
+    assert_eq!(plan.index(), None);
+    assert_eq!(plan.residual_filters(), ["a"]);
+    assert_eq!(query.await.map(ids), Ok(vec![1, 2]));
+
+    let query = store
+        .query::<JsValue>()
+        .with_filter("a", 1)
+        .with_filter("b", 1);
+    assert_eq!(query.explain().expect("explain").index(), Some("ab"));
+    assert_eq!(query.await.map(ids), Ok(vec![1]));
+}
+
+#[wasm_bindgen_test]
+pub async fn out_of_line_keys() {
+    let db = random_db_with_init(|_, db| {
+        let store = db.create_object_store(&db.name()).build()?;
+        store.create_index("a", "a".into()).build()?;
+        Ok(())
+    })
+    .await;
+
+    open_tx!(db, Readwrite > (tx, store));
+    store
+        .put(record(&[("id", 1), ("a", 1)]))
+        .with_key(1)
+        .await
+        .expect("put 1");
+    store
+        .put(record(&[("id", 2)]))
+        .with_key(2)
+        .await
+        .expect("put 2");
+
+    let query = store.query::<JsValue>();
+    let plan = query.explain().expect("explain");
+    assert_eq!(plan.index(), None);
+    assert_eq!(plan.range(), None);
+    assert!(!plan.sorts_in_rust());
+    assert_eq!(query.await.map(ids), Ok(vec![1, 2]));
+
+    let query = store.query::<JsValue>().with_filter("a", 1);
+    assert_eq!(query.explain().expect("explain").index(), Some("a"));
+    assert_eq!(query.await.map(ids), Ok(vec![1]));
+}
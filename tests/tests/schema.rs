@@ -0,0 +1,160 @@
+use crate::prelude::*;
+use idb_fut::database::Database;
+use idb_fut::error::OpenDbError;
+use idb_fut::query_source::QuerySource;
+use idb_fut::schema::{IndexSchema, Schema, SchemaMismatch, StoreSchema};
+use idb_fut::transaction::TransactionMode;
+use idb_fut::KeyPath;
+
+fn users() -> StoreSchema {
+    StoreSchema::new("users")
+        .with_key_path("id".into())
+        .with_index(IndexSchema::new("email", "email".into()).with_unique(true))
+}
+
+async fn open(name: &str, version: u8, schema: Schema) -> Database {
+    Database::open(name)
+        .with_version(version)
+        .with_schema(schema)
+        .await
+        .expect("open")
+}
+
+#[wasm_bindgen_test]
+pub async fn create() {
+    let db = open(
+        &random_str(),
+        1,
+        Schema::new()
+            .with_store(users())
+            .with_store(StoreSchema::new("log").with_auto_increment(true)),
+    )
+    .await;
+
+    let mut names = db.object_store_names().collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, ["log", "users"]);
+
+    let tx = db.transaction("users").build().unwrap();
+    let store = tx.object_store("users").unwrap();
+    assert_eq!(store.key_path(), Some(KeyPath::One("id".into())));
+
+    let index = store.index("email").unwrap();
+    assert_eq!(index.key_path(), Some(KeyPath::One("email".into())));
+}
+
+#[wasm_bindgen_test]
+pub async fn upgrade() {
+    let name = random_str();
+    {
+        let db = open(
+            &name,
+            1,
+            Schema::new()
+                .with_store(users())
+                .with_store(StoreSchema::new("log")),
+        )
+        .await;
+        let tx = db
+            .transaction("users")
+            .with_mode(TransactionMode::Readwrite)
+            .build()
+            .unwrap();
+        let obj = js_sys::Object::new();
+        js_sys::Reflect::set(&obj, &"id".into(), &1.into()).unwrap();
+        js_sys::Reflect::set(&obj, &"email".into(), &"a@b.c".into()).unwrap();
+        tx.object_store("users")
+            .unwrap()
+            .put(JsValue::from(obj))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        db.close();
+    }
+
+    let db = open(
+        &name,
+        2,
+        Schema::new().with_store(users().with_index(IndexSchema::new("name", "name".into()))),
+    )
+    .await;
+    assert_eq!(db.object_store_names().collect::<Vec<_>>(), ["users"]);
+
+    let tx = db.transaction("users").build().unwrap();
+    let store = tx.object_store("users").unwrap();
+    let mut indices = store.index_names().collect::<Vec<_>>();
+    indices.sort_unstable();
+    assert_eq!(indices, ["email", "name"]);
+    assert_eq!(store.count().await.unwrap(), 1);
+}
+
+#[wasm_bindgen_test]
+pub async fn snapshot() {
+    let name = random_str();
+    let schema = Schema::new()
+        .with_store(StoreSchema::new("log").with_auto_increment(true))
+        .with_store(
+            users().with_index(IndexSchema::new("tags", "tags".into()).with_multi_entry(true)),
+        );
+    let db = open(&name, 3, schema.clone()).await;
+
+    let snapshot = db.schema_snapshot().expect("snapshot");
+    assert_eq!(snapshot.name(), name);
+    assert_eq!(snapshot.version(), 3.0);
+    assert_eq!(snapshot.schema(), &schema);
+    assert_eq!(snapshot.into_schema().diff(&schema), []);
+}
+
+#[wasm_bindgen_test]
+pub async fn snapshot_empty() {
+    let db = open(&random_str(), 1, Schema::new()).await;
+    assert_eq!(db.schema_snapshot().unwrap().schema(), &Schema::new());
+}
+
+#[wasm_bindgen_test]
+pub async fn expected_schema_matches() {
+    let schema = Schema::new().with_store(users());
+    let db = Database::open(&random_str())
+        .with_version(1u8)
+        .with_schema(schema.clone())
+        .with_expected_schema(schema)
+        .await
+        .expect("open");
+
+    assert_eq!(db.object_store_names().collect::<Vec<_>>(), ["users"]);
+}
+
+#[wasm_bindgen_test]
+pub async fn expected_schema_mismatch() {
+    let name = random_str();
+    open(
+        &name,
+        1,
+        Schema::new().with_store(StoreSchema::new("users")),
+    )
+    .await
+    .close();
+
+    let err = Database::open(&name)
+        .with_expected_schema(Schema::new().with_store(users()))
+        .await
+        .expect_err("open");
+
+    let OpenDbError::SchemaMismatch(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert_eq!(
+        err.mismatches(),
+        [
+            SchemaMismatch::StoreKeyPath {
+                store: "users".into(),
+                expected: Some(KeyPath::One("id".into())),
+                actual: None,
+            },
+            SchemaMismatch::MissingIndex {
+                store: "users".into(),
+                index: "email".into(),
+            },
+        ]
+    );
+}
@@ -0,0 +1,249 @@
+use crate::prelude::*;
+use idb_fut::typed::TypedStore;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "serde")] {
+        type Codec = idb_fut::typed::Serde;
+    } else {
+        type Codec = idb_fut::typed::Primitive;
+    }
+}
+
+type Store<'a> = TypedStore<'a, i8, KeyVal, Codec>;
+
+#[wasm_bindgen_test]
+pub async fn put_get_delete() {
+    let db = random_db_keyval().await;
+    open_tx!(db, Readwrite > (tx, store));
+    let store = Store::new(store);
+
+    let record = KeyVal::new(-1, 1);
+    assert_eq!(store.put(&record).await, Ok(-1));
+    assert_eq!(store.get(-1).await, Ok(Some(record)));
+    assert_eq!(store.get(-2).await, Ok(None));
+    assert_dom_exc!(store.add(&record).await.unwrap_err(), ConstraintError);
+
+    store.delete(-1).await.expect("delete");
+    assert_eq!(store.count().await, Ok(0));
+}
+
+#[wasm_bindgen_test]
+pub async fn get_all_count() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    open_tx!(db, Readonly > (tx, store));
+    let store = Store::new(store);
+
+    let expected = KeyVal::iter_range().collect::<Vec<_>>();
+    assert_eq!(store.get_all().await.as_deref(), Ok(expected.as_slice()));
+    assert_eq!(
+        store
+            .get_all()
+            .with_query(*Key::MIN..=-8)
+            .with_limit(2)
+            .await,
+        Ok(expected[..2].to_vec()),
+    );
+
+    let keys = (*Key::MIN..=*Key::MAX).collect::<Vec<_>>();
+    assert_eq!(store.get_all_keys().await, Ok(keys));
+    assert_eq!(store.count().await, Ok(KeyVal::RANGE_LEN));
+    assert_eq!(store.count().with_query(-2..).await, Ok(2));
+}
+
+#[cfg(feature = "cursors")]
+#[wasm_bindgen_test]
+pub async fn cursor() {
+    let db = random_db_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    open_tx!(db, Readwrite > (tx, store));
+    let store = Store::new(store);
+
+    let mut cursor = store
+        .open_cursor()
+        .with_query(-2..)
+        .await
+        .expect("open_cursor")
+        .expect("cursor empty");
+
+    assert_eq!(cursor.primary_key(), Ok(Some(-2)));
+    assert_eq!(cursor.next_record().await, Ok(Some(KeyVal::new(-2, 2))));
+
+    cursor
+        .update(&KeyVal::new(-2, 5))
+        .expect("update")
+        .await
+        .expect("update await");
+    assert_eq!(cursor.next_record().await, Ok(Some(KeyVal::new(-1, 1))));
+    assert_eq!(cursor.next_record().await, Ok(None));
+
+    assert_eq!(store.get(-2).await, Ok(Some(KeyVal::new(-2, 5))));
+}
+
+#[cfg(feature = "indices")]
+#[wasm_bindgen_test]
+pub async fn index() {
+    let db = random_db_idx_keyval().await;
+    KeyVal::insert_keyval_docs(&db).await;
+
+    open_tx!(db, Readonly > (tx, store));
+    let store = Store::new(store);
+    let index = store.index::<u8>(&db.name()).expect("index");
+
+    assert_eq!(index.get(3).await, Ok(Some(KeyVal::new(-3, 3))));
+    assert_eq!(
+        index.get_all_keys().with_query(..=2).await,
+        Ok(vec![-1, -2])
+    );
+    assert_eq!(index.count().with_query(5..).await, Ok(6));
+}
+
+#[cfg(feature = "json")]
+#[wasm_bindgen_test]
+pub async fn json_codec() {
+    use idb_fut::typed::Json;
+
+    let db = random_db_with_store().await;
+    open_tx!(db, Readwrite > (tx, store));
+    let store = TypedStore::<u32, KeyVal, Json>::new(store);
+
+    let record = KeyVal::new(-1, 1);
+    assert_eq!(store.put(&record).with_key(1).await, Ok(1));
+    assert_eq!(store.get(1).await, Ok(Some(record)));
+
+    let raw = store.as_inner().get::<String, _, _>(1u32).primitive();
+    assert_eq!(
+        raw.unwrap().await,
+        Ok(Some(r#"{"key":-1,"value":1}"#.to_string()))
+    );
+}
+
+#[cfg(feature = "postcard")]
+#[wasm_bindgen_test]
+pub async fn postcard_codec_per_request() {
+    use idb_fut::typed::{Postcard, Serde};
+
+    let db = random_db_with_store().await;
+    open_tx!(db, Readwrite > (tx, store));
+    let store = TypedStore::<u32, KeyVal, Serde>::new(store);
+
+    let record = KeyVal::new(-2, 2);
+    store
+        .put(&record)
+        .with_key(1)
+        .with_codec::<Postcard>()
+        .await
+        .expect("put");
+
+    assert_eq!(
+        store.get(1).with_codec::<Postcard>().await,
+        Ok(Some(record))
+    );
+    assert!(store.get(1).await.is_err());
+    assert_eq!(
+        store.get_all().with_codec::<Postcard>().await,
+        Ok(vec![record])
+    );
+}
+
+#[cfg(all(feature = "compression", feature = "json"))]
+#[wasm_bindgen_test]
+pub async fn compressed_codec() {
+    use idb_fut::typed::{Compressed, Json};
+    use idb_fut::typed_array::Uint8Array;
+
+    let db = random_db_with_store().await;
+    open_tx!(db, Readwrite > (tx, store));
+    let store = TypedStore::<u32, KeyVal, Compressed<Json>>::new(store);
+
+    let legacy = KeyVal::new(-1, 1);
+    let compressed = KeyVal::new(-2, 2);
+    store
+        .put(&legacy)
+        .with_key(1)
+        .with_codec::<Json>()
+        .await
+        .expect("put legacy");
+    store
+        .put(&compressed)
+        .with_key(2)
+        .await
+        .expect("put compressed");
+
+    let raw = store.as_inner().get::<Uint8Array, _, _>(2u32).primitive();
+    assert!(raw.unwrap().await.expect("raw").is_some());
+
+    assert_eq!(store.get(1).await, Ok(Some(legacy)));
+    assert_eq!(store.get(2).await, Ok(Some(compressed)));
+    assert_eq!(store.get_all().await, Ok(vec![legacy, compressed]));
+}
+
+#[cfg(all(feature = "compression", feature = "json"))]
+#[wasm_bindgen_test]
+pub async fn compressed_codec_errors() {
+    use idb_fut::typed::{Compressed, Json};
+    use idb_fut::typed_array::Uint8Array;
+
+    let db = random_db_with_store().await;
+    open_tx!(db, Readwrite > (tx, store));
+
+    // Header, string format, a declared size of 2GiB & a 1-byte block
+    let corrupt = Uint8Array::from(vec![0, b'i', b'z', 1, 0, 0, 0, 0x80, 0]);
+    store
+        .put(corrupt)
+        .with_key(2u32)
+        .primitive()
+        .unwrap()
+        .await
+        .expect("put corrupt");
+
+    let store = TypedStore::<u32, KeyVal, Compressed<Json>>::new(store);
+    assert!(store.get(2).await.is_err());
+}
+
+#[cfg(all(feature = "encryption", feature = "json"))]
+#[wasm_bindgen_test]
+pub async fn encrypted_codec() {
+    use idb_fut::typed::{Encrypted, EncryptionKey, Json, KeyRing};
+    use std::cell::Cell;
+
+    thread_local! {
+        static VERSION: Cell<u32> = const { Cell::new(1) };
+    }
+
+    struct Keys;
+    impl KeyRing for Keys {
+        fn current_version() -> u32 {
+            VERSION.get()
+        }
+
+        fn key(version: u32) -> Option<EncryptionKey> {
+            matches!(version, 1 | 2).then(|| [version as u8; 32])
+        }
+    }
+
+    type Codec = Encrypted<Json, Keys>;
+
+    let db = random_db_with_store().await;
+    open_tx!(db, Readwrite > (tx, store));
+    let store = TypedStore::<u32, KeyVal, Codec>::new(store);
+
+    let old = KeyVal::new(-1, 1);
+    let new = KeyVal::new(-2, 2);
+    store.put(&old).with_key(1).await.expect("put v1");
+    VERSION.set(2);
+    store.put(&new).with_key(2).await.expect("put v2");
+
+    assert_eq!(store.get(1).await, Ok(Some(old)));
+    assert_eq!(store.get_all().await, Ok(vec![old, new]));
+
+    store
+        .put(&old)
+        .with_key(3)
+        .with_codec::<Json>()
+        .await
+        .expect("put plaintext");
+    assert!(store.get(3).await.is_err());
+}
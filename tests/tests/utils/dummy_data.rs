@@ -90,6 +90,20 @@ impl KeyVal {
     }
 }
 
+impl From<Key> for idb_fut::IdbKey {
+    #[inline]
+    fn from(key: Key) -> Self {
+        key.0.into()
+    }
+}
+
+impl From<Value> for idb_fut::IdbKey {
+    #[inline]
+    fn from(value: Value) -> Self {
+        value.0.into()
+    }
+}
+
 impl Debug for KeyVal {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Debug::fmt(&(self.key(), self.value()), f)